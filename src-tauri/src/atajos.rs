@@ -0,0 +1,374 @@
+// Atajos de teclado globales configurables y ventana de captura rápida de InfoGov
+// Las combinaciones se guardan en la tabla configuracion y se pueden cambiar sin reiniciar
+
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindowBuilder};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Modifiers, Shortcut, ShortcutState};
+
+use crate::error::{AppError, Result};
+use crate::models::captura::FilaCapturaRapida;
+use crate::models::configuracion::{AccionAtajo, AtajoTeclado, EstadoAtajo};
+use crate::utils::infogov_html::lote_desde_html;
+use crate::utils::infogov_parser::InfoGovExpediente;
+
+/// Etiqueta de la ventana de captura rápida
+pub const VENTANA_CAPTURA_RAPIDA: &str = "captura-rapida";
+
+/// Evento con el resumen de una captura de InfoGov (creados, actualizados y errores)
+pub const EVENTO_CAPTURA_INFOGOV: &str = "captura_infogov_resumen";
+/// Evento con las filas leídas del portapapeles (lo escucha la ventana de captura rápida)
+pub const EVENTO_CAPTURA_RAPIDA: &str = "captura_rapida";
+pub const EVENTO_ABRIR_BUSQUEDA: &str = "abrir_busqueda";
+pub const EVENTO_ABRIR_ORDEN_COMPRA: &str = "abrir_formulario_oc";
+
+/// Atajos que la aplicación tiene registrados en el sistema
+#[derive(Default)]
+pub struct AtajosActivos(Mutex<Vec<EstadoAtajo>>);
+
+impl AtajosActivos {
+    pub fn estado(&self) -> Vec<EstadoAtajo> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+/// Última captura leída por el atajo, a la espera de que el usuario la confirme
+#[derive(Default)]
+pub struct CapturaRapida(Mutex<Vec<FilaCapturaRapida>>);
+
+impl CapturaRapida {
+    pub fn filas(&self) -> Vec<FilaCapturaRapida> {
+        self.0.lock().unwrap().clone()
+    }
+
+    pub fn reemplazar(&self, filas: Vec<FilaCapturaRapida>) {
+        *self.0.lock().unwrap() = filas;
+    }
+
+    pub fn vaciar(&self) {
+        self.0.lock().unwrap().clear();
+    }
+}
+
+/// Valida los atajos y los devuelve normalizados ("alt + i" → "Alt+I"), uno por acción
+/// - La combinación debe tener algún modificador (salvo F1-F24) para no bloquear la escritura
+/// - Dos acciones no pueden compartir combinación
+/// - Una combinación vacía deja la acción sin atajo
+pub fn validar(atajos: &[AtajoTeclado]) -> Result<Vec<AtajoTeclado>> {
+    let mut validados: Vec<AtajoTeclado> = Vec::new();
+    let mut usados: Vec<(Shortcut, AccionAtajo)> = Vec::new();
+
+    for accion in AccionAtajo::TODAS {
+        let combinacion = atajos
+            .iter()
+            .rev()
+            .find(|a| a.accion == accion)
+            .map(|a| a.combinacion.as_str())
+            .unwrap_or_else(|| accion.combinacion_de_fabrica());
+
+        let Some(atajo) = parsear(combinacion)? else {
+            validados.push(AtajoTeclado { accion, combinacion: String::new() });
+            continue;
+        };
+
+        if let Some((_, otra)) = usados.iter().find(|(usado, _)| usado.id() == atajo.id()) {
+            return Err(AppError::Validation(format!(
+                "{} ya está asignado a \"{}\"",
+                formatear(&atajo),
+                otra.descripcion()
+            )));
+        }
+
+        usados.push((atajo, accion));
+        validados.push(AtajoTeclado { accion, combinacion: formatear(&atajo) });
+    }
+
+    Ok(validados)
+}
+
+fn parsear(combinacion: &str) -> Result<Option<Shortcut>> {
+    let combinacion: String = combinacion.split_whitespace().collect();
+    if combinacion.is_empty() {
+        return Ok(None);
+    }
+
+    let atajo = Shortcut::from_str(&combinacion)
+        .map_err(|e| AppError::Validation(format!("Atajo inválido \"{}\": {}", combinacion, e)))?;
+
+    let tecla = atajo.key.to_string();
+    let es_tecla_funcion = tecla.strip_prefix('F').is_some_and(|n| n.parse::<u8>().is_ok());
+    if atajo.mods.is_empty() && !es_tecla_funcion {
+        return Err(AppError::Validation(format!(
+            "El atajo \"{}\" necesita Ctrl, Alt, Shift o Super",
+            combinacion
+        )));
+    }
+
+    Ok(Some(atajo))
+}
+
+/// Texto legible de la combinación, en un formato que se vuelve a parsear igual
+fn formatear(atajo: &Shortcut) -> String {
+    let mut partes = Vec::new();
+    for (modificador, nombre) in [
+        (Modifiers::CONTROL, "Ctrl"),
+        (Modifiers::ALT, "Alt"),
+        (Modifiers::SHIFT, "Shift"),
+        (Modifiers::SUPER, "Super"),
+    ] {
+        if atajo.mods.contains(modificador) {
+            partes.push(nombre.to_string());
+        }
+    }
+
+    let tecla = atajo.key.to_string();
+    let tecla = tecla
+        .strip_prefix("Key")
+        .or_else(|| tecla.strip_prefix("Digit"))
+        .unwrap_or(&tecla);
+    partes.push(tecla.to_string());
+
+    partes.join("+")
+}
+
+/// Registra los atajos al iniciar la aplicación
+/// Si alguno no se puede registrar (otra aplicación ya lo usa) queda informado en get_atajos y se sigue con el resto
+pub fn registrar_al_iniciar(app: &AppHandle, atajos: &[AtajoTeclado]) {
+    let atajos = validar(atajos).unwrap_or_else(|e| {
+        eprintln!("⚠️ Atajos guardados inválidos, se usan los de fábrica: {}", e);
+        validar(&[]).expect("atajos de fábrica válidos")
+    });
+
+    let estado = registrar(app, &atajos);
+    for atajo in &estado {
+        match &atajo.error {
+            Some(error) => eprintln!("⚠️ No se pudo registrar {} ({}): {}", atajo.combinacion, atajo.descripcion, error),
+            None if atajo.registrado => println!("✅ Atajo {} registrado ({})", atajo.combinacion, atajo.descripcion),
+            None => {}
+        }
+    }
+    *app.state::<AtajosActivos>().inner().0.lock().unwrap() = estado;
+}
+
+/// Reemplaza los atajos registrados por los nuevos (ya validados)
+/// Si una combinación que cambió no se puede registrar, se restauran los anteriores y se informa el conflicto
+pub fn reemplazar(app: &AppHandle, nuevos: &[AtajoTeclado]) -> Result<Vec<EstadoAtajo>> {
+    let activos = app.state::<AtajosActivos>();
+    let mut actuales = activos.inner().0.lock().unwrap();
+
+    desregistrar(app, &actuales);
+    let estado = registrar(app, nuevos);
+
+    let conflicto = estado.iter().find(|nuevo| {
+        nuevo.error.is_some()
+            && !actuales
+                .iter()
+                .any(|actual| actual.accion == nuevo.accion && actual.combinacion == nuevo.combinacion)
+    });
+
+    if let Some(conflicto) = conflicto {
+        let mensaje = format!(
+            "No se pudo registrar {} para \"{}\": {}",
+            conflicto.combinacion,
+            conflicto.descripcion,
+            conflicto.error.as_deref().unwrap_or_default()
+        );
+
+        desregistrar(app, &estado);
+        let anteriores: Vec<AtajoTeclado> = actuales
+            .iter()
+            .map(|a| AtajoTeclado { accion: a.accion, combinacion: a.combinacion.clone() })
+            .collect();
+        *actuales = registrar(app, &anteriores);
+
+        return Err(AppError::Validation(mensaje));
+    }
+
+    *actuales = estado.clone();
+    Ok(estado)
+}
+
+fn registrar(app: &AppHandle, atajos: &[AtajoTeclado]) -> Vec<EstadoAtajo> {
+    atajos
+        .iter()
+        .map(|atajo| {
+            let accion = atajo.accion;
+            let resultado = if atajo.combinacion.is_empty() {
+                Ok(false)
+            } else {
+                app.global_shortcut()
+                    .on_shortcut(atajo.combinacion.as_str(), move |app, _shortcut, event| {
+                        if event.state == ShortcutState::Pressed {
+                            ejecutar(app, accion);
+                        }
+                    })
+                    .map(|_| true)
+            };
+
+            EstadoAtajo {
+                accion,
+                descripcion: accion.descripcion().to_string(),
+                combinacion: atajo.combinacion.clone(),
+                registrado: resultado.as_ref().is_ok_and(|registrado| *registrado),
+                error: resultado.err().map(|e| e.to_string()),
+            }
+        })
+        .collect()
+}
+
+fn desregistrar(app: &AppHandle, atajos: &[EstadoAtajo]) {
+    for atajo in atajos.iter().filter(|a| a.registrado) {
+        if let Err(e) = app.global_shortcut().unregister(atajo.combinacion.as_str()) {
+            eprintln!("⚠️ Error liberando atajo {}: {}", atajo.combinacion, e);
+        }
+    }
+}
+
+fn ejecutar(app: &AppHandle, accion: AccionAtajo) {
+    println!("⌨️ Atajo: {}", accion.descripcion());
+
+    match accion {
+        AccionAtajo::CapturarInfoGov => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                capturar_infogov(&app);
+            });
+        }
+        AccionAtajo::AbrirBusqueda => {
+            mostrar_ventana_principal(app);
+            let _ = app.emit(EVENTO_ABRIR_BUSQUEDA, ());
+        }
+        AccionAtajo::AbrirOrdenCompra => {
+            mostrar_ventana_principal(app);
+            let _ = app.emit(EVENTO_ABRIR_ORDEN_COMPRA, ());
+        }
+        AccionAtajo::AlternarVentana => {
+            if let Some(window) = app.get_webview_window("main") {
+                if window.is_visible().unwrap_or(false) {
+                    let _ = window.hide();
+                } else {
+                    mostrar_ventana_principal(app);
+                }
+            }
+        }
+    }
+}
+
+fn mostrar_ventana_principal(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+/// Lee la selección de InfoGov del portapapeles y la muestra en la ventana de captura rápida
+/// No guarda nada: el usuario confirma (y corrige tema o estado) desde la ventana
+fn capturar_infogov(app: &AppHandle) {
+    let filas = match leer_portapapeles() {
+        Ok(filas) if !filas.is_empty() => filas,
+        Ok(_) => {
+            emitir_error(app, "No se encontraron expedientes de InfoGov en el portapapeles".to_string());
+            return;
+        }
+        Err(e) => {
+            emitir_error(app, format!("Error al leer portapapeles: {}", e));
+            return;
+        }
+    };
+    println!("📋 Captura rápida: {} filas", filas.len());
+
+    app.state::<CapturaRapida>().reemplazar(filas.clone());
+
+    if let Err(e) = abrir_ventana_captura(app) {
+        emitir_error(app, format!("Error al abrir la captura rápida: {}", e));
+        return;
+    }
+    let _ = app.emit(EVENTO_CAPTURA_RAPIDA, &filas);
+}
+
+/// La grilla de InfoGov también se copia como HTML: conserva los límites de columna
+/// Si no hay tabla HTML se interpreta el texto plano
+fn leer_portapapeles() -> std::result::Result<Vec<FilaCapturaRapida>, arboard::Error> {
+    let mut portapapeles = arboard::Clipboard::new()?;
+
+    let filas = match portapapeles.get().html().ok().as_deref().and_then(lote_desde_html) {
+        Some(filas) => filas,
+        None => InfoGovExpediente::lote_desde_portapapeles(&portapapeles.get_text()?),
+    };
+
+    Ok(filas.into_iter().map(FilaCapturaRapida::from).collect())
+}
+
+/// Muestra la ventana de captura rápida (siempre visible, encima del navegador con InfoGov)
+fn abrir_ventana_captura(app: &AppHandle) -> tauri::Result<()> {
+    if let Some(ventana) = app.get_webview_window(VENTANA_CAPTURA_RAPIDA) {
+        ventana.show()?;
+        ventana.set_focus()?;
+        return Ok(());
+    }
+
+    WebviewWindowBuilder::new(app, VENTANA_CAPTURA_RAPIDA, WebviewUrl::App("index.html".into()))
+        .title("Captura rápida - InfoGov")
+        .inner_size(760.0, 440.0)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .center()
+        .build()?;
+
+    Ok(())
+}
+
+/// Oculta la ventana de captura rápida (después de guardar o descartar)
+pub fn cerrar_ventana_captura(app: &AppHandle) {
+    if let Some(ventana) = app.get_webview_window(VENTANA_CAPTURA_RAPIDA) {
+        let _ = ventana.hide();
+    }
+}
+
+fn emitir_error(app: &AppHandle, error: String) {
+    eprintln!("❌ {}", error);
+    let _ = app.emit("expediente_error", serde_json::json!({
+        "error": error,
+        "timestamp": chrono::Utc::now().to_rfc3339()
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn atajo(accion: AccionAtajo, combinacion: &str) -> AtajoTeclado {
+        AtajoTeclado { accion, combinacion: combinacion.to_string() }
+    }
+
+    #[test]
+    fn test_validar_normaliza_y_completa() {
+        let atajos = validar(&[
+            atajo(AccionAtajo::CapturarInfoGov, "ctrl + shift + i"),
+            atajo(AccionAtajo::AlternarVentana, ""),
+        ])
+        .unwrap();
+
+        let combinaciones: Vec<&str> = atajos.iter().map(|a| a.combinacion.as_str()).collect();
+        assert_eq!(combinaciones, vec!["Ctrl+Shift+I", "Alt+B", "Alt+O", ""]);
+
+        // El texto normalizado vuelve a dar el mismo atajo
+        assert_eq!(validar(&atajos).unwrap(), atajos);
+        assert_eq!(validar(&[atajo(AccionAtajo::AbrirBusqueda, "F2")]).unwrap()[1].combinacion, "F2");
+    }
+
+    #[test]
+    fn test_validar_detecta_conflictos() {
+        let error = validar(&[
+            atajo(AccionAtajo::AbrirBusqueda, "Alt+O"),
+            atajo(AccionAtajo::AbrirOrdenCompra, "option+o"),
+        ])
+        .unwrap_err();
+        assert!(error.to_string().contains("Abrir búsqueda"));
+
+        assert!(validar(&[atajo(AccionAtajo::AbrirBusqueda, "I")]).is_err());
+        assert!(validar(&[atajo(AccionAtajo::AbrirBusqueda, "Alt+Ñ")]).is_err());
+    }
+}
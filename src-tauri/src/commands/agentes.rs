@@ -2,13 +2,13 @@
 // Estas funciones son invocadas desde el frontend con invoke()
 
 use tauri::State;
-use uuid::Uuid;
-use std::sync::Arc;
 use chrono::Utc;
 use serde::Serialize;
 
-use crate::db::DatabaseManager;
+use crate::db::DatabasePool;
 use crate::models::agente::{Agente, CreateAgente, UpdateAgente};
+use crate::repositories::AgenteRepository;
+use crate::services::SettingsService;
 
 /// Estructura extendida del agente con cálculo de semáforo
 #[derive(Debug, Serialize)]
@@ -19,21 +19,15 @@ pub struct AgenteConSemaforo {
     pub semaforo_status: Option<String>,
 }
 
-/// Obtener todos los agentes
+/// Obtener todos los agentes de la zona
 #[tauri::command]
-pub async fn get_all_agentes(db: State<'_, Arc<DatabaseManager>>) -> Result<Vec<AgenteConSemaforo>, String> {
-    let pool = db.sqlite();
-    
-    let agentes = sqlx::query_as::<_, Agente>(
-        r#"
-        SELECT * FROM agentes 
-        WHERE activo = true
-        ORDER BY apellido, nombre ASC
-        "#
-    )
-    .fetch_all(pool)
-    .await
-    .map_err(|e| format!("Error al obtener agentes: {}", e))?;
+pub async fn get_all_agentes(pools: State<'_, DatabasePool>) -> Result<Vec<AgenteConSemaforo>, String> {
+    let zona_id = SettingsService::zona_activa(pools.get_sqlite())
+        .await
+        .map_err(|e| e.to_string())?;
+    let agentes = AgenteRepository::get_activos(pools.get_sqlite(), &zona_id)
+        .await
+        .map_err(|e| e.to_string())?;
     
     // Calcular semáforo para cada agente
     let agentes_con_semaforo: Vec<AgenteConSemaforo> = agentes
@@ -51,143 +45,58 @@ pub async fn get_all_agentes(db: State<'_, Arc<DatabaseManager>>) -> Result<Vec<
     Ok(agentes_con_semaforo)
 }
 
-/// Obtener un agente por ID
+/// Obtener un agente de la zona por ID
 #[tauri::command]
-pub async fn get_agente(db: State<'_, Arc<DatabaseManager>>, id: String) -> Result<Agente, String> {
-    let uuid = Uuid::parse_str(&id)
-        .map_err(|_| "ID inválido".to_string())?;
-    
-    let pool = db.sqlite();
-    
-    let agente = sqlx::query_as::<_, Agente>(
-        "SELECT * FROM agentes WHERE id = ?"
-    )
-    .bind(uuid)
-    .fetch_one(pool)
-    .await
-    .map_err(|e| format!("Error al obtener agente: {}", e))?;
-    
-    Ok(agente)
+pub async fn get_agente(pools: State<'_, DatabasePool>, id: String) -> Result<Agente, String> {
+    let zona_id = SettingsService::zona_activa(pools.get_sqlite())
+        .await
+        .map_err(|e| e.to_string())?;
+    AgenteRepository::get_by_id(pools.get_sqlite(), &zona_id, &id)
+        .await
+        .map_err(|e| e.to_string())
 }
 
-/// Crear un nuevo agente
+/// Crear un nuevo agente en la zona
 #[tauri::command]
 pub async fn create_agente(
-    db: State<'_, Arc<DatabaseManager>>,
+    pools: State<'_, DatabasePool>,
     data: CreateAgente
 ) -> Result<Agente, String> {
-    let pool = db.sqlite();
-    let id = Uuid::new_v4();
-    let now = Utc::now();
-    
-    sqlx::query(
-        r#"
-        INSERT INTO agentes (
-            id, dni, nombre, apellido, email, telefono,
-            legajo, cargo, area, fecha_ingreso, activo,
-            licencia_conducir, vencimiento_licencia,
-            created_at, updated_at
-        )
-        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-        "#
-    )
-    .bind(id)
-    .bind(&data.dni)
-    .bind(&data.nombre)
-    .bind(&data.apellido)
-    .bind(&data.email)
-    .bind(&data.telefono)
-    .bind(&data.legajo)
-    .bind(&data.cargo)
-    .bind(&data.area)
-    .bind(data.fecha_ingreso)
-    .bind(true)
-    .bind(&data.licencia_conducir)
-    .bind(data.vencimiento_licencia)
-    .bind(now)
-    .bind(now)
-    .execute(pool)
-    .await
-    .map_err(|e| format!("Error al crear agente: {}", e))?;
-    
-    // Obtener el agente creado
-    get_agente(db, id.to_string()).await
+    let zona_id = SettingsService::zona_activa(pools.get_sqlite())
+        .await
+        .map_err(|e| e.to_string())?;
+    AgenteRepository::create(pools.get_sqlite(), &zona_id, data)
+        .await
+        .map_err(|e| e.to_string())
 }
 
 /// Actualizar un agente existente
 #[tauri::command]
 pub async fn update_agente(
-    db: State<'_, Arc<DatabaseManager>>,
+    pools: State<'_, DatabasePool>,
     id: String,
     data: UpdateAgente
 ) -> Result<Agente, String> {
-    let uuid = Uuid::parse_str(&id)
-        .map_err(|_| "ID inválido".to_string())?;
-    
-    let pool = db.sqlite();
-    
-    // Construir query dinámica solo con campos presentes
-    let mut query_parts = vec!["UPDATE agentes SET updated_at = ?"];
-    
-    if data.nombre.is_some() { query_parts.push("nombre = ?"); }
-    if data.apellido.is_some() { query_parts.push("apellido = ?"); }
-    if data.email.is_some() { query_parts.push("email = ?"); }
-    if data.telefono.is_some() { query_parts.push("telefono = ?"); }
-    if data.cargo.is_some() { query_parts.push("cargo = ?"); }
-    if data.area.is_some() { query_parts.push("area = ?"); }
-    if data.activo.is_some() { query_parts.push("activo = ?"); }
-    if data.licencia_conducir.is_some() { query_parts.push("licencia_conducir = ?"); }
-    if data.vencimiento_licencia.is_some() { query_parts.push("vencimiento_licencia = ?"); }
-    
-    query_parts.push("WHERE id = ?");
-    
-    let query_str = query_parts.join(", ").replace(", WHERE", " WHERE");
-    let mut query = sqlx::query(&query_str);
-    
-    query = query.bind(Utc::now());
-    if let Some(v) = data.nombre { query = query.bind(v); }
-    if let Some(v) = data.apellido { query = query.bind(v); }
-    if let Some(v) = data.email { query = query.bind(v); }
-    if let Some(v) = data.telefono { query = query.bind(v); }
-    if let Some(v) = data.cargo { query = query.bind(v); }
-    if let Some(v) = data.area { query = query.bind(v); }
-    if let Some(v) = data.activo { query = query.bind(v); }
-    if let Some(v) = data.licencia_conducir { query = query.bind(v); }
-    if let Some(v) = data.vencimiento_licencia { query = query.bind(v); }
-    query = query.bind(uuid);
-    
-    query.execute(pool)
+    let zona_id = SettingsService::zona_activa(pools.get_sqlite())
         .await
-        .map_err(|e| format!("Error al actualizar agente: {}", e))?;
-    
-    get_agente(db, id).await
+        .map_err(|e| e.to_string())?;
+    AgenteRepository::update(pools.get_sqlite(), &zona_id, &id, data)
+        .await
+        .map_err(|e| e.to_string())
 }
 
 /// Eliminar un agente (soft delete)
 #[tauri::command]
 pub async fn delete_agente(
-    db: State<'_, Arc<DatabaseManager>>,
+    pools: State<'_, DatabasePool>,
     id: String
 ) -> Result<(), String> {
-    let uuid = Uuid::parse_str(&id)
-        .map_err(|_| "ID inválido".to_string())?;
-    
-    let pool = db.sqlite();
-    
-    sqlx::query(
-        r#"
-        UPDATE agentes 
-        SET activo = false, updated_at = ?
-        WHERE id = ?
-        "#
-    )
-    .bind(Utc::now())
-    .bind(uuid)
-    .execute(pool)
-    .await
-    .map_err(|e| format!("Error al eliminar agente: {}", e))?;
-    
-    Ok(())
+    let zona_id = SettingsService::zona_activa(pools.get_sqlite())
+        .await
+        .map_err(|e| e.to_string())?;
+    AgenteRepository::delete(pools.get_sqlite(), &zona_id, &id)
+        .await
+        .map_err(|e| e.to_string())
 }
 
 /// Calcular semáforo de licencias según días restantes
@@ -230,9 +139,9 @@ pub struct EstadisticasLicencias {
 
 #[tauri::command]
 pub async fn get_estadisticas_licencias(
-    db: State<'_, Arc<DatabaseManager>>
+    pools: State<'_, DatabasePool>
 ) -> Result<EstadisticasLicencias, String> {
-    let agentes = get_all_agentes(db).await?;
+    let agentes = get_all_agentes(pools).await?;
     
     let total_agentes = agentes.len() as i32;
     let sin_licencia = agentes.iter()
@@ -0,0 +1,77 @@
+// Tauri Commands para los atajos de teclado globales y la captura rápida de InfoGov
+
+use tauri::{AppHandle, Emitter, State};
+
+use crate::atajos::{self, AtajosActivos, CapturaRapida, EVENTO_CAPTURA_INFOGOV};
+use crate::db::DatabasePool;
+use crate::models::captura::{FilaCapturaRapida, OrigenCaptura};
+use crate::models::configuracion::{AtajoTeclado, EstadoAtajo};
+use crate::models::expediente::ResumenCapturaInfoGov;
+use crate::repositories::{ConfiguracionRepository, ExpedienteRepository};
+
+/// Obtener los atajos configurados y si quedaron registrados
+#[tauri::command]
+pub fn get_atajos(activos: State<'_, AtajosActivos>) -> Result<Vec<EstadoAtajo>, String> {
+    Ok(activos.estado())
+}
+
+/// Cambiar atajos: se validan, se registran en el momento y recién entonces se guardan
+/// Las acciones que no vienen en la lista conservan su combinación
+#[tauri::command]
+pub async fn update_atajos(
+    app: AppHandle,
+    pools: State<'_, DatabasePool>,
+    atajos: Vec<AtajoTeclado>,
+) -> Result<Vec<EstadoAtajo>, String> {
+    let mut configurados = ConfiguracionRepository::atajos(pools.get_sqlite())
+        .await
+        .map_err(|e| e.to_string())?;
+    for cambio in atajos {
+        if let Some(atajo) = configurados.iter_mut().find(|a| a.accion == cambio.accion) {
+            atajo.combinacion = cambio.combinacion;
+        }
+    }
+
+    let validados = atajos::validar(&configurados).map_err(|e| e.to_string())?;
+    let estado = atajos::reemplazar(&app, &validados).map_err(|e| e.to_string())?;
+
+    ConfiguracionRepository::guardar_atajos(pools.get_sqlite(), &validados)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(estado)
+}
+
+/// Filas leídas por el último atajo de captura (la ventana de captura rápida las pide al abrirse)
+#[tauri::command]
+pub fn get_captura_rapida(captura: State<'_, CapturaRapida>) -> Result<Vec<FilaCapturaRapida>, String> {
+    Ok(captura.filas())
+}
+
+/// Guardar la captura rápida con las correcciones del usuario
+#[tauri::command]
+pub async fn guardar_captura_rapida(
+    app: AppHandle,
+    pools: State<'_, DatabasePool>,
+    captura: State<'_, CapturaRapida>,
+    filas: Vec<FilaCapturaRapida>,
+) -> Result<ResumenCapturaInfoGov, String> {
+    let filas = filas.into_iter().map(Into::into).collect();
+    let resumen = ExpedienteRepository::upsert_lote_infogov(pools.get_sqlite(), filas, OrigenCaptura::Atajo)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    captura.vaciar();
+    atajos::cerrar_ventana_captura(&app);
+    let _ = app.emit(EVENTO_CAPTURA_INFOGOV, &resumen);
+
+    Ok(resumen)
+}
+
+/// Descartar la captura rápida sin guardar
+#[tauri::command]
+pub fn descartar_captura_rapida(app: AppHandle, captura: State<'_, CapturaRapida>) -> Result<(), String> {
+    captura.vaciar();
+    atajos::cerrar_ventana_captura(&app);
+    Ok(())
+}
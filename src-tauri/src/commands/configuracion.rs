@@ -0,0 +1,57 @@
+// Tauri Commands para la configuración de la instalación (institución, zona de riego, IVA, servidor)
+
+use tauri::{AppHandle, Emitter, State};
+
+use crate::db::database::init_postgres;
+use crate::db::supervisor::EVENTO_ESTADO_DB;
+use crate::db::DatabasePool;
+use crate::models::configuracion::Settings;
+use crate::services::SettingsService;
+use crate::sync::reiniciar_marcas;
+
+/// Obtener la configuración vigente (con los valores de fábrica para lo que no se configuró)
+/// La contraseña de PostgreSQL no sale de la base local
+#[tauri::command]
+pub async fn get_settings(pools: State<'_, DatabasePool>) -> Result<Settings, String> {
+    SettingsService::cargar(pools.get_sqlite())
+        .await
+        .map(|settings| settings.para_frontend())
+        .map_err(|e| e.to_string())
+}
+
+/// Validar y guardar la configuración
+/// Si cambió el servidor PostgreSQL se reconecta en el momento y se avisa al frontend
+/// Si cambió la zona, la próxima sincronización descarga desde cero las filas de la zona nueva
+#[tauri::command]
+pub async fn update_settings(
+    app: AppHandle,
+    pools: State<'_, DatabasePool>,
+    settings: Settings,
+) -> Result<Settings, String> {
+    let (anterior, guardada) = SettingsService::reemplazar(pools.get_sqlite(), &settings)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if guardada.zona.id != anterior.zona.id {
+        let _turno = pools.turno_sincronizacion().await;
+        reiniciar_marcas(pools.get_sqlite())
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    if guardada.base_datos.postgres_url != anterior.base_datos.postgres_url {
+        pools.configurar_postgres(guardada.base_datos.postgres_url.as_deref()).await;
+
+        if let Some(postgres) = pools.get_postgres() {
+            if let Err(e) = init_postgres(&postgres).await {
+                eprintln!("❌ Error en migraciones de PostgreSQL: {}", e);
+                pools.set_postgres(None);
+                pools.registrar_fallo(&e.to_string());
+            }
+        }
+
+        let _ = app.emit(EVENTO_ESTADO_DB, pools.estado());
+    }
+
+    Ok(guardada.para_frontend())
+}
@@ -1,14 +1,25 @@
 // Tauri Commands para Expedientes
 // Estas funciones son invocadas desde el frontend con invoke()
 
+use std::path::Path;
+
 use tauri::State;
 use serde::{Deserialize, Serialize};
 
 use crate::db::DatabasePool;
-use crate::models::expediente::{CreateExpediente, EstadoExpediente, Expediente, TipoExpediente, UpdateExpediente, CategoriaGasto};
-use crate::repositories::ExpedienteRepository;
-use crate::utils::infogov_parser::InfoGovExpediente;
-use crate::services::GastoClassifier;
+use crate::models::expediente::{
+    CreateExpediente, EstadoExpediente, Expediente, ExpedienteFilter, PaginaExpedientes,
+    ResultadoBusqueda, ResumenCapturaInfoGov, TipoExpediente, UpdateExpediente, CategoriaGasto,
+    VistaPreviaImportacion,
+};
+use crate::models::captura::{CapturaInfoGov, OrigenCaptura, ResultadoDeshacer};
+use crate::models::estado_infogov::{EstadoInfoGovPendiente, MapeoEstadoInfoGov};
+use crate::models::ExpedienteMovimiento;
+use crate::repositories::{CapturaRepository, EstadoInfoGovRepository, ExpedienteRepository, MovimientoRepository};
+use crate::utils::infogov_archivo::lote_desde_archivo;
+use crate::utils::infogov_html::lote_desde_html;
+use crate::utils::infogov_parser::{FilaInfoGov, InfoGovExpediente};
+use crate::services::{EstadoExpedienteService, GastoClassifier, SettingsService};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProcesarExpedienteResult {
@@ -27,10 +38,13 @@ pub struct ClassificationResult {
 }
 
 
-/// Obtener todos los expedientes
+/// Obtener todos los expedientes de la zona
 #[tauri::command]
 pub async fn get_expedientes(pools: State<'_, DatabasePool>) -> Result<Vec<Expediente>, String> {
-    ExpedienteRepository::get_all(pools.get_sqlite())
+    let zona_id = SettingsService::zona_activa(pools.get_sqlite())
+        .await
+        .map_err(|e| e.to_string())?;
+    ExpedienteRepository::get_all(pools.get_sqlite(), &zona_id)
         .await
         .map_err(|e| e.to_string())
 }
@@ -41,33 +55,57 @@ pub async fn obtener_expedientes(pools: State<'_, DatabasePool>) -> Result<Vec<E
     get_expedientes(pools).await
 }
 
-/// Obtener un expediente por ID
+/// Listar expedientes con filtros, orden y paginación (resuelto en la base de datos)
+#[tauri::command]
+pub async fn filtrar_expedientes(
+    pools: State<'_, DatabasePool>,
+    filtro: ExpedienteFilter,
+) -> Result<PaginaExpedientes, String> {
+    let zona_id = SettingsService::zona_activa(pools.get_sqlite())
+        .await
+        .map_err(|e| e.to_string())?;
+    ExpedienteRepository::filtrar(pools.get_sqlite(), &zona_id, &filtro)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Obtener un expediente de la zona por ID
 #[tauri::command]
 pub async fn get_expediente(pools: State<'_, DatabasePool>, id: String) -> Result<Expediente, String> {
-    ExpedienteRepository::get_by_id(pools.get_sqlite(), &id)
+    let zona_id = SettingsService::zona_activa(pools.get_sqlite())
+        .await
+        .map_err(|e| e.to_string())?;
+    ExpedienteRepository::get_by_id(pools.get_sqlite(), &zona_id, &id)
         .await
         .map_err(|e| e.to_string())
 }
 
-/// Crear un nuevo expediente
+/// Crear un nuevo expediente en la zona
 #[tauri::command]
 pub async fn create_expediente(
     pools: State<'_, DatabasePool>,
     data: CreateExpediente,
 ) -> Result<Expediente, String> {
-    ExpedienteRepository::create(pools.get_sqlite(), data)
+    let zona_id = SettingsService::zona_activa(pools.get_sqlite())
+        .await
+        .map_err(|e| e.to_string())?;
+    ExpedienteRepository::create(pools.get_sqlite(), &zona_id, data)
         .await
         .map_err(|e| e.to_string())
 }
 
 /// Actualizar un expediente existente
+/// Los cambios de estado se validan contra la máquina de estados del expediente
 #[tauri::command]
 pub async fn update_expediente(
     pools: State<'_, DatabasePool>,
     id: String,
     data: UpdateExpediente,
 ) -> Result<Expediente, String> {
-    ExpedienteRepository::update(pools.get_sqlite(), &id, data)
+    let zona_id = SettingsService::zona_activa(pools.get_sqlite())
+        .await
+        .map_err(|e| e.to_string())?;
+    EstadoExpedienteService::actualizar(pools.get_sqlite(), &zona_id, &id, data)
         .await
         .map_err(|e| e.to_string())
 }
@@ -75,7 +113,10 @@ pub async fn update_expediente(
 /// Eliminar un expediente
 #[tauri::command]
 pub async fn delete_expediente(pools: State<'_, DatabasePool>, id: String) -> Result<(), String> {
-    ExpedienteRepository::delete(pools.get_sqlite(), &id)
+    let zona_id = SettingsService::zona_activa(pools.get_sqlite())
+        .await
+        .map_err(|e| e.to_string())?;
+    ExpedienteRepository::delete(pools.get_sqlite(), &zona_id, &id)
         .await
         .map_err(|e| e.to_string())
 }
@@ -83,10 +124,153 @@ pub async fn delete_expediente(pools: State<'_, DatabasePool>, id: String) -> Re
 /// Buscar expedientes por texto
 #[tauri::command]
 pub async fn search_expedientes(pools: State<'_, DatabasePool>, query: String) -> Result<Vec<Expediente>, String> {
-    ExpedienteRepository::search(pools.get_sqlite(), &query)
+    let zona_id = SettingsService::zona_activa(pools.get_sqlite())
+        .await
+        .map_err(|e| e.to_string())?;
+    ExpedienteRepository::search(pools.get_sqlite(), &zona_id, &query)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Búsqueda de texto completo (insensible a acentos y mayúsculas)
+/// Admite filtros por campo: gde:, infogov:, prov:, tema:, asunto:, obs:, numero:, caratula:, resumen:
+#[tauri::command]
+pub async fn buscar_expedientes(
+    pools: State<'_, DatabasePool>,
+    query: String,
+    limite: Option<i64>,
+) -> Result<Vec<ResultadoBusqueda>, String> {
+    let zona_id = SettingsService::zona_activa(pools.get_sqlite())
+        .await
+        .map_err(|e| e.to_string())?;
+    ExpedienteRepository::buscar_texto_completo(pools.get_sqlite(), &zona_id, &query, limite.unwrap_or(50))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Obtener el historial de pases (línea de tiempo) de un expediente
+#[tauri::command]
+pub async fn get_historial_expediente(
+    pools: State<'_, DatabasePool>,
+    id: String,
+) -> Result<Vec<ExpedienteMovimiento>, String> {
+    MovimientoRepository::get_by_expediente(pools.get_sqlite(), &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Obtener dónde se encontraba un expediente en una fecha dada (formato YYYY-MM-DD)
+#[tauri::command]
+pub async fn get_ubicacion_expediente(
+    pools: State<'_, DatabasePool>,
+    id: String,
+    fecha: String,
+) -> Result<ExpedienteMovimiento, String> {
+    // Se toma el final del día para incluir los pases registrados esa misma fecha
+    let fecha = chrono::NaiveDate::parse_from_str(&fecha, "%Y-%m-%d")
+        .map_err(|_| format!("Fecha inválida: {} (se espera YYYY-MM-DD)", fecha))?
+        .and_hms_opt(23, 59, 59)
+        .ok_or_else(|| "Fecha inválida".to_string())?
+        .and_utc();
+
+    MovimientoRepository::get_ubicacion_en_fecha(pools.get_sqlite(), &id, fecha)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Procesar y guardar todas las filas de una selección de la bandeja de InfoGov
+/// Si llega el HTML de la grilla se usa ése; el texto plano queda como alternativa
+#[tauri::command]
+pub async fn procesar_lote_infogov(
+    pools: State<'_, DatabasePool>,
+    raw_text: String,
+    raw_html: Option<String>,
+) -> Result<ResumenCapturaInfoGov, String> {
+    let filas = raw_html
+        .as_deref()
+        .and_then(lote_desde_html)
+        .unwrap_or_else(|| InfoGovExpediente::lote_desde_portapapeles(&raw_text));
+    ExpedienteRepository::upsert_lote_infogov(pools.get_sqlite(), filas, OrigenCaptura::Portapapeles)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Elegir la bandeja exportada de InfoGov a importar (CSV o XLSX)
+#[tauri::command]
+pub fn seleccionar_archivo_infogov() -> Result<Option<String>, String> {
+    let selected = rfd::FileDialog::new()
+        .add_filter("Bandeja de InfoGov", &["xlsx", "csv"])
+        .pick_file();
+    Ok(selected.map(|path| path.to_string_lossy().to_string()))
+}
+
+/// Vista previa de la importación de una bandeja exportada: qué expedientes se crean o modifican
+/// No escribe en la base
+#[tauri::command]
+pub async fn previsualizar_importacion_infogov(
+    pools: State<'_, DatabasePool>,
+    ruta: String,
+) -> Result<VistaPreviaImportacion, String> {
+    let filas = lote_desde_archivo(Path::new(&ruta))?;
+    ExpedienteRepository::previsualizar_lote_infogov(pools.get_sqlite(), filas)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Importar una bandeja exportada de InfoGov (CSV o XLSX) con las mismas reglas que la captura
+#[tauri::command]
+pub async fn importar_bandeja_infogov(
+    pools: State<'_, DatabasePool>,
+    ruta: String,
+) -> Result<ResumenCapturaInfoGov, String> {
+    let filas = lote_desde_archivo(Path::new(&ruta))?;
+    ExpedienteRepository::upsert_lote_infogov(pools.get_sqlite(), filas, OrigenCaptura::Archivo)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Obtener el mapeo de estados de InfoGov a estados del expediente
+#[tauri::command]
+pub async fn get_mapeo_estados_infogov(pools: State<'_, DatabasePool>) -> Result<Vec<MapeoEstadoInfoGov>, String> {
+    EstadoInfoGovRepository::get_all(pools.get_sqlite())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Crear o modificar la equivalencia de un estado de InfoGov
+/// Los expedientes en la cola de revisión con ese estado se actualizan en el momento
+#[tauri::command]
+pub async fn guardar_mapeo_estado_infogov(
+    pools: State<'_, DatabasePool>,
+    estado_infogov: String,
+    estado: EstadoExpediente,
+) -> Result<MapeoEstadoInfoGov, String> {
+    EstadoInfoGovRepository::guardar(pools.get_sqlite(), &estado_infogov, estado)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Eliminar la equivalencia de un estado de InfoGov
+#[tauri::command]
+pub async fn eliminar_mapeo_estado_infogov(
+    pools: State<'_, DatabasePool>,
+    estado_infogov: String,
+) -> Result<(), String> {
+    EstadoInfoGovRepository::delete(pools.get_sqlite(), &estado_infogov)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Obtener los estados de InfoGov sin equivalencia (cola de revisión)
+#[tauri::command]
+pub async fn get_estados_infogov_pendientes(
+    pools: State<'_, DatabasePool>,
+) -> Result<Vec<EstadoInfoGovPendiente>, String> {
+    EstadoInfoGovRepository::pendientes(pools.get_sqlite())
         .await
         .map_err(|e| e.to_string())
 }
+
 /// Procesar y guardar un expediente desde el portapapeles (atajo Alt+I)
 /// Parsea el texto capturado, extrae datos de InfoGov y realiza un UPSERT en la base de datos
 #[tauri::command]
@@ -105,20 +289,21 @@ pub async fn procesar_y_guardar_expediente(
     println!("   estado: {}", infogov_exp.estado);
     println!("   resumen: {}", infogov_exp.resumen);
 
-    // Intentar hacer UPSERT
-    let result = ExpedienteRepository::upsert_from_infogov(
-        pools.get_sqlite(),
-        infogov_exp.clone(),
-    )
-    .await
-    .map_err(|e| {
-        eprintln!("Error al procesar expediente: {}", e);
-        format!("Error al procesar expediente: {}", e)
-    })?;
+    // UPSERT como lote de una fila, para que quede en el historial de capturas
+    let fila = FilaInfoGov { fila: 1, texto: raw_text, resultado: Ok(infogov_exp.clone()) };
+    let resumen = ExpedienteRepository::upsert_lote_infogov(pools.get_sqlite(), vec![fila], OrigenCaptura::Atajo)
+        .await
+        .map_err(|e| format!("Error al procesar expediente: {}", e))?;
+
+    if let Some(error) = resumen.errores.first() {
+        eprintln!("Error al procesar expediente: {}", error.error);
+        return Err(format!("Error al procesar expediente: {}", error.error));
+    }
+    let id = resumen.creados.iter().chain(&resumen.actualizados).next().map(|e| e.id.clone());
 
     Ok(ProcesarExpedienteResult {
         success: true,
-        id: Some(result.id.clone()),
+        id,
         resumen: infogov_exp.resumen.clone(),
         mensaje: format!(
             "✅ Expediente {} procesado correctamente",
@@ -128,11 +313,36 @@ pub async fn procesar_y_guardar_expediente(
     })
 }
 
+/// Capturas de InfoGov más recientes, con el texto original y el expediente antes y después
+#[tauri::command]
+pub async fn get_capturas_infogov(
+    pools: State<'_, DatabasePool>,
+    limite: Option<i64>,
+) -> Result<Vec<CapturaInfoGov>, String> {
+    CapturaRepository::recientes(pools.get_sqlite(), limite.unwrap_or(50))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Deshacer las últimas capturas de InfoGov (por defecto, la última)
+#[tauri::command]
+pub async fn deshacer_captura(
+    pools: State<'_, DatabasePool>,
+    cantidad: Option<i64>,
+) -> Result<ResultadoDeshacer, String> {
+    CapturaRepository::deshacer(pools.get_sqlite(), cantidad.unwrap_or(1))
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// Obtener notificaciones y expedientes pendientes
 #[tauri::command]
 pub async fn get_expedientes_notificaciones(pools: State<'_, DatabasePool>) -> Result<serde_json::Value, String> {
-    // Obtener todos los expedientes
-    let expedientes = ExpedienteRepository::get_all(pools.get_sqlite())
+    // Obtener todos los expedientes de la zona
+    let zona_id = SettingsService::zona_activa(pools.get_sqlite())
+        .await
+        .map_err(|e| e.to_string())?;
+    let expedientes = ExpedienteRepository::get_all(pools.get_sqlite(), &zona_id)
         .await
         .map_err(|e| {
             e.to_string()
@@ -390,7 +600,7 @@ pub async fn populate_mock_data(pools: State<'_, DatabasePool>) -> Result<String
             agente_responsable_id: None,
             caratula: None,
             resolucion_nro: None,
-            nro_gde: Some("2026-GDE-123".to_string()),
+            nro_gde: "EX-2026-00000123-GDEMZA-DGIRR".parse().ok(),
             fecha_pase: None,
             oficina: None,
             buzon_grupal: None,
@@ -564,9 +774,12 @@ pub async fn populate_mock_data(pools: State<'_, DatabasePool>) -> Result<String
         },
     ];
 
+    let zona_id = SettingsService::zona_activa(pools.get_sqlite())
+        .await
+        .map_err(|e| e.to_string())?;
     let mut count = 0;
     for exp in expedientes {
-        match ExpedienteRepository::create(pools.get_sqlite(), exp).await {
+        match ExpedienteRepository::create(pools.get_sqlite(), &zona_id, exp).await {
             Ok(_) => count += 1,
             Err(e) => eprintln!("Error insertando expediente: {}", e),
         }
@@ -614,7 +827,10 @@ pub async fn get_gastos_by_vehiculo(
     pools: State<'_, DatabasePool>,
     vehiculo_id: String,
 ) -> Result<Vec<Expediente>, String> {
-    ExpedienteRepository::get_by_vehiculo_id(pools.get_sqlite(), &vehiculo_id)
+    let zona_id = SettingsService::zona_activa(pools.get_sqlite())
+        .await
+        .map_err(|e| e.to_string())?;
+    ExpedienteRepository::get_by_vehiculo_id(pools.get_sqlite(), &zona_id, &vehiculo_id)
         .await
         .map_err(|e| e.to_string())
 }
@@ -625,7 +841,10 @@ pub async fn get_gastos_by_categoria(
     pools: State<'_, DatabasePool>,
     categoria: String,
 ) -> Result<Vec<Expediente>, String> {
-    ExpedienteRepository::get_by_categoria_gasto(pools.get_sqlite(), &categoria)
+    let zona_id = SettingsService::zona_activa(pools.get_sqlite())
+        .await
+        .map_err(|e| e.to_string())?;
+    ExpedienteRepository::get_by_categoria_gasto(pools.get_sqlite(), &zona_id, &categoria)
         .await
         .map_err(|e| e.to_string())
 }
@@ -7,6 +7,8 @@ use sqlx::FromRow;
 use tauri::State;
 
 use crate::db::DatabasePool;
+use crate::repositories::ZonaRepository;
+use crate::services::SettingsService;
 
 #[derive(Debug, FromRow)]
 struct ExpedientePendienteRow {
@@ -29,6 +31,8 @@ pub async fn exportar_excel_pendientes(
     output_dir: Option<String>,
 ) -> Result<String, String> {
     let pool = pools.get_sqlite();
+    let zona_id = SettingsService::zona_activa(pool).await.map_err(|e| e.to_string())?;
+    let zona = ZonaRepository::get_by_id(pool, &zona_id).await.map_err(|e| e.to_string())?;
     let columns = get_expedientes_columns(pool).await?;
 
     let has_pagado = columns.contains("pagado");
@@ -57,6 +61,7 @@ pub async fn exportar_excel_pendientes(
             CAST({monto_col} AS REAL) AS monto
         FROM expedientes
         WHERE UPPER(tipo) = 'PAGO'
+          AND zona_id = ?
           AND {where_pago}
         ORDER BY
           CASE WHEN {fecha_col} IS NULL OR {fecha_col} = '' THEN 1 ELSE 0 END,
@@ -66,6 +71,7 @@ pub async fn exportar_excel_pendientes(
     );
 
     let expedientes = sqlx::query_as::<_, ExpedientePendienteRow>(&query)
+        .bind(&zona_id)
         .fetch_all(pool)
         .await
         .map_err(|e| format!("Error al obtener expedientes pendientes: {}", e))?;
@@ -113,7 +119,7 @@ pub async fn exportar_excel_pendientes(
             clone_template_style(sheet, 4, row, 7);
             clone_template_style(sheet, 4, row, 10);
 
-            set_string_cell(sheet, row, 1, "Malargüe");
+            set_string_cell(sheet, row, 1, &zona.nombre);
             set_string_cell(sheet, row, 2, exp.nro_infogov.as_deref().unwrap_or(""));
             set_string_cell(sheet, row, 3, exp.nro_gde.as_deref().unwrap_or(""));
             set_string_cell(sheet, row, 4, &format_date_ddmmyyyy(exp.fecha_envio_gde.as_deref()));
@@ -131,7 +137,11 @@ pub async fn exportar_excel_pendientes(
 
 #[tauri::command]
 pub async fn exportar_excel_todos(pools: State<'_, DatabasePool>) -> Result<String, String> {
-    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM expedientes")
+    let zona_id = SettingsService::zona_activa(pools.get_sqlite())
+        .await
+        .map_err(|e| e.to_string())?;
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM expedientes WHERE zona_id = ?")
+        .bind(&zona_id)
         .fetch_one(pools.get_sqlite())
         .await
         .map_err(|e| format!("Error al obtener expedientes: {}", e))?;
@@ -140,7 +150,11 @@ pub async fn exportar_excel_todos(pools: State<'_, DatabasePool>) -> Result<Stri
 
 #[tauri::command]
 pub async fn exportar_excel_movilidades(pools: State<'_, DatabasePool>) -> Result<String, String> {
-    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM vehiculos")
+    let zona_id = SettingsService::zona_activa(pools.get_sqlite())
+        .await
+        .map_err(|e| e.to_string())?;
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM vehiculos WHERE zona_id = ?")
+        .bind(&zona_id)
         .fetch_one(pools.get_sqlite())
         .await
         .map_err(|e| format!("Error al obtener vehículos: {}", e))?;
@@ -149,7 +163,11 @@ pub async fn exportar_excel_movilidades(pools: State<'_, DatabasePool>) -> Resul
 
 #[tauri::command]
 pub async fn exportar_excel_personal(pools: State<'_, DatabasePool>) -> Result<String, String> {
-    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM agentes")
+    let zona_id = SettingsService::zona_activa(pools.get_sqlite())
+        .await
+        .map_err(|e| e.to_string())?;
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM agentes WHERE zona_id = ?")
+        .bind(&zona_id)
         .fetch_one(pools.get_sqlite())
         .await
         .map_err(|e| format!("Error al obtener personal: {}", e))?;
@@ -7,6 +7,10 @@ pub mod vehiculos;
 pub mod tickets;
 pub mod agentes;
 pub mod exports;
+pub mod sync;
+pub mod atajos;
+pub mod configuracion;
+pub mod zonas;
 
 pub use expedientes::*;
 pub use ordenes_compra::*;
@@ -14,3 +18,7 @@ pub use vehiculos::*;
 pub use tickets::*;
 pub use agentes::*;
 pub use exports::*;
+pub use sync::*;
+pub use atajos::*;
+pub use configuracion::*;
+pub use zonas::*;
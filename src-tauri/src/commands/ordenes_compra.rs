@@ -1,19 +1,96 @@
 // Comandos Tauri para Órdenes de Compra
 use crate::db::DatabasePool;
+use crate::error::AppError;
+use crate::models::configuracion::Settings;
 use crate::models::orden_compra::*;
-use crate::utils::excel_generator::{ejecutar_conversion_excel_a_pdf_con_datos, ejecutar_llenado_excel_con_datos, OCExcelData, OCRenglon};
+use crate::services::{EstadoOrdenCompraService, SettingsService};
+use crate::utils::conversor_pdf::elegir_conversor;
+use crate::utils::excel_generator::{llenar_plantilla_oc, OCExcelData, OCRenglon};
 use chrono::{Datelike, NaiveDate};
 use serde::Serialize;
-use sqlx::{Row, SqlitePool, PgPool};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{PgConnection, PgPool, Row, SqliteConnection, SqlitePool};
+use std::collections::HashMap;
 use uuid::Uuid;
 
+/// Columnas de OrdenCompra en PostgreSQL (montos y fechas convertidos para el FromRow)
+const COLUMNAS_OC_PG: &str = "id::text, numero_oc, pedido_nro, destino, fecha::text, \
+    expediente_id::text, resolucion_nro, forma_pago, plazo_entrega, \
+    es_iva_inscripto, tipo_contratacion, subtotal::float8, iva::float8, total::float8, \
+    estado, motivo_anulacion, fecha_anulacion::text";
+
+const COLUMNAS_OC_SQLITE: &str = "id, numero_oc, pedido_nro, destino, fecha, expediente_id, resolucion_nro, \
+    forma_pago, plazo_entrega, es_iva_inscripto, tipo_contratacion, subtotal, iva, total, \
+    estado, motivo_anulacion, fecha_anulacion";
+
+// Numeración por zona ($1) y año ($2) con secuencias_oc, dentro de la transacción que guarda la OC
+// (si se deshace, el número no se consume). Cada base lleva su secuencia; como las OC también llegan
+// por la sincronización, el siguiente número nunca es menor que el mayor ya guardado
+
+/// Toma el siguiente número y pedido (bloquea la fila de la secuencia hasta el commit)
+const TOMAR_NUMERO_PG: &str = r#"
+    INSERT INTO secuencias_oc (zona_id, año, ultimo_numero, ultimo_pedido)
+    VALUES (
+        $1::uuid, $2,
+        (SELECT COALESCE(MAX(split_part(numero_oc, '/', 1)::int), 0) + 1 FROM ordenes_compra WHERE zona_id = $1::uuid AND año = $2),
+        (SELECT COALESCE(MAX(pedido_nro), 0) + 1 FROM ordenes_compra WHERE zona_id = $1::uuid AND año = $2)
+    )
+    ON CONFLICT (zona_id, año) DO UPDATE SET
+        ultimo_numero = GREATEST(secuencias_oc.ultimo_numero + 1, EXCLUDED.ultimo_numero),
+        ultimo_pedido = GREATEST(secuencias_oc.ultimo_pedido + 1, EXCLUDED.ultimo_pedido),
+        updated_at = CURRENT_TIMESTAMP
+    RETURNING ultimo_numero, ultimo_pedido
+"#;
+
+/// También la usa la resolución de conflictos para renumerar una OC que repite el número del servidor
+pub(crate) const TOMAR_NUMERO_SQLITE: &str = r#"
+    INSERT INTO secuencias_oc (zona_id, año, ultimo_numero, ultimo_pedido)
+    VALUES (
+        ?1, ?2,
+        (SELECT COALESCE(MAX(CAST(substr(numero_oc, 1, instr(numero_oc, '/') - 1) AS INTEGER)), 0) + 1
+         FROM ordenes_compra WHERE zona_id = ?1 AND año = ?2),
+        (SELECT COALESCE(MAX(pedido_nro), 0) + 1 FROM ordenes_compra WHERE zona_id = ?1 AND año = ?2)
+    )
+    ON CONFLICT (zona_id, año) DO UPDATE SET
+        ultimo_numero = MAX(ultimo_numero + 1, excluded.ultimo_numero),
+        ultimo_pedido = MAX(ultimo_pedido + 1, excluded.ultimo_pedido),
+        updated_at = CURRENT_TIMESTAMP
+    RETURNING ultimo_numero, ultimo_pedido
+"#;
+
+/// El número que tomaría la próxima OC, sin consumirlo (vista previa)
+const PROXIMO_NUMERO_PG: &str = r#"
+    SELECT
+        GREATEST(
+            COALESCE((SELECT ultimo_numero FROM secuencias_oc WHERE zona_id = $1::uuid AND año = $2), 0),
+            COALESCE((SELECT MAX(split_part(numero_oc, '/', 1)::int) FROM ordenes_compra WHERE zona_id = $1::uuid AND año = $2), 0)
+        ) + 1,
+        GREATEST(
+            COALESCE((SELECT ultimo_pedido FROM secuencias_oc WHERE zona_id = $1::uuid AND año = $2), 0),
+            COALESCE((SELECT MAX(pedido_nro) FROM ordenes_compra WHERE zona_id = $1::uuid AND año = $2), 0)
+        ) + 1
+"#;
+
+const PROXIMO_NUMERO_SQLITE: &str = r#"
+    SELECT
+        MAX(
+            COALESCE((SELECT ultimo_numero FROM secuencias_oc WHERE zona_id = ?1 AND año = ?2), 0),
+            COALESCE((SELECT MAX(CAST(substr(numero_oc, 1, instr(numero_oc, '/') - 1) AS INTEGER))
+                      FROM ordenes_compra WHERE zona_id = ?1 AND año = ?2), 0)
+        ) + 1,
+        MAX(
+            COALESCE((SELECT ultimo_pedido FROM secuencias_oc WHERE zona_id = ?1 AND año = ?2), 0),
+            COALESCE((SELECT MAX(pedido_nro) FROM ordenes_compra WHERE zona_id = ?1 AND año = ?2), 0)
+        ) + 1
+"#;
+
 /// Obtener todos los proveedores
 #[tauri::command]
 pub async fn obtener_proveedores(
     pools: tauri::State<'_, DatabasePool>,
 ) -> Result<Vec<Proveedor>, String> {
-    if let Some(pg_pool) = &pools.postgres {
-        match get_proveedores_postgres(pg_pool).await {
+    if let Some(pg_pool) = pools.get_postgres() {
+        match get_proveedores_postgres(&pg_pool).await {
             Ok(proveedores) => {
                 println!("✓ Proveedores obtenidos de PostgreSQL");
                 return Ok(proveedores);
@@ -62,8 +139,8 @@ pub async fn crear_proveedor(
 ) -> Result<Proveedor, String> {
     let id = Uuid::new_v4().to_string();
     
-    if let Some(pg_pool) = &pools.postgres {
-        match create_proveedor_postgres(pg_pool, &id, &data).await {
+    if let Some(pg_pool) = pools.get_postgres() {
+        match create_proveedor_postgres(&pg_pool, &id, &data).await {
             Ok(proveedor) => return Ok(proveedor),
             Err(e) => eprintln!("⚠️ Error PostgreSQL: {}", e),
         }
@@ -115,8 +192,8 @@ async fn create_proveedor_sqlite(pool: &SqlitePool, id: &str, data: &CreateProve
 pub async fn obtener_config_topes(
     pools: tauri::State<'_, DatabasePool>,
 ) -> Result<Vec<ConfigTope>, String> {
-    if let Some(pg_pool) = &pools.postgres {
-        match get_topes_postgres(pg_pool).await {
+    if let Some(pg_pool) = pools.get_postgres() {
+        match get_topes_postgres(&pg_pool).await {
             Ok(topes) => return Ok(topes),
             Err(e) => eprintln!("⚠️ Error PostgreSQL: {}", e),
         }
@@ -151,8 +228,8 @@ pub async fn actualizar_config_tope(
     pools: tauri::State<'_, DatabasePool>,
     data: UpdateConfigTope,
 ) -> Result<ConfigTope, String> {
-    if let Some(pg_pool) = &pools.postgres {
-        match update_tope_postgres(pg_pool, &data).await {
+    if let Some(pg_pool) = pools.get_postgres() {
+        match update_tope_postgres(&pg_pool, &data).await {
             Ok(tope) => return Ok(tope),
             Err(e) => eprintln!("⚠️ Error PostgreSQL: {}", e),
         }
@@ -240,9 +317,11 @@ pub async fn preparar_nueva_oc(
     expediente_id: String,
 ) -> Result<NuevaOCPreparada, String> {
     println!("📦 Preparando OC para expediente: {}", expediente_id);
+
+    let settings = SettingsService::cargar(&pools.sqlite).await.map_err(|e| e.to_string())?;
     
-    if let Some(pg_pool) = &pools.postgres {
-        match preparar_nueva_oc_postgres(pg_pool, &expediente_id).await {
+    if let Some(pg_pool) = pools.get_postgres() {
+        match preparar_nueva_oc_postgres(&pg_pool, &expediente_id, &settings).await {
             Ok(prep) => {
                 println!("✓ OC preparada exitosamente desde PostgreSQL");
                 return Ok(prep);
@@ -253,7 +332,7 @@ pub async fn preparar_nueva_oc(
         }
     }
 
-    match preparar_nueva_oc_sqlite(&pools.sqlite, &expediente_id).await {
+    match preparar_nueva_oc_sqlite(&pools.sqlite, &expediente_id, &settings).await {
         Ok(prep) => {
             println!("✓ OC preparada exitosamente desde SQLite");
             Ok(prep)
@@ -266,37 +345,31 @@ pub async fn preparar_nueva_oc(
     }
 }
 
-async fn preparar_nueva_oc_postgres(pool: &PgPool, expediente_id: &str) -> Result<NuevaOCPreparada, sqlx::Error> {
+async fn preparar_nueva_oc_postgres(pool: &PgPool, expediente_id: &str, settings: &Settings) -> Result<NuevaOCPreparada, sqlx::Error> {
     let expediente = sqlx::query_as::<_, ExpedienteOCData>(
         r#"
         SELECT id::text, numero, año, asunto, nro_infogov, nro_gde, caratula, resolucion_nro,
                oc_señor, oc_domicilio, oc_cuit, oc_descripcion_zona, oc_forma_pago, oc_plazo_entrega
         FROM expedientes
-        WHERE id = $1::uuid
+        WHERE id = $1::uuid AND zona_id = $2::uuid
         "#
     )
     .bind(expediente_id)
+    .bind(&settings.zona.id)
     .fetch_one(pool)
     .await?;
 
     let topes = get_topes_postgres(pool).await?;
 
-    let año_actual = chrono::Utc::now().year();
-    let ultima_oc: Option<String> = sqlx::query_scalar(
-        "SELECT numero_oc FROM ordenes_compra WHERE fecha >= $1 ORDER BY fecha DESC, pedido_nro DESC LIMIT 1"
-    )
-    .bind(format!("{}-01-01", año_actual))
-    .fetch_optional(pool)
-    .await?;
-
-    let numero_oc = generar_numero_oc(ultima_oc, año_actual);
-
-    let pedido_nro: i32 = sqlx::query_scalar(
-        "SELECT COALESCE(MAX(pedido_nro), 0) + 1 FROM ordenes_compra WHERE fecha >= $1"
-    )
-    .bind(format!("{}-01-01", año_actual))
-    .fetch_one(pool)
-    .await?;
+    // Número tentativo: el definitivo se toma de la secuencia al guardar
+    let hoy = fecha_local();
+    let año_actual = hoy.year();
+    let (numero, pedido_nro): (i32, i32) = sqlx::query_as(PROXIMO_NUMERO_PG)
+        .bind(&settings.zona.id)
+        .bind(año_actual)
+        .fetch_one(pool)
+        .await?;
+    let numero_oc = formatear_numero_oc(numero, año_actual);
 
     let subtotal = 0.0;
     let iva = 0.0;
@@ -308,8 +381,8 @@ async fn preparar_nueva_oc_postgres(pool: &PgPool, expediente_id: &str) -> Resul
         expediente: expediente.clone(),
         numero_oc,
         pedido_nro,
-        fecha: chrono::Utc::now().format("%Y-%m-%d").to_string(),
-        destino: expediente.oc_descripcion_zona.clone().unwrap_or_else(|| "ZONA RIEGO MALARGUE".to_string()),
+        fecha: hoy.format("%Y-%m-%d").to_string(),
+        destino: expediente.oc_descripcion_zona.clone().unwrap_or_else(|| settings.zona.destino.clone()),
         forma_pago: expediente.oc_forma_pago.clone().unwrap_or_else(|| "Transferencia".to_string()),
         plazo_entrega: expediente.oc_plazo_entrega.clone().unwrap_or_else(|| "-".to_string()),
         es_iva_inscripto: true,
@@ -321,37 +394,31 @@ async fn preparar_nueva_oc_postgres(pool: &PgPool, expediente_id: &str) -> Resul
     })
 }
 
-async fn preparar_nueva_oc_sqlite(pool: &SqlitePool, expediente_id: &str) -> Result<NuevaOCPreparada, sqlx::Error> {
+async fn preparar_nueva_oc_sqlite(pool: &SqlitePool, expediente_id: &str, settings: &Settings) -> Result<NuevaOCPreparada, sqlx::Error> {
     let expediente = sqlx::query_as::<_, ExpedienteOCData>(
         r#"
         SELECT id, numero, año, asunto, nro_infogov, nro_gde, caratula, resolucion_nro,
                oc_señor, oc_domicilio, oc_cuit, oc_descripcion_zona, oc_forma_pago, oc_plazo_entrega
         FROM expedientes
-        WHERE id = ?
+        WHERE id = ? AND zona_id = ?
         "#
     )
     .bind(expediente_id)
+    .bind(&settings.zona.id)
     .fetch_one(pool)
     .await?;
 
     let topes = get_topes_sqlite(pool).await?;
 
-    let año_actual = chrono::Utc::now().year();
-    let ultima_oc: Option<String> = sqlx::query_scalar(
-        "SELECT numero_oc FROM ordenes_compra WHERE fecha >= ? ORDER BY fecha DESC, pedido_nro DESC LIMIT 1"
-    )
-    .bind(format!("{}-01-01", año_actual))
-    .fetch_optional(pool)
-    .await?;
-
-    let numero_oc = generar_numero_oc(ultima_oc, año_actual);
-
-    let pedido_nro: i32 = sqlx::query_scalar(
-        "SELECT COALESCE(MAX(pedido_nro), 0) + 1 FROM ordenes_compra WHERE fecha >= ?"
-    )
-    .bind(format!("{}-01-01", año_actual))
-    .fetch_one(pool)
-    .await?;
+    // Número tentativo: el definitivo se toma de la secuencia al guardar
+    let hoy = fecha_local();
+    let año_actual = hoy.year();
+    let (numero, pedido_nro): (i32, i32) = sqlx::query_as(PROXIMO_NUMERO_SQLITE)
+        .bind(&settings.zona.id)
+        .bind(año_actual)
+        .fetch_one(pool)
+        .await?;
+    let numero_oc = formatear_numero_oc(numero, año_actual);
 
     let subtotal = 0.0;
     let iva = 0.0;
@@ -363,8 +430,8 @@ async fn preparar_nueva_oc_sqlite(pool: &SqlitePool, expediente_id: &str) -> Res
         expediente: expediente.clone(),
         numero_oc,
         pedido_nro,
-        fecha: chrono::Utc::now().format("%Y-%m-%d").to_string(),
-        destino: expediente.oc_descripcion_zona.clone().unwrap_or_else(|| "ZONA RIEGO MALARGUE".to_string()),
+        fecha: hoy.format("%Y-%m-%d").to_string(),
+        destino: expediente.oc_descripcion_zona.clone().unwrap_or_else(|| settings.zona.destino.clone()),
         forma_pago: expediente.oc_forma_pago.clone().unwrap_or_else(|| "Transferencia".to_string()),
         plazo_entrega: expediente.oc_plazo_entrega.clone().unwrap_or_else(|| "-".to_string()),
         es_iva_inscripto: true,
@@ -382,21 +449,33 @@ pub async fn crear_orden_compra(
     pools: tauri::State<'_, DatabasePool>,
     data: CreateOrdenCompra,
 ) -> Result<OrdenCompraCompleta, String> {
-    if let Some(pg_pool) = &pools.postgres {
-        match create_oc_postgres(pg_pool, data.clone()).await {
+    let settings = SettingsService::cargar(&pools.sqlite).await.map_err(|e| e.to_string())?;
+
+    if let Some(pg_pool) = pools.get_postgres() {
+        match create_oc_postgres(&pg_pool, data.clone(), &settings).await {
             Ok(oc) => return Ok(oc),
             Err(e) => eprintln!("⚠️ Error PostgreSQL: {}", e),
         }
     }
 
-    create_oc_sqlite(&pools.sqlite, data)
+    create_oc_sqlite(&pools.sqlite, data, &settings)
         .await
         .map_err(|e| e.to_string())
 }
 
-async fn create_oc_postgres(pool: &PgPool, data: CreateOrdenCompra) -> Result<OrdenCompraCompleta, sqlx::Error> {
+async fn create_oc_postgres(pool: &PgPool, data: CreateOrdenCompra, settings: &Settings) -> Result<OrdenCompraCompleta, sqlx::Error> {
     let mut tx = pool.begin().await?;
 
+    // Número y pedido de la secuencia de la zona: la fila queda bloqueada hasta el commit
+    let hoy = fecha_local();
+    let año_actual = hoy.year();
+    let (numero, pedido_nro): (i32, i32) = sqlx::query_as(TOMAR_NUMERO_PG)
+        .bind(&settings.zona.id)
+        .bind(año_actual)
+        .fetch_one(&mut *tx)
+        .await?;
+    let numero_oc = formatear_numero_oc(numero, año_actual);
+
     // Obtener topes
     let topes = sqlx::query_as::<_, ConfigTope>(
         "SELECT id, tipo_contratacion, monto_maximo::float8 as monto_maximo FROM config_topes"
@@ -405,41 +484,30 @@ async fn create_oc_postgres(pool: &PgPool, data: CreateOrdenCompra) -> Result<Or
     .await?;
 
     // Calcular totales
-    let (subtotal, iva, total) = calcular_totales(&data.renglones, data.es_iva_inscripto);
+    let (subtotal, iva, total) = calcular_totales(&data.renglones, data.es_iva_inscripto, &settings.iva);
     let tipo_contratacion = determinar_tipo_contratacion(total, &topes);
     let total_en_letras = monto_a_letras(total);
 
-    // Obtener última OC del año actual
-    let año_actual = chrono::Utc::now().year();
-    let ultima_oc: Option<String> = sqlx::query_scalar(
-        "SELECT numero_oc FROM ordenes_compra WHERE fecha >= $1 ORDER BY fecha DESC, pedido_nro DESC LIMIT 1"
-    )
-    .bind(format!("{}-01-01", año_actual))
-    .fetch_optional(&mut *tx)
-    .await?;
-
-    let numero_oc = generar_numero_oc(ultima_oc, año_actual);
     let id = Uuid::new_v4().to_string();
+    let estado = if data.borrador { EstadoOrdenCompra::Borrador } else { EstadoOrdenCompra::Emitida };
 
     // Insertar orden
-    let orden = sqlx::query_as::<_, OrdenCompra>(
+    let orden = sqlx::query_as::<_, OrdenCompra>(&format!(
         r#"
         INSERT INTO ordenes_compra (
             id, numero_oc, destino, expediente_id, resolucion_nro, 
             forma_pago, plazo_entrega, es_iva_inscripto, tipo_contratacion,
-            subtotal, iva, total, fecha
+            subtotal, iva, total, fecha, zona_id, estado, año, pedido_nro
         ) VALUES (
-            $1::uuid, $2, $3, $4::uuid, $5, $6, $7, $8, $9, $10, $11, $12, CURRENT_DATE
+            $1::uuid, $2, $3, $4::uuid, $5, $6, $7, $8, $9, $10, $11, $12, $17::date, $13::uuid, $14, $15, $16
         )
-        RETURNING 
-            id::text, numero_oc, pedido_nro, destino, fecha::text, 
-            expediente_id::text, resolucion_nro, forma_pago, plazo_entrega, 
-            es_iva_inscripto, tipo_contratacion, subtotal::float8, iva::float8, total::float8
-        "#
-    )
+        RETURNING {}
+        "#,
+        COLUMNAS_OC_PG
+    ))
     .bind(&id)
     .bind(&numero_oc)
-    .bind(data.destino.clone().unwrap_or_else(|| "ZONA RIEGO MALARGUE".to_string()))
+    .bind(data.destino.clone().unwrap_or_else(|| settings.zona.destino.clone()))
     .bind(&data.expediente_id)
     .bind(&data.resolucion_nro)
     .bind(&data.forma_pago)
@@ -449,33 +517,15 @@ async fn create_oc_postgres(pool: &PgPool, data: CreateOrdenCompra) -> Result<Or
     .bind(subtotal)
     .bind(iva)
     .bind(total)
+    .bind(&settings.zona.id)
+    .bind(estado.as_str())
+    .bind(año_actual)
+    .bind(pedido_nro)
+    .bind(hoy.format("%Y-%m-%d").to_string())
     .fetch_one(&mut *tx)
     .await?;
 
-    // Insertar renglones
-    let mut renglones = Vec::new();
-    for (idx, renglon_data) in data.renglones.iter().enumerate() {
-        let renglon_id = Uuid::new_v4().to_string();
-        let renglon = sqlx::query_as::<_, OrdenCompraRenglon>(
-            r#"
-            INSERT INTO orden_compra_renglones (
-                id, oc_id, renglon_nro, cantidad, detalle, marca, valor_unitario
-            ) VALUES ($1::uuid, $2::uuid, $3, $4, $5, $6, $7)
-            RETURNING id::text, oc_id::text, renglon_nro, cantidad::float8, detalle, marca, valor_unitario::float8
-            "#
-        )
-        .bind(&renglon_id)
-        .bind(&id)
-        .bind((idx + 1) as i32)
-        .bind(renglon_data.cantidad)
-        .bind(&renglon_data.detalle)
-        .bind(&renglon_data.marca)
-        .bind(renglon_data.valor_unitario)
-        .fetch_one(&mut *tx)
-        .await?;
-        
-        renglones.push(renglon);
-    }
+    let renglones = insertar_renglones_postgres(&mut tx, &id, &data.renglones).await?;
 
     tx.commit().await?;
 
@@ -486,9 +536,20 @@ async fn create_oc_postgres(pool: &PgPool, data: CreateOrdenCompra) -> Result<Or
     })
 }
 
-async fn create_oc_sqlite(pool: &SqlitePool, data: CreateOrdenCompra) -> Result<OrdenCompraCompleta, sqlx::Error> {
+async fn create_oc_sqlite(pool: &SqlitePool, data: CreateOrdenCompra, settings: &Settings) -> Result<OrdenCompraCompleta, sqlx::Error> {
     let mut tx = pool.begin().await?;
 
+    // Número y pedido de la secuencia de la zona; al ser la primera escritura la transacción
+    // toma el bloqueo de la base antes de leer nada
+    let hoy = fecha_local();
+    let año_actual = hoy.year();
+    let (numero, pedido_nro): (i32, i32) = sqlx::query_as(TOMAR_NUMERO_SQLITE)
+        .bind(&settings.zona.id)
+        .bind(año_actual)
+        .fetch_one(&mut *tx)
+        .await?;
+    let numero_oc = formatear_numero_oc(numero, año_actual);
+
     // Obtener topes
     let topes = sqlx::query_as::<_, ConfigTope>(
         "SELECT id, tipo_contratacion, monto_maximo FROM config_topes"
@@ -497,27 +558,13 @@ async fn create_oc_sqlite(pool: &SqlitePool, data: CreateOrdenCompra) -> Result<
     .await?;
 
     // Calcular totales
-    let (subtotal, iva, total) = calcular_totales(&data.renglones, data.es_iva_inscripto);
+    let (subtotal, iva, total) = calcular_totales(&data.renglones, data.es_iva_inscripto, &settings.iva);
     let tipo_contratacion = determinar_tipo_contratacion(total, &topes);
     let total_en_letras = monto_a_letras(total);
 
-    // Obtener última OC del año actual
-    let año_actual = chrono::Utc::now().year();
-    let ultima_oc: Option<String> = sqlx::query_scalar(
-        "SELECT numero_oc FROM ordenes_compra WHERE fecha >= ? ORDER BY fecha DESC, pedido_nro DESC LIMIT 1"
-    )
-    .bind(format!("{}-01-01", año_actual))
-    .fetch_optional(&mut *tx)
-    .await?;
-
-    let numero_oc = generar_numero_oc(ultima_oc, año_actual);
     let id = Uuid::new_v4().to_string();
-    let fecha = chrono::Utc::now().format("%Y-%m-%d").to_string();
-
-    // Obtener siguiente pedido_nro
-    let pedido_nro: i32 = sqlx::query_scalar("SELECT COALESCE(MAX(pedido_nro), 0) + 1 FROM ordenes_compra")
-        .fetch_one(&mut *tx)
-        .await?;
+    let fecha = hoy.format("%Y-%m-%d").to_string();
+    let estado = if data.borrador { EstadoOrdenCompra::Borrador } else { EstadoOrdenCompra::Emitida };
 
     // Insertar orden
     sqlx::query(
@@ -525,14 +572,14 @@ async fn create_oc_sqlite(pool: &SqlitePool, data: CreateOrdenCompra) -> Result<
         INSERT INTO ordenes_compra (
             id, numero_oc, pedido_nro, destino, fecha, expediente_id, resolucion_nro, 
             forma_pago, plazo_entrega, es_iva_inscripto, tipo_contratacion,
-            subtotal, iva, total
-        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            subtotal, iva, total, zona_id, estado, año
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         "#
     )
     .bind(&id)
     .bind(&numero_oc)
     .bind(pedido_nro)
-    .bind(data.destino.clone().unwrap_or_else(|| "ZONA RIEGO MALARGUE".to_string()))
+    .bind(data.destino.clone().unwrap_or_else(|| settings.zona.destino.clone()))
     .bind(&fecha)
     .bind(&data.expediente_id)
     .bind(&data.resolucion_nro)
@@ -543,6 +590,9 @@ async fn create_oc_sqlite(pool: &SqlitePool, data: CreateOrdenCompra) -> Result<
     .bind(subtotal)
     .bind(iva)
     .bind(total)
+    .bind(&settings.zona.id)
+    .bind(estado.as_str())
+    .bind(año_actual)
     .execute(&mut *tx)
     .await?;
 
@@ -550,7 +600,7 @@ async fn create_oc_sqlite(pool: &SqlitePool, data: CreateOrdenCompra) -> Result<
         id: id.clone(),
         numero_oc,
         pedido_nro,
-        destino: data.destino.unwrap_or_else(|| "ZONA RIEGO MALARGUE".to_string()),
+        destino: data.destino.unwrap_or_else(|| settings.zona.destino.clone()),
         fecha,
         expediente_id: data.expediente_id.clone(),
         resolucion_nro: data.resolucion_nro.clone(),
@@ -561,28 +611,179 @@ async fn create_oc_sqlite(pool: &SqlitePool, data: CreateOrdenCompra) -> Result<
         subtotal,
         iva,
         total,
+        estado,
+        motivo_anulacion: None,
+        fecha_anulacion: None,
     };
 
-    // Insertar renglones
-    let mut renglones = Vec::new();
-    for (idx, renglon_data) in data.renglones.iter().enumerate() {
+    let renglones = insertar_renglones_sqlite(&mut tx, &id, &data.renglones).await?;
+
+    tx.commit().await?;
+
+    Ok(OrdenCompraCompleta {
+        orden,
+        renglones,
+        total_en_letras,
+    })
+}
+
+/// Obtener las órdenes de compra de la zona
+#[tauri::command]
+pub async fn obtener_ordenes_compra(
+    pools: tauri::State<'_, DatabasePool>,
+) -> Result<Vec<OrdenCompra>, String> {
+    let zona_id = SettingsService::zona_activa(&pools.sqlite).await.map_err(|e| e.to_string())?;
+
+    if let Some(pg_pool) = pools.get_postgres() {
+        match get_oc_postgres(&pg_pool, &zona_id).await {
+            Ok(ordenes) => return Ok(ordenes),
+            Err(e) => eprintln!("⚠️ Error PostgreSQL: {}", e),
+        }
+    }
+
+    get_oc_sqlite(&pools.sqlite, &zona_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+async fn get_oc_postgres(pool: &PgPool, zona_id: &str) -> Result<Vec<OrdenCompra>, sqlx::Error> {
+    let ordenes = sqlx::query_as::<_, OrdenCompra>(&format!(
+        r#"
+        SELECT {}
+        FROM ordenes_compra 
+        WHERE zona_id = $1::uuid
+        ORDER BY fecha DESC, pedido_nro DESC
+        "#,
+        COLUMNAS_OC_PG
+    ))
+    .bind(zona_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(ordenes)
+}
+
+async fn get_oc_sqlite(pool: &SqlitePool, zona_id: &str) -> Result<Vec<OrdenCompra>, sqlx::Error> {
+    let rows = sqlx::query(&format!(
+        "SELECT {} FROM ordenes_compra WHERE zona_id = ? ORDER BY fecha DESC, pedido_nro DESC",
+        COLUMNAS_OC_SQLITE
+    ))
+    .bind(zona_id)
+    .fetch_all(pool)
+    .await?;
+
+    rows.iter().map(orden_desde_fila).collect()
+}
+
+/// Informe de conciliación de la numeración de OC de la zona activa (huecos y repetidos por año)
+#[tauri::command]
+pub async fn conciliar_numeracion_oc(
+    pools: tauri::State<'_, DatabasePool>,
+) -> Result<InformeNumeracion, String> {
+    let zona_id = SettingsService::zona_activa(&pools.sqlite).await.map_err(|e| e.to_string())?;
+
+    if let Some(pg_pool) = pools.get_postgres() {
+        match get_numeros_oc_postgres(&pg_pool, &zona_id).await {
+            Ok(numeros) => return Ok(conciliar_numeracion(&numeros)),
+            Err(e) => eprintln!("⚠️ Error PostgreSQL: {}", e),
+        }
+    }
+
+    get_numeros_oc_sqlite(&pools.sqlite, &zona_id)
+        .await
+        .map(|numeros| conciliar_numeracion(&numeros))
+        .map_err(|e| e.to_string())
+}
+
+async fn get_numeros_oc_postgres(pool: &PgPool, zona_id: &str) -> Result<Vec<(String, String)>, sqlx::Error> {
+    sqlx::query_as("SELECT id::text, numero_oc FROM ordenes_compra WHERE zona_id = $1::uuid ORDER BY created_at, id")
+        .bind(zona_id)
+        .fetch_all(pool)
+        .await
+}
+
+async fn get_numeros_oc_sqlite(pool: &SqlitePool, zona_id: &str) -> Result<Vec<(String, String)>, sqlx::Error> {
+    sqlx::query_as("SELECT id, numero_oc FROM ordenes_compra WHERE zona_id = ? ORDER BY created_at, id")
+        .bind(zona_id)
+        .fetch_all(pool)
+        .await
+}
+
+fn orden_desde_fila(row: &SqliteRow) -> Result<OrdenCompra, sqlx::Error> {
+    Ok(OrdenCompra {
+        id: row.get("id"),
+        numero_oc: row.get("numero_oc"),
+        pedido_nro: row.get("pedido_nro"),
+        destino: row.get("destino"),
+        fecha: row.get("fecha"),
+        expediente_id: row.get("expediente_id"),
+        resolucion_nro: row.get("resolucion_nro"),
+        forma_pago: row.get("forma_pago"),
+        plazo_entrega: row.get("plazo_entrega"),
+        es_iva_inscripto: row.get::<i32, _>("es_iva_inscripto") == 1,
+        tipo_contratacion: row.get("tipo_contratacion"),
+        subtotal: row.get("subtotal"),
+        iva: row.get("iva"),
+        total: row.get("total"),
+        estado: EstadoOrdenCompra::try_from(row.get::<String, _>("estado")).map_err(|e| sqlx::Error::Decode(e.into()))?,
+        motivo_anulacion: row.get("motivo_anulacion"),
+        fecha_anulacion: row.get("fecha_anulacion"),
+    })
+}
+
+async fn insertar_renglones_postgres(
+    conn: &mut PgConnection,
+    oc_id: &str,
+    renglones: &[CreateRenglon],
+) -> Result<Vec<OrdenCompraRenglon>, sqlx::Error> {
+    let mut insertados = Vec::new();
+    for (idx, renglon_data) in renglones.iter().enumerate() {
+        let renglon = sqlx::query_as::<_, OrdenCompraRenglon>(
+            r#"
+            INSERT INTO orden_compra_renglones (
+                id, oc_id, renglon_nro, cantidad, detalle, marca, valor_unitario
+            ) VALUES ($1::uuid, $2::uuid, $3, $4, $5, $6, $7)
+            RETURNING id::text, oc_id::text, renglon_nro, cantidad::float8, detalle, marca, valor_unitario::float8
+            "#
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(oc_id)
+        .bind((idx + 1) as i32)
+        .bind(renglon_data.cantidad)
+        .bind(&renglon_data.detalle)
+        .bind(&renglon_data.marca)
+        .bind(renglon_data.valor_unitario)
+        .fetch_one(&mut *conn)
+        .await?;
+
+        insertados.push(renglon);
+    }
+    Ok(insertados)
+}
+
+async fn insertar_renglones_sqlite(
+    conn: &mut SqliteConnection,
+    oc_id: &str,
+    renglones: &[CreateRenglon],
+) -> Result<Vec<OrdenCompraRenglon>, sqlx::Error> {
+    let mut insertados = Vec::new();
+    for (idx, renglon_data) in renglones.iter().enumerate() {
         let renglon_id = Uuid::new_v4().to_string();
         sqlx::query(
             "INSERT INTO orden_compra_renglones (id, oc_id, renglon_nro, cantidad, detalle, marca, valor_unitario) VALUES (?, ?, ?, ?, ?, ?, ?)"
         )
         .bind(&renglon_id)
-        .bind(&id)
+        .bind(oc_id)
         .bind((idx + 1) as i32)
         .bind(renglon_data.cantidad)
         .bind(&renglon_data.detalle)
         .bind(&renglon_data.marca)
         .bind(renglon_data.valor_unitario)
-        .execute(&mut *tx)
+        .execute(&mut *conn)
         .await?;
 
-        renglones.push(OrdenCompraRenglon {
+        insertados.push(OrdenCompraRenglon {
             id: renglon_id,
-            oc_id: id.clone(),
+            oc_id: oc_id.to_string(),
             renglon_nro: (idx + 1) as i32,
             cantidad: renglon_data.cantidad,
             detalle: renglon_data.detalle.clone(),
@@ -590,76 +791,493 @@ async fn create_oc_sqlite(pool: &SqlitePool, data: CreateOrdenCompra) -> Result<
             valor_unitario: renglon_data.valor_unitario,
         });
     }
+    Ok(insertados)
+}
 
-    tx.commit().await?;
+// Ciclo de vida: borrador -> emitida -> recibida (parcial) o anulada
+// Las reglas están en EstadoOrdenCompraService; cada consulta corre en una transacción que bloquea la OC
 
-    Ok(OrdenCompraCompleta {
-        orden,
-        renglones,
-        total_en_letras,
-    })
+/// Los errores de base de datos (o una OC que el servidor todavía no recibió) pasan al respaldo local;
+/// las validaciones se informan tal cual
+fn usar_respaldo_local(error: &AppError) -> bool {
+    matches!(error, AppError::Database(_) | AppError::NotFound(_))
 }
 
-/// Obtener todas las órdenes de compra
+/// Modificar una OC en borrador: datos y renglones (el número no cambia)
 #[tauri::command]
-pub async fn obtener_ordenes_compra(
+pub async fn editar_orden_compra(
     pools: tauri::State<'_, DatabasePool>,
-) -> Result<Vec<OrdenCompra>, String> {
-    if let Some(pg_pool) = &pools.postgres {
-        match get_oc_postgres(pg_pool).await {
-            Ok(ordenes) => return Ok(ordenes),
+    id: String,
+    data: UpdateOrdenCompra,
+) -> Result<OrdenCompraCompleta, String> {
+    let settings = SettingsService::cargar(&pools.sqlite).await.map_err(|e| e.to_string())?;
+
+    if let Some(pg_pool) = pools.get_postgres() {
+        match editar_oc_postgres(&pg_pool, &id, &data, &settings).await {
+            Ok(oc) => return Ok(oc),
+            Err(e) if usar_respaldo_local(&e) => eprintln!("⚠️ Error PostgreSQL: {}", e),
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+
+    editar_oc_sqlite(&pools.sqlite, &id, &data, &settings)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Emitir una OC en borrador
+#[tauri::command]
+pub async fn emitir_orden_compra(
+    pools: tauri::State<'_, DatabasePool>,
+    id: String,
+) -> Result<OrdenCompra, String> {
+    let zona_id = SettingsService::zona_activa(&pools.sqlite).await.map_err(|e| e.to_string())?;
+
+    if let Some(pg_pool) = pools.get_postgres() {
+        match emitir_oc_postgres(&pg_pool, &zona_id, &id).await {
+            Ok(orden) => return Ok(orden),
+            Err(e) if usar_respaldo_local(&e) => eprintln!("⚠️ Error PostgreSQL: {}", e),
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+
+    emitir_oc_sqlite(&pools.sqlite, &zona_id, &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Anular una OC (borrador o emitida) con su motivo; el número queda usado
+#[tauri::command]
+pub async fn anular_orden_compra(
+    pools: tauri::State<'_, DatabasePool>,
+    id: String,
+    motivo: String,
+) -> Result<OrdenCompra, String> {
+    let zona_id = SettingsService::zona_activa(&pools.sqlite).await.map_err(|e| e.to_string())?;
+
+    if let Some(pg_pool) = pools.get_postgres() {
+        match anular_oc_postgres(&pg_pool, &zona_id, &id, &motivo).await {
+            Ok(orden) => return Ok(orden),
+            Err(e) if usar_respaldo_local(&e) => eprintln!("⚠️ Error PostgreSQL: {}", e),
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+
+    anular_oc_sqlite(&pools.sqlite, &zona_id, &id, &motivo)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Registrar una entrega del proveedor; la OC pasa a RECIBIDA_PARCIAL o RECIBIDA
+#[tauri::command]
+pub async fn registrar_entrega_orden_compra(
+    pools: tauri::State<'_, DatabasePool>,
+    id: String,
+    data: CreateEntrega,
+) -> Result<OrdenCompra, String> {
+    let fecha = fecha_entrega(&data).map_err(|e| e.to_string())?;
+    let zona_id = SettingsService::zona_activa(&pools.sqlite).await.map_err(|e| e.to_string())?;
+
+    if let Some(pg_pool) = pools.get_postgres() {
+        match registrar_entrega_postgres(&pg_pool, &zona_id, &id, &data, &fecha).await {
+            Ok(orden) => return Ok(orden),
+            Err(e) if usar_respaldo_local(&e) => eprintln!("⚠️ Error PostgreSQL: {}", e),
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+
+    registrar_entrega_sqlite(&pools.sqlite, &zona_id, &id, &data, &fecha)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Entregas registradas de una OC
+#[tauri::command]
+pub async fn obtener_entregas_orden_compra(
+    pools: tauri::State<'_, DatabasePool>,
+    id: String,
+) -> Result<Vec<OrdenCompraEntrega>, String> {
+    let zona_id = SettingsService::zona_activa(&pools.sqlite).await.map_err(|e| e.to_string())?;
+
+    if let Some(pg_pool) = pools.get_postgres() {
+        match get_entregas_postgres(&pg_pool, &zona_id, &id).await {
+            Ok(entregas) => return Ok(entregas),
             Err(e) => eprintln!("⚠️ Error PostgreSQL: {}", e),
         }
     }
 
-    get_oc_sqlite(&pools.sqlite)
+    get_entregas_sqlite(&pools.sqlite, &zona_id, &id)
         .await
         .map_err(|e| e.to_string())
 }
 
-async fn get_oc_postgres(pool: &PgPool) -> Result<Vec<OrdenCompra>, sqlx::Error> {
-    let ordenes = sqlx::query_as::<_, OrdenCompra>(
+/// Fecha de la entrega (YYYY-MM-DD), hoy si no se indicó
+fn fecha_entrega(data: &CreateEntrega) -> Result<String, AppError> {
+    match data.fecha.as_deref().map(str::trim).filter(|f| !f.is_empty()) {
+        Some(fecha) => NaiveDate::parse_from_str(fecha, "%Y-%m-%d")
+            .map(|f| f.format("%Y-%m-%d").to_string())
+            .map_err(|_| AppError::Validation(format!("Fecha de entrega inválida: {}", fecha))),
+        None => Ok(fecha_local().format("%Y-%m-%d").to_string()),
+    }
+}
+
+/// Fecha de hoy en la hora del puesto: en UTC la OC cargada de noche cae al día siguiente
+/// y el 31/12 tomaría número del año que viene
+fn fecha_local() -> NaiveDate {
+    chrono::Local::now().naive_local().date()
+}
+
+async fn get_oc_por_id_postgres(conn: &mut PgConnection, zona_id: &str, id: &str) -> Result<OrdenCompra, AppError> {
+    sqlx::query_as::<_, OrdenCompra>(&format!(
+        "SELECT {} FROM ordenes_compra WHERE id = $1::uuid AND zona_id = $2::uuid FOR UPDATE",
+        COLUMNAS_OC_PG
+    ))
+    .bind(id)
+    .bind(zona_id)
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Orden de compra {}", id)))
+}
+
+async fn get_oc_por_id_sqlite(conn: &mut SqliteConnection, zona_id: &str, id: &str) -> Result<OrdenCompra, AppError> {
+    let row = sqlx::query(&format!("SELECT {} FROM ordenes_compra WHERE id = ? AND zona_id = ?", COLUMNAS_OC_SQLITE))
+        .bind(id)
+        .bind(zona_id)
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Orden de compra {}", id)))?;
+    Ok(orden_desde_fila(&row)?)
+}
+
+async fn get_renglones_postgres(conn: &mut PgConnection, oc_id: &str) -> Result<Vec<OrdenCompraRenglon>, sqlx::Error> {
+    sqlx::query_as::<_, OrdenCompraRenglon>(
         r#"
-        SELECT 
-            id::text, numero_oc, pedido_nro, destino, fecha::text, 
-            expediente_id::text, resolucion_nro, forma_pago, plazo_entrega, 
-            es_iva_inscripto, tipo_contratacion, subtotal::float8, iva::float8, total::float8
-        FROM ordenes_compra 
-        ORDER BY fecha DESC, pedido_nro DESC
+        SELECT id::text, oc_id::text, renglon_nro, cantidad::float8, detalle, marca, valor_unitario::float8
+        FROM orden_compra_renglones
+        WHERE oc_id = $1::uuid
+        ORDER BY renglon_nro
         "#
     )
-    .fetch_all(pool)
+    .bind(oc_id)
+    .fetch_all(&mut *conn)
+    .await
+}
+
+async fn get_renglones_sqlite(conn: &mut SqliteConnection, oc_id: &str) -> Result<Vec<OrdenCompraRenglon>, sqlx::Error> {
+    sqlx::query_as::<_, OrdenCompraRenglon>(
+        "SELECT id, oc_id, renglon_nro, cantidad, detalle, marca, valor_unitario FROM orden_compra_renglones WHERE oc_id = ? ORDER BY renglon_nro"
+    )
+    .bind(oc_id)
+    .fetch_all(&mut *conn)
+    .await
+}
+
+async fn editar_oc_postgres(pool: &PgPool, id: &str, data: &UpdateOrdenCompra, settings: &Settings) -> Result<OrdenCompraCompleta, AppError> {
+    let zona_id = &settings.zona.id;
+    let mut tx = pool.begin().await?;
+
+    let actual = get_oc_por_id_postgres(&mut tx, zona_id, id).await?;
+    EstadoOrdenCompraService::validar_edicion(&actual.estado)?;
+
+    let topes = sqlx::query_as::<_, ConfigTope>(
+        "SELECT id, tipo_contratacion, monto_maximo::float8 as monto_maximo FROM config_topes"
+    )
+    .fetch_all(&mut *tx)
     .await?;
-    Ok(ordenes)
+
+    let (subtotal, iva, total) = calcular_totales(&data.renglones, data.es_iva_inscripto, &settings.iva);
+    let tipo_contratacion = determinar_tipo_contratacion(total, &topes);
+
+    let orden = sqlx::query_as::<_, OrdenCompra>(&format!(
+        r#"
+        UPDATE ordenes_compra
+        SET destino = $1, resolucion_nro = $2, forma_pago = $3, plazo_entrega = $4, es_iva_inscripto = $5,
+            tipo_contratacion = $6, subtotal = $7, iva = $8, total = $9, updated_at = CURRENT_TIMESTAMP
+        WHERE id = $10::uuid
+        RETURNING {}
+        "#,
+        COLUMNAS_OC_PG
+    ))
+    .bind(data.destino.clone().unwrap_or_else(|| actual.destino.clone()))
+    .bind(&data.resolucion_nro)
+    .bind(&data.forma_pago)
+    .bind(data.plazo_entrega.clone().unwrap_or_else(|| "-".to_string()))
+    .bind(data.es_iva_inscripto)
+    .bind(&tipo_contratacion)
+    .bind(subtotal)
+    .bind(iva)
+    .bind(total)
+    .bind(id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sqlx::query("DELETE FROM orden_compra_renglones WHERE oc_id = $1::uuid")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    let renglones = insertar_renglones_postgres(&mut tx, id, &data.renglones).await?;
+
+    tx.commit().await?;
+
+    Ok(OrdenCompraCompleta {
+        orden,
+        renglones,
+        total_en_letras: monto_a_letras(total),
+    })
 }
 
-async fn get_oc_sqlite(pool: &SqlitePool) -> Result<Vec<OrdenCompra>, sqlx::Error> {
-    let rows = sqlx::query(
-        "SELECT id, numero_oc, pedido_nro, destino, fecha, expediente_id, resolucion_nro, forma_pago, plazo_entrega, es_iva_inscripto, tipo_contratacion, subtotal, iva, total FROM ordenes_compra ORDER BY fecha DESC, pedido_nro DESC"
+async fn editar_oc_sqlite(pool: &SqlitePool, id: &str, data: &UpdateOrdenCompra, settings: &Settings) -> Result<OrdenCompraCompleta, AppError> {
+    let zona_id = &settings.zona.id;
+    let mut tx = pool.begin().await?;
+
+    let actual = get_oc_por_id_sqlite(&mut tx, zona_id, id).await?;
+    EstadoOrdenCompraService::validar_edicion(&actual.estado)?;
+
+    let topes = sqlx::query_as::<_, ConfigTope>(
+        "SELECT id, tipo_contratacion, monto_maximo FROM config_topes"
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let (subtotal, iva, total) = calcular_totales(&data.renglones, data.es_iva_inscripto, &settings.iva);
+    let tipo_contratacion = determinar_tipo_contratacion(total, &topes);
+
+    sqlx::query(
+        r#"
+        UPDATE ordenes_compra
+        SET destino = ?, resolucion_nro = ?, forma_pago = ?, plazo_entrega = ?, es_iva_inscripto = ?,
+            tipo_contratacion = ?, subtotal = ?, iva = ?, total = ?, updated_at = CURRENT_TIMESTAMP
+        WHERE id = ?
+        "#
     )
-    .fetch_all(pool)
+    .bind(data.destino.clone().unwrap_or_else(|| actual.destino.clone()))
+    .bind(&data.resolucion_nro)
+    .bind(&data.forma_pago)
+    .bind(data.plazo_entrega.clone().unwrap_or_else(|| "-".to_string()))
+    .bind(data.es_iva_inscripto as i32)
+    .bind(&tipo_contratacion)
+    .bind(subtotal)
+    .bind(iva)
+    .bind(total)
+    .bind(id)
+    .execute(&mut *tx)
     .await?;
 
-    let ordenes = rows.into_iter().map(|row| {
-        OrdenCompra {
-            id: row.get("id"),
-            numero_oc: row.get("numero_oc"),
-            pedido_nro: row.get("pedido_nro"),
-            destino: row.get("destino"),
-            fecha: row.get("fecha"),
-            expediente_id: row.get("expediente_id"),
-            resolucion_nro: row.get("resolucion_nro"),
-            forma_pago: row.get("forma_pago"),
-            plazo_entrega: row.get("plazo_entrega"),
-            es_iva_inscripto: row.get::<i32, _>("es_iva_inscripto") == 1,
-            tipo_contratacion: row.get("tipo_contratacion"),
-            subtotal: row.get("subtotal"),
-            iva: row.get("iva"),
-            total: row.get("total"),
-        }
-    }).collect();
+    sqlx::query("DELETE FROM orden_compra_renglones WHERE oc_id = ?")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    let renglones = insertar_renglones_sqlite(&mut tx, id, &data.renglones).await?;
+    let orden = get_oc_por_id_sqlite(&mut tx, zona_id, id).await?;
 
-    Ok(ordenes)
+    tx.commit().await?;
+
+    Ok(OrdenCompraCompleta {
+        orden,
+        renglones,
+        total_en_letras: monto_a_letras(total),
+    })
+}
+
+async fn emitir_oc_postgres(pool: &PgPool, zona_id: &str, id: &str) -> Result<OrdenCompra, AppError> {
+    let mut tx = pool.begin().await?;
+
+    let actual = get_oc_por_id_postgres(&mut tx, zona_id, id).await?;
+    let renglones = get_renglones_postgres(&mut tx, id).await?;
+    EstadoOrdenCompraService::validar_emision(&actual.estado, renglones.len())?;
+
+    let orden = sqlx::query_as::<_, OrdenCompra>(&format!(
+        "UPDATE ordenes_compra SET estado = $1, updated_at = CURRENT_TIMESTAMP WHERE id = $2::uuid RETURNING {}",
+        COLUMNAS_OC_PG
+    ))
+    .bind(EstadoOrdenCompra::Emitida.as_str())
+    .bind(id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(orden)
+}
+
+async fn emitir_oc_sqlite(pool: &SqlitePool, zona_id: &str, id: &str) -> Result<OrdenCompra, AppError> {
+    let mut tx = pool.begin().await?;
+
+    let actual = get_oc_por_id_sqlite(&mut tx, zona_id, id).await?;
+    let renglones = get_renglones_sqlite(&mut tx, id).await?;
+    EstadoOrdenCompraService::validar_emision(&actual.estado, renglones.len())?;
+
+    sqlx::query("UPDATE ordenes_compra SET estado = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?")
+        .bind(EstadoOrdenCompra::Emitida.as_str())
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    let orden = get_oc_por_id_sqlite(&mut tx, zona_id, id).await?;
+
+    tx.commit().await?;
+    Ok(orden)
+}
+
+async fn anular_oc_postgres(pool: &PgPool, zona_id: &str, id: &str, motivo: &str) -> Result<OrdenCompra, AppError> {
+    let mut tx = pool.begin().await?;
+
+    let actual = get_oc_por_id_postgres(&mut tx, zona_id, id).await?;
+    let motivo = EstadoOrdenCompraService::validar_anulacion(&actual.estado, motivo)?;
+
+    let orden = sqlx::query_as::<_, OrdenCompra>(&format!(
+        r#"
+        UPDATE ordenes_compra
+        SET estado = $1, motivo_anulacion = $2, fecha_anulacion = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP
+        WHERE id = $3::uuid
+        RETURNING {}
+        "#,
+        COLUMNAS_OC_PG
+    ))
+    .bind(EstadoOrdenCompra::Anulada.as_str())
+    .bind(&motivo)
+    .bind(id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(orden)
+}
+
+async fn anular_oc_sqlite(pool: &SqlitePool, zona_id: &str, id: &str, motivo: &str) -> Result<OrdenCompra, AppError> {
+    let mut tx = pool.begin().await?;
+
+    let actual = get_oc_por_id_sqlite(&mut tx, zona_id, id).await?;
+    let motivo = EstadoOrdenCompraService::validar_anulacion(&actual.estado, motivo)?;
+
+    sqlx::query(
+        r#"
+        UPDATE ordenes_compra
+        SET estado = ?, motivo_anulacion = ?, fecha_anulacion = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP
+        WHERE id = ?
+        "#
+    )
+    .bind(EstadoOrdenCompra::Anulada.as_str())
+    .bind(&motivo)
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+    let orden = get_oc_por_id_sqlite(&mut tx, zona_id, id).await?;
+
+    tx.commit().await?;
+    Ok(orden)
+}
+
+async fn registrar_entrega_postgres(pool: &PgPool, zona_id: &str, id: &str, data: &CreateEntrega, fecha: &str) -> Result<OrdenCompra, AppError> {
+    let mut tx = pool.begin().await?;
+
+    let actual = get_oc_por_id_postgres(&mut tx, zona_id, id).await?;
+    let renglones = get_renglones_postgres(&mut tx, id).await?;
+    let recibido: HashMap<String, f64> = sqlx::query_as::<_, (String, f64)>(
+        "SELECT renglon_id::text, SUM(cantidad)::float8 FROM orden_compra_entregas WHERE oc_id = $1::uuid GROUP BY renglon_id"
+    )
+    .bind(id)
+    .fetch_all(&mut *tx)
+    .await?
+    .into_iter()
+    .collect();
+    let nuevo = EstadoOrdenCompraService::estado_tras_entrega(&actual.estado, &renglones, &recibido, data)?;
+
+    for item in &data.renglones {
+        sqlx::query(
+            r#"
+            INSERT INTO orden_compra_entregas (id, oc_id, renglon_id, cantidad, fecha, remito, observaciones)
+            VALUES ($1::uuid, $2::uuid, $3::uuid, $4, $5::date, $6, $7)
+            "#
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(id)
+        .bind(&item.renglon_id)
+        .bind(item.cantidad)
+        .bind(fecha)
+        .bind(&data.remito)
+        .bind(&data.observaciones)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    let orden = sqlx::query_as::<_, OrdenCompra>(&format!(
+        "UPDATE ordenes_compra SET estado = $1, updated_at = CURRENT_TIMESTAMP WHERE id = $2::uuid RETURNING {}",
+        COLUMNAS_OC_PG
+    ))
+    .bind(nuevo.as_str())
+    .bind(id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(orden)
+}
+
+async fn registrar_entrega_sqlite(pool: &SqlitePool, zona_id: &str, id: &str, data: &CreateEntrega, fecha: &str) -> Result<OrdenCompra, AppError> {
+    let mut tx = pool.begin().await?;
+
+    let actual = get_oc_por_id_sqlite(&mut tx, zona_id, id).await?;
+    let renglones = get_renglones_sqlite(&mut tx, id).await?;
+    let recibido: HashMap<String, f64> = sqlx::query_as::<_, (String, f64)>(
+        "SELECT renglon_id, SUM(cantidad) FROM orden_compra_entregas WHERE oc_id = ? GROUP BY renglon_id"
+    )
+    .bind(id)
+    .fetch_all(&mut *tx)
+    .await?
+    .into_iter()
+    .collect();
+    let nuevo = EstadoOrdenCompraService::estado_tras_entrega(&actual.estado, &renglones, &recibido, data)?;
+
+    for item in &data.renglones {
+        sqlx::query(
+            "INSERT INTO orden_compra_entregas (id, oc_id, renglon_id, cantidad, fecha, remito, observaciones) VALUES (?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(id)
+        .bind(&item.renglon_id)
+        .bind(item.cantidad)
+        .bind(fecha)
+        .bind(&data.remito)
+        .bind(&data.observaciones)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    sqlx::query("UPDATE ordenes_compra SET estado = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?")
+        .bind(nuevo.as_str())
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    let orden = get_oc_por_id_sqlite(&mut tx, zona_id, id).await?;
+
+    tx.commit().await?;
+    Ok(orden)
+}
+
+async fn get_entregas_postgres(pool: &PgPool, zona_id: &str, oc_id: &str) -> Result<Vec<OrdenCompraEntrega>, sqlx::Error> {
+    sqlx::query_as::<_, OrdenCompraEntrega>(
+        r#"
+        SELECT id::text, oc_id::text, renglon_id::text, cantidad::float8, fecha::text, remito, observaciones
+        FROM orden_compra_entregas
+        WHERE oc_id = $1::uuid
+          AND oc_id IN (SELECT id FROM ordenes_compra WHERE zona_id = $2::uuid)
+        ORDER BY fecha, created_at
+        "#
+    )
+    .bind(oc_id)
+    .bind(zona_id)
+    .fetch_all(pool)
+    .await
+}
+
+async fn get_entregas_sqlite(pool: &SqlitePool, zona_id: &str, oc_id: &str) -> Result<Vec<OrdenCompraEntrega>, sqlx::Error> {
+    sqlx::query_as::<_, OrdenCompraEntrega>(
+        "SELECT id, oc_id, renglon_id, cantidad, fecha, remito, observaciones FROM orden_compra_entregas \
+         WHERE oc_id = ? AND oc_id IN (SELECT id FROM ordenes_compra WHERE zona_id = ?) ORDER BY fecha, created_at"
+    )
+    .bind(oc_id)
+    .bind(zona_id)
+    .fetch_all(pool)
+    .await
 }
 
 /// Generar PDF de Orden de Compra
@@ -687,6 +1305,9 @@ pub struct GenerarPDFOCRequest {
     pub forma_pago: String,
     pub plazo_entrega: String,
     pub es_iva_inscripto: bool,
+    /// Estado de la OC guardada (None: OC nueva todavía sin guardar); las anuladas llevan el sello ANULADA
+    #[serde(default)]
+    pub estado: Option<EstadoOrdenCompra>,
 }
 
 #[derive(serde::Deserialize)]
@@ -699,218 +1320,172 @@ pub struct PDFRenglon {
     pub total: f64,
 }
 
+/// El PDF se produce con el conversor configurado (LibreOffice o el renderizador nativo)
+/// Devuelve un archivo con todas las copias o uno por copia, según `opciones.salida`
 #[tauri::command]
-pub async fn generar_pdf(data: GenerarPDFOCRequest, output_dir: Option<String>) -> Result<String, String> {
+pub async fn generar_pdf(
+    pools: tauri::State<'_, DatabasePool>,
+    data: GenerarPDFOCRequest,
+    output_dir: Option<String>,
+    opciones: Option<OpcionesCopias>,
+) -> Result<Vec<String>, String> {
     println!("📄 Generando PDF para OC {}", data.numero_oc);
 
+    let settings = SettingsService::cargar(&pools.sqlite).await.map_err(|e| e.to_string())?;
+    let opciones = opciones.unwrap_or_default();
+    validar_opciones_copias(&opciones)?;
+
     tauri::async_runtime::spawn_blocking(move || {
         let t0 = std::time::Instant::now();
-        // Obtener ruta de la plantilla Excel
-        let exe_dir = std::env::current_exe()
-            .map_err(|e| format!("Error al obtener directorio ejecutable: {}", e))?;
-        let exe_parent = exe_dir.parent()
-            .ok_or("No se pudo obtener directorio padre del ejecutable")?;
-
-        // Buscar plantilla en resources
-        let template_path = exe_parent.join("resources/templates/MODELO_ORDEN_DE_COMPRA.xlsx");
-
-        if !template_path.exists() {
-            return Err(format!("Plantilla Excel no encontrada en: {:?}", template_path));
-        }
-        println!("⏱️ plantilla verificada: {} ms", t0.elapsed().as_millis());
+        let template_path = ruta_plantilla_oc()?;
+        let conversor = elegir_conversor(&settings).map_err(|e| e.to_string())?;
+        println!("⏱️ conversor elegido ({}): {} ms", conversor.nombre(), t0.elapsed().as_millis());
 
         // Construir rutas de salida
         let docs_dir = resolve_output_dir(output_dir)?;
         std::fs::create_dir_all(&docs_dir)
             .map_err(|e| format!("Error al crear directorio: {}", e))?;
 
-        let safe_oc_number = data.numero_oc.replace("/", "-");
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map_err(|e| format!("Error al obtener timestamp: {}", e))?
-            .as_secs();
-        let excel_filename = format!("OC-{}-temp-{}.xlsx", safe_oc_number, timestamp);
-        let pdf_filename = format!("OC-{}-{}.pdf", safe_oc_number, timestamp);
-
-        let temp_excel_path = docs_dir.join(&excel_filename);
-        let pdf_path = docs_dir.join(&pdf_filename);
-        println!("⏱️ rutas preparadas: {} ms", t0.elapsed().as_millis());
-
-        // Crear datos Excel
-        let destino_limpio = data.destino
-            .replace("Zona Riego", "")
-            .replace("ZONA RIEGO", "")
-            .trim()
-            .to_string();
-
-        // Formatear fecha de ISO (2026-02-11) a español (11 de febrero de 2026)
-        let fecha_formateada = formatear_fecha_español(&data.fecha)
-            .unwrap_or_else(|_| data.fecha.clone());
-
-        // Formatear expediente completo con GDE/InfoGov si existe
-        let expediente_completo = formatear_expediente_completo(
-            &data.expediente_numero,
-            data.expediente_año,
-            data.nro_gde.as_deref(),
-            data.nro_infogov.as_deref()
-        );
-
-        let excel_data = OCExcelData {
-            numero_oc: data.numero_oc.clone(),
-            pedido_nro: data.pedido_nro,
-            destino: destino_limpio,
-            fecha: fecha_formateada,
-            expediente_numero: expediente_completo,
-            expediente_año: data.expediente_año,
-            resolucion_nro: data.resolucion_nro.clone(),
-            tipo_contratacion: data.tipo_contratacion.clone(),
-            señor: data.señor.clone(),
-            domicilio: data.domicilio.clone(),
-            cuit: data.cuit.clone(),
-            descripcion_zona: data.descripcion_zona.clone(),
-            renglones: data.renglones.iter().map(|r| OCRenglon {
-                numero: r.numero,
-                cantidad: r.cantidad,
-                concepto: r.concepto.clone(),
-                marca: r.marca.clone(),
-                valor_unitario: r.valor_unitario,
-                total: r.total,
-            }).collect(),
-            subtotal: data.subtotal,
-            iva: data.iva,
-            total: data.total,
-            total_en_letras: data.total_en_letras.clone(),
-            forma_pago: data.forma_pago.clone(),
-            plazo_entrega: data.plazo_entrega.clone(),
-            es_iva_inscripto: data.es_iva_inscripto,
-        };
-        println!("⏱️ datos excel armados: {} ms", t0.elapsed().as_millis());
-
-        // Copiar plantilla sin modificar
-        std::fs::copy(&template_path, &temp_excel_path)
-            .map_err(|e| format!("Error al copiar plantilla: {}", e))?;
-
-        println!("✓ Plantilla copiada: {:?}", temp_excel_path);
-        println!("⏱️ plantilla copiada: {} ms", t0.elapsed().as_millis());
-
-        // Convertir Excel a PDF usando PowerShell (que también escribirá los datos)
-        ejecutar_conversion_excel_a_pdf_con_datos(
-            temp_excel_path.to_str().ok_or("Ruta de Excel inválida")?,
-            pdf_path.to_str().ok_or("Ruta de PDF inválida")?,
-            excel_data,
-        )
-        .map_err(|e| format!("Error al convertir a PDF: {}", e))?;
-        println!("⏱️ conversión a PDF: {} ms", t0.elapsed().as_millis());
-
-        let mut final_pdf_path = pdf_path.clone();
-        let mut found = false;
-        for _ in 0..10 {
-            if final_pdf_path.exists() {
-                found = true;
-                break;
-            }
-            std::thread::sleep(std::time::Duration::from_millis(500));
+        let (safe_oc_number, timestamp) = nombre_archivo_oc(&data.numero_oc)?;
+        let mut excel_data = armar_datos_excel(&data, &settings);
+        excel_data.marca_agua = opciones.marca_agua(&settings.impresion);
+
+        let mut generados = Vec::new();
+        for copias in opciones.archivos(&data.tipo_contratacion, &settings.impresion) {
+            let sufijo = sufijo_archivo(&opciones, &copias);
+            let temp_excel_path = docs_dir.join(format!("OC-{}{}-temp-{}.xlsx", safe_oc_number, sufijo, timestamp));
+            let pdf_path = docs_dir.join(format!("OC-{}{}-{}.pdf", safe_oc_number, sufijo, timestamp));
+            excel_data.copias = copias;
+
+            // Completar una copia de la plantilla y convertirla
+            std::fs::copy(&template_path, &temp_excel_path)
+                .map_err(|e| format!("Error al copiar plantilla: {}", e))?;
+            let resultado = llenar_plantilla_oc(&temp_excel_path, &excel_data)
+                .and_then(|_| conversor.convertir(&temp_excel_path, &excel_data, &pdf_path));
+            let _ = std::fs::remove_file(&temp_excel_path);
+            resultado.map_err(|e| e.to_string())?;
+            println!("⏱️ conversión a PDF: {} ms", t0.elapsed().as_millis());
+
+            let path_str = pdf_path.to_str()
+                .ok_or("No se pudo convertir la ruta a string")?
+                .to_string();
+            println!("✓ PDF generado exitosamente en: {}", path_str);
+            generados.push(path_str);
         }
 
-        if !found {
-            if let Some(stem) = pdf_path.file_stem().and_then(|s| s.to_str()) {
-                let prefix = match stem.rsplit_once('-') {
-                    Some((base, _)) => format!("{}-", base),
-                    None => stem.to_string(),
-                };
-                let mut candidates: Vec<(std::path::PathBuf, std::time::SystemTime)> = Vec::new();
-                if let Ok(entries) = std::fs::read_dir(&docs_dir) {
-                    for entry in entries.flatten() {
-                        let path = entry.path();
-                        if path.extension().and_then(|e| e.to_str()).map(|e| e.eq_ignore_ascii_case("pdf")).unwrap_or(false) {
-                            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                                if name.starts_with(&prefix) {
-                                    if let Ok(modified) = entry.metadata().and_then(|m| m.modified()) {
-                                        candidates.push((path, modified));
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-
-                if let Some((path, _)) = candidates.into_iter().max_by_key(|(_, m)| *m) {
-                    final_pdf_path = path;
-                    found = true;
-                }
-            }
-        }
+        Ok(generados)
+    })
+    .await
+    .map_err(|e| format!("Error en hilo de generación de PDF: {}", e))?
+}
 
-        if !found {
-            return Err("El PDF no fue generado correctamente".to_string());
-        }
-        println!("⏱️ verificación PDF: {} ms", t0.elapsed().as_millis());
+#[tauri::command]
+pub async fn generar_excel(
+    pools: tauri::State<'_, DatabasePool>,
+    data: GenerarPDFOCRequest,
+    output_dir: Option<String>,
+    opciones: Option<OpcionesCopias>,
+) -> Result<Vec<String>, String> {
+    println!("📄 Generando Excel para OC {}", data.numero_oc);
 
-        println!("✓ PDF generado exitosamente en: {:?}", final_pdf_path);
+    let settings = SettingsService::cargar(&pools.sqlite).await.map_err(|e| e.to_string())?;
+    let opciones = opciones.unwrap_or_default();
+    validar_opciones_copias(&opciones)?;
 
-        let path_str = final_pdf_path.to_str()
-            .ok_or("No se pudo convertir la ruta a string")?
-            .to_string();
+    tauri::async_runtime::spawn_blocking(move || {
+        let template_path = ruta_plantilla_oc()?;
 
-        println!("✓ PDF generado exitosamente en: {}", path_str);
-        println!("⏱️ total generar_pdf: {} ms", t0.elapsed().as_millis());
-        Ok(path_str)
+        // Construir rutas de salida
+        let docs_dir = resolve_output_dir(output_dir)?;
+        std::fs::create_dir_all(&docs_dir)
+            .map_err(|e| format!("Error al crear directorio: {}", e))?;
+
+        let (safe_oc_number, timestamp) = nombre_archivo_oc(&data.numero_oc)?;
+        let mut excel_data = armar_datos_excel(&data, &settings);
+        excel_data.marca_agua = opciones.marca_agua(&settings.impresion);
+
+        let mut generados = Vec::new();
+        for copias in opciones.archivos(&data.tipo_contratacion, &settings.impresion) {
+            let sufijo = sufijo_archivo(&opciones, &copias);
+            let excel_path = docs_dir.join(format!("OC-{}{}-{}.xlsx", safe_oc_number, sufijo, timestamp));
+            excel_data.copias = copias;
+
+            std::fs::copy(&template_path, &excel_path)
+                .map_err(|e| format!("Error al copiar plantilla: {}", e))?;
+            llenar_plantilla_oc(&excel_path, &excel_data).map_err(|e| e.to_string())?;
+
+            let path_str = excel_path.to_str()
+                .ok_or("No se pudo convertir la ruta a string")?
+                .to_string();
+            println!("✓ Excel generado exitosamente en: {}", path_str);
+            generados.push(path_str);
+        }
+
+        Ok(generados)
     })
     .await
-    .map_err(|e| format!("Error en hilo de generación de PDF: {}", e))?
+    .map_err(|e| format!("Error en hilo de generación de Excel: {}", e))?
 }
 
-#[tauri::command]
-pub async fn generar_excel(data: GenerarPDFOCRequest, output_dir: Option<String>) -> Result<String, String> {
-    println!("📄 Generando Excel para OC {}", data.numero_oc);
-    
-    // Obtener ruta de la plantilla Excel
+fn validar_opciones_copias(opciones: &OpcionesCopias) -> Result<(), String> {
+    if opciones.copias.as_ref().is_some_and(|copias| copias.is_empty()) {
+        return Err("Debe elegir al menos una copia".to_string());
+    }
+    Ok(())
+}
+
+/// Con un archivo por copia el nombre lleva la leyenda (OC-15-2026-DUPLICADO-...)
+fn sufijo_archivo(opciones: &OpcionesCopias, copias: &[Copia]) -> String {
+    match (opciones.salida, copias) {
+        (SalidaCopias::ArchivoPorCopia, [copia]) => format!("-{}", copia.etiqueta()),
+        _ => String::new(),
+    }
+}
+
+/// Plantilla Excel de la OC (resources/templates junto al ejecutable)
+fn ruta_plantilla_oc() -> Result<std::path::PathBuf, String> {
     let exe_dir = std::env::current_exe()
         .map_err(|e| format!("Error al obtener directorio ejecutable: {}", e))?;
     let exe_parent = exe_dir.parent()
         .ok_or("No se pudo obtener directorio padre del ejecutable")?;
-    
-    // Buscar plantilla en resources
+
     let template_path = exe_parent.join("resources/templates/MODELO_ORDEN_DE_COMPRA.xlsx");
-    
     if !template_path.exists() {
         return Err(format!("Plantilla Excel no encontrada en: {:?}", template_path));
     }
-    
-    // Construir rutas de salida
-    let docs_dir = resolve_output_dir(output_dir)?;
-    std::fs::create_dir_all(&docs_dir)
-        .map_err(|e| format!("Error al crear directorio: {}", e))?;
-    
-    let safe_oc_number = data.numero_oc.replace("/", "-");
+    Ok(template_path)
+}
+
+/// Número de OC apto para nombre de archivo y marca de tiempo para no pisar generaciones anteriores
+fn nombre_archivo_oc(numero_oc: &str) -> Result<(String, u64), String> {
     let timestamp = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .map_err(|e| format!("Error al obtener timestamp: {}", e))?
         .as_secs();
-    let excel_filename = format!("OC-{}-{}.xlsx", safe_oc_number, timestamp);
-    
-    let excel_path = docs_dir.join(&excel_filename);
-    
-    // Crear datos Excel
+    Ok((numero_oc.replace('/', "-"), timestamp))
+}
+
+/// Datos de la planilla con fecha y expediente ya formateados para imprimir
+fn armar_datos_excel(data: &GenerarPDFOCRequest, settings: &Settings) -> OCExcelData {
     let destino_limpio = data.destino
         .replace("Zona Riego", "")
         .replace("ZONA RIEGO", "")
         .trim()
         .to_string();
-    
+
     // Formatear fecha de ISO (2026-02-11) a español (11 de febrero de 2026)
-    let fecha_formateada = formatear_fecha_español(&data.fecha)
+    let fecha_formateada = formatear_fecha_español(&data.fecha, &settings.zona.localidad)
         .unwrap_or_else(|_| data.fecha.clone());
-    
+
     // Formatear expediente completo con GDE/InfoGov si existe
     let expediente_completo = formatear_expediente_completo(
-        &data.expediente_numero, 
+        &data.expediente_numero,
         data.expediente_año,
         data.nro_gde.as_deref(),
         data.nro_infogov.as_deref()
     );
-    
-    let excel_data = OCExcelData {
+
+    OCExcelData {
         numero_oc: data.numero_oc.clone(),
         pedido_nro: data.pedido_nro,
         destino: destino_limpio,
@@ -938,31 +1513,16 @@ pub async fn generar_excel(data: GenerarPDFOCRequest, output_dir: Option<String>
         forma_pago: data.forma_pago.clone(),
         plazo_entrega: data.plazo_entrega.clone(),
         es_iva_inscripto: data.es_iva_inscripto,
-    };
-    
-    // Copiar plantilla sin modificar
-    std::fs::copy(&template_path, &excel_path)
-        .map_err(|e| format!("Error al copiar plantilla: {}", e))?;
-    
-    println!("✓ Plantilla copiada: {:?}", excel_path);
-    
-    // Llenar Excel usando PowerShell
-    ejecutar_llenado_excel_con_datos(
-        excel_path.to_str().ok_or("Ruta de Excel inválida")?,
-        excel_data,
-    )
-    .map_err(|e| format!("Error al generar Excel: {}", e))?;
-
-    let path_str = excel_path.to_str()
-        .ok_or("No se pudo convertir la ruta a string")?
-        .to_string();
-
-    println!("✓ Excel generado exitosamente en: {}", path_str);
-    Ok(path_str)
+        alicuota_iva: settings.iva.texto(data.es_iva_inscripto),
+        // Las completa quien genera cada archivo
+        copias: vec![Copia::Original],
+        marca_agua: false,
+        anulada: data.estado == Some(EstadoOrdenCompra::Anulada),
+    }
 }
 
-/// Formatear fecha de ISO (2026-02-11) a español (11 de febrero de 2026)
-fn formatear_fecha_español(fecha_iso: &str) -> Result<String, String> {
+/// Formatear fecha de ISO (2026-02-11) a español con la localidad (Mendoza, 11 de febrero de 2026)
+fn formatear_fecha_español(fecha_iso: &str, localidad: &str) -> Result<String, String> {
     let fecha = NaiveDate::parse_from_str(fecha_iso, "%Y-%m-%d")
         .map_err(|e| format!("Error al parsear fecha: {}", e))?;
     
@@ -974,7 +1534,7 @@ fn formatear_fecha_español(fecha_iso: &str) -> Result<String, String> {
     let mes = meses.get(fecha.month0() as usize)
         .ok_or("Mes inválido")?;
     
-    Ok(format!("Mendoza, {} de {} de {}", fecha.day(), mes, fecha.year()))
+    Ok(format!("{}, {} de {} de {}", localidad, fecha.day(), mes, fecha.year()))
 }
 
 fn resolve_output_dir(output_dir: Option<String>) -> Result<std::path::PathBuf, String> {
@@ -0,0 +1,64 @@
+// Tauri Commands para la sincronización con PostgreSQL
+
+use tauri::State;
+
+use crate::db::DatabasePool;
+use crate::sync::{
+    reintentar_pendientes, sincronizar, ConflictoRepository, ConflictoSincronizacion, OutboxRepository,
+    ResolucionConflicto, ResultadoPush, ResultadoSincronizacion, ResumenOutbox,
+};
+
+/// Cambios locales pendientes de enviar, agrupados por tabla
+#[tauri::command]
+pub async fn get_pendientes_sincronizacion(pools: State<'_, DatabasePool>) -> Result<Vec<ResumenOutbox>, String> {
+    OutboxRepository::resumen(pools.get_sqlite())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Reintentar ya todos los cambios pendientes (ignora el backoff)
+#[tauri::command]
+pub async fn reintentar_sincronizacion(pools: State<'_, DatabasePool>) -> Result<ResultadoPush, String> {
+    let postgres = pools
+        .get_postgres()
+        .ok_or_else(|| "PostgreSQL no disponible (modo offline)".to_string())?;
+
+    reintentar_pendientes(pools.inner(), &postgres)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Sincronizar ya: envía todo lo pendiente y descarga los cambios remotos
+/// Devuelve lo enviado y, por tabla, lo recibido
+#[tauri::command]
+pub async fn sync_now(pools: State<'_, DatabasePool>) -> Result<ResultadoSincronizacion, String> {
+    let postgres = pools
+        .get_postgres()
+        .ok_or_else(|| "PostgreSQL no disponible (modo offline)".to_string())?;
+
+    sincronizar(pools.inner(), &postgres, i64::MAX)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Conflictos de sincronización pendientes de resolver
+#[tauri::command]
+pub async fn get_conflictos_sincronizacion(
+    pools: State<'_, DatabasePool>,
+) -> Result<Vec<ConflictoSincronizacion>, String> {
+    ConflictoRepository::pendientes(pools.get_sqlite())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Resolver un conflicto: mantener la versión local, la remota o combinar campos
+#[tauri::command]
+pub async fn resolver_conflicto_sincronizacion(
+    pools: State<'_, DatabasePool>,
+    id: String,
+    resolucion: ResolucionConflicto,
+) -> Result<(), String> {
+    ConflictoRepository::resolver(pools.get_sqlite(), &id, &resolucion)
+        .await
+        .map_err(|e| e.to_string())
+}
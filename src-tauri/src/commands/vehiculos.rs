@@ -2,147 +2,73 @@
 // Estas funciones son invocadas desde el frontend con invoke()
 
 use tauri::State;
-use uuid::Uuid;
-use std::sync::Arc;
-use chrono::Utc;
 
-use crate::db::DatabaseManager;
+use crate::db::DatabasePool;
 use crate::models::vehiculo::{Vehiculo, CreateVehiculo};
+use crate::repositories::VehiculoRepository;
+use crate::services::SettingsService;
 
-/// Obtener todos los vehículos
+/// Obtener todos los vehículos de la zona
 #[tauri::command]
-pub async fn get_all_vehiculos(db: State<'_, Arc<DatabaseManager>>) -> Result<Vec<Vehiculo>, String> {
-    let pool = db.sqlite();
-    
-    let vehiculos = sqlx::query_as::<_, Vehiculo>(
-        r#"
-        SELECT * FROM vehiculos 
-        WHERE activo = true
-        ORDER BY patente ASC
-        "#
-    )
-    .fetch_all(pool)
-    .await
-    .map_err(|e| format!("Error al obtener vehículos: {}", e))?;
-    
-    Ok(vehiculos)
+pub async fn get_all_vehiculos(pools: State<'_, DatabasePool>) -> Result<Vec<Vehiculo>, String> {
+    let zona_id = SettingsService::zona_activa(pools.get_sqlite())
+        .await
+        .map_err(|e| e.to_string())?;
+    VehiculoRepository::get_activos(pools.get_sqlite(), &zona_id)
+        .await
+        .map_err(|e| e.to_string())
 }
 
-/// Obtener un vehículo por ID
+/// Obtener un vehículo de la zona por ID
 #[tauri::command]
-pub async fn get_vehiculo(db: State<'_, Arc<DatabaseManager>>, id: String) -> Result<Vehiculo, String> {
-    let uuid = Uuid::parse_str(&id)
-        .map_err(|_| "ID inválido".to_string())?;
-    
-    let pool = db.sqlite();
-    
-    let vehiculo = sqlx::query_as::<_, Vehiculo>(
-        "SELECT * FROM vehiculos WHERE id = ?"
-    )
-    .bind(uuid)
-    .fetch_one(pool)
-    .await
-    .map_err(|e| format!("Error al obtener vehículo: {}", e))?;
-    
-    Ok(vehiculo)
+pub async fn get_vehiculo(pools: State<'_, DatabasePool>, id: String) -> Result<Vehiculo, String> {
+    let zona_id = SettingsService::zona_activa(pools.get_sqlite())
+        .await
+        .map_err(|e| e.to_string())?;
+    VehiculoRepository::get_by_id(pools.get_sqlite(), &zona_id, &id)
+        .await
+        .map_err(|e| e.to_string())
 }
 
-/// Crear un nuevo vehículo
+/// Crear un nuevo vehículo en la zona
 #[tauri::command]
 pub async fn create_vehiculo(
-    db: State<'_, Arc<DatabaseManager>>,
+    pools: State<'_, DatabasePool>,
     data: CreateVehiculo
 ) -> Result<Vehiculo, String> {
-    let pool = db.sqlite();
-    let id = Uuid::new_v4();
-    let now = Utc::now();
-    
-    sqlx::query(
-        r#"
-        INSERT INTO vehiculos (
-            id, patente, marca, modelo, año, tipo,
-            numero_motor, numero_chasis, color,
-            activo, kilometraje_actual, capacidad_tanque,
-            area_asignada, created_at, updated_at
-        )
-        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-        "#
-    )
-    .bind(id)
-    .bind(&data.patente)
-    .bind(&data.marca)
-    .bind(&data.modelo)
-    .bind(data.año)
-    .bind(&data.tipo)
-    .bind(&data.numero_motor)
-    .bind(&data.numero_chasis)
-    .bind(&data.color)
-    .bind(true)
-    .bind(data.kilometraje_actual)
-    .bind(data.capacidad_tanque)
-    .bind(&data.area_asignada)
-    .bind(now)
-    .bind(now)
-    .execute(pool)
-    .await
-    .map_err(|e| format!("Error al crear vehículo: {}", e))?;
-    
-    // Obtener el vehículo creado
-    get_vehiculo(db, id.to_string()).await
+    let zona_id = SettingsService::zona_activa(pools.get_sqlite())
+        .await
+        .map_err(|e| e.to_string())?;
+    VehiculoRepository::create(pools.get_sqlite(), &zona_id, data)
+        .await
+        .map_err(|e| e.to_string())
 }
 
 /// Actualizar el kilometraje de un vehículo
 #[tauri::command]
 pub async fn update_kilometraje(
-    db: State<'_, Arc<DatabaseManager>>,
+    pools: State<'_, DatabasePool>,
     id: String,
     kilometraje: f64
 ) -> Result<(), String> {
-    let uuid = Uuid::parse_str(&id)
-        .map_err(|_| "ID inválido".to_string())?;
-    
-    let pool = db.sqlite();
-    
-    sqlx::query(
-        r#"
-        UPDATE vehiculos 
-        SET kilometraje_actual = ?, updated_at = ?
-        WHERE id = ?
-        "#
-    )
-    .bind(kilometraje)
-    .bind(Utc::now())
-    .bind(uuid)
-    .execute(pool)
-    .await
-    .map_err(|e| format!("Error al actualizar kilometraje: {}", e))?;
-    
-    Ok(())
+    let zona_id = SettingsService::zona_activa(pools.get_sqlite())
+        .await
+        .map_err(|e| e.to_string())?;
+    VehiculoRepository::update_kilometraje(pools.get_sqlite(), &zona_id, &id, kilometraje)
+        .await
+        .map_err(|e| e.to_string())
 }
 
 /// Eliminar un vehículo (soft delete)
 #[tauri::command]
 pub async fn delete_vehiculo(
-    db: State<'_, Arc<DatabaseManager>>,
+    pools: State<'_, DatabasePool>,
     id: String
 ) -> Result<(), String> {
-    let uuid = Uuid::parse_str(&id)
-        .map_err(|_| "ID inválido".to_string())?;
-    
-    let pool = db.sqlite();
-    
-    sqlx::query(
-        r#"
-        UPDATE vehiculos 
-        SET activo = false, updated_at = ?
-        WHERE id = ?
-        "#
-    )
-    .bind(Utc::now())
-    .bind(uuid)
-    .execute(pool)
-    .await
-    .map_err(|e| format!("Error al eliminar vehículo: {}", e))?;
-    
-    Ok(())
+    let zona_id = SettingsService::zona_activa(pools.get_sqlite())
+        .await
+        .map_err(|e| e.to_string())?;
+    VehiculoRepository::delete(pools.get_sqlite(), &zona_id, &id)
+        .await
+        .map_err(|e| e.to_string())
 }
@@ -0,0 +1,23 @@
+// Tauri Commands para las zonas de riego (jefaturas)
+
+use tauri::State;
+
+use crate::db::DatabasePool;
+use crate::models::zona::{CreateZona, Zona};
+use crate::repositories::ZonaRepository;
+
+/// Obtener las zonas activas (para elegir la de la instalación)
+#[tauri::command]
+pub async fn get_zonas(pools: State<'_, DatabasePool>) -> Result<Vec<Zona>, String> {
+    ZonaRepository::get_activas(pools.get_sqlite())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Crear una zona nueva (se sincroniza con el servidor como el resto de las tablas)
+#[tauri::command]
+pub async fn create_zona(pools: State<'_, DatabasePool>, data: CreateZona) -> Result<Zona, String> {
+    ZonaRepository::create(pools.get_sqlite(), data)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -1,18 +1,45 @@
 use sqlx::sqlite::{SqlitePool, SqlitePoolOptions, SqliteConnectOptions};
 use sqlx::postgres::{PgPool, PgPoolOptions};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
 use std::str::FromStr;
 use std::path::Path;
 use std::fs;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 use crate::error::Result;
-use crate::db::migrations::run_sqlite_migrations;
+use crate::db::migrador;
+
+/// Estado de conexión de las bases de datos (se envía al frontend)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EstadoBaseDatos {
+    /// SQLite siempre está disponible (offline-first)
+    pub sqlite_conectado: bool,
+    /// Hay URL de PostgreSQL en la configuración de la instalación
+    pub postgres_configurado: bool,
+    pub postgres_conectado: bool,
+    /// Último chequeo o reconexión exitosa contra PostgreSQL
+    pub ultimo_exito: Option<DateTime<Utc>>,
+    /// Último chequeo o reconexión fallida
+    pub ultimo_fallo: Option<DateTime<Utc>>,
+    pub ultimo_error: Option<String>,
+}
 
-/// Pool de conexiones SQLite y PostgreSQL
+/// Servicio de base de datos de la aplicación: pool SQLite y PostgreSQL
+/// Es el único estado de base de datos registrado en Tauri; todos los commands lo reciben como State<DatabasePool>
+/// El pool PostgreSQL puede aparecer o desaparecer en tiempo de ejecución (ver db::supervisor)
 #[derive(Clone)]
 pub struct DatabasePool {
     /// Pool SQLite para almacenamiento local offline
     pub sqlite: SqlitePool,
-    /// Pool PostgreSQL para sincronización remota (opcional)
-    pub postgres: Option<PgPool>,
+    /// Pool PostgreSQL para sincronización remota (None mientras no hay conexión)
+    postgres: Arc<RwLock<Option<PgPool>>>,
+    /// URL de PostgreSQL, para reconectar (puede cambiar desde la configuración)
+    postgres_url: Arc<RwLock<Option<String>>>,
+    /// Resultado de los últimos intentos de conexión
+    estado: Arc<RwLock<EstadoBaseDatos>>,
+    /// Turno de sincronización: el worker y los commands no procesan la outbox a la vez
+    sincronizacion: Arc<tokio::sync::Mutex<()>>,
 }
 
 impl DatabasePool {
@@ -25,36 +52,106 @@ impl DatabasePool {
         // Inicializar SQLite
         let sqlite = create_sqlite_pool(sqlite_path).await?;
         
-        // Inicializar PostgreSQL si la URL está disponible
-        let postgres = if let Some(url) = postgres_url {
-            match create_postgres_pool(url).await {
-                Ok(pool) => Some(pool),
-                Err(e) => {
-                    eprintln!("⚠️ PostgreSQL no disponible: {}. Usando SQLite en modo offline.", e);
-                    None
-                }
-            }
-        } else {
-            None
+        let pools = DatabasePool {
+            sqlite,
+            postgres: Arc::new(RwLock::new(None)),
+            postgres_url: Arc::new(RwLock::new(None)),
+            estado: Arc::new(RwLock::new(EstadoBaseDatos {
+                sqlite_conectado: true,
+                ..Default::default()
+            })),
+            sincronizacion: Arc::new(tokio::sync::Mutex::new(())),
+        };
+
+        pools.configurar_postgres(postgres_url).await;
+
+        Ok(pools)
+    }
+
+    /// Cambia la URL de PostgreSQL (None = solo SQLite) e intenta conectar
+    /// El pool anterior se descarta; si la conexión falla, el supervisor sigue reintentando
+    pub async fn configurar_postgres(&self, postgres_url: Option<&str>) {
+        if let Ok(mut url) = self.postgres_url.write() {
+            *url = postgres_url.map(str::to_string);
+        }
+        if let Ok(mut estado) = self.estado.write() {
+            estado.postgres_configurado = postgres_url.is_some();
+            estado.ultimo_error = None;
+        }
+        self.set_postgres(None);
+
+        let Some(url) = postgres_url else {
+            return;
         };
 
-        Ok(DatabasePool { sqlite, postgres })
+        match create_postgres_pool(url).await {
+            Ok(pool) => {
+                self.set_postgres(Some(pool));
+                self.registrar_exito();
+            }
+            Err(e) => {
+                eprintln!("⚠️ PostgreSQL no disponible: {}. Usando SQLite en modo offline.", e);
+                self.registrar_fallo(&e.to_string());
+            }
+        }
     }
 
     /// Verifica si hay conexión PostgreSQL disponible
     pub fn has_postgres(&self) -> bool {
-        self.postgres.is_some()
+        self.postgres.read().map(|p| p.is_some()).unwrap_or(false)
     }
 
-    /// Obtiene referencia al pool PostgreSQL
-    pub fn get_postgres(&self) -> Option<&PgPool> {
-        self.postgres.as_ref()
+    /// Obtiene el pool PostgreSQL (si está conectado)
+    /// PgPool es un handle compartido: clonarlo no abre conexiones nuevas
+    pub fn get_postgres(&self) -> Option<PgPool> {
+        self.postgres.read().ok().and_then(|p| p.clone())
     }
 
     /// Obtiene referencia al pool SQLite
     pub fn get_sqlite(&self) -> &SqlitePool {
         &self.sqlite
     }
+
+    /// URL de PostgreSQL configurada
+    pub fn postgres_url(&self) -> Option<String> {
+        self.postgres_url.read().ok().and_then(|url| url.clone())
+    }
+
+    /// Reemplaza el pool PostgreSQL (None al perder la conexión)
+    pub fn set_postgres(&self, pool: Option<PgPool>) {
+        if let Ok(mut postgres) = self.postgres.write() {
+            *postgres = pool;
+        }
+    }
+
+    /// Espera el turno de sincronización y lo retiene hasta soltar el guard
+    /// Se toma para toda la pasada (outbox y descarga): dos envíos de la misma entrada se verían como conflicto
+    pub async fn turno_sincronizacion(&self) -> tokio::sync::MutexGuard<'_, ()> {
+        self.sincronizacion.lock().await
+    }
+
+    /// Estado actual de las conexiones
+    pub fn estado(&self) -> EstadoBaseDatos {
+        let mut estado = self.estado.read().map(|e| e.clone()).unwrap_or_default();
+        estado.postgres_conectado = self.has_postgres();
+        estado
+    }
+
+    /// Registra un chequeo exitoso contra PostgreSQL
+    pub fn registrar_exito(&self) {
+        if let Ok(mut estado) = self.estado.write() {
+            estado.ultimo_exito = Some(Utc::now());
+            estado.ultimo_error = None;
+        }
+    }
+
+    /// Registra un chequeo o reconexión fallida contra PostgreSQL
+    pub fn registrar_fallo(&self, error: &str) {
+        if let Ok(mut estado) = self.estado.write() {
+            estado.ultimo_fallo = Some(Utc::now());
+            estado.ultimo_error = Some(error.to_string());
+        }
+    }
 }
 
 /// Crea un pool de conexiones SQLite
@@ -83,9 +180,10 @@ async fn create_sqlite_pool(db_path: &str) -> Result<SqlitePool> {
 }
 
 /// Crea un pool de conexiones PostgreSQL
-async fn create_postgres_pool(database_url: &str) -> Result<PgPool> {
+pub(crate) async fn create_postgres_pool(database_url: &str) -> Result<PgPool> {
     let pool = PgPoolOptions::new()
         .max_connections(10)
+        .acquire_timeout(Duration::from_secs(10))
         .connect(database_url)
         .await?;
 
@@ -99,22 +197,24 @@ async fn create_postgres_pool(database_url: &str) -> Result<PgPool> {
 /// # Argumentos
 /// * `pools` - Pool de conexiones
 pub async fn init_databases(pools: &DatabasePool) -> Result<()> {
-    // Ejecutar migraciones en SQLite (base de datos local)
-    run_sqlite_migrations(&pools.sqlite).await?;
+    // Migraciones versionadas en SQLite (base de datos local)
+    migrador::migrar_sqlite(&pools.sqlite).await?;
     println!("✓ Migraciones completadas en SQLite");
 
-    // Ejecutar migraciones de OC en SQLite
-    crate::db::migrations_oc::run_sqlite_oc_migrations(&pools.sqlite).await?;
-
     // Si PostgreSQL está disponible, ejecutar migraciones allá también
-    if let Some(postgres) = &pools.postgres {
-        // Ejecutar migraciones de OC en PostgreSQL
-        crate::db::migrations_oc::run_postgres_oc_migrations(postgres).await?;
+    if let Some(postgres) = pools.get_postgres() {
+        init_postgres(&postgres).await?;
     }
 
     Ok(())
 }
 
+/// Migraciones de PostgreSQL (al iniciar o al reconectar si el servidor no estaba disponible)
+pub async fn init_postgres(postgres: &PgPool) -> Result<()> {
+    migrador::migrar_postgres(postgres).await?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -124,4 +224,18 @@ mod tests {
         let pool = create_sqlite_pool(":memory:").await;
         assert!(pool.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_estado_conexion() {
+        let pools = DatabasePool::new(":memory:", None).await.unwrap();
+        let estado = pools.estado();
+        assert!(estado.sqlite_conectado);
+        assert!(!estado.postgres_configurado && !estado.postgres_conectado);
+
+        pools.registrar_fallo("timeout");
+        pools.registrar_exito();
+        let estado = pools.estado();
+        assert!(estado.ultimo_fallo.is_some() && estado.ultimo_exito.is_some());
+        assert!(estado.ultimo_error.is_none());
+    }
 }
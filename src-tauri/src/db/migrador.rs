@@ -0,0 +1,792 @@
+// Migraciones versionadas de SQLite y PostgreSQL
+// Un único conjunto ordenado (ver migrations, migrations_oc y migrations_sync); cada base registra
+// en esquema_migraciones las versiones aplicadas con su checksum
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::{Connection, PgConnection, PgPool, SqliteConnection, SqlitePool};
+use std::collections::HashMap;
+use std::time::Instant;
+
+use crate::db::DatabasePool;
+use crate::error::{AppError, Result};
+
+/// Clave del advisory lock de PostgreSQL: evita que dos instancias migren el servidor a la vez
+const BLOQUEO_MIGRACIONES_PG: i64 = 0x4745_5354_4f52;
+
+/// Motor de base de datos sobre el que corre una migración
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Motor {
+    Sqlite,
+    Postgres,
+}
+
+/// Paso de una migración
+#[derive(Debug, Clone)]
+pub enum Paso {
+    /// Una o más sentencias SQL
+    Sql(String),
+    /// Agrega la columna sólo si falta (instalaciones anteriores al control de versiones)
+    AgregarColumna {
+        tabla: &'static str,
+        columna: &'static str,
+        tipo: &'static str,
+    },
+    /// Reemplaza una tabla con esquema anterior (detectado por `columna_legada`) copiando sus datos
+    /// `esquema` lleva el marcador {tabla}; `copia` inserta en {tabla}_nueva desde la tabla vieja
+    ReconstruirTabla {
+        tabla: &'static str,
+        columna_legada: &'static str,
+        esquema: &'static str,
+        copia: &'static str,
+    },
+}
+
+impl Paso {
+    pub fn sql(sql: impl Into<String>) -> Self {
+        Paso::Sql(sql.into())
+    }
+
+    /// Texto canónico del paso (entra en el checksum)
+    fn canonico(&self) -> String {
+        match self {
+            Paso::Sql(sql) => sql.trim().to_string(),
+            Paso::AgregarColumna { tabla, columna, tipo } => {
+                format!("ADD COLUMN {}.{} {}", tabla, columna, tipo)
+            }
+            Paso::ReconstruirTabla { tabla, columna_legada, esquema, copia } => format!(
+                "REBUILD {} WHEN {}\n{}\n{}",
+                tabla,
+                columna_legada,
+                esquema.trim(),
+                copia.trim()
+            ),
+        }
+    }
+}
+
+/// Pasos de una migración para un motor, con su reversión opcional
+#[derive(Debug, Clone)]
+pub struct Script {
+    pub up: Vec<Paso>,
+    pub down: Option<Vec<Paso>>,
+}
+
+impl Script {
+    /// Migración irreversible
+    pub fn new(up: Vec<Paso>) -> Self {
+        Script { up, down: None }
+    }
+
+    pub fn reversible(mut self, down: Vec<Paso>) -> Self {
+        self.down = Some(down);
+        self
+    }
+}
+
+/// Migración del conjunto; puede aplicar a uno o a ambos motores
+#[derive(Debug, Clone)]
+pub struct Migracion {
+    pub version: i64,
+    pub descripcion: &'static str,
+    pub sqlite: Option<Script>,
+    pub postgres: Option<Script>,
+}
+
+impl Migracion {
+    pub fn script(&self, motor: Motor) -> Option<&Script> {
+        match motor {
+            Motor::Sqlite => self.sqlite.as_ref(),
+            Motor::Postgres => self.postgres.as_ref(),
+        }
+    }
+
+    /// SHA-256 de los pasos `up` del motor: detecta migraciones editadas después de aplicarse
+    pub fn checksum(&self, motor: Motor) -> Option<String> {
+        let script = self.script(motor)?;
+        let mut hasher = Sha256::new();
+        for paso in &script.up {
+            hasher.update(paso.canonico().as_bytes());
+            hasher.update(b"\n");
+        }
+        Some(hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect())
+    }
+}
+
+/// Conjunto completo de migraciones, ordenado por versión
+pub fn migraciones() -> Vec<Migracion> {
+    let mut todas = crate::db::migrations::migraciones();
+    todas.extend(crate::db::migrations_oc::migraciones());
+    todas.extend(crate::db::migrations_sync::migraciones());
+    todas.sort_by_key(|m| m.version);
+    todas
+}
+
+/// Fila de esquema_migraciones
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct MigracionAplicada {
+    pub version: i64,
+    pub descripcion: String,
+    pub checksum: String,
+    pub aplicada_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum EstadoAplicacion {
+    Aplicada,
+    Pendiente,
+    /// Aplicada con un checksum distinto al actual
+    Modificada,
+    /// Registrada en la base pero ausente en esta versión de la aplicación
+    Desconocida,
+}
+
+/// Línea del reporte `migrate status`
+#[derive(Debug, Clone, Serialize)]
+pub struct EstadoMigracion {
+    pub version: i64,
+    pub descripcion: String,
+    pub estado: EstadoAplicacion,
+    pub reversible: bool,
+    pub aplicada_at: Option<DateTime<Utc>>,
+}
+
+/// Reporte de estado de ambas bases (PostgreSQL es None si no hay conexión)
+#[derive(Debug, Clone, Serialize)]
+pub struct EstadoMigraciones {
+    pub sqlite: Vec<EstadoMigracion>,
+    pub postgres: Option<Vec<EstadoMigracion>>,
+}
+
+/// Compara el conjunto de migraciones de un motor con las versiones aplicadas en la base
+pub fn estado(migraciones: &[Migracion], motor: Motor, aplicadas: &[MigracionAplicada]) -> Vec<EstadoMigracion> {
+    let por_version: HashMap<i64, &MigracionAplicada> = aplicadas.iter().map(|a| (a.version, a)).collect();
+
+    let mut reporte: Vec<EstadoMigracion> = migraciones
+        .iter()
+        .filter_map(|m| {
+            let script = m.script(motor)?;
+            let aplicada = por_version.get(&m.version);
+            let estado = match aplicada {
+                None => EstadoAplicacion::Pendiente,
+                Some(a) if Some(&a.checksum) != m.checksum(motor).as_ref() => EstadoAplicacion::Modificada,
+                Some(_) => EstadoAplicacion::Aplicada,
+            };
+            Some(EstadoMigracion {
+                version: m.version,
+                descripcion: m.descripcion.to_string(),
+                estado,
+                reversible: script.down.is_some(),
+                aplicada_at: aplicada.map(|a| a.aplicada_at),
+            })
+        })
+        .collect();
+
+    for a in aplicadas {
+        if !migraciones.iter().any(|m| m.version == a.version && m.script(motor).is_some()) {
+            reporte.push(EstadoMigracion {
+                version: a.version,
+                descripcion: a.descripcion.clone(),
+                estado: EstadoAplicacion::Desconocida,
+                reversible: false,
+                aplicada_at: Some(a.aplicada_at),
+            });
+        }
+    }
+
+    reporte.sort_by_key(|e| e.version);
+    reporte
+}
+
+fn error_migracion(version: i64, descripcion: &str, mensaje: impl ToString) -> AppError {
+    AppError::MigracionFallida {
+        version,
+        descripcion: descripcion.to_string(),
+        mensaje: mensaje.to_string(),
+    }
+}
+
+/// Migraciones pendientes de un motor, en orden
+/// Falla si alguna aplicada fue modificada o no existe en esta versión de la aplicación
+pub fn pendientes<'a>(
+    migraciones: &'a [Migracion],
+    motor: Motor,
+    aplicadas: &[MigracionAplicada],
+) -> Result<Vec<&'a Migracion>> {
+    for linea in estado(migraciones, motor, aplicadas) {
+        match linea.estado {
+            EstadoAplicacion::Modificada => {
+                return Err(error_migracion(
+                    linea.version,
+                    &linea.descripcion,
+                    "el checksum no coincide: la migración se modificó después de aplicarse",
+                ))
+            }
+            EstadoAplicacion::Desconocida => {
+                return Err(error_migracion(
+                    linea.version,
+                    &linea.descripcion,
+                    "la base tiene una migración que esta versión de la aplicación no conoce",
+                ))
+            }
+            _ => {}
+        }
+    }
+
+    Ok(migraciones
+        .iter()
+        .filter(|m| m.script(motor).is_some() && !aplicadas.iter().any(|a| a.version == m.version))
+        .collect())
+}
+
+/// Migraciones a revertir para volver a `hasta_version` (la más nueva primero)
+/// Falla sin tocar nada si alguna no tiene reversión
+pub fn a_revertir<'a>(
+    migraciones: &'a [Migracion],
+    motor: Motor,
+    aplicadas: &[MigracionAplicada],
+    hasta_version: i64,
+) -> Result<Vec<&'a Migracion>> {
+    let mut versiones: Vec<&MigracionAplicada> = aplicadas.iter().filter(|a| a.version > hasta_version).collect();
+    versiones.sort_by_key(|a| std::cmp::Reverse(a.version));
+
+    versiones
+        .into_iter()
+        .map(|a| {
+            let migracion = migraciones
+                .iter()
+                .find(|m| m.version == a.version && m.script(motor).is_some())
+                .ok_or_else(|| error_migracion(a.version, &a.descripcion, "migración desconocida, no se puede revertir"))?;
+            if migracion.script(motor).and_then(|s| s.down.as_ref()).is_none() {
+                return Err(error_migracion(a.version, &a.descripcion, "la migración no es reversible"));
+            }
+            Ok(migracion)
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// SQLite
+// ---------------------------------------------------------------------------
+
+const TABLA_VERSIONES_SQLITE: &str = r#"
+    CREATE TABLE IF NOT EXISTS esquema_migraciones (
+        version INTEGER PRIMARY KEY,
+        descripcion TEXT NOT NULL,
+        checksum TEXT NOT NULL,
+        aplicada_at DATETIME NOT NULL,
+        duracion_ms INTEGER NOT NULL
+    )
+"#;
+
+async fn aplicadas_sqlite(conn: &mut SqliteConnection) -> Result<Vec<MigracionAplicada>> {
+    sqlx::query(TABLA_VERSIONES_SQLITE).execute(&mut *conn).await?;
+    Ok(sqlx::query_as(
+        "SELECT version, descripcion, checksum, aplicada_at FROM esquema_migraciones ORDER BY version",
+    )
+    .fetch_all(&mut *conn)
+    .await?)
+}
+
+async fn ejecutar_paso_sqlite(conn: &mut SqliteConnection, paso: &Paso) -> Result<()> {
+    match paso {
+        Paso::Sql(sql) => {
+            sqlx::Executor::execute(&mut *conn, sql.as_str()).await?;
+        }
+        Paso::AgregarColumna { tabla, columna, tipo } => {
+            let existe: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM pragma_table_info(?) WHERE name = ?")
+                .bind(tabla)
+                .bind(columna)
+                .fetch_one(&mut *conn)
+                .await?;
+            if existe == 0 {
+                sqlx::query(&format!("ALTER TABLE {} ADD COLUMN {} {}", tabla, columna, tipo))
+                    .execute(&mut *conn)
+                    .await?;
+            }
+        }
+        Paso::ReconstruirTabla { tabla, columna_legada, esquema, copia } => {
+            let legada: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM pragma_table_info(?) WHERE name = ?")
+                .bind(tabla)
+                .bind(columna_legada)
+                .fetch_one(&mut *conn)
+                .await?;
+            if legada > 0 {
+                sqlx::query(&format!("DROP TABLE IF EXISTS {}_nueva", tabla)).execute(&mut *conn).await?;
+                sqlx::query(&esquema.replace("{tabla}", &format!("{}_nueva", tabla)))
+                    .execute(&mut *conn)
+                    .await?;
+                sqlx::query(copia).execute(&mut *conn).await?;
+                sqlx::query(&format!("DROP TABLE {}", tabla)).execute(&mut *conn).await?;
+                sqlx::query(&format!("ALTER TABLE {t}_nueva RENAME TO {t}", t = tabla))
+                    .execute(&mut *conn)
+                    .await?;
+                println!("✓ Tabla {} migrada al esquema nuevo", tabla);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Aplica (o revierte) una migración en su propia transacción y la registra
+async fn ejecutar_sqlite(conn: &mut SqliteConnection, migracion: &Migracion, revertir: bool) -> Result<()> {
+    let script = migracion.script(Motor::Sqlite).expect("migración sin script SQLite");
+    let pasos = if revertir { script.down.as_deref().unwrap_or_default() } else { &script.up };
+    let inicio = Instant::now();
+
+    let mut tx = conn.begin().await?;
+    for paso in pasos {
+        ejecutar_paso_sqlite(&mut tx, paso)
+            .await
+            .map_err(|e| error_migracion(migracion.version, migracion.descripcion, e))?;
+    }
+
+    if revertir {
+        sqlx::query("DELETE FROM esquema_migraciones WHERE version = ?")
+            .bind(migracion.version)
+            .execute(&mut *tx)
+            .await?;
+    } else {
+        sqlx::query(
+            "INSERT INTO esquema_migraciones (version, descripcion, checksum, aplicada_at, duracion_ms) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(migracion.version)
+        .bind(migracion.descripcion)
+        .bind(migracion.checksum(Motor::Sqlite))
+        .bind(Utc::now())
+        .bind(inicio.elapsed().as_millis() as i64)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Ejecuta las migraciones con las claves foráneas desactivadas (necesario para reconstruir tablas)
+/// y las reactiva aunque alguna falle
+async fn con_claves_foraneas_desactivadas(
+    conn: &mut SqliteConnection,
+    migraciones: &[&Migracion],
+    revertir: bool,
+) -> Result<()> {
+    sqlx::query("PRAGMA foreign_keys = OFF").execute(&mut *conn).await?;
+
+    let mut resultado = Ok(());
+    for migracion in migraciones {
+        resultado = ejecutar_sqlite(conn, migracion, revertir).await;
+        if resultado.is_err() {
+            break;
+        }
+    }
+
+    sqlx::query("PRAGMA foreign_keys = ON").execute(&mut *conn).await?;
+    resultado
+}
+
+/// Aplica las migraciones SQLite pendientes; devuelve las versiones aplicadas
+pub async fn migrar_sqlite(pool: &SqlitePool) -> Result<Vec<i64>> {
+    let todas = migraciones();
+    let mut conn = pool.acquire().await?;
+    let aplicadas = aplicadas_sqlite(&mut conn).await?;
+    let pendientes = pendientes(&todas, Motor::Sqlite, &aplicadas)?;
+
+    con_claves_foraneas_desactivadas(&mut conn, &pendientes, false).await?;
+
+    if !pendientes.is_empty() {
+        println!("✓ {} migraciones aplicadas en SQLite", pendientes.len());
+    }
+    Ok(pendientes.iter().map(|m| m.version).collect())
+}
+
+/// Revierte las migraciones SQLite posteriores a `hasta_version`; devuelve las versiones revertidas
+pub async fn revertir_sqlite(pool: &SqlitePool, hasta_version: i64) -> Result<Vec<i64>> {
+    let todas = migraciones();
+    let mut conn = pool.acquire().await?;
+    let aplicadas = aplicadas_sqlite(&mut conn).await?;
+    let revertir = a_revertir(&todas, Motor::Sqlite, &aplicadas, hasta_version)?;
+
+    con_claves_foraneas_desactivadas(&mut conn, &revertir, true).await?;
+    Ok(revertir.iter().map(|m| m.version).collect())
+}
+
+pub async fn estado_sqlite(pool: &SqlitePool) -> Result<Vec<EstadoMigracion>> {
+    let mut conn = pool.acquire().await?;
+    let aplicadas = aplicadas_sqlite(&mut conn).await?;
+    Ok(estado(&migraciones(), Motor::Sqlite, &aplicadas))
+}
+
+// ---------------------------------------------------------------------------
+// PostgreSQL
+// ---------------------------------------------------------------------------
+
+const TABLA_VERSIONES_POSTGRES: &str = r#"
+    CREATE TABLE IF NOT EXISTS esquema_migraciones (
+        version BIGINT PRIMARY KEY,
+        descripcion TEXT NOT NULL,
+        checksum TEXT NOT NULL,
+        aplicada_at TIMESTAMPTZ NOT NULL,
+        duracion_ms BIGINT NOT NULL
+    )
+"#;
+
+async fn aplicadas_postgres(conn: &mut PgConnection, todas: &[Migracion]) -> Result<Vec<MigracionAplicada>> {
+    sqlx::query(TABLA_VERSIONES_POSTGRES).execute(&mut *conn).await?;
+    adoptar_historial_sqlx(conn, todas).await?;
+    Ok(sqlx::query_as(
+        "SELECT version, descripcion, checksum, aplicada_at FROM esquema_migraciones ORDER BY version",
+    )
+    .fetch_all(&mut *conn)
+    .await?)
+}
+
+/// Los servidores migrados antes con sqlx-cli tienen su historial en _sqlx_migrations:
+/// esas versiones se registran como aplicadas la primera vez
+async fn adoptar_historial_sqlx(conn: &mut PgConnection, todas: &[Migracion]) -> Result<()> {
+    let registradas: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM esquema_migraciones")
+        .fetch_one(&mut *conn)
+        .await?;
+    let existe_sqlx: bool = sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+        .fetch_one(&mut *conn)
+        .await?;
+    if registradas > 0 || !existe_sqlx {
+        return Ok(());
+    }
+
+    let versiones: Vec<i64> = sqlx::query_scalar("SELECT version FROM _sqlx_migrations WHERE success ORDER BY version")
+        .fetch_all(&mut *conn)
+        .await?;
+    for version in versiones {
+        if let Some(m) = todas.iter().find(|m| m.version == version && m.postgres.is_some()) {
+            sqlx::query(
+                "INSERT INTO esquema_migraciones (version, descripcion, checksum, aplicada_at, duracion_ms) VALUES ($1, $2, $3, NOW(), 0)",
+            )
+            .bind(m.version)
+            .bind(m.descripcion)
+            .bind(m.checksum(Motor::Postgres))
+            .execute(&mut *conn)
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+async fn ejecutar_postgres(conn: &mut PgConnection, migracion: &Migracion, revertir: bool) -> Result<()> {
+    let script = migracion.script(Motor::Postgres).expect("migración sin script PostgreSQL");
+    let pasos = if revertir { script.down.as_deref().unwrap_or_default() } else { &script.up };
+    let inicio = Instant::now();
+
+    let mut tx = conn.begin().await?;
+    for paso in pasos {
+        let resultado = match paso {
+            Paso::Sql(sql) => sqlx::Executor::execute(&mut *tx, sql.as_str()).await.map(|_| ()),
+            Paso::AgregarColumna { tabla, columna, tipo } => sqlx::query(&format!(
+                "ALTER TABLE {} ADD COLUMN IF NOT EXISTS {} {}",
+                tabla, columna, tipo
+            ))
+            .execute(&mut *tx)
+            .await
+            .map(|_| ()),
+            Paso::ReconstruirTabla { .. } => {
+                return Err(error_migracion(
+                    migracion.version,
+                    migracion.descripcion,
+                    "ReconstruirTabla sólo está disponible en SQLite",
+                ))
+            }
+        };
+        resultado.map_err(|e| error_migracion(migracion.version, migracion.descripcion, e))?;
+    }
+
+    if revertir {
+        sqlx::query("DELETE FROM esquema_migraciones WHERE version = $1")
+            .bind(migracion.version)
+            .execute(&mut *tx)
+            .await?;
+    } else {
+        sqlx::query(
+            "INSERT INTO esquema_migraciones (version, descripcion, checksum, aplicada_at, duracion_ms) VALUES ($1, $2, $3, $4, $5)",
+        )
+        .bind(migracion.version)
+        .bind(migracion.descripcion)
+        .bind(migracion.checksum(Motor::Postgres))
+        .bind(Utc::now())
+        .bind(inicio.elapsed().as_millis() as i64)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Ejecuta las migraciones PostgreSQL bajo el advisory lock (liberado aunque fallen)
+async fn con_bloqueo_postgres(pool: &PgPool, hasta_version: Option<i64>) -> Result<Vec<i64>> {
+    let todas = migraciones();
+    let mut conn = pool.acquire().await?;
+    sqlx::query("SELECT pg_advisory_lock($1)")
+        .bind(BLOQUEO_MIGRACIONES_PG)
+        .execute(&mut *conn)
+        .await?;
+
+    let resultado: Result<Vec<i64>> = async {
+        let aplicadas = aplicadas_postgres(&mut conn, &todas).await?;
+        let (lote, revertir) = match hasta_version {
+            Some(version) => (a_revertir(&todas, Motor::Postgres, &aplicadas, version)?, true),
+            None => (pendientes(&todas, Motor::Postgres, &aplicadas)?, false),
+        };
+        for migracion in &lote {
+            ejecutar_postgres(&mut conn, migracion, revertir).await?;
+        }
+        Ok(lote.iter().map(|m| m.version).collect())
+    }
+    .await;
+
+    sqlx::query("SELECT pg_advisory_unlock($1)")
+        .bind(BLOQUEO_MIGRACIONES_PG)
+        .execute(&mut *conn)
+        .await?;
+    resultado
+}
+
+/// Aplica las migraciones PostgreSQL pendientes; devuelve las versiones aplicadas
+pub async fn migrar_postgres(pool: &PgPool) -> Result<Vec<i64>> {
+    let aplicadas = con_bloqueo_postgres(pool, None).await?;
+    if !aplicadas.is_empty() {
+        println!("✓ {} migraciones aplicadas en PostgreSQL", aplicadas.len());
+    }
+    Ok(aplicadas)
+}
+
+/// Revierte las migraciones PostgreSQL posteriores a `hasta_version`
+pub async fn revertir_postgres(pool: &PgPool, hasta_version: i64) -> Result<Vec<i64>> {
+    con_bloqueo_postgres(pool, Some(hasta_version)).await
+}
+
+pub async fn estado_postgres(pool: &PgPool) -> Result<Vec<EstadoMigracion>> {
+    let todas = migraciones();
+    let mut conn = pool.acquire().await?;
+    let aplicadas = aplicadas_postgres(&mut conn, &todas).await?;
+    Ok(estado(&todas, Motor::Postgres, &aplicadas))
+}
+
+/// Estado de las migraciones en ambas bases
+pub async fn estado_migraciones(pools: &DatabasePool) -> Result<EstadoMigraciones> {
+    let postgres = match pools.get_postgres() {
+        Some(pg) => Some(estado_postgres(&pg).await?),
+        None => None,
+    };
+    Ok(EstadoMigraciones {
+        sqlite: estado_sqlite(pools.get_sqlite()).await?,
+        postgres,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Línea de comandos: `gestor-irrigacion migrate <status|up|down VERSION [--postgres]>`
+// ---------------------------------------------------------------------------
+
+fn imprimir_estado(base: &str, reporte: &[EstadoMigracion]) {
+    println!("{}:", base);
+    for linea in reporte {
+        println!(
+            "  {:>14}  {:<12} {:<3} {:<45} {}",
+            linea.version,
+            format!("{:?}", linea.estado).to_uppercase(),
+            if linea.reversible { "↺" } else { "" },
+            linea.descripcion,
+            linea.aplicada_at.map(|f| f.to_rfc3339()).unwrap_or_default()
+        );
+    }
+}
+
+/// Ejecuta el subcomando `migrate`; devuelve el código de salida del proceso
+pub async fn ejecutar_cli(pools: &DatabasePool, args: &[String]) -> i32 {
+    let resultado: Result<()> = async {
+        match args.first().map(String::as_str) {
+            Some("status") => {
+                let reporte = estado_migraciones(pools).await?;
+                imprimir_estado("SQLite", &reporte.sqlite);
+                match &reporte.postgres {
+                    Some(pg) => imprimir_estado("PostgreSQL", pg),
+                    None => println!("PostgreSQL: sin conexión"),
+                }
+            }
+            Some("up") => crate::db::init_databases(pools).await?,
+            Some("down") => {
+                let version: i64 = args
+                    .get(1)
+                    .and_then(|v| v.parse().ok())
+                    .ok_or_else(|| AppError::Validation("migrate down requiere la versión destino".to_string()))?;
+                let revertidas = if args.iter().any(|a| a == "--postgres") {
+                    let pg = pools
+                        .get_postgres()
+                        .ok_or_else(|| AppError::Validation("PostgreSQL no disponible".to_string()))?;
+                    revertir_postgres(&pg, version).await?
+                } else {
+                    revertir_sqlite(pools.get_sqlite(), version).await?
+                };
+                println!("✓ Migraciones revertidas: {:?}", revertidas);
+            }
+            _ => println!("Uso: migrate <status | up | down VERSION [--postgres]>"),
+        }
+        Ok(())
+    }
+    .await;
+
+    match resultado {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_util::{captura, pool_en_memoria, pool_vacio};
+    use crate::models::zona::ZONA_PREDETERMINADA;
+    use crate::repositories::ExpedienteRepository;
+
+    fn prueba(version: i64, up: &str, down: Option<&str>) -> Migracion {
+        let script = Script::new(vec![Paso::sql(up)]);
+        Migracion {
+            version,
+            descripcion: "prueba",
+            sqlite: Some(match down {
+                Some(d) => script.reversible(vec![Paso::sql(d)]),
+                None => script,
+            }),
+            postgres: None,
+        }
+    }
+
+    fn aplicada(m: &Migracion) -> MigracionAplicada {
+        MigracionAplicada {
+            version: m.version,
+            descripcion: m.descripcion.to_string(),
+            checksum: m.checksum(Motor::Sqlite).unwrap(),
+            aplicada_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_versiones_unicas_y_ordenadas() {
+        let todas = migraciones();
+        assert!(todas.windows(2).all(|w| w[0].version < w[1].version));
+        assert!(todas.iter().all(|m| m.sqlite.is_some() || m.postgres.is_some()));
+        assert_ne!(todas[0].checksum(Motor::Sqlite), todas[1].checksum(Motor::Sqlite));
+    }
+
+    #[test]
+    fn test_plan_detecta_modificadas_y_desconocidas() {
+        let a = prueba(1, "CREATE TABLE a (id INTEGER)", None);
+        let b = prueba(2, "CREATE TABLE b (id INTEGER)", Some("DROP TABLE b"));
+        let todas = vec![a.clone(), b.clone()];
+
+        let plan = pendientes(&todas, Motor::Sqlite, &[aplicada(&a)]).unwrap();
+        assert_eq!(plan.iter().map(|m| m.version).collect::<Vec<_>>(), vec![2]);
+
+        let mut editada = aplicada(&a);
+        editada.checksum = "otro".to_string();
+        assert!(matches!(
+            pendientes(&todas, Motor::Sqlite, &[editada]),
+            Err(AppError::MigracionFallida { version: 1, .. })
+        ));
+
+        let futura = aplicada(&prueba(3, "CREATE TABLE c (id INTEGER)", None));
+        let reporte = estado(&todas, Motor::Sqlite, &[aplicada(&a), futura.clone()]);
+        assert_eq!(reporte[2].estado, EstadoAplicacion::Desconocida);
+        assert!(pendientes(&todas, Motor::Sqlite, &[futura]).is_err());
+
+        // Revertir hasta 0 exige que todas sean reversibles
+        assert!(a_revertir(&todas, Motor::Sqlite, &[aplicada(&a), aplicada(&b)], 1).is_ok());
+        assert!(a_revertir(&todas, Motor::Sqlite, &[aplicada(&a), aplicada(&b)], 0).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_migrar_es_idempotente_y_reversible() {
+        let pool = pool_vacio().await;
+
+        let aplicadas = migrar_sqlite(&pool).await.unwrap();
+        assert_eq!(aplicadas.len(), migraciones().iter().filter(|m| m.sqlite.is_some()).count());
+        assert!(migrar_sqlite(&pool).await.unwrap().is_empty());
+        assert!(estado_sqlite(&pool).await.unwrap().iter().all(|e| e.estado == EstadoAplicacion::Aplicada));
+
+        // Revertir todo lo reversible y volver a aplicarlo
+        let base = migraciones()
+            .iter()
+            .filter(|m| m.sqlite.as_ref().is_some_and(|s| s.down.is_none()))
+            .map(|m| m.version)
+            .max()
+            .unwrap();
+        let revertidas = revertir_sqlite(&pool, base).await.unwrap();
+        assert!(!revertidas.is_empty());
+        let sin_outbox: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM sqlite_master WHERE name = 'sync_outbox'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(sin_outbox, 0);
+
+        assert_eq!(migrar_sqlite(&pool).await.unwrap(), revertidas.into_iter().rev().collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_fallo_se_reporta_y_no_se_registra() {
+        let pool = pool_vacio().await;
+        let mut conn = pool.acquire().await.unwrap();
+        aplicadas_sqlite(&mut conn).await.unwrap();
+
+        let rota = prueba(99, "CREATE TABLE x (id INTEGER); INSERT INTO tabla_inexistente VALUES (1)", None);
+        let error = con_claves_foraneas_desactivadas(&mut conn, &[&rota], false).await.unwrap_err();
+        assert!(matches!(error, AppError::MigracionFallida { version: 99, .. }));
+
+        // La transacción se deshizo: ni la tabla ni el registro de versión quedaron
+        assert!(aplicadas_sqlite(&mut conn).await.unwrap().is_empty());
+        let tabla: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM sqlite_master WHERE name = 'x'")
+            .fetch_one(&mut *conn)
+            .await
+            .unwrap();
+        assert_eq!(tabla, 0);
+    }
+
+    #[tokio::test]
+    async fn test_oc_repetidas_de_antes_quedan_fuera_del_indice() {
+        let pool = pool_en_memoria().await;
+        let exp = ExpedienteRepository::upsert_from_infogov(&pool, captura("1-1-2026", "2026-01-10", "INICIADO")).await.unwrap();
+        revertir_sqlite(&pool, 20261018000007).await.unwrap();
+
+        let insertar = |id: &'static str, creada: &'static str| {
+            sqlx::query(
+                "INSERT INTO ordenes_compra (id, numero_oc, pedido_nro, fecha, expediente_id, forma_pago, tipo_contratacion, \
+                 subtotal, iva, total, zona_id, created_at) VALUES (?, '7/2026', 7, '2026-03-01', ?, 'Transferencia', 'Contratación directa', \
+                 0, 0, 0, ?, ?)",
+            )
+            .bind(id)
+            .bind(&exp.id)
+            .bind(ZONA_PREDETERMINADA)
+            .bind(creada)
+            .execute(&pool)
+        };
+        insertar("oc-1", "2026-03-01 10:00:00").await.unwrap();
+        insertar("oc-2", "2026-03-02 10:00:00").await.unwrap();
+        migrar_sqlite(&pool).await.unwrap();
+
+        // Las dos conservan número y año; sólo la segunda queda marcada
+        let filas: Vec<(String, Option<i64>, i64)> =
+            sqlx::query_as("SELECT id, año, numero_repetido FROM ordenes_compra ORDER BY id")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(filas, vec![("oc-1".to_string(), Some(2026), 0), ("oc-2".to_string(), Some(2026), 1)]);
+
+        // Un repetido nuevo ya no entra
+        insertar("oc-3", "2026-03-03 10:00:00").await.unwrap();
+        assert!(sqlx::query("UPDATE ordenes_compra SET año = 2026 WHERE id = 'oc-3'").execute(&pool).await.is_err());
+    }
+}
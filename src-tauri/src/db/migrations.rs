@@ -1,311 +1,694 @@
-use sqlx::SqlitePool;
-use crate::error::Result;
+// Migraciones del esquema base: expedientes, personal, movilidades y búsqueda
+// Las versiones 20260205* y 20260218* comparten número con los scripts de migrations/postgres
+use crate::db::migrador::{Migracion, Paso, Script};
+use crate::db::migrations_sync::{
+    revertir_triggers_outbox, revertir_updated_at_servidor_tabla, triggers_outbox, updated_at_servidor_tabla,
+};
 
-/// Ejecuta las migraciones de base de datos SQLite
-pub async fn run_sqlite_migrations(pool: &SqlitePool) -> Result<()> {
-    // Tabla de expedientes
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS expedientes (
-            id TEXT PRIMARY KEY,
-            numero TEXT NOT NULL,
-            año INTEGER NOT NULL,
-            tipo TEXT NOT NULL,
-            asunto TEXT NOT NULL,
-            descripcion TEXT,
-            area_responsable TEXT NOT NULL DEFAULT 'Sin definir',
-            prioridad TEXT NOT NULL DEFAULT 'MEDIA',
-            estado TEXT NOT NULL DEFAULT 'INICIADO',
-            fecha_inicio DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
-            fecha_vencimiento DATETIME,
-            fecha_finalizacion DATETIME,
-            agente_responsable_id TEXT,
-            archivos_adjuntos TEXT,
-            observaciones TEXT,
-            synced_at DATETIME,
-            nro_infogov TEXT,
-            nro_gde TEXT,
-            caratula TEXT,
-            resolucion_nro TEXT,
-            archivo TEXT,
-            tema TEXT,
-            fecha_pase DATETIME,
-            oficina TEXT,
-            buzon_grupal TEXT,
-            hacer TEXT,
-            resumen TEXT,
-            oc_señor TEXT,
-            oc_domicilio TEXT,
-            oc_cuit TEXT,
-            oc_descripcion_zona TEXT,
-            oc_forma_pago TEXT,
-            oc_plazo_entrega TEXT,
-            factura_path TEXT,
-            categoria_gasto TEXT,
-            vehiculo_id TEXT,
-            created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-            updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
-        )
-        "#,
-    )
-    .execute(pool)
-    .await?;
-
-    // Asegurar columnas nuevas en instalaciones existentes
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN nro_infogov TEXT")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN nro_gde TEXT")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN caratula TEXT")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN resolucion_nro TEXT")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN area_responsable TEXT")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN prioridad TEXT")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN estado TEXT")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN fecha_inicio DATETIME")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN fecha_vencimiento DATETIME")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN fecha_finalizacion DATETIME")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN agente_responsable_id TEXT")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN archivos_adjuntos TEXT")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN observaciones TEXT")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN synced_at DATETIME")
-        .execute(pool)
-        .await
-        .ok();
+const EXPEDIENTES_PG: &str = include_str!("../../migrations/postgres/20260205000002_create_expedientes.sql");
+const AGENTES_PG: &str = include_str!("../../migrations/postgres/20260205000001_create_agentes.sql");
+const VEHICULOS_PG: &str = include_str!("../../migrations/postgres/20260205000003_create_vehiculos.sql");
+const TICKETS_PG: &str = include_str!("../../migrations/postgres/20260205000004_create_tickets.sql");
+const CAMPOS_EXPEDIENTE_PG: &str = include_str!("../../migrations/postgres/20260218000001_add_expediente_fields.sql");
 
+/// Columnas de expedientes agregadas después del esquema original (instalaciones existentes)
+const COLUMNAS_EXPEDIENTE: &[(&str, &str)] = &[
+    ("nro_infogov", "TEXT"),
+    ("nro_gde", "TEXT"),
+    ("caratula", "TEXT"),
+    ("resolucion_nro", "TEXT"),
+    ("area_responsable", "TEXT"),
+    ("prioridad", "TEXT"),
+    ("estado", "TEXT"),
+    ("fecha_inicio", "DATETIME"),
+    ("fecha_vencimiento", "DATETIME"),
+    ("fecha_finalizacion", "DATETIME"),
+    ("agente_responsable_id", "TEXT"),
+    ("archivos_adjuntos", "TEXT"),
+    ("observaciones", "TEXT"),
+    ("synced_at", "DATETIME"),
     // Campos para Órdenes de Compra
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN oc_señor TEXT")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN oc_domicilio TEXT")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN oc_cuit TEXT")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN oc_descripcion_zona TEXT")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN oc_forma_pago TEXT")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN oc_plazo_entrega TEXT")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN factura_path TEXT")
-        .execute(pool)
-        .await
-        .ok();
-
-    // Nuevos campos comunes a todos los expedientes
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN archivo TEXT")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN tema TEXT")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN fecha_pase DATETIME")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN oficina TEXT")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN buzon_grupal TEXT")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN hacer TEXT")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN resumen TEXT")
-        .execute(pool)
-        .await
-        .ok();
-
-    // Campos para vinculación con vehículos
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN categoria_gasto TEXT")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN vehiculo_id TEXT")
-        .execute(pool)
-        .await
-        .ok();
-
-    // Normalizar valores nulos para columnas obligatorias
-    sqlx::query("UPDATE expedientes SET area_responsable = 'Sin definir' WHERE area_responsable IS NULL")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("UPDATE expedientes SET prioridad = 'MEDIA' WHERE prioridad IS NULL")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("UPDATE expedientes SET estado = 'INICIADO' WHERE estado IS NULL")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("UPDATE expedientes SET fecha_inicio = COALESCE(fecha_inicio, created_at, CURRENT_TIMESTAMP) WHERE fecha_inicio IS NULL")
-        .execute(pool)
-        .await
-        .ok();
-
-    // Normalizar enums a SCREAMING_SNAKE_CASE para evitar errores de decode
-    sqlx::query("UPDATE expedientes SET tipo = UPPER(tipo) WHERE tipo IS NOT NULL")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("UPDATE expedientes SET prioridad = UPPER(prioridad) WHERE prioridad IS NOT NULL")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("UPDATE expedientes SET estado = UPPER(estado) WHERE estado IS NOT NULL")
-        .execute(pool)
-        .await
-        .ok();
-
-    // Tabla de agentes
-    sqlx::query(
+    ("oc_señor", "TEXT"),
+    ("oc_domicilio", "TEXT"),
+    ("oc_cuit", "TEXT"),
+    ("oc_descripcion_zona", "TEXT"),
+    ("oc_forma_pago", "TEXT"),
+    ("oc_plazo_entrega", "TEXT"),
+    ("factura_path", "TEXT"),
+    // Campos comunes a todos los expedientes
+    ("archivo", "TEXT"),
+    ("tema", "TEXT"),
+    ("fecha_pase", "DATETIME"),
+    ("oficina", "TEXT"),
+    ("buzon_grupal", "TEXT"),
+    ("hacer", "TEXT"),
+    ("resumen", "TEXT"),
+    // Vinculación con vehículos
+    ("categoria_gasto", "TEXT"),
+    ("vehiculo_id", "TEXT"),
+];
+
+/// Migraciones del esquema base
+pub fn migraciones() -> Vec<Migracion> {
+    let mut campos_expediente: Vec<Paso> = COLUMNAS_EXPEDIENTE
+        .iter()
+        .map(|&(columna, tipo)| Paso::AgregarColumna { tabla: "expedientes", columna, tipo })
+        .collect();
+    campos_expediente.push(Paso::sql(NORMALIZAR_EXPEDIENTES));
+
+    vec![
+        // Personal: las instalaciones con el esquema anterior se reconstruyen conservando los datos
+        Migracion {
+            version: 20260205000001,
+            descripcion: "crear_agentes",
+            sqlite: Some(Script::new(vec![
+                Paso::ReconstruirTabla {
+                    tabla: "agentes",
+                    columna_legada: "tipo_licencia",
+                    esquema: ESQUEMA_AGENTES,
+                    copia: COPIA_AGENTES,
+                },
+                Paso::sql(ESQUEMA_AGENTES.replace("{tabla}", "IF NOT EXISTS agentes")),
+                Paso::sql("CREATE INDEX IF NOT EXISTS idx_agentes_activo ON agentes(activo)"),
+                Paso::sql("CREATE INDEX IF NOT EXISTS idx_agentes_legajo ON agentes(legajo)"),
+            ])),
+            postgres: Some(Script::new(vec![Paso::sql(AGENTES_PG)])),
+        },
+        Migracion {
+            version: 20260205000002,
+            descripcion: "crear_expedientes",
+            sqlite: Some(Script::new(vec![Paso::sql(ESQUEMA_EXPEDIENTES)])),
+            postgres: Some(Script::new(vec![Paso::sql(EXPEDIENTES_PG)])),
+        },
+        Migracion {
+            version: 20260205000003,
+            descripcion: "crear_vehiculos",
+            sqlite: Some(Script::new(vec![
+                Paso::ReconstruirTabla {
+                    tabla: "vehiculos",
+                    columna_legada: "kilometraje",
+                    esquema: ESQUEMA_VEHICULOS,
+                    copia: COPIA_VEHICULOS,
+                },
+                Paso::sql(ESQUEMA_VEHICULOS.replace("{tabla}", "IF NOT EXISTS vehiculos")),
+                Paso::sql("CREATE INDEX IF NOT EXISTS idx_vehiculos_activo ON vehiculos(activo)"),
+                Paso::sql("CREATE INDEX IF NOT EXISTS idx_vehiculos_patente ON vehiculos(patente)"),
+            ])),
+            postgres: Some(Script::new(vec![Paso::sql(VEHICULOS_PG)])),
+        },
+        Migracion {
+            version: 20260205000004,
+            descripcion: "crear_tickets",
+            sqlite: Some(Script::new(vec![
+                Paso::ReconstruirTabla {
+                    tabla: "tickets_combustible",
+                    columna_legada: "precio_total",
+                    esquema: ESQUEMA_TICKETS,
+                    copia: COPIA_TICKETS,
+                },
+                Paso::sql(ESQUEMA_TICKETS.replace("{tabla}", "IF NOT EXISTS tickets_combustible")),
+                Paso::sql("CREATE INDEX IF NOT EXISTS idx_tickets_vehiculo ON tickets_combustible(vehiculo_id, fecha_carga)"),
+            ])),
+            postgres: Some(Script::new(vec![Paso::sql(TICKETS_PG)])),
+        },
+        Migracion {
+            version: 20260218000001,
+            descripcion: "agregar_campos_expediente",
+            sqlite: Some(Script::new(campos_expediente)),
+            postgres: Some(Script::new(vec![Paso::sql(CAMPOS_EXPEDIENTE_PG)])),
+        },
+        Migracion {
+            version: 20260301000001,
+            descripcion: "crear_consumibles",
+            sqlite: Some(
+                Script::new(vec![Paso::sql(
+                    r#"
+                    CREATE TABLE IF NOT EXISTS consumibles (
+                        id TEXT PRIMARY KEY,
+                        nombre TEXT NOT NULL,
+                        categoria TEXT NOT NULL,
+                        cantidad REAL NOT NULL,
+                        unidad TEXT DEFAULT 'Unidad',
+                        stock_minimo INTEGER DEFAULT 10,
+                        created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                        updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+                    )
+                    "#,
+                )])
+                .reversible(vec![Paso::sql("DROP TABLE IF EXISTS consumibles")]),
+            ),
+            postgres: None,
+        },
+        // Historial de pases de expedientes (un registro por movimiento)
+        Migracion {
+            version: 20260301000002,
+            descripcion: "crear_expediente_movimientos",
+            sqlite: Some(
+                Script::new(vec![Paso::sql(
+                    r#"
+                    CREATE TABLE IF NOT EXISTS expediente_movimientos (
+                        id TEXT PRIMARY KEY,
+                        expediente_id TEXT NOT NULL,
+                        oficina_origen TEXT,
+                        oficina_destino TEXT,
+                        fecha_pase DATETIME NOT NULL,
+                        estado TEXT,
+                        estado_infogov TEXT,
+                        origen TEXT NOT NULL DEFAULT 'MANUAL',
+                        capturado_por TEXT,
+                        observaciones TEXT,
+                        created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                        FOREIGN KEY (expediente_id) REFERENCES expedientes(id) ON DELETE CASCADE
+                    );
+                    CREATE INDEX IF NOT EXISTS idx_movimientos_expediente ON expediente_movimientos(expediente_id, fecha_pase);
+                    "#,
+                )])
+                .reversible(vec![Paso::sql("DROP TABLE IF EXISTS expediente_movimientos")]),
+            ),
+            postgres: None,
+        },
+        Migracion {
+            version: 20260301000003,
+            descripcion: "crear_busqueda_expedientes",
+            sqlite: Some(
+                Script::new(vec![Paso::sql(BUSQUEDA_EXPEDIENTES)]).reversible(vec![Paso::sql(
+                    r#"
+                    DROP TRIGGER IF EXISTS expedientes_fts_insert;
+                    DROP TRIGGER IF EXISTS expedientes_fts_update;
+                    DROP TRIGGER IF EXISTS expedientes_fts_delete;
+                    DROP TABLE IF EXISTS expedientes_fts;
+                    "#,
+                )]),
+            ),
+            postgres: None,
+        },
+        Migracion {
+            version: 20261018000001,
+            descripcion: "mapear_estados_infogov",
+            sqlite: Some(
+                Script::new(vec![
+                    Paso::AgregarColumna { tabla: "expedientes", columna: "estado_infogov", tipo: "TEXT" },
+                    Paso::sql(ESTADOS_INFOGOV),
+                    Paso::sql(RESCATAR_ESTADOS_INFOGOV),
+                ])
+                // estado_infogov queda en expedientes: bajar la migración no pierde el dato capturado
+                .reversible(vec![Paso::sql(
+                    r#"
+                    DROP TABLE IF EXISTS infogov_estados_pendientes;
+                    DROP TABLE IF EXISTS infogov_estados;
+                    "#,
+                )]),
+            ),
+            postgres: Some(Script::new(vec![Paso::sql(
+                "ALTER TABLE expedientes ADD COLUMN IF NOT EXISTS estado_infogov VARCHAR(100)",
+            )])),
+        },
+        Migracion {
+            version: 20261018000002,
+            descripcion: "crear_capturas_infogov",
+            sqlite: Some(
+                Script::new(vec![Paso::sql(
+                    r#"
+                    CREATE TABLE IF NOT EXISTS capturas_infogov (
+                        id TEXT PRIMARY KEY,
+                        lote_id TEXT NOT NULL,
+                        fila INTEGER NOT NULL,
+                        origen TEXT NOT NULL,
+                        texto TEXT NOT NULL,
+                        parseado TEXT,
+                        error TEXT,
+                        accion TEXT NOT NULL,
+                        expediente_id TEXT,
+                        movimiento_id TEXT,
+                        antes TEXT,
+                        despues TEXT,
+                        created_at DATETIME NOT NULL,
+                        deshecha_at DATETIME
+                    );
+                    CREATE INDEX IF NOT EXISTS idx_capturas_infogov_fecha ON capturas_infogov(created_at);
+                    "#,
+                )])
+                .reversible(vec![Paso::sql("DROP TABLE IF EXISTS capturas_infogov")]),
+            ),
+            postgres: None,
+        },
+        Migracion {
+            version: 20261018000003,
+            descripcion: "crear_configuracion",
+            sqlite: Some(
+                Script::new(vec![Paso::sql(
+                    r#"
+                    CREATE TABLE IF NOT EXISTS configuracion (
+                        clave TEXT PRIMARY KEY,
+                        valor TEXT NOT NULL,
+                        updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+                    );
+
+                    INSERT OR IGNORE INTO configuracion (clave, valor) VALUES
+                        ('infogov.fecha_inicio', 'FECHA_PASE'),
+                        ('infogov.area_responsable', 'InfoGov'),
+                        ('infogov.prioridad', 'MEDIA');
+                    "#,
+                )])
+                .reversible(vec![Paso::sql("DROP TABLE IF EXISTS configuracion")]),
+            ),
+            postgres: None,
+        },
+        Migracion {
+            version: 20261018000004,
+            descripcion: "atajos_configurables",
+            sqlite: Some(
+                Script::new(vec![Paso::sql(
+                    r#"
+                    INSERT OR IGNORE INTO configuracion (clave, valor) VALUES
+                        ('atajos.capturar_infogov', 'Alt+I'),
+                        ('atajos.abrir_busqueda', 'Alt+B'),
+                        ('atajos.abrir_orden_compra', 'Alt+O'),
+                        ('atajos.alternar_ventana', 'Alt+G');
+                    "#,
+                )])
+                .reversible(vec![Paso::sql("DELETE FROM configuracion WHERE clave LIKE 'atajos.%'")]),
+            ),
+            postgres: None,
+        },
+        Migracion {
+            version: 20261018000005,
+            descripcion: "configuracion_instalacion",
+            sqlite: Some(
+                Script::new(vec![Paso::sql(
+                    r#"
+                    INSERT OR IGNORE INTO configuracion (clave, valor) VALUES
+                        ('institucion.nombre', 'DEPARTAMENTO GENERAL DE IRRIGACIÓN'),
+                        ('institucion.cuit', '30-9991963-1'),
+                        ('zona.nombre', 'Malargüe'),
+                        ('zona.destino', 'ZONA RIEGO MALARGUE'),
+                        ('zona.domicilio', 'Avda. San Martín 258 - Malargüe - Mendoza'),
+                        ('zona.localidad', 'Mendoza'),
+                        ('iva.inscripto', '21'),
+                        ('iva.no_inscripto', '10.5'),
+                        ('base_datos.postgres_url', '');
+                    "#,
+                )])
+                .reversible(vec![Paso::sql(
+                    r#"
+                    DELETE FROM configuracion
+                    WHERE clave LIKE 'institucion.%' OR clave LIKE 'zona.%' OR clave LIKE 'iva.%' OR clave LIKE 'base_datos.%'
+                    "#,
+                )]),
+            ),
+            postgres: None,
+        },
+        // Jefaturas de zona: la base central guarda varias y cada instalación trabaja con la suya
+        Migracion {
+            version: 20261018000006,
+            descripcion: "crear_zonas",
+            sqlite: Some(Script::new(zonas_sqlite()).reversible(revertir_zonas_sqlite())),
+            postgres: Some(Script::new(zonas_postgres()).reversible(revertir_zonas_postgres())),
+        },
+    ]
+}
+
+/// Tablas que pertenecen a una zona (mismo nombre local y remoto)
+const TABLAS_CON_ZONA: [&str; 4] = ["agentes", "vehiculos", "expedientes", "ordenes_compra"];
+
+/// Zona predeterminada: las filas existentes quedan en ella (el default sólo cubre altas sin zona explícita)
+fn zonas_sqlite() -> Vec<Paso> {
+    // La zona predeterminada se inserta antes de los triggers y ya sincronizada:
+    // el servidor crea la misma fila en su migración
+    let mut pasos = vec![Paso::sql(
         r#"
-        CREATE TABLE IF NOT EXISTS agentes (
+        CREATE TABLE IF NOT EXISTS zonas (
             id TEXT PRIMARY KEY,
+            codigo TEXT UNIQUE NOT NULL,
             nombre TEXT NOT NULL,
-            apellido TEXT NOT NULL,
-            dni TEXT UNIQUE NOT NULL,
-            legajo TEXT UNIQUE NOT NULL,
-            area TEXT NOT NULL,
-            tipo_licencia TEXT,
-            fecha_vencimiento_licencia DATE,
+            activa INTEGER NOT NULL DEFAULT 1,
             created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-            updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
-        )
-        "#,
-    )
-    .execute(pool)
-    .await?;
+            updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+            synced_at DATETIME,
+            sync_version INTEGER NOT NULL DEFAULT 0
+        );
 
-    // Tabla de vehículos
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS vehiculos (
-            id TEXT PRIMARY KEY,
-            patente TEXT UNIQUE NOT NULL,
-            tipo TEXT NOT NULL,
-            marca TEXT NOT NULL,
-            modelo TEXT NOT NULL,
-            año INTEGER NOT NULL,
-            kilometraje INTEGER DEFAULT 0,
-            estado TEXT DEFAULT 'Activo',
-            created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-            updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
-        )
+        INSERT OR IGNORE INTO zonas (id, codigo, nombre, synced_at)
+        VALUES (
+            '00000000-0000-0000-0000-000000000001',
+            'MALARGUE',
+            COALESCE((SELECT NULLIF(TRIM(valor), '') FROM configuracion WHERE clave = 'zona.nombre'), 'Malargüe'),
+            CURRENT_TIMESTAMP
+        );
+
+        -- La zona de la instalación pasa a ser una referencia a la tabla
+        INSERT OR IGNORE INTO configuracion (clave, valor) VALUES ('zona.id', '00000000-0000-0000-0000-000000000001');
+        DELETE FROM configuracion WHERE clave = 'zona.nombre';
         "#,
-    )
-    .execute(pool)
-    .await?;
+    )];
 
-    // Tabla de tickets de combustible
-    sqlx::query(
+    for t in TABLAS_CON_ZONA {
+        pasos.push(Paso::AgregarColumna {
+            tabla: t,
+            columna: "zona_id",
+            tipo: "TEXT NOT NULL DEFAULT '00000000-0000-0000-0000-000000000001'",
+        });
+        pasos.push(Paso::sql(format!("CREATE INDEX IF NOT EXISTS idx_{t}_zona ON {t}(zona_id)", t = t)));
+    }
+
+    pasos.push(Paso::sql(triggers_outbox("zonas")));
+    pasos
+}
+
+fn revertir_zonas_sqlite() -> Vec<Paso> {
+    let mut pasos: Vec<Paso> = TABLAS_CON_ZONA
+        .iter()
+        .map(|t| Paso::sql(format!("DROP INDEX IF EXISTS idx_{t}_zona; ALTER TABLE {t} DROP COLUMN zona_id;", t = t)))
+        .collect();
+
+    pasos.push(Paso::sql(format!(
         r#"
-        CREATE TABLE IF NOT EXISTS tickets_combustible (
-            id TEXT PRIMARY KEY,
-            vehiculo_id TEXT NOT NULL,
-            fecha DATE NOT NULL,
-            litros REAL NOT NULL,
-            precio_total REAL NOT NULL,
-            kilometraje_actual INTEGER NOT NULL,
-            created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-            updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-            FOREIGN KEY (vehiculo_id) REFERENCES vehiculos(id)
-        )
+        {}
+        DELETE FROM sync_outbox WHERE tabla = 'zonas';
+        INSERT OR REPLACE INTO configuracion (clave, valor)
+        SELECT 'zona.nombre', nombre FROM zonas
+        WHERE id = (SELECT valor FROM configuracion WHERE clave = 'zona.id');
+        DELETE FROM configuracion WHERE clave = 'zona.id';
+        DROP TABLE IF EXISTS zonas;
         "#,
-    )
-    .execute(pool)
-    .await?;
+        revertir_triggers_outbox("zonas")
+    )));
+    pasos
+}
 
-    // Tabla de consumibles
-    sqlx::query(
+fn zonas_postgres() -> Vec<Paso> {
+    let mut pasos = vec![Paso::sql(
         r#"
-        CREATE TABLE IF NOT EXISTS consumibles (
-            id TEXT PRIMARY KEY,
+        CREATE TABLE IF NOT EXISTS zonas (
+            id UUID PRIMARY KEY,
+            codigo TEXT UNIQUE NOT NULL,
             nombre TEXT NOT NULL,
-            categoria TEXT NOT NULL,
-            cantidad REAL NOT NULL,
-            unidad TEXT DEFAULT 'Unidad',
-            stock_minimo INTEGER DEFAULT 10,
-            created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-            updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
-        )
+            activa BOOLEAN NOT NULL DEFAULT TRUE,
+            created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            sync_version INTEGER NOT NULL DEFAULT 0
+        );
+
+        INSERT INTO zonas (id, codigo, nombre)
+        VALUES ('00000000-0000-0000-0000-000000000001', 'MALARGUE', 'Malargüe')
+        ON CONFLICT (id) DO NOTHING;
         "#,
+    )];
+
+    for t in TABLAS_CON_ZONA {
+        pasos.push(Paso::sql(format!(
+            r#"
+            ALTER TABLE IF EXISTS "{t}" ADD COLUMN IF NOT EXISTS zona_id UUID NOT NULL
+                DEFAULT '00000000-0000-0000-0000-000000000001' REFERENCES zonas(id);
+            CREATE INDEX IF NOT EXISTS idx_{t}_zona ON "{t}"(zona_id);
+            "#,
+            t = t
+        )));
+    }
+
+    pasos.push(Paso::sql(updated_at_servidor_tabla("zonas")));
+    pasos
+}
+
+fn revertir_zonas_postgres() -> Vec<Paso> {
+    let mut pasos: Vec<Paso> = TABLAS_CON_ZONA
+        .iter()
+        .map(|t| {
+            Paso::sql(format!(
+                "DROP INDEX IF EXISTS idx_{t}_zona; ALTER TABLE IF EXISTS \"{t}\" DROP COLUMN IF EXISTS zona_id;",
+                t = t
+            ))
+        })
+        .collect();
+    pasos.push(Paso::sql(revertir_updated_at_servidor_tabla("zonas")));
+    pasos.push(Paso::sql("DROP TABLE IF EXISTS zonas"));
+    pasos
+}
+
+// Equivalencias entre el estado libre de InfoGov (clave normalizada) y el estado interno
+// Se pueden editar desde la aplicación; estas son las de la bandeja de Irrigación
+const ESTADOS_INFOGOV: &str = r#"
+    CREATE TABLE IF NOT EXISTS infogov_estados (
+        estado_infogov TEXT PRIMARY KEY,
+        estado TEXT NOT NULL,
+        created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+        updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+    );
+
+    CREATE TABLE IF NOT EXISTS infogov_estados_pendientes (
+        estado_infogov TEXT PRIMARY KEY,
+        apariciones INTEGER NOT NULL DEFAULT 1,
+        ultimo_expediente_id TEXT,
+        primera_vez DATETIME DEFAULT CURRENT_TIMESTAMP,
+        ultima_vez DATETIME DEFAULT CURRENT_TIMESTAMP
+    );
+
+    INSERT OR IGNORE INTO infogov_estados (estado_infogov, estado) VALUES
+        ('iniciado', 'INICIADO'),
+        ('en tramite', 'ENPROCESO'),
+        ('contratacion directa', 'ENPROCESO'),
+        ('licitacion privada', 'ENPROCESO'),
+        ('licitacion publica', 'ENPROCESO'),
+        ('concurso de precios', 'ENPROCESO'),
+        ('en revision', 'ENREVISION'),
+        ('observado', 'OBSERVADO'),
+        ('finalizado', 'FINALIZADO'),
+        ('archivado', 'ARCHIVADO');
+"#;
+
+// Las capturas anteriores guardaban el estado de InfoGov en `estado` y rompían el decode del enum:
+// se pasa el texto a estado_infogov, se encola para revisión y el expediente vuelve a INICIADO
+// (la cola se reconcilia con el mapeo al listarla, ahí se aplican las equivalencias que ya existan)
+pub(crate) const RESCATAR_ESTADOS_INFOGOV: &str = r#"
+    UPDATE expedientes SET estado_infogov = estado
+    WHERE estado NOT IN ('INICIADO', 'ENPROCESO', 'ENREVISION', 'OBSERVADO', 'FINALIZADO', 'ARCHIVADO');
+
+    INSERT OR IGNORE INTO infogov_estados_pendientes (estado_infogov, apariciones, ultimo_expediente_id)
+    SELECT estado, COUNT(*), MAX(id) FROM expedientes
+    WHERE estado NOT IN ('INICIADO', 'ENPROCESO', 'ENREVISION', 'OBSERVADO', 'FINALIZADO', 'ARCHIVADO')
+    GROUP BY estado;
+
+    UPDATE expedientes SET estado = 'INICIADO'
+    WHERE estado NOT IN ('INICIADO', 'ENPROCESO', 'ENREVISION', 'OBSERVADO', 'FINALIZADO', 'ARCHIVADO');
+"#;
+
+const ESQUEMA_EXPEDIENTES: &str = r#"
+    CREATE TABLE IF NOT EXISTS expedientes (
+        id TEXT PRIMARY KEY,
+        numero TEXT NOT NULL,
+        año INTEGER NOT NULL,
+        tipo TEXT NOT NULL,
+        asunto TEXT NOT NULL,
+        descripcion TEXT,
+        area_responsable TEXT NOT NULL DEFAULT 'Sin definir',
+        prioridad TEXT NOT NULL DEFAULT 'MEDIA',
+        estado TEXT NOT NULL DEFAULT 'INICIADO',
+        fecha_inicio DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        fecha_vencimiento DATETIME,
+        fecha_finalizacion DATETIME,
+        agente_responsable_id TEXT,
+        archivos_adjuntos TEXT,
+        observaciones TEXT,
+        synced_at DATETIME,
+        nro_infogov TEXT,
+        nro_gde TEXT,
+        caratula TEXT,
+        resolucion_nro TEXT,
+        archivo TEXT,
+        tema TEXT,
+        fecha_pase DATETIME,
+        oficina TEXT,
+        buzon_grupal TEXT,
+        hacer TEXT,
+        resumen TEXT,
+        oc_señor TEXT,
+        oc_domicilio TEXT,
+        oc_cuit TEXT,
+        oc_descripcion_zona TEXT,
+        oc_forma_pago TEXT,
+        oc_plazo_entrega TEXT,
+        factura_path TEXT,
+        categoria_gasto TEXT,
+        vehiculo_id TEXT,
+        created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+        updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
     )
-    .execute(pool)
-    .await?;
+"#;
 
-    // Crear índices para mejorar performance
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_expedientes_estado ON expedientes(estado)")
-        .execute(pool)
-        .await?;
+// Normalizar valores nulos de columnas obligatorias y enums a SCREAMING_SNAKE_CASE (evita errores de decode)
+const NORMALIZAR_EXPEDIENTES: &str = r#"
+    UPDATE expedientes SET area_responsable = 'Sin definir' WHERE area_responsable IS NULL;
+    UPDATE expedientes SET prioridad = 'MEDIA' WHERE prioridad IS NULL;
+    UPDATE expedientes SET estado = 'INICIADO' WHERE estado IS NULL;
+    UPDATE expedientes SET fecha_inicio = COALESCE(fecha_inicio, created_at, CURRENT_TIMESTAMP) WHERE fecha_inicio IS NULL;
+    UPDATE expedientes SET tipo = UPPER(tipo) WHERE tipo IS NOT NULL;
+    UPDATE expedientes SET prioridad = UPPER(prioridad) WHERE prioridad IS NOT NULL;
+    UPDATE expedientes SET estado = UPPER(estado) WHERE estado IS NOT NULL;
+    CREATE INDEX IF NOT EXISTS idx_expedientes_estado ON expedientes(estado);
+    CREATE INDEX IF NOT EXISTS idx_expedientes_tipo ON expedientes(tipo);
+"#;
 
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_expedientes_tipo ON expedientes(tipo)")
-        .execute(pool)
-        .await?;
+// Índice de búsqueda de texto completo (FTS5) y triggers que lo mantienen sincronizado
+// unicode61 + remove_diacritics pliega mayúsculas y acentos ("reparacion" encuentra "Reparación")
+const BUSQUEDA_EXPEDIENTES: &str = r#"
+    CREATE VIRTUAL TABLE IF NOT EXISTS expedientes_fts USING fts5(
+        expediente_id UNINDEXED,
+        numero,
+        asunto,
+        tema,
+        resumen,
+        caratula,
+        nro_infogov,
+        nro_gde,
+        observaciones,
+        proveedor,
+        tokenize = 'unicode61 remove_diacritics 2'
+    );
 
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_agentes_legajo ON agentes(legajo)")
-        .execute(pool)
-        .await?;
+    CREATE TRIGGER IF NOT EXISTS expedientes_fts_insert AFTER INSERT ON expedientes BEGIN
+        INSERT INTO expedientes_fts (
+            expediente_id, numero, asunto, tema, resumen, caratula,
+            nro_infogov, nro_gde, observaciones, proveedor
+        ) VALUES (
+            new.id, new.numero, new.asunto, new.tema, new.resumen, new.caratula,
+            new.nro_infogov, new.nro_gde, new.observaciones, new.oc_señor
+        );
+    END;
 
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_vehiculos_patente ON vehiculos(patente)")
-        .execute(pool)
-        .await?;
+    CREATE TRIGGER IF NOT EXISTS expedientes_fts_update AFTER UPDATE ON expedientes BEGIN
+        DELETE FROM expedientes_fts WHERE expediente_id = old.id;
+        INSERT INTO expedientes_fts (
+            expediente_id, numero, asunto, tema, resumen, caratula,
+            nro_infogov, nro_gde, observaciones, proveedor
+        ) VALUES (
+            new.id, new.numero, new.asunto, new.tema, new.resumen, new.caratula,
+            new.nro_infogov, new.nro_gde, new.observaciones, new.oc_señor
+        );
+    END;
 
-    println!("✓ Migraciones SQLite completadas");
+    CREATE TRIGGER IF NOT EXISTS expedientes_fts_delete AFTER DELETE ON expedientes BEGIN
+        DELETE FROM expedientes_fts WHERE expediente_id = old.id;
+    END;
 
-    Ok(())
-}
+    -- Indexar expedientes existentes que todavía no están en el índice
+    INSERT INTO expedientes_fts (
+        expediente_id, numero, asunto, tema, resumen, caratula,
+        nro_infogov, nro_gde, observaciones, proveedor
+    )
+    SELECT id, numero, asunto, tema, resumen, caratula,
+           nro_infogov, nro_gde, observaciones, oc_señor
+    FROM expedientes
+    WHERE id NOT IN (SELECT expediente_id FROM expedientes_fts);
+"#;
+
+const ESQUEMA_AGENTES: &str = r#"
+    CREATE TABLE {tabla} (
+        id TEXT PRIMARY KEY,
+        dni TEXT UNIQUE NOT NULL,
+        nombre TEXT NOT NULL,
+        apellido TEXT NOT NULL,
+        email TEXT,
+        telefono TEXT,
+        legajo TEXT UNIQUE NOT NULL,
+        cargo TEXT NOT NULL DEFAULT '',
+        area TEXT NOT NULL,
+        fecha_ingreso DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        activo INTEGER NOT NULL DEFAULT 1,
+        licencia_conducir TEXT,
+        vencimiento_licencia DATETIME,
+        created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        synced_at DATETIME
+    )
+"#;
+
+const ESQUEMA_VEHICULOS: &str = r#"
+    CREATE TABLE {tabla} (
+        id TEXT PRIMARY KEY,
+        patente TEXT UNIQUE NOT NULL,
+        marca TEXT NOT NULL,
+        modelo TEXT NOT NULL,
+        año INTEGER NOT NULL,
+        tipo TEXT NOT NULL,
+        numero_motor TEXT,
+        numero_chasis TEXT,
+        color TEXT,
+        activo INTEGER NOT NULL DEFAULT 1,
+        kilometraje_actual REAL NOT NULL DEFAULT 0,
+        capacidad_tanque REAL NOT NULL DEFAULT 0,
+        vencimiento_seguro DATETIME,
+        vencimiento_vtv DATETIME,
+        vencimiento_habilitacion DATETIME,
+        area_asignada TEXT,
+        agente_asignado_id TEXT REFERENCES agentes(id) ON DELETE SET NULL,
+        observaciones TEXT,
+        created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        synced_at DATETIME
+    )
+"#;
+
+const ESQUEMA_TICKETS: &str = r#"
+    CREATE TABLE {tabla} (
+        id TEXT PRIMARY KEY,
+        vehiculo_id TEXT NOT NULL REFERENCES vehiculos(id) ON DELETE CASCADE,
+        agente_id TEXT REFERENCES agentes(id) ON DELETE SET NULL,
+        fecha_carga DATETIME NOT NULL,
+        numero_ticket TEXT,
+        tipo_combustible TEXT NOT NULL,
+        litros REAL NOT NULL,
+        precio_por_litro REAL NOT NULL,
+        monto_total REAL NOT NULL,
+        kilometraje REAL NOT NULL,
+        kilometraje_anterior REAL,
+        estacion_servicio TEXT NOT NULL DEFAULT '',
+        localidad TEXT NOT NULL DEFAULT '',
+        observaciones TEXT,
+        created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        synced_at DATETIME
+    )
+"#;
+
+// Copia de los datos del esquema anterior (tipo_licencia, kilometraje, fecha/precio_total)
+const COPIA_AGENTES: &str = r#"
+            INSERT INTO agentes_nueva (
+                id, dni, nombre, apellido, legajo, area, licencia_conducir, vencimiento_licencia,
+                fecha_ingreso, created_at, updated_at
+            )
+            SELECT id, dni, nombre, apellido, legajo, area, tipo_licencia,
+                   CASE WHEN length(fecha_vencimiento_licencia) = 10
+                        THEN fecha_vencimiento_licencia || ' 00:00:00'
+                        ELSE fecha_vencimiento_licencia END,
+                   COALESCE(created_at, CURRENT_TIMESTAMP),
+                   COALESCE(created_at, CURRENT_TIMESTAMP),
+                   COALESCE(updated_at, CURRENT_TIMESTAMP)
+            FROM agentes
+            "#;
+
+const COPIA_VEHICULOS: &str = r#"
+            INSERT INTO vehiculos_nueva (
+                id, patente, marca, modelo, año, tipo, activo, kilometraje_actual, created_at, updated_at
+            )
+            SELECT id, patente, marca, modelo, año, UPPER(tipo),
+                   CASE WHEN estado IS NULL OR estado = 'Activo' THEN 1 ELSE 0 END,
+                   COALESCE(kilometraje, 0),
+                   COALESCE(created_at, CURRENT_TIMESTAMP),
+                   COALESCE(updated_at, CURRENT_TIMESTAMP)
+            FROM vehiculos
+            "#;
+
+const COPIA_TICKETS: &str = r#"
+            INSERT INTO tickets_combustible_nueva (
+                id, vehiculo_id, fecha_carga, tipo_combustible, litros, precio_por_litro,
+                monto_total, kilometraje, created_at, updated_at
+            )
+            SELECT id, vehiculo_id,
+                   CASE WHEN length(fecha) = 10 THEN fecha || ' 00:00:00' ELSE fecha END,
+                   'NAFTA', litros,
+                   CASE WHEN litros > 0 THEN precio_total / litros ELSE 0 END,
+                   precio_total, kilometraje_actual,
+                   COALESCE(created_at, CURRENT_TIMESTAMP),
+                   COALESCE(updated_at, CURRENT_TIMESTAMP)
+            FROM tickets_combustible
+            "#;
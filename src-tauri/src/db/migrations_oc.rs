@@ -1,271 +1,365 @@
 // Migraciones para el sistema de Órdenes de Compra
-use sqlx::{SqlitePool, PgPool};
-use crate::error::Result;
+// Las columnas de OC en expedientes las agrega la migración agregar_campos_expediente
+use crate::db::migrador::{Migracion, Paso, Script};
+use crate::db::migrations_sync::{
+    revertir_triggers_outbox, revertir_updated_at_servidor_tabla, triggers_outbox, updated_at_servidor_tabla,
+};
 
-/// Ejecuta las migraciones del sistema de OC en SQLite
-pub async fn run_sqlite_oc_migrations(pool: &SqlitePool) -> Result<()> {
-    // Tabla proveedores
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS proveedores (
-            id TEXT PRIMARY KEY,
-            nombre TEXT NOT NULL,
-            cuit TEXT UNIQUE NOT NULL,
-            domicilio TEXT NOT NULL,
-            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
-            updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
-        )
-        "#
-    )
-    .execute(pool)
-    .await?;
-
-    // Actualizar tabla expedientes (agregar campos si no existen)
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN nro_infogov TEXT")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN nro_gde TEXT")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN caratula TEXT")
-        .execute(pool)
-        .await
-        .ok();
-    sqlx::query("ALTER TABLE expedientes ADD COLUMN resolucion_nro TEXT")
-        .execute(pool)
-        .await
-        .ok();
-
-    // Tabla config_topes
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS config_topes (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            tipo_contratacion TEXT NOT NULL UNIQUE,
-            monto_maximo REAL NOT NULL,
-            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
-            updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
-        )
-        "#
-    )
-    .execute(pool)
-    .await?;
-
-    // Insertar valores por defecto (montos en ARS 2026)
-    sqlx::query(
-        r#"
-        INSERT OR IGNORE INTO config_topes (tipo_contratacion, monto_maximo) VALUES
-            ('Contratación directa', 5000000.00),
-            ('Contratación directa con publicación', 15000000.00),
-            ('Licitación pública de menor monto', 50000000.00),
-            ('Licitación pública de mayor monto', 999999999.99)
-        "#
-    )
-    .execute(pool)
-    .await?;
-
-    // Tabla ordenes_compra
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS ordenes_compra (
-            id TEXT PRIMARY KEY,
-            numero_oc TEXT NOT NULL,
-            pedido_nro INTEGER NOT NULL,
-            destino TEXT NOT NULL DEFAULT 'ZONA RIEGO MALARGUE',
-            fecha TEXT NOT NULL,
-            expediente_id TEXT NOT NULL,
-            resolucion_nro TEXT,
-            forma_pago TEXT NOT NULL,
-            plazo_entrega TEXT NOT NULL DEFAULT '-',
-            es_iva_inscripto INTEGER NOT NULL DEFAULT 1,
-            tipo_contratacion TEXT NOT NULL,
-            subtotal REAL NOT NULL,
-            iva REAL NOT NULL,
-            total REAL NOT NULL,
-            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
-            updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
-            FOREIGN KEY (expediente_id) REFERENCES expedientes(id) ON DELETE RESTRICT
-        )
-        "#
-    )
-    .execute(pool)
-    .await?;
-
-    // Tabla orden_compra_renglones
-    sqlx::query(
+/// Migraciones del sistema de OC
+pub fn migraciones() -> Vec<Migracion> {
+    vec![
+        Migracion {
+            version: 20260301000004,
+            descripcion: "crear_ordenes_compra",
+            sqlite: Some(Script::new(vec![Paso::sql(ORDENES_COMPRA_SQLITE)]).reversible(vec![Paso::sql(REVERTIR_ORDENES_COMPRA)])),
+            postgres: Some(Script::new(vec![Paso::sql(ORDENES_COMPRA_POSTGRES)]).reversible(vec![Paso::sql(REVERTIR_ORDENES_COMPRA)])),
+        },
+        // Estado de la OC (las existentes ya se entregaron: quedan EMITIDA) y entregas del proveedor
+        Migracion {
+            version: 20261018000007,
+            descripcion: "ciclo_de_vida_ordenes_compra",
+            sqlite: Some(Script::new(ciclo_de_vida_sqlite()).reversible(revertir_ciclo_de_vida_sqlite())),
+            postgres: Some(Script::new(ciclo_de_vida_postgres()).reversible(revertir_ciclo_de_vida_postgres())),
+        },
+        // Numeración sin huecos ni repetidos: secuencia por zona y año, y número único dentro de la zona y el año
+        // (el número se repite entre zonas: cada jefatura numera las suyas). Las repetidas de antes quedan
+        // marcadas y fuera del índice; el informe de conciliación las lista para corregirlas a mano
+        Migracion {
+            version: 20261018000008,
+            descripcion: "secuencias_oc",
+            sqlite: Some(
+                Script::new(vec![
+                    Paso::AgregarColumna { tabla: "ordenes_compra", columna: "año", tipo: "INTEGER" },
+                    Paso::AgregarColumna { tabla: "ordenes_compra", columna: "numero_repetido", tipo: "INTEGER NOT NULL DEFAULT 0" },
+                    Paso::sql(SECUENCIAS_OC_SQLITE),
+                ])
+                .reversible(vec![Paso::sql(REVERTIR_SECUENCIAS_OC_SQLITE)]),
+            ),
+            postgres: Some(
+                Script::new(vec![Paso::sql(SECUENCIAS_OC_POSTGRES)]).reversible(vec![Paso::sql(REVERTIR_SECUENCIAS_OC_POSTGRES)]),
+            ),
+        },
+    ]
+}
+
+// El año sale del número (XX/YYYY). Las OC anteriores con número repetido se marcan, salvo la primera,
+// y el índice único (parcial) no las alcanza: los datos no se tocan
+const SECUENCIAS_OC_SQLITE: &str = r#"
+    UPDATE ordenes_compra SET año = CAST(substr(numero_oc, instr(numero_oc, '/') + 1) AS INTEGER)
+    WHERE numero_oc GLOB '[0-9]*/[0-9][0-9][0-9][0-9]';
+
+    UPDATE ordenes_compra SET numero_repetido = 1
+    WHERE año IS NOT NULL AND EXISTS (
+        SELECT 1 FROM ordenes_compra o
+        WHERE o.zona_id = ordenes_compra.zona_id
+          AND o.año = ordenes_compra.año
+          AND o.numero_oc = ordenes_compra.numero_oc
+          AND (o.created_at < ordenes_compra.created_at
+               OR (o.created_at = ordenes_compra.created_at AND o.id < ordenes_compra.id))
+    );
+
+    CREATE UNIQUE INDEX IF NOT EXISTS idx_oc_numero_unico ON ordenes_compra(zona_id, año, numero_oc)
+    WHERE numero_repetido = 0;
+
+    CREATE TABLE IF NOT EXISTS secuencias_oc (
+        zona_id TEXT NOT NULL,
+        año INTEGER NOT NULL,
+        ultimo_numero INTEGER NOT NULL DEFAULT 0,
+        ultimo_pedido INTEGER NOT NULL DEFAULT 0,
+        updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+        PRIMARY KEY (zona_id, año)
+    );
+
+    INSERT OR IGNORE INTO secuencias_oc (zona_id, año, ultimo_numero, ultimo_pedido)
+    SELECT zona_id, año, MAX(CAST(substr(numero_oc, 1, instr(numero_oc, '/') - 1) AS INTEGER)), MAX(pedido_nro)
+    FROM ordenes_compra
+    WHERE año IS NOT NULL
+    GROUP BY zona_id, año;
+"#;
+
+const REVERTIR_SECUENCIAS_OC_SQLITE: &str = r#"
+    DROP TABLE IF EXISTS secuencias_oc;
+    DROP INDEX IF EXISTS idx_oc_numero_unico;
+    ALTER TABLE ordenes_compra DROP COLUMN numero_repetido;
+    ALTER TABLE ordenes_compra DROP COLUMN año;
+"#;
+
+const SECUENCIAS_OC_POSTGRES: &str = r#"
+    ALTER TABLE ordenes_compra ADD COLUMN IF NOT EXISTS año INTEGER;
+    ALTER TABLE ordenes_compra ADD COLUMN IF NOT EXISTS numero_repetido BOOLEAN NOT NULL DEFAULT FALSE;
+
+    UPDATE ordenes_compra SET año = split_part(numero_oc, '/', 2)::int
+    WHERE año IS NULL AND numero_oc ~ '^[0-9]+/[0-9]{4}$';
+
+    UPDATE ordenes_compra oc SET numero_repetido = TRUE
+    WHERE oc.año IS NOT NULL AND EXISTS (
+        SELECT 1 FROM ordenes_compra o
+        WHERE o.zona_id = oc.zona_id
+          AND o.año = oc.año
+          AND o.numero_oc = oc.numero_oc
+          AND (o.created_at, o.id::text) < (oc.created_at, oc.id::text)
+    );
+
+    CREATE UNIQUE INDEX IF NOT EXISTS idx_oc_numero_unico ON ordenes_compra(zona_id, año, numero_oc)
+    WHERE NOT numero_repetido;
+
+    CREATE TABLE IF NOT EXISTS secuencias_oc (
+        zona_id UUID NOT NULL REFERENCES zonas(id),
+        año INTEGER NOT NULL,
+        ultimo_numero INTEGER NOT NULL DEFAULT 0,
+        ultimo_pedido INTEGER NOT NULL DEFAULT 0,
+        updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        PRIMARY KEY (zona_id, año)
+    );
+
+    INSERT INTO secuencias_oc (zona_id, año, ultimo_numero, ultimo_pedido)
+    SELECT zona_id, año, MAX(split_part(numero_oc, '/', 1)::int), MAX(pedido_nro)
+    FROM ordenes_compra
+    WHERE año IS NOT NULL
+    GROUP BY zona_id, año
+    ON CONFLICT (zona_id, año) DO NOTHING;
+"#;
+
+const REVERTIR_SECUENCIAS_OC_POSTGRES: &str = r#"
+    DROP TABLE IF EXISTS secuencias_oc;
+    DROP INDEX IF EXISTS idx_oc_numero_unico;
+    ALTER TABLE ordenes_compra DROP COLUMN IF EXISTS numero_repetido;
+    ALTER TABLE ordenes_compra DROP COLUMN IF EXISTS año;
+"#;
+
+/// Columnas del ciclo de vida en ordenes_compra
+const COLUMNAS_ESTADO_OC: [(&str, &str); 3] = [
+    ("estado", "TEXT NOT NULL DEFAULT 'EMITIDA'"),
+    ("motivo_anulacion", "TEXT"),
+    ("fecha_anulacion", "DATETIME"),
+];
+
+fn ciclo_de_vida_sqlite() -> Vec<Paso> {
+    let mut pasos: Vec<Paso> = COLUMNAS_ESTADO_OC
+        .iter()
+        .map(|&(columna, tipo)| Paso::AgregarColumna { tabla: "ordenes_compra", columna, tipo })
+        .collect();
+
+    pasos.push(Paso::sql(
         r#"
-        CREATE TABLE IF NOT EXISTS orden_compra_renglones (
+        CREATE INDEX IF NOT EXISTS idx_oc_estado ON ordenes_compra(estado);
+
+        CREATE TABLE IF NOT EXISTS orden_compra_entregas (
             id TEXT PRIMARY KEY,
             oc_id TEXT NOT NULL,
-            renglon_nro INTEGER NOT NULL,
+            renglon_id TEXT NOT NULL,
             cantidad REAL NOT NULL,
-            detalle TEXT NOT NULL,
-            marca TEXT,
-            valor_unitario REAL NOT NULL,
-            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
-            updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
-            FOREIGN KEY (oc_id) REFERENCES ordenes_compra(id) ON DELETE CASCADE
-        )
-        "#
-    )
-    .execute(pool)
-    .await?;
-
-    // Índices para performance
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_proveedores_cuit ON proveedores(cuit)")
-        .execute(pool)
-        .await?;
-
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_expedientes_infogov ON expedientes(nro_infogov)")
-        .execute(pool)
-        .await?;
-
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_oc_numero ON ordenes_compra(numero_oc)")
-        .execute(pool)
-        .await?;
-
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_oc_fecha ON ordenes_compra(fecha)")
-        .execute(pool)
-        .await?;
-
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_renglones_oc ON orden_compra_renglones(oc_id)")
-        .execute(pool)
-        .await?;
-
-    println!("✅ Migraciones de OC ejecutadas en SQLite");
-    Ok(())
+            fecha TEXT NOT NULL,
+            remito TEXT,
+            observaciones TEXT,
+            created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+            updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+            synced_at DATETIME,
+            sync_version INTEGER NOT NULL DEFAULT 0,
+            FOREIGN KEY (oc_id) REFERENCES ordenes_compra(id) ON DELETE CASCADE,
+            FOREIGN KEY (renglon_id) REFERENCES orden_compra_renglones(id) ON DELETE RESTRICT
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_entregas_oc ON orden_compra_entregas(oc_id);
+        "#,
+    ));
+    pasos.push(Paso::sql(triggers_outbox("orden_compra_entregas")));
+    pasos
 }
 
-/// Ejecuta las migraciones del sistema de OC en PostgreSQL
-pub async fn run_postgres_oc_migrations(pool: &PgPool) -> Result<()> {
-    // Tabla proveedores
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS proveedores (
-            id UUID PRIMARY KEY,
-            nombre TEXT NOT NULL,
-            cuit TEXT UNIQUE NOT NULL,
-            domicilio TEXT NOT NULL,
-            created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
-            updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
-        )
-        "#
-    )
-    .execute(pool)
-    .await?;
-
-    // Agregar columnas a expedientes si no existen
-    sqlx::query(
-        r#"
-        ALTER TABLE expedientes 
-        ADD COLUMN IF NOT EXISTS nro_infogov TEXT UNIQUE,
-        ADD COLUMN IF NOT EXISTS nro_gde TEXT,
-        ADD COLUMN IF NOT EXISTS caratula TEXT,
-        ADD COLUMN IF NOT EXISTS resolucion_nro TEXT
-        "#
-    )
-    .execute(pool)
-    .await.ok(); // Ignorar si ya existen
-
-    // Tabla config_topes
-    sqlx::query(
+fn revertir_ciclo_de_vida_sqlite() -> Vec<Paso> {
+    vec![Paso::sql(format!(
         r#"
-        CREATE TABLE IF NOT EXISTS config_topes (
-            id SERIAL PRIMARY KEY,
-            tipo_contratacion TEXT NOT NULL UNIQUE,
-            monto_maximo DECIMAL(15, 2) NOT NULL,
-            created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
-            updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
-        )
-        "#
-    )
-    .execute(pool)
-    .await?;
-
-    // Insertar valores por defecto
-    sqlx::query(
-        r#"
-        INSERT INTO config_topes (tipo_contratacion, monto_maximo) VALUES
-            ('Contratación directa', 5000000.00),
-            ('Contratación directa con publicación', 15000000.00),
-            ('Licitación pública de menor monto', 50000000.00),
-            ('Licitación pública de mayor monto', 999999999.99)
-        ON CONFLICT (tipo_contratacion) DO NOTHING
-        "#
-    )
-    .execute(pool)
-    .await?;
-
-    // Tabla ordenes_compra
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS ordenes_compra (
-            id UUID PRIMARY KEY,
-            numero_oc TEXT NOT NULL,
-            pedido_nro SERIAL NOT NULL,
-            destino TEXT NOT NULL DEFAULT 'ZONA RIEGO MALARGUE',
-            fecha DATE NOT NULL DEFAULT CURRENT_DATE,
-            expediente_id UUID NOT NULL,
-            resolucion_nro TEXT,
-            forma_pago TEXT NOT NULL,
-            plazo_entrega TEXT NOT NULL DEFAULT '-',
-            es_iva_inscripto BOOLEAN NOT NULL DEFAULT TRUE,
-            tipo_contratacion TEXT NOT NULL,
-            subtotal DECIMAL(15, 2) NOT NULL,
-            iva DECIMAL(15, 2) NOT NULL,
-            total DECIMAL(15, 2) NOT NULL,
-            created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
-            updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
-            FOREIGN KEY (expediente_id) REFERENCES expedientes(id) ON DELETE RESTRICT
-        )
-        "#
-    )
-    .execute(pool)
-    .await?;
-
-    // Tabla orden_compra_renglones
-    sqlx::query(
+        {}
+        DELETE FROM sync_outbox WHERE tabla = 'orden_compra_entregas';
+        DROP TABLE IF EXISTS orden_compra_entregas;
+        DROP INDEX IF EXISTS idx_oc_estado;
+        ALTER TABLE ordenes_compra DROP COLUMN fecha_anulacion;
+        ALTER TABLE ordenes_compra DROP COLUMN motivo_anulacion;
+        ALTER TABLE ordenes_compra DROP COLUMN estado;
+        "#,
+        revertir_triggers_outbox("orden_compra_entregas")
+    ))]
+}
+
+fn ciclo_de_vida_postgres() -> Vec<Paso> {
+    vec![
+        Paso::sql(
+            r#"
+            ALTER TABLE ordenes_compra ADD COLUMN IF NOT EXISTS estado TEXT NOT NULL DEFAULT 'EMITIDA'
+                CHECK (estado IN ('BORRADOR', 'EMITIDA', 'ANULADA', 'RECIBIDA_PARCIAL', 'RECIBIDA'));
+            ALTER TABLE ordenes_compra ADD COLUMN IF NOT EXISTS motivo_anulacion TEXT;
+            ALTER TABLE ordenes_compra ADD COLUMN IF NOT EXISTS fecha_anulacion TIMESTAMP;
+            CREATE INDEX IF NOT EXISTS idx_oc_estado ON ordenes_compra(estado);
+
+            CREATE TABLE IF NOT EXISTS orden_compra_entregas (
+                id UUID PRIMARY KEY,
+                oc_id UUID NOT NULL REFERENCES ordenes_compra(id) ON DELETE CASCADE,
+                renglon_id UUID NOT NULL REFERENCES orden_compra_renglones(id) ON DELETE RESTRICT,
+                cantidad DECIMAL(10, 2) NOT NULL CHECK (cantidad > 0),
+                fecha DATE NOT NULL DEFAULT CURRENT_DATE,
+                remito TEXT,
+                observaciones TEXT,
+                created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                sync_version INTEGER NOT NULL DEFAULT 0
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_entregas_oc ON orden_compra_entregas(oc_id);
+            "#,
+        ),
+        Paso::sql(updated_at_servidor_tabla("orden_compra_entregas")),
+    ]
+}
+
+fn revertir_ciclo_de_vida_postgres() -> Vec<Paso> {
+    vec![Paso::sql(format!(
         r#"
-        CREATE TABLE IF NOT EXISTS orden_compra_renglones (
-            id UUID PRIMARY KEY,
-            oc_id UUID NOT NULL,
-            renglon_nro INTEGER NOT NULL,
-            cantidad DECIMAL(10, 2) NOT NULL,
-            detalle TEXT NOT NULL,
-            marca TEXT,
-            valor_unitario DECIMAL(15, 2) NOT NULL,
-            created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
-            updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
-            FOREIGN KEY (oc_id) REFERENCES ordenes_compra(id) ON DELETE CASCADE
-        )
-        "#
-    )
-    .execute(pool)
-    .await?;
-
-    // Índices
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_proveedores_cuit ON proveedores(cuit)")
-        .execute(pool)
-        .await?;
-
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_expedientes_infogov ON expedientes(nro_infogov)")
-        .execute(pool)
-        .await?;
-
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_oc_numero ON ordenes_compra(numero_oc)")
-        .execute(pool)
-        .await?;
-
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_oc_fecha ON ordenes_compra(fecha)")
-        .execute(pool)
-        .await?;
-
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_renglones_oc ON orden_compra_renglones(oc_id)")
-        .execute(pool)
-        .await?;
-
-    println!("✅ Migraciones de OC ejecutadas en PostgreSQL");
-    Ok(())
+        {}
+        DROP TABLE IF EXISTS orden_compra_entregas;
+        DROP INDEX IF EXISTS idx_oc_estado;
+        ALTER TABLE ordenes_compra DROP COLUMN IF EXISTS fecha_anulacion;
+        ALTER TABLE ordenes_compra DROP COLUMN IF EXISTS motivo_anulacion;
+        ALTER TABLE ordenes_compra DROP COLUMN IF EXISTS estado;
+        "#,
+        revertir_updated_at_servidor_tabla("orden_compra_entregas")
+    ))]
 }
+
+// Proveedores, topes por tipo de contratación (montos en ARS 2026), órdenes y renglones
+const ORDENES_COMPRA_SQLITE: &str = r#"
+    CREATE TABLE IF NOT EXISTS proveedores (
+        id TEXT PRIMARY KEY,
+        nombre TEXT NOT NULL,
+        cuit TEXT UNIQUE NOT NULL,
+        domicilio TEXT NOT NULL,
+        created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+    );
+
+    CREATE TABLE IF NOT EXISTS config_topes (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        tipo_contratacion TEXT NOT NULL UNIQUE,
+        monto_maximo REAL NOT NULL,
+        created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+    );
+
+    INSERT OR IGNORE INTO config_topes (tipo_contratacion, monto_maximo) VALUES
+        ('Contratación directa', 5000000.00),
+        ('Contratación directa con publicación', 15000000.00),
+        ('Licitación pública de menor monto', 50000000.00),
+        ('Licitación pública de mayor monto', 999999999.99);
+
+    CREATE TABLE IF NOT EXISTS ordenes_compra (
+        id TEXT PRIMARY KEY,
+        numero_oc TEXT NOT NULL,
+        pedido_nro INTEGER NOT NULL,
+        destino TEXT NOT NULL DEFAULT 'ZONA RIEGO MALARGUE',
+        fecha TEXT NOT NULL,
+        expediente_id TEXT NOT NULL,
+        resolucion_nro TEXT,
+        forma_pago TEXT NOT NULL,
+        plazo_entrega TEXT NOT NULL DEFAULT '-',
+        es_iva_inscripto INTEGER NOT NULL DEFAULT 1,
+        tipo_contratacion TEXT NOT NULL,
+        subtotal REAL NOT NULL,
+        iva REAL NOT NULL,
+        total REAL NOT NULL,
+        created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        FOREIGN KEY (expediente_id) REFERENCES expedientes(id) ON DELETE RESTRICT
+    );
+
+    CREATE TABLE IF NOT EXISTS orden_compra_renglones (
+        id TEXT PRIMARY KEY,
+        oc_id TEXT NOT NULL,
+        renglon_nro INTEGER NOT NULL,
+        cantidad REAL NOT NULL,
+        detalle TEXT NOT NULL,
+        marca TEXT,
+        valor_unitario REAL NOT NULL,
+        created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        FOREIGN KEY (oc_id) REFERENCES ordenes_compra(id) ON DELETE CASCADE
+    );
+
+    CREATE INDEX IF NOT EXISTS idx_proveedores_cuit ON proveedores(cuit);
+    CREATE INDEX IF NOT EXISTS idx_expedientes_infogov ON expedientes(nro_infogov);
+    CREATE INDEX IF NOT EXISTS idx_oc_numero ON ordenes_compra(numero_oc);
+    CREATE INDEX IF NOT EXISTS idx_oc_fecha ON ordenes_compra(fecha);
+    CREATE INDEX IF NOT EXISTS idx_renglones_oc ON orden_compra_renglones(oc_id);
+"#;
+
+const ORDENES_COMPRA_POSTGRES: &str = r#"
+    CREATE TABLE IF NOT EXISTS proveedores (
+        id UUID PRIMARY KEY,
+        nombre TEXT NOT NULL,
+        cuit TEXT UNIQUE NOT NULL,
+        domicilio TEXT NOT NULL,
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+    );
+
+    CREATE TABLE IF NOT EXISTS config_topes (
+        id SERIAL PRIMARY KEY,
+        tipo_contratacion TEXT NOT NULL UNIQUE,
+        monto_maximo DECIMAL(15, 2) NOT NULL,
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+    );
+
+    INSERT INTO config_topes (tipo_contratacion, monto_maximo) VALUES
+        ('Contratación directa', 5000000.00),
+        ('Contratación directa con publicación', 15000000.00),
+        ('Licitación pública de menor monto', 50000000.00),
+        ('Licitación pública de mayor monto', 999999999.99)
+    ON CONFLICT (tipo_contratacion) DO NOTHING;
+
+    CREATE TABLE IF NOT EXISTS ordenes_compra (
+        id UUID PRIMARY KEY,
+        numero_oc TEXT NOT NULL,
+        pedido_nro SERIAL NOT NULL,
+        destino TEXT NOT NULL DEFAULT 'ZONA RIEGO MALARGUE',
+        fecha DATE NOT NULL DEFAULT CURRENT_DATE,
+        expediente_id UUID NOT NULL,
+        resolucion_nro TEXT,
+        forma_pago TEXT NOT NULL,
+        plazo_entrega TEXT NOT NULL DEFAULT '-',
+        es_iva_inscripto BOOLEAN NOT NULL DEFAULT TRUE,
+        tipo_contratacion TEXT NOT NULL,
+        subtotal DECIMAL(15, 2) NOT NULL,
+        iva DECIMAL(15, 2) NOT NULL,
+        total DECIMAL(15, 2) NOT NULL,
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        FOREIGN KEY (expediente_id) REFERENCES expedientes(id) ON DELETE RESTRICT
+    );
+
+    CREATE TABLE IF NOT EXISTS orden_compra_renglones (
+        id UUID PRIMARY KEY,
+        oc_id UUID NOT NULL,
+        renglon_nro INTEGER NOT NULL,
+        cantidad DECIMAL(10, 2) NOT NULL,
+        detalle TEXT NOT NULL,
+        marca TEXT,
+        valor_unitario DECIMAL(15, 2) NOT NULL,
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        FOREIGN KEY (oc_id) REFERENCES ordenes_compra(id) ON DELETE CASCADE
+    );
+
+    CREATE INDEX IF NOT EXISTS idx_proveedores_cuit ON proveedores(cuit);
+    CREATE INDEX IF NOT EXISTS idx_expedientes_infogov ON expedientes(nro_infogov);
+    CREATE INDEX IF NOT EXISTS idx_oc_numero ON ordenes_compra(numero_oc);
+    CREATE INDEX IF NOT EXISTS idx_oc_fecha ON ordenes_compra(fecha);
+    CREATE INDEX IF NOT EXISTS idx_renglones_oc ON orden_compra_renglones(oc_id);
+"#;
+
+const REVERTIR_ORDENES_COMPRA: &str = r#"
+    DROP INDEX IF EXISTS idx_expedientes_infogov;
+    DROP TABLE IF EXISTS orden_compra_renglones;
+    DROP TABLE IF EXISTS ordenes_compra;
+    DROP TABLE IF EXISTS config_topes;
+    DROP TABLE IF EXISTS proveedores;
+"#;
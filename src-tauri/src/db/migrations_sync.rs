@@ -0,0 +1,285 @@
+// Migraciones para la sincronización SQLite -> PostgreSQL
+// Las escrituras locales quedan registradas en sync_outbox mediante triggers
+use crate::db::migrador::{Migracion, Paso, Script};
+
+/// Tablas locales con outbox al crearse la sincronización
+/// Lista fija (no TABLAS_SINCRONIZADAS): una tabla sincronizada nueva necesita su propia migración
+const TABLAS_LOCALES: [&str; 7] = [
+    "agentes",
+    "vehiculos",
+    "proveedores",
+    "expedientes",
+    "tickets_combustible",
+    "ordenes_compra",
+    "orden_compra_renglones",
+];
+
+/// Las mismas tablas con su nombre en PostgreSQL
+const TABLAS_REMOTAS: [&str; 7] = [
+    "agentes",
+    "vehiculos",
+    "proveedores",
+    "expedientes",
+    "tickets",
+    "ordenes_compra",
+    "orden_compra_renglones",
+];
+
+/// Migraciones de sincronización (requieren todas las tablas sincronizadas)
+pub fn migraciones() -> Vec<Migracion> {
+    vec![
+        Migracion {
+            version: 20260301000005,
+            descripcion: "crear_sync_outbox",
+            sqlite: Some(Script::new(outbox()).reversible(vec![Paso::sql(revertir_outbox())])),
+            postgres: None,
+        },
+        // Versión del servidor sobre la que se basa la fila (detección de conflictos)
+        Migracion {
+            version: 20260301000006,
+            descripcion: "agregar_sync_version",
+            sqlite: Some(
+                Script::new(
+                    TABLAS_LOCALES
+                        .iter()
+                        .map(|&tabla| Paso::AgregarColumna {
+                            tabla,
+                            columna: "sync_version",
+                            tipo: "INTEGER NOT NULL DEFAULT 0",
+                        })
+                        .collect(),
+                )
+                .reversible(
+                    TABLAS_LOCALES
+                        .iter()
+                        .map(|t| Paso::sql(format!("ALTER TABLE {} DROP COLUMN sync_version", t)))
+                        .collect(),
+                ),
+            ),
+            postgres: Some(
+                Script::new(
+                    TABLAS_REMOTAS
+                        .iter()
+                        .map(|t| {
+                            Paso::sql(format!(
+                                "ALTER TABLE IF EXISTS \"{}\" ADD COLUMN IF NOT EXISTS sync_version INTEGER NOT NULL DEFAULT 0",
+                                t
+                            ))
+                        })
+                        .collect(),
+                )
+                .reversible(
+                    TABLAS_REMOTAS
+                        .iter()
+                        .map(|t| Paso::sql(format!("ALTER TABLE IF EXISTS \"{}\" DROP COLUMN IF EXISTS sync_version", t)))
+                        .collect(),
+                ),
+            ),
+        },
+        // Conflictos detectados entre ediciones locales y remotas
+        Migracion {
+            version: 20260301000007,
+            descripcion: "crear_sync_conflicts",
+            sqlite: Some(
+                Script::new(vec![Paso::sql(
+                    r#"
+                    CREATE TABLE IF NOT EXISTS sync_conflicts (
+                        id TEXT PRIMARY KEY,
+                        tabla TEXT NOT NULL,
+                        registro_id TEXT NOT NULL,
+                        version_local INTEGER NOT NULL,
+                        version_remota INTEGER NOT NULL,
+                        datos_locales TEXT NOT NULL,
+                        datos_remotos TEXT,
+                        estado TEXT NOT NULL DEFAULT 'PENDIENTE',
+                        resolucion TEXT,
+                        detectado_at DATETIME NOT NULL,
+                        resuelto_at DATETIME
+                    );
+                    CREATE INDEX IF NOT EXISTS idx_sync_conflicts_registro ON sync_conflicts(tabla, registro_id, estado);
+                    "#,
+                )])
+                .reversible(vec![Paso::sql("DROP TABLE IF EXISTS sync_conflicts")]),
+            ),
+            postgres: None,
+        },
+        // Descarga incremental: marca de agua local por tabla y updated_at fijado por el servidor
+        Migracion {
+            version: 20260301000008,
+            descripcion: "crear_sync_pull",
+            sqlite: Some(
+                Script::new(vec![Paso::sql(
+                    r#"
+                    CREATE TABLE IF NOT EXISTS sync_pull_estado (
+                        tabla TEXT PRIMARY KEY,
+                        ultimo_updated_at TEXT,
+                        ultimo_id TEXT,
+                        ultima_descarga DATETIME
+                    )
+                    "#,
+                )])
+                .reversible(vec![Paso::sql("DROP TABLE IF EXISTS sync_pull_estado")]),
+            ),
+            postgres: Some(Script::new(updated_at_servidor()).reversible(revertir_updated_at_servidor())),
+        },
+    ]
+}
+
+/// Cola persistente de cambios pendientes de enviar (sobrevive reinicios) y sus triggers
+/// Un único registro por fila: los cambios sucesivos se reemplazan entre sí
+fn outbox() -> Vec<Paso> {
+    let mut pasos = vec![Paso::sql(
+        r#"
+        CREATE TABLE IF NOT EXISTS sync_outbox (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            tabla TEXT NOT NULL,
+            registro_id TEXT NOT NULL,
+            operacion TEXT NOT NULL,
+            intentos INTEGER NOT NULL DEFAULT 0,
+            proximo_intento DATETIME,
+            ultimo_error TEXT,
+            created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+            UNIQUE (tabla, registro_id)
+        );
+        CREATE INDEX IF NOT EXISTS idx_sync_outbox_proximo ON sync_outbox(proximo_intento);
+        "#,
+    )];
+
+    for t in TABLAS_LOCALES {
+        pasos.push(Paso::AgregarColumna { tabla: t, columna: "synced_at", tipo: "DATETIME" });
+        pasos.push(Paso::sql(triggers_outbox(t)));
+    }
+
+    pasos
+}
+
+/// Triggers que encolan en sync_outbox las altas, modificaciones y bajas de una tabla sincronizada
+/// y encolan las filas existentes que nunca se sincronizaron (la tabla necesita id y synced_at)
+/// El texto no se puede tocar: forma parte del checksum de las migraciones aplicadas
+pub(crate) fn triggers_outbox(t: &str) -> String {
+    format!(
+        r#"
+        -- Alta: las filas que llegan ya sincronizadas (descargadas del servidor) no se encolan
+        CREATE TRIGGER IF NOT EXISTS sync_{t}_insert AFTER INSERT ON {t}
+        WHEN NEW.synced_at IS NULL
+        BEGIN
+            INSERT OR REPLACE INTO sync_outbox (tabla, registro_id, operacion)
+            VALUES ('{t}', NEW.id, 'UPSERT');
+        END;
+
+        -- Modificación: se ignoran los cambios que sólo marcan synced_at (los hace el worker)
+        CREATE TRIGGER IF NOT EXISTS sync_{t}_update AFTER UPDATE ON {t}
+        WHEN OLD.synced_at IS NEW.synced_at
+        BEGIN
+            INSERT OR REPLACE INTO sync_outbox (tabla, registro_id, operacion)
+            VALUES ('{t}', NEW.id, 'UPSERT');
+            UPDATE {t} SET synced_at = NULL WHERE id = NEW.id AND synced_at IS NOT NULL;
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS sync_{t}_delete AFTER DELETE ON {t}
+        BEGIN
+            INSERT OR REPLACE INTO sync_outbox (tabla, registro_id, operacion)
+            VALUES ('{t}', OLD.id, 'DELETE');
+        END;
+
+        -- Encolar filas existentes que nunca se sincronizaron
+        INSERT OR IGNORE INTO sync_outbox (tabla, registro_id, operacion)
+        SELECT '{t}', id, 'UPSERT' FROM {t} WHERE synced_at IS NULL;
+        "#,
+        t = t
+    )
+}
+
+pub(crate) fn revertir_triggers_outbox(t: &str) -> String {
+    format!(
+        "DROP TRIGGER IF EXISTS sync_{t}_insert; DROP TRIGGER IF EXISTS sync_{t}_update; DROP TRIGGER IF EXISTS sync_{t}_delete;\n",
+        t = t
+    )
+}
+
+/// synced_at queda: es parte del esquema de las tablas
+fn revertir_outbox() -> String {
+    let mut sql: String = TABLAS_LOCALES
+        .iter()
+        .map(|t| revertir_triggers_outbox(t))
+        .collect();
+    sql.push_str("DROP TABLE IF EXISTS sync_outbox;");
+    sql
+}
+
+/// Los clientes envían su propio updated_at: el servidor lo pisa con su hora en altas y modificaciones
+/// (es la marca de agua de la descarga incremental)
+fn updated_at_servidor() -> Vec<Paso> {
+    let mut pasos = vec![Paso::sql(
+        r#"
+        CREATE OR REPLACE FUNCTION sync_tocar_updated_at()
+        RETURNS TRIGGER AS $$
+        BEGIN
+            NEW.updated_at = NOW();
+            RETURN NEW;
+        END;
+        $$ LANGUAGE plpgsql
+        "#,
+    )];
+
+    for t in TABLAS_REMOTAS {
+        pasos.push(Paso::sql(updated_at_servidor_tabla(t)));
+    }
+
+    pasos
+}
+
+/// Trigger que fija updated_at con la hora del servidor y el índice de la descarga incremental
+/// (requiere la función sync_tocar_updated_at; el texto entra en el checksum)
+pub(crate) fn updated_at_servidor_tabla(t: &str) -> String {
+    format!(
+        r#"
+        DROP TRIGGER IF EXISTS sync_{t}_updated_at ON "{t}";
+        CREATE TRIGGER sync_{t}_updated_at
+        BEFORE INSERT OR UPDATE ON "{t}"
+        FOR EACH ROW EXECUTE FUNCTION sync_tocar_updated_at();
+        CREATE INDEX IF NOT EXISTS idx_{t}_sync_updated_at ON "{t}"(updated_at, id);
+        "#,
+        t = t
+    )
+}
+
+pub(crate) fn revertir_updated_at_servidor_tabla(t: &str) -> String {
+    format!(
+        "DROP TRIGGER IF EXISTS sync_{t}_updated_at ON \"{t}\"; DROP INDEX IF EXISTS idx_{t}_sync_updated_at;",
+        t = t
+    )
+}
+
+fn revertir_updated_at_servidor() -> Vec<Paso> {
+    let mut pasos: Vec<Paso> = TABLAS_REMOTAS
+        .iter()
+        .map(|t| Paso::sql(revertir_updated_at_servidor_tabla(t)))
+        .collect();
+    pasos.push(Paso::sql("DROP FUNCTION IF EXISTS sync_tocar_updated_at()"));
+    pasos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::TABLAS_SINCRONIZADAS;
+
+    #[test]
+    fn test_tablas_coinciden_con_sincronizadas() {
+        // Una tabla agregada a TABLAS_SINCRONIZADAS necesita una migración que cree sus triggers
+        // Las agregadas después de crear la sincronización tienen la suya
+        // (zonas: crear_zonas, orden_compra_entregas: ciclo_de_vida_ordenes_compra)
+        let posteriores = [("zonas", "zonas"), ("orden_compra_entregas", "orden_compra_entregas")];
+        let mut esperadas: Vec<(&str, &str)> = TABLAS_LOCALES
+            .iter()
+            .copied()
+            .zip(TABLAS_REMOTAS)
+            .chain(posteriores)
+            .collect();
+        let mut sincronizadas: Vec<(&str, &str)> = TABLAS_SINCRONIZADAS.iter().map(|t| (t.local, t.remota)).collect();
+        esperadas.sort();
+        sincronizadas.sort();
+        assert_eq!(sincronizadas, esperadas);
+    }
+}
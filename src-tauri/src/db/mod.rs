@@ -2,9 +2,13 @@
 // Maneja la conexión dual SQLite (local) + PostgreSQL (remoto)
 
 pub mod database;
-pub mod manager;
+pub mod migrador;
 pub mod migrations;
 pub mod migrations_oc;
+pub mod migrations_sync;
+pub mod supervisor;
+#[cfg(test)]
+pub(crate) mod test_util;
 
-pub use database::{DatabasePool, init_databases};
-pub use manager::{DatabaseManager, DatabaseConfig, config_from_env};
+pub use database::{DatabasePool, EstadoBaseDatos, init_databases};
+pub use supervisor::iniciar_supervisor_postgres;
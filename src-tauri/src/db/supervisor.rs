@@ -0,0 +1,95 @@
+// Supervisor de la conexión con PostgreSQL
+// Chequea periódicamente el servidor, reconecta cuando vuelve y avisa al frontend de cada cambio
+
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter};
+
+use crate::db::database::{create_postgres_pool, init_postgres, DatabasePool};
+
+/// Intervalo entre chequeos
+const INTERVALO_CHEQUEO: Duration = Duration::from_secs(15);
+
+/// Tiempo máximo de un chequeo o intento de reconexión
+const TIEMPO_MAXIMO_CHEQUEO: Duration = Duration::from_secs(10);
+
+/// Evento emitido al frontend cuando PostgreSQL se conecta o desconecta
+pub const EVENTO_ESTADO_DB: &str = "db_status_changed";
+
+/// Lanza el supervisor
+/// Corre aunque no haya URL configurada: la URL puede cargarse después desde la configuración
+pub fn iniciar_supervisor_postgres(app: AppHandle, pools: DatabasePool) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let conectado_antes = pools.has_postgres();
+            let conectado = verificar_conexion(&pools).await;
+
+            if conectado != conectado_antes {
+                let _ = app.emit(EVENTO_ESTADO_DB, pools.estado());
+            }
+
+            tokio::time::sleep(INTERVALO_CHEQUEO).await;
+        }
+    });
+}
+
+/// Chequea la conexión actual o intenta reconectar. Devuelve si quedó conectado
+pub async fn verificar_conexion(pools: &DatabasePool) -> bool {
+    match pools.get_postgres() {
+        Some(pool) => {
+            let chequeo = tokio::time::timeout(TIEMPO_MAXIMO_CHEQUEO, sqlx::query("SELECT 1").execute(&pool)).await;
+            match chequeo {
+                Ok(Ok(_)) => {
+                    pools.registrar_exito();
+                    true
+                }
+                Ok(Err(e)) => desconectar(pools, &e.to_string()),
+                Err(_) => desconectar(pools, "Tiempo de espera agotado"),
+            }
+        }
+        None => reconectar(pools).await,
+    }
+}
+
+/// Descarta el pool caído: los commands pasan a modo offline hasta la reconexión
+fn desconectar(pools: &DatabasePool, error: &str) -> bool {
+    eprintln!("⚠️ PostgreSQL no responde: {}. Pasando a modo offline.", error);
+    pools.set_postgres(None);
+    pools.registrar_fallo(error);
+    false
+}
+
+/// Crea un pool nuevo y ejecuta las migraciones remotas (pudieron no correr al iniciar)
+async fn reconectar(pools: &DatabasePool) -> bool {
+    let Some(url) = pools.postgres_url() else {
+        return false;
+    };
+
+    let pool = match tokio::time::timeout(TIEMPO_MAXIMO_CHEQUEO, create_postgres_pool(&url)).await {
+        Ok(Ok(pool)) => pool,
+        Ok(Err(e)) => {
+            pools.registrar_fallo(&e.to_string());
+            return false;
+        }
+        Err(_) => {
+            pools.registrar_fallo("Tiempo de espera agotado");
+            return false;
+        }
+    };
+
+    if let Err(e) = init_postgres(&pool).await {
+        eprintln!("❌ Error en migraciones de PostgreSQL al reconectar: {}", e);
+        pools.registrar_fallo(&e.to_string());
+        return false;
+    }
+
+    // La URL pudo cambiar desde la configuración mientras se conectaba
+    if pools.postgres_url().as_deref() != Some(url.as_str()) {
+        return false;
+    }
+
+    pools.set_postgres(Some(pool));
+    pools.registrar_exito();
+    println!("✅ Reconectado a PostgreSQL");
+    true
+}
@@ -0,0 +1,36 @@
+// Utilidades compartidas por los tests que usan la base de datos
+
+use sqlx::sqlite::SqlitePoolOptions;
+use sqlx::SqlitePool;
+
+use crate::db::migrador::migrar_sqlite;
+use crate::utils::infogov_parser::InfoGovExpediente;
+
+/// Base SQLite en memoria sin migrar (una sola conexión: cada conexión tendría su propia base)
+pub async fn pool_vacio() -> SqlitePool {
+    SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap()
+}
+
+/// Base SQLite en memoria con todas las migraciones aplicadas
+pub async fn pool_en_memoria() -> SqlitePool {
+    let pool = pool_vacio().await;
+    migrar_sqlite(&pool).await.unwrap();
+    pool
+}
+
+/// Captura de InfoGov de la oficina GDEMZA
+pub fn captura(nro: &str, fecha_pase: &str, estado: &str) -> InfoGovExpediente {
+    InfoGovExpediente {
+        nro_infogov: nro.to_string(),
+        tema: format!("Expediente {}", nro),
+        nro_gde: "EX-2026-01216856-GDEMZA-DGIRR".to_string(),
+        fecha_pase: fecha_pase.to_string(),
+        estado: estado.to_string(),
+        oficina: Some("GDEMZA".to_string()),
+        resumen: nro.to_string(),
+    }
+}
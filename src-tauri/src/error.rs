@@ -12,6 +12,13 @@ pub enum AppError {
     #[error("Error de migración: {0}")]
     Migration(#[from] sqlx::migrate::MigrateError),
     
+    #[error("Error en la migración {version} ({descripcion}): {mensaje}")]
+    MigracionFallida {
+        version: i64,
+        descripcion: String,
+        mensaje: String,
+    },
+
     #[error("Error de sincronización: {0}")]
     Sync(String),
     
@@ -21,6 +28,21 @@ pub enum AppError {
     #[error("Error de validación: {0}")]
     Validation(String),
     
+    #[error("Error en la plantilla de la orden de compra: {0}")]
+    Plantilla(String),
+
+    #[error("El conversor a PDF {conversor} no está disponible: {mensaje}")]
+    ConversorNoDisponible {
+        conversor: &'static str,
+        mensaje: String,
+    },
+
+    #[error("Error al convertir a PDF con {conversor}: {mensaje}")]
+    ConversionPdf {
+        conversor: &'static str,
+        mensaje: String,
+    },
+
     #[error("Error de I/O: {0}")]
     Io(#[from] io::Error),
     
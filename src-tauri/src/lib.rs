@@ -1,19 +1,20 @@
 // Módulos principales
+pub mod atajos;
 pub mod db;
 pub mod error;
 pub mod models;
 pub mod repositories;
 pub mod commands;
 pub mod services;
+pub mod sync;
 pub mod utils;
 
 // Re-exports
 pub use db::{DatabasePool, init_databases};
 pub use error::AppError;
+pub use atajos::EVENTO_CAPTURA_INFOGOV;
 
 use std::env;
-use tauri_plugin_global_shortcut::GlobalShortcutExt;
-use tauri::Emitter;
 use tauri::Manager;
 use tauri::menu::{MenuBuilder, MenuItemBuilder};
 use tauri::tray::{TrayIconBuilder, TrayIconEvent, MouseButton, MouseButtonState};
@@ -25,13 +26,13 @@ fn greet(name: &str) -> String {
 }
 
 #[tauri::command]
-async fn get_db_status(pools: tauri::State<'_, DatabasePool>) -> Result<String, String> {
-    let has_postgres = pools.has_postgres();
-    
-    Ok(format!(
-        "SQLite: ✅ Conectado | PostgreSQL: {}",
-        if has_postgres { "✅ Conectado" } else { "⚠️ Desconectado (modo offline)" }
-    ))
+async fn get_db_status(pools: tauri::State<'_, DatabasePool>) -> Result<db::EstadoBaseDatos, String> {
+    Ok(pools.estado())
+}
+
+#[tauri::command]
+async fn get_estado_migraciones(pools: tauri::State<'_, DatabasePool>) -> Result<db::migrador::EstadoMigraciones, String> {
+    db::migrador::estado_migraciones(&pools).await.map_err(|e| e.to_string())
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -42,26 +43,66 @@ pub fn run() {
         .build()
         .unwrap()
         .block_on(async {
-            // Obtener rutas de bases de datos del entorno
+            // La base local es el único dato que no puede vivir en la configuración (la contiene)
             let sqlite_path = env::var("SQLITE_PATH").unwrap_or_else(|_| "../app.db".to_string());
-            let postgres_url = env::var("DATABASE_URL").ok();
 
             // Inicializar pools de bases de datos
-            let pools = DatabasePool::new(&sqlite_path, postgres_url.as_deref())
+            let pools = DatabasePool::new(&sqlite_path, None)
                 .await
                 .expect("Error al inicializar pools de bases de datos");
 
+            // El servidor PostgreSQL sale de la configuración de la instalación
+            // (en una base nueva todavía no existe la tabla: se arranca sólo con SQLite)
+            let settings = services::SettingsService::cargar(pools.get_sqlite())
+                .await
+                .unwrap_or_default();
+            pools.configurar_postgres(settings.base_datos.postgres_url.as_deref()).await;
+
+            // Subcomando de mantenimiento: `migrate status | up | down VERSION [--postgres]`
+            let args: Vec<String> = env::args().collect();
+            if args.get(1).map(String::as_str) == Some("migrate") {
+                std::process::exit(db::migrador::ejecutar_cli(&pools, &args[2..]).await);
+            }
+
             // Ejecutar migraciones
             init_databases(&pools)
                 .await
                 .expect("Error al ejecutar migraciones");
 
+            // Instalaciones que configuraban el servidor en .env: DATABASE_URL se copia una vez a la configuración
+            let database_url = env::var("DATABASE_URL").ok();
+            match services::SettingsService::importar_database_url(pools.get_sqlite(), database_url.as_deref()).await {
+                Ok(Some(url)) => {
+                    println!("📝 DATABASE_URL copiada a Configuración > Instalación (ya no se lee del entorno)");
+                    pools.configurar_postgres(Some(url.as_str())).await;
+                    if let Some(postgres) = pools.get_postgres() {
+                        db::database::init_postgres(&postgres)
+                            .await
+                            .expect("Error al ejecutar migraciones de PostgreSQL");
+                    }
+                }
+                Ok(None) => {
+                    if database_url.is_some() && database_url != pools.postgres_url() {
+                        eprintln!("⚠️ DATABASE_URL se ignora: el servidor PostgreSQL se configura en Configuración > Instalación");
+                    }
+                }
+                Err(e) => eprintln!("⚠️ No se pudo revisar DATABASE_URL: {}", e),
+            }
+
             println!("🚀 Base de datos inicializada correctamente");
             println!("📍 SQLite: {}", sqlite_path);
-            if let Some(url) = &postgres_url {
-                println!("📍 PostgreSQL: {}", url);
+            if let Some(url) = pools.postgres_url() {
+                println!("📍 PostgreSQL: {}", models::configuracion::ocultar_password(&url));
             }
 
+            // Atajos globales configurados (se registran al armar la aplicación)
+            let atajos_configurados = repositories::ConfiguracionRepository::atajos(pools.get_sqlite())
+                .await
+                .unwrap_or_else(|e| {
+                    eprintln!("⚠️ Error leyendo atajos configurados (se usan los de fábrica): {}", e);
+                    Vec::new()
+                });
+
             // Iniciar Tauri
             tauri::Builder::default()
                 .plugin(tauri_plugin_opener::init())
@@ -75,18 +116,35 @@ pub fn run() {
                     }
                 })
                 .manage(pools.clone())
+                .manage(atajos::AtajosActivos::default())
+                .manage(atajos::CapturaRapida::default())
                 .invoke_handler(tauri::generate_handler![
                     greet,
                     get_db_status,
+                    get_estado_migraciones,
                     // Commands de Expedientes
                     commands::get_expedientes,
                     commands::obtener_expedientes,
                     commands::get_expediente,
+                    commands::filtrar_expedientes,
                     commands::create_expediente,
                     commands::update_expediente,
                     commands::delete_expediente,
                     commands::search_expedientes,
+                    commands::buscar_expedientes,
+                    commands::get_historial_expediente,
+                    commands::get_ubicacion_expediente,
                     commands::procesar_y_guardar_expediente,
+                    commands::procesar_lote_infogov,
+                    commands::get_capturas_infogov,
+                    commands::deshacer_captura,
+                    commands::seleccionar_archivo_infogov,
+                    commands::previsualizar_importacion_infogov,
+                    commands::importar_bandeja_infogov,
+                    commands::get_mapeo_estados_infogov,
+                    commands::guardar_mapeo_estado_infogov,
+                    commands::eliminar_mapeo_estado_infogov,
+                    commands::get_estados_infogov_pendientes,
                     commands::get_expedientes_notificaciones,
                     commands::populate_mock_data,
                     commands::clasificar_gasto_expediente,
@@ -100,6 +158,12 @@ pub fn run() {
                     commands::preparar_nueva_oc,
                     commands::crear_orden_compra,
                     commands::obtener_ordenes_compra,
+                    commands::editar_orden_compra,
+                    commands::emitir_orden_compra,
+                    commands::anular_orden_compra,
+                    commands::registrar_entrega_orden_compra,
+                    commands::obtener_entregas_orden_compra,
+                    commands::conciliar_numeracion_oc,
                     commands::generar_pdf,
                     commands::generar_excel,
                     // Commands de Vehículos
@@ -127,6 +191,24 @@ pub fn run() {
                     commands::exportar_excel_movilidades,
                     commands::exportar_excel_personal,
                     commands::seleccionar_directorio_guardado,
+                    // Commands de Sincronización
+                    commands::get_pendientes_sincronizacion,
+                    commands::reintentar_sincronizacion,
+                    commands::sync_now,
+                    commands::get_conflictos_sincronizacion,
+                    commands::resolver_conflicto_sincronizacion,
+                    // Commands de Atajos y Captura rápida
+                    commands::get_atajos,
+                    commands::update_atajos,
+                    commands::get_captura_rapida,
+                    commands::guardar_captura_rapida,
+                    commands::descartar_captura_rapida,
+                    // Commands de Configuración
+                    commands::get_settings,
+                    commands::update_settings,
+                    // Commands de Zonas
+                    commands::get_zonas,
+                    commands::create_zona,
                 ])
                 .setup(move |app| {
                     let app_handle = app.handle();
@@ -177,29 +259,14 @@ pub fn run() {
                     // Mantener referencia viva del tray durante toda la ejecución
                     app.manage(tray_icon);
 
-                    // Registrar el atajo Alt+I en el setup
-                    let shortcut_manager = app.global_shortcut();
-                    
-                    // Registrar el atajo Alt+I
-                    if let Err(e) = shortcut_manager.register("alt+i") {
-                        eprintln!("⚠️ Error registrando atajo Alt+I (continuando sin atajo): {}", e);
-                    } else {
-                        println!("✅ Atajo Alt+I registrado correctamente");
+                    // Reconexión automática con PostgreSQL (emite db_status_changed)
+                    db::iniciar_supervisor_postgres(app.handle().clone(), pools_clone.clone());
 
-                        // Escuchar evento del atajo
-                        let app = app_handle.clone();
-                        let pools = pools_clone.clone();
-                        let _ = shortcut_manager.on_shortcut("alt+i", move |_app, shortcut, _event| {
-                            let app = app.clone();
-                            let pools = pools.clone();
-                            
-                            println!("⌨️ Atajo presionado: {}", shortcut);
-                            
-                            tauri::async_runtime::spawn(async move {
-                                procesar_atajo_infogov(&app, &pools).await;
-                            });
-                        });
-                    }
+                    // Sincronización en segundo plano con PostgreSQL
+                    sync::iniciar_worker_sincronizacion(pools_clone.clone());
+
+                    // Atajos globales (capturar InfoGov, búsqueda, OC, mostrar/ocultar)
+                    atajos::registrar_al_iniciar(app_handle, &atajos_configurados);
 
                     Ok(())
                 })
@@ -207,64 +274,3 @@ pub fn run() {
                 .expect("error while running tauri application");
         });
 }
-
-/// Procesa el atajo Alt+I: captura portapapeles y guarda expediente
-async fn procesar_atajo_infogov(app: &tauri::AppHandle, pools: &crate::db::DatabasePool) {
-    use tauri_plugin_clipboard_manager::ClipboardExt;
-    use crate::repositories::ExpedienteRepository;
-    use crate::utils::infogov_parser::InfoGovExpediente;
-
-    println!("🔥 Atajo Alt+I activado");
-
-    // Leer portapapeles
-    match app.clipboard().read_text() {
-        Ok(text) => {
-            println!("📋 Texto del portapapeles: {} caracteres", text.len());
-            
-            // Procesar expediente desde InfoGov
-            match InfoGovExpediente::from_clipboard(&text) {
-                Ok(infogov_exp) => {
-                    // Guardar en base de datos
-                    match ExpedienteRepository::upsert_from_infogov(
-                        pools.get_sqlite(),
-                        infogov_exp.clone(),
-                    ).await {
-                        Ok(result) => {
-                            println!("✅ Expediente procesado: {}", infogov_exp.nro_infogov);
-                            
-                            // Emitir evento al frontend
-                            let _ = app.emit("expediente_procesado", serde_json::json!({
-                                "success": true,
-                                "id": result.id,
-                                "resumen": infogov_exp.resumen,
-                                "nro_infogov": infogov_exp.nro_infogov,
-                                "mensaje": format!("✅ Expediente {} procesado correctamente", infogov_exp.nro_infogov)
-                            }));
-                        }
-                        Err(e) => {
-                            eprintln!("❌ Error al guardar: {}", e);
-                            let _ = app.emit("expediente_error", serde_json::json!({
-                                "error": format!("Error al guardar: {}", e),
-                                "timestamp": chrono::Utc::now().to_rfc3339()
-                            }));
-                        }
-                    }
-                }
-                Err(e) => {
-                    eprintln!("❌ Error al parsear: {}", e);
-                    let _ = app.emit("expediente_error", serde_json::json!({
-                        "error": e,
-                        "timestamp": chrono::Utc::now().to_rfc3339()
-                    }));
-                }
-            }
-        }
-        Err(e) => {
-            eprintln!("❌ Error al leer portapapeles: {}", e);
-            let _ = app.emit("expediente_error", serde_json::json!({
-                "error": format!("Error al leer portapapeles: {}", e),
-                "timestamp": chrono::Utc::now().to_rfc3339()
-            }));
-        }
-    }
-}
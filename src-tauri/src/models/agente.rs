@@ -1,14 +1,13 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
-use uuid::Uuid;
 
 /// Modelo de Agente/Personal
 /// Representa a un empleado de la Jefatura de Zona de Riego
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Agente {
     /// ID único (UUID v4) - permite sincronización sin conflictos
-    pub id: Uuid,
+    pub id: String,
     
     /// Datos personales
     pub dni: String,
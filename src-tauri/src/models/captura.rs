@@ -0,0 +1,107 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::models::expediente::Expediente;
+use crate::utils::infogov_parser::{FilaInfoGov, InfoGovExpediente};
+
+/// Registro de una fila capturada de InfoGov (Alt+I, pegado o archivo)
+/// Guarda el texto original, lo que se interpretó y el expediente antes y después de aplicarla
+#[derive(Debug, Clone, Serialize)]
+pub struct CapturaInfoGov {
+    pub id: String,
+    /// Todas las filas de una misma captura comparten lote
+    pub lote_id: String,
+    pub fila: i64,
+    pub origen: OrigenCaptura,
+    pub texto: String,
+    pub parseado: Option<InfoGovExpediente>,
+    pub error: Option<String>,
+    pub accion: AccionCaptura,
+    pub expediente_id: Option<String>,
+    /// Pase registrado por la captura (se borra al deshacerla)
+    pub movimiento_id: Option<String>,
+    pub antes: Option<Expediente>,
+    pub despues: Option<Expediente>,
+    pub created_at: DateTime<Utc>,
+    pub deshecha_at: Option<DateTime<Utc>>,
+}
+
+/// De dónde vino la captura
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, Eq)]
+#[sqlx(type_name = "origen_captura", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OrigenCaptura {
+    /// Atajo global Alt+I
+    Atajo,
+    /// Texto o grilla pegados desde la interfaz
+    Portapapeles,
+    /// Bandeja exportada (CSV o XLSX)
+    Archivo,
+}
+
+/// Qué hizo la captura con el expediente
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, Eq)]
+#[sqlx(type_name = "accion_captura", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AccionCaptura {
+    Creado,
+    Actualizado,
+    /// La fila no se pudo interpretar o guardar; no hay nada que deshacer
+    Error,
+}
+
+/// Resultado de deshacer las últimas capturas
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ResultadoDeshacer {
+    pub deshechas: Vec<CapturaDeshecha>,
+    /// Capturas que no se pudieron deshacer (el expediente cambió después)
+    pub omitidas: Vec<CapturaOmitida>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CapturaDeshecha {
+    pub id: String,
+    pub expediente_id: String,
+    pub accion: AccionCaptura,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CapturaOmitida {
+    pub id: String,
+    pub expediente_id: Option<String>,
+    pub motivo: String,
+}
+
+/// Fila leída por el atajo de captura, tal como la muestra la ventana de captura rápida
+/// El usuario puede corregir tema y estado antes de guardar
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilaCapturaRapida {
+    pub fila: usize,
+    pub texto: String,
+    pub expediente: Option<InfoGovExpediente>,
+    pub error: Option<String>,
+}
+
+impl From<FilaInfoGov> for FilaCapturaRapida {
+    fn from(fila: FilaInfoGov) -> Self {
+        let (expediente, error) = match fila.resultado {
+            Ok(expediente) => (Some(expediente), None),
+            Err(error) => (None, Some(error)),
+        };
+        FilaCapturaRapida { fila: fila.fila, texto: fila.texto, expediente, error }
+    }
+}
+
+impl From<FilaCapturaRapida> for FilaInfoGov {
+    fn from(fila: FilaCapturaRapida) -> Self {
+        let resultado = match (fila.expediente, fila.error) {
+            (Some(mut expediente), _) => {
+                // Tema o estado corregidos: el resumen se vuelve a armar
+                expediente.tema = expediente.tema.trim().to_string();
+                expediente.estado = expediente.estado.trim().to_string();
+                expediente.resumen = format!("{} - {} - {}", expediente.nro_infogov, expediente.tema, expediente.nro_gde);
+                Ok(expediente)
+            }
+            (None, error) => Err(error.unwrap_or_else(|| "Fila sin datos".to_string())),
+        };
+        FilaInfoGov { fila: fila.fila, texto: fila.texto, resultado }
+    }
+}
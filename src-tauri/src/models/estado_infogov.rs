@@ -0,0 +1,61 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::models::expediente::EstadoExpediente;
+
+/// Equivalencia entre un estado de InfoGov (texto libre) y el estado interno del expediente
+/// La clave se guarda normalizada (ver `normalizar_estado_infogov`)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MapeoEstadoInfoGov {
+    pub estado_infogov: String,
+    pub estado: EstadoExpediente,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Estado de InfoGov sin equivalencia, pendiente de revisión
+/// Los expedientes capturados con este estado conservan su estado interno hasta que se mapee
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct EstadoInfoGovPendiente {
+    /// Texto tal como vino de InfoGov
+    pub estado_infogov: String,
+    pub apariciones: i64,
+    pub ultimo_expediente_id: Option<String>,
+    pub primera_vez: DateTime<Utc>,
+    pub ultima_vez: DateTime<Utc>,
+}
+
+/// Clave de búsqueda de un estado de InfoGov: minúsculas, sin acentos y con espacios colapsados
+/// ("Contratación  Directa" y "CONTRATACION DIRECTA" son el mismo estado)
+pub fn normalizar_estado_infogov(estado: &str) -> String {
+    estado
+        .to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'á' => 'a',
+            'é' => 'e',
+            'í' => 'i',
+            'ó' => 'o',
+            'ú' | 'ü' => 'u',
+            '_' | '-' | '.' => ' ',
+            _ => c,
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalizar_estado_infogov() {
+        assert_eq!(normalizar_estado_infogov("Contratación Directa"), "contratacion directa");
+        assert_eq!(normalizar_estado_infogov("  CONTRATACIóN   DIRECTA "), "contratacion directa");
+        assert_eq!(normalizar_estado_infogov("En_Trámite"), "en tramite");
+        assert_eq!(normalizar_estado_infogov(""), "");
+    }
+}
@@ -2,6 +2,8 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+use crate::models::numero_gde::NumeroGde;
+
 /// Modelo de Expediente
 /// Representa un expediente administrativo (InfoGov, GDE, etc.)
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -23,6 +25,8 @@ pub struct Expediente {
     pub fecha_pase: Option<DateTime<Utc>>,
     pub oficina: Option<String>,
     pub estado: EstadoExpediente,
+    /// Estado tal como lo informa InfoGov (texto libre, ver infogov_estados)
+    pub estado_infogov: Option<String>,
     pub buzon_grupal: Option<String>,
     pub hacer: Option<String>,
     pub resumen: Option<String>, // Combinación de nro_infogov + resumen + nro_gde
@@ -70,6 +74,17 @@ pub struct Expediente {
     pub synced_at: Option<DateTime<Utc>>,
 }
 
+/// Resultado de la búsqueda de texto completo
+/// Incluye el expediente, su relevancia (bm25, menor es mejor) y un fragmento resaltado
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ResultadoBusqueda {
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub expediente: Expediente,
+    pub relevancia: f64,
+    pub fragmento: String,
+}
+
 /// Tipo de expediente según el sistema
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "tipo_expediente", rename_all = "SCREAMING_SNAKE_CASE")]
@@ -87,7 +102,7 @@ pub enum TipoExpediente {
 }
 
 /// Estado del expediente en su ciclo de vida
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq, Eq)]
 #[sqlx(type_name = "estado_expediente", rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum EstadoExpediente {
     #[sqlx(rename = "INICIADO")]
@@ -105,7 +120,7 @@ pub enum EstadoExpediente {
 }
 
 /// Nivel de prioridad
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq, Eq)]
 #[sqlx(type_name = "prioridad", rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum Prioridad {
     #[sqlx(rename = "BAJA")]
@@ -144,7 +159,8 @@ pub struct CreateExpediente {
     pub archivo: Option<String>,
     pub nro_infogov: Option<String>,
     pub tema: Option<String>,
-    pub nro_gde: Option<String>,
+    /// Validado al deserializar (ver NumeroGde)
+    pub nro_gde: Option<NumeroGde>,
     pub fecha_inicio: DateTime<Utc>,
     pub fecha_pase: Option<DateTime<Utc>>,
     pub oficina: Option<String>,
@@ -176,12 +192,12 @@ pub struct CreateExpediente {
 }
 
 /// Datos para actualizar un expediente
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 pub struct UpdateExpediente {
     pub archivo: Option<String>,
     pub nro_infogov: Option<String>,
     pub tema: Option<String>,
-    pub nro_gde: Option<String>,
+    pub nro_gde: Option<NumeroGde>,
     pub fecha_pase: Option<DateTime<Utc>>,
     pub oficina: Option<String>,
     pub buzon_grupal: Option<String>,
@@ -210,3 +226,127 @@ pub struct UpdateExpediente {
     pub categoria_gasto: Option<CategoriaGasto>,
     pub vehiculo_id: Option<String>,
 }
+
+/// Filtro estructurado para listar expedientes
+/// Todos los criterios son opcionales y se combinan con AND
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ExpedienteFilter {
+    pub tipo: Option<TipoExpediente>,
+    /// Conjunto de estados aceptados (vacío = todos)
+    #[serde(default)]
+    pub estados: Vec<EstadoExpediente>,
+    pub prioridad: Option<Prioridad>,
+    pub area_responsable: Option<String>,
+    pub agente_responsable_id: Option<String>,
+
+    /// Rangos de fechas (inclusivos)
+    pub fecha_inicio_desde: Option<DateTime<Utc>>,
+    pub fecha_inicio_hasta: Option<DateTime<Utc>>,
+    pub fecha_pase_desde: Option<DateTime<Utc>>,
+    pub fecha_pase_hasta: Option<DateTime<Utc>>,
+    pub fecha_vencimiento_desde: Option<DateTime<Utc>>,
+    pub fecha_vencimiento_hasta: Option<DateTime<Utc>>,
+
+    pub vehiculo_id: Option<String>,
+    pub categoria_gasto: Option<CategoriaGasto>,
+    /// true = solo con Orden de Compra, false = solo sin Orden de Compra
+    pub tiene_oc: Option<bool>,
+
+    /// Orden de los resultados (por defecto: más recientes primero)
+    #[serde(default)]
+    pub orden: Vec<OrdenExpediente>,
+
+    /// Paginación por offset (la página empieza en 1)
+    pub pagina: Option<i64>,
+    pub por_pagina: Option<i64>,
+}
+
+/// Criterio de ordenamiento
+#[derive(Debug, Clone, Deserialize)]
+pub struct OrdenExpediente {
+    pub campo: CampoOrdenExpediente,
+    #[serde(default)]
+    pub descendente: bool,
+}
+
+/// Columnas por las que se puede ordenar
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CampoOrdenExpediente {
+    Numero,
+    #[serde(rename = "año")]
+    Año,
+    Asunto,
+    Prioridad,
+    Estado,
+    FechaInicio,
+    FechaPase,
+    FechaVencimiento,
+    CreatedAt,
+    UpdatedAt,
+}
+
+/// Página de resultados con el total de coincidencias
+#[derive(Debug, Clone, Serialize)]
+pub struct PaginaExpedientes {
+    pub items: Vec<Expediente>,
+    pub total: i64,
+    pub pagina: i64,
+    pub por_pagina: i64,
+}
+
+/// Expediente creado o actualizado por una captura de InfoGov
+#[derive(Debug, Clone, Serialize)]
+pub struct ExpedienteCapturado {
+    /// Número de fila dentro de la selección (desde 1)
+    pub fila: usize,
+    pub id: String,
+    pub nro_infogov: String,
+    pub resumen: String,
+}
+
+/// Fila de una captura de InfoGov que no se pudo importar
+#[derive(Debug, Clone, Serialize)]
+pub struct ErrorCaptura {
+    pub fila: usize,
+    pub texto: String,
+    pub error: String,
+}
+
+/// Campo que cambiaría al importar una fila de la bandeja
+#[derive(Debug, Clone, Serialize)]
+pub struct CampoImportado {
+    pub campo: String,
+    pub antes: Option<String>,
+    pub despues: Option<String>,
+}
+
+/// Efecto de una fila de la bandeja sobre la base
+#[derive(Debug, Clone, Serialize)]
+pub struct CambiosImportacion {
+    pub fila: usize,
+    /// Expediente existente (None si la fila crea uno nuevo)
+    pub id: Option<String>,
+    pub nro_infogov: String,
+    pub cambios: Vec<CampoImportado>,
+}
+
+/// Vista previa (sin escribir) de la importación de una bandeja exportada de InfoGov
+/// Las filas se emparejan con los expedientes existentes por nro_infogov o, si no lo tienen, por nro_gde
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VistaPreviaImportacion {
+    pub nuevos: Vec<CambiosImportacion>,
+    pub modificados: Vec<CambiosImportacion>,
+    pub sin_cambios: usize,
+    pub errores: Vec<ErrorCaptura>,
+    /// Estados de InfoGov sin equivalencia: irán a la cola de revisión
+    pub estados_sin_mapeo: Vec<String>,
+}
+
+/// Resultado de importar una selección de la bandeja de InfoGov (evento captura_infogov_resumen)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ResumenCapturaInfoGov {
+    pub creados: Vec<ExpedienteCapturado>,
+    pub actualizados: Vec<ExpedienteCapturado>,
+    pub errores: Vec<ErrorCaptura>,
+}
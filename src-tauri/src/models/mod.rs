@@ -6,9 +6,18 @@ pub mod expediente;
 pub mod vehiculo;
 pub mod ticket;
 pub mod orden_compra;
+pub mod movimiento;
+pub mod numero_gde;
+pub mod estado_infogov;
+pub mod captura;
+pub mod configuracion;
+pub mod zona;
 
 // Re-exportar para facilitar el uso
 pub use agente::Agente;
 pub use expediente::{Expediente, CategoriaGasto};
 pub use vehiculo::Vehiculo;
 pub use ticket::Ticket;
+pub use movimiento::ExpedienteMovimiento;
+pub use numero_gde::NumeroGde;
+pub use zona::Zona;
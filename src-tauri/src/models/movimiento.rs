@@ -0,0 +1,52 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Modelo de Movimiento de Expediente
+/// Representa un pase del expediente entre oficinas (línea de tiempo)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ExpedienteMovimiento {
+    /// ID único (UUID v4 como string)
+    pub id: String,
+    pub expediente_id: String,
+
+    /// Datos del pase
+    pub oficina_origen: Option<String>,
+    pub oficina_destino: Option<String>,
+    pub fecha_pase: DateTime<Utc>,
+    /// Estado interno del expediente después del pase (ENPROCESO, FINALIZADO, ...)
+    pub estado: Option<String>,
+    /// Estado tal como lo informó InfoGov (sólo en pases capturados)
+    pub estado_infogov: Option<String>,
+
+    /// Quién y cómo se registró el pase
+    pub origen: OrigenMovimiento,
+    pub capturado_por: Option<String>,
+    pub observaciones: Option<String>,
+
+    /// Metadatos
+    pub created_at: DateTime<Utc>,
+}
+
+/// Forma en que se registró el movimiento
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "origen_movimiento", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OrigenMovimiento {
+    #[sqlx(rename = "INFOGOV")]
+    InfoGov,
+    #[sqlx(rename = "MANUAL")]
+    Manual,
+}
+
+/// Datos para registrar un nuevo movimiento
+#[derive(Debug, Clone)]
+pub struct CreateMovimiento {
+    pub expediente_id: String,
+    pub oficina_origen: Option<String>,
+    pub oficina_destino: Option<String>,
+    pub fecha_pase: DateTime<Utc>,
+    pub estado: Option<String>,
+    pub estado_infogov: Option<String>,
+    pub origen: OrigenMovimiento,
+    pub observaciones: Option<String>,
+}
@@ -0,0 +1,289 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// Número de documento del sistema GDE
+/// Formato: TIPO-AÑO-NÚMERO-ECOSISTEMA-REPARTICIÓN (ej: EX-2026-01216856-GDEMZA-DGIRR)
+/// En el ecosistema nacional la repartición lleva el organismo tras '#' (IF-2021-12345678-APN-DNV#MTR)
+/// y el número suele copiarse con un segmento vacío antes del ecosistema (EX-2021-12345678- -APN-DNV#MTR)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct NumeroGde {
+    pub tipo: TipoDocumentoGde,
+    pub año: u16,
+    /// Número correlativo (se muestra con 8 dígitos)
+    pub numero: u32,
+    /// GDEMZA (Mendoza), GDEBA (Buenos Aires), APN (Administración Pública Nacional), ...
+    pub ecosistema: String,
+    /// Repartición generadora, opcionalmente con el organismo (DNV#MTR)
+    pub reparticion: String,
+}
+
+/// Tipo de documento GDE
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TipoDocumentoGde {
+    /// Expediente
+    Ex,
+    /// Informe
+    If,
+    /// Nota
+    No,
+    /// Resolución
+    Re,
+    /// Providencia
+    Pv,
+}
+
+impl TipoDocumentoGde {
+    pub fn sigla(&self) -> &'static str {
+        match self {
+            TipoDocumentoGde::Ex => "EX",
+            TipoDocumentoGde::If => "IF",
+            TipoDocumentoGde::No => "NO",
+            TipoDocumentoGde::Re => "RE",
+            TipoDocumentoGde::Pv => "PV",
+        }
+    }
+}
+
+impl FromStr for TipoDocumentoGde {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_uppercase().as_str() {
+            "EX" => Ok(TipoDocumentoGde::Ex),
+            "IF" => Ok(TipoDocumentoGde::If),
+            "NO" => Ok(TipoDocumentoGde::No),
+            "RE" => Ok(TipoDocumentoGde::Re),
+            "PV" => Ok(TipoDocumentoGde::Pv),
+            otro => Err(format!("Tipo de documento GDE desconocido: {}", otro)),
+        }
+    }
+}
+
+impl NumeroGde {
+    /// Busca el primer número GDE válido dentro de un texto libre
+    pub fn buscar(texto: &str) -> Option<Self> {
+        // Se compila una sola vez: se usa en cada fila de los lotes de InfoGov
+        static CANDIDATO: OnceLock<Regex> = OnceLock::new();
+        let candidato = CANDIDATO.get_or_init(|| {
+            Regex::new(
+                r"(?i)\b(EX|IF|NO|RE|PV)\s*-\s*\d{4}\s*-\s*\d{1,8}\s*-[\s-]*[A-Z]{2,10}\s*-\s*[A-Z0-9]+(?:#[A-Z0-9]+)*",
+            )
+            .expect("regex número GDE")
+        });
+
+        candidato.find_iter(texto).find_map(|m| m.as_str().parse().ok())
+    }
+}
+
+impl FromStr for NumeroGde {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let original = s.trim();
+        if original.is_empty() {
+            return Err("El número GDE está vacío".to_string());
+        }
+
+        // Segmentos sin espacios; los vacíos ("- -") se descartan
+        let segmentos: Vec<String> = original
+            .to_uppercase()
+            .split('-')
+            .map(|seg| seg.split_whitespace().collect::<String>())
+            .filter(|seg| !seg.is_empty())
+            .collect();
+
+        let [tipo, año, numero, ecosistema, reparticion] = segmentos.as_slice() else {
+            return Err(match segmentos.len() {
+                4 => format!("Falta la repartición en el número GDE: {}", original),
+                n => format!("El número GDE debe tener 5 segmentos (tiene {}): {}", n, original),
+            });
+        };
+
+        let tipo: TipoDocumentoGde = tipo.parse()?;
+
+        if año.len() != 4 || !año.chars().all(|c| c.is_ascii_digit()) {
+            return Err(format!("Año inválido en el número GDE: {}", año));
+        }
+        let año: u16 = año.parse().map_err(|_| format!("Año inválido en el número GDE: {}", año))?;
+        if !(2000..=2099).contains(&año) {
+            return Err(format!("Año fuera de rango en el número GDE: {}", año));
+        }
+
+        if numero.is_empty() || numero.len() > 8 || !numero.chars().all(|c| c.is_ascii_digit()) {
+            return Err(format!("Número inválido en el número GDE: {}", numero));
+        }
+        let numero: u32 = numero.parse().map_err(|_| format!("Número inválido en el número GDE: {}", numero))?;
+        if numero == 0 {
+            return Err("El número GDE no puede ser 0".to_string());
+        }
+
+        if !(2..=10).contains(&ecosistema.len()) || !ecosistema.chars().all(|c| c.is_ascii_uppercase()) {
+            return Err(format!("Ecosistema inválido en el número GDE: {}", ecosistema));
+        }
+
+        let partes: Vec<&str> = reparticion.split('#').collect();
+        let parte_valida = |p: &&str| {
+            !p.is_empty()
+                && p.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+                && p.chars().any(|c| c.is_ascii_uppercase())
+        };
+        if reparticion.len() > 30 || partes.len() > 2 || !partes.iter().all(parte_valida) {
+            return Err(format!("Repartición inválida en el número GDE: {}", reparticion));
+        }
+
+        Ok(NumeroGde {
+            tipo,
+            año,
+            numero,
+            ecosistema: ecosistema.clone(),
+            reparticion: reparticion.clone(),
+        })
+    }
+}
+
+impl fmt::Display for NumeroGde {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}-{}-{:08}-{}-{}",
+            self.tipo.sigla(),
+            self.año,
+            self.numero,
+            self.ecosistema,
+            self.reparticion
+        )
+    }
+}
+
+impl TryFrom<String> for NumeroGde {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<NumeroGde> for String {
+    fn from(numero: NumeroGde) -> Self {
+        numero.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// (entrada, forma normalizada esperada o None si es inválido)
+    const CASOS: &[(&str, Option<&str>)] = &[
+        // Formato de Mendoza
+        ("EX-2026-01216856-GDEMZA-DGIRR", Some("EX-2026-01216856-GDEMZA-DGIRR")),
+        ("ex-2026-01216856-gdemza-dgirr", Some("EX-2026-01216856-GDEMZA-DGIRR")),
+        ("  EX-2026-01216856-GDEMZA-DGIRR  ", Some("EX-2026-01216856-GDEMZA-DGIRR")),
+        ("EX - 2026 - 01216856 - GDEMZA - DGIRR", Some("EX-2026-01216856-GDEMZA-DGIRR")),
+        ("EX-2026-1216856-GDEMZA-DGIRR", Some("EX-2026-01216856-GDEMZA-DGIRR")),
+        ("EX-2026-00000001-GDEMZA-DGIRR", Some("EX-2026-00000001-GDEMZA-DGIRR")),
+        // Otros tipos de documento
+        ("IF-2025-00012345-GDEMZA-DGIRR", Some("IF-2025-00012345-GDEMZA-DGIRR")),
+        ("NO-2024-12345678-GDEMZA-SUBSGRAL", Some("NO-2024-12345678-GDEMZA-SUBSGRAL")),
+        ("RE-2023-87654321-GDEMZA-HCA", Some("RE-2023-87654321-GDEMZA-HCA")),
+        ("PV-2026-00000042-GDEMZA-DGIRR", Some("PV-2026-00000042-GDEMZA-DGIRR")),
+        // Ecosistemas y reparticiones de distinta longitud
+        ("EX-2026-01216856-GDEBA-DGIRR", Some("EX-2026-01216856-GDEBA-DGIRR")),
+        ("EX-2026-01216856-GDEMZA-DG", Some("EX-2026-01216856-GDEMZA-DG")),
+        ("EX-2026-01216856-GDEMZA-DIRECCIONGENERALIRRIGACION", Some("EX-2026-01216856-GDEMZA-DIRECCIONGENERALIRRIGACION")),
+        ("EX-2026-01216856-GDEMZA-DPV2", Some("EX-2026-01216856-GDEMZA-DPV2")),
+        // Ecosistema nacional (APN) con organismo y segmento vacío
+        ("IF-2021-12345678-APN-DNV#MTR", Some("IF-2021-12345678-APN-DNV#MTR")),
+        ("EX-2021-12345678- -APN-DNV#MTR", Some("EX-2021-12345678-APN-DNV#MTR")),
+        ("EX-2021-12345678-  -APN-DGA#MTR", Some("EX-2021-12345678-APN-DGA#MTR")),
+        ("ex-2021-12345678- -apn-dga#mtr", Some("EX-2021-12345678-APN-DGA#MTR")),
+        // Inválidos
+        ("", None),
+        ("2026-GDE-123", None),
+        ("XX-2026-01216856-GDEMZA-DGIRR", None),
+        ("EX-26-01216856-GDEMZA-DGIRR", None),
+        ("EX-1999-01216856-GDEMZA-DGIRR", None),
+        ("EX-2026-123456789-GDEMZA-DGIRR", None),
+        ("EX-2026-00000000-GDEMZA-DGIRR", None),
+        ("EX-2026-0121685A-GDEMZA-DGIRR", None),
+        ("EX-2026-01216856-G-DGIRR", None),
+        ("EX-2026-01216856-GDEMENDOZA1-DGIRR", None),
+        ("EX-2026-01216856-GDEMZA", None),
+        ("EX-2026-01216856-APN-", None),
+        ("EX-2026-01216856-GDEMZA-DGIRR-EXTRA", None),
+        ("EX-2026-01216856-GDEMZA-DG IRR!", None),
+        ("EX-2026-01216856-GDEMZA-123", None),
+        ("EX-2026-01216856-APN-DNV#", None),
+        ("EX-2026-01216856-APN-DNV#MTR#X", None),
+    ];
+
+    #[test]
+    fn test_parse_y_display() {
+        for (entrada, esperado) in CASOS {
+            let resultado = entrada.parse::<NumeroGde>();
+            match esperado {
+                Some(normalizado) => {
+                    let numero = resultado.unwrap_or_else(|e| panic!("{:?} debería ser válido: {}", entrada, e));
+                    assert_eq!(numero.to_string(), *normalizado, "entrada {:?}", entrada);
+                    // La forma normalizada vuelve a parsear al mismo valor
+                    assert_eq!(normalizado.parse::<NumeroGde>().unwrap(), numero);
+                }
+                None => assert!(resultado.is_err(), "{:?} debería ser inválido", entrada),
+            }
+        }
+    }
+
+    #[test]
+    fn test_segmentos() {
+        let numero: NumeroGde = "EX-2021-12345678- -APN-DNV#MTR".parse().unwrap();
+        assert_eq!(numero.tipo, TipoDocumentoGde::Ex);
+        assert_eq!(numero.año, 2021);
+        assert_eq!(numero.numero, 12345678);
+        assert_eq!(numero.ecosistema, "APN");
+        assert_eq!(numero.reparticion, "DNV#MTR");
+    }
+
+    #[test]
+    fn test_mensajes_de_error() {
+        assert!("EX-2026-01216856-GDEMZA".parse::<NumeroGde>().unwrap_err().contains("repartición"));
+        assert!("XX-2026-01216856-GDEMZA-DGIRR".parse::<NumeroGde>().unwrap_err().contains("Tipo"));
+        assert!("EX-2026-0121685A-GDEMZA-DGIRR".parse::<NumeroGde>().unwrap_err().contains("Número"));
+    }
+
+    /// (texto libre, número encontrado)
+    const BUSQUEDAS: &[(&str, Option<&str>)] = &[
+        (
+            "817619 30 2026 Reparación 18/2/2026 EX-2026-01216856-GDEMZA-DGIRR Contratación Directa",
+            Some("EX-2026-01216856-GDEMZA-DGIRR"),
+        ),
+        ("Ver IF-2021-12345678- -APN-DNV#MTR adjunto", Some("IF-2021-12345678-APN-DNV#MTR")),
+        ("Nota NO-2024-00000012-GDEBA-SSGA y más texto", Some("NO-2024-00000012-GDEBA-SSGA")),
+        // El primero es inválido (año): se toma el siguiente
+        ("EX-1990-00000001-GDEMZA-DGIRR / EX-2026-00000002-GDEMZA-DGIRR", Some("EX-2026-00000002-GDEMZA-DGIRR")),
+        ("no hay número acá", None),
+        ("2026-GDE-123", None),
+    ];
+
+    #[test]
+    fn test_buscar_en_texto() {
+        for (texto, esperado) in BUSQUEDAS {
+            assert_eq!(
+                NumeroGde::buscar(texto).map(|n| n.to_string()).as_deref(),
+                *esperado,
+                "texto {:?}",
+                texto
+            );
+        }
+    }
+
+    #[test]
+    fn test_serde() {
+        let numero: NumeroGde = serde_json::from_str("\"ex-2026-1216856-gdemza-dgirr\"").unwrap();
+        assert_eq!(serde_json::to_string(&numero).unwrap(), "\"EX-2026-01216856-GDEMZA-DGIRR\"");
+        assert!(serde_json::from_str::<NumeroGde>("\"2026-GDE-123\"").is_err());
+    }
+}
@@ -1,6 +1,10 @@
 // Modelos para el sistema de Órdenes de Compra
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
+use crate::models::configuracion::{Impresion, TasasIva};
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Proveedor {
     pub id: String,
@@ -29,6 +33,47 @@ pub struct UpdateConfigTope {
     pub monto_maximo: f64,
 }
 
+/// Estado de la OC en su ciclo de vida (ver EstadoOrdenCompraService)
+/// Las OC no se borran: una anulada conserva su número, que no vuelve a asignarse
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum EstadoOrdenCompra {
+    /// Numerada pero todavía editable; no se entregó al proveedor
+    Borrador,
+    Emitida,
+    Anulada,
+    RecibidaParcial,
+    Recibida,
+}
+
+impl EstadoOrdenCompra {
+    /// Nombre del estado tal como se guarda en la base de datos
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EstadoOrdenCompra::Borrador => "BORRADOR",
+            EstadoOrdenCompra::Emitida => "EMITIDA",
+            EstadoOrdenCompra::Anulada => "ANULADA",
+            EstadoOrdenCompra::RecibidaParcial => "RECIBIDA_PARCIAL",
+            EstadoOrdenCompra::Recibida => "RECIBIDA",
+        }
+    }
+}
+
+impl TryFrom<String> for EstadoOrdenCompra {
+    type Error = String;
+
+    fn try_from(valor: String) -> Result<Self, Self::Error> {
+        match valor.as_str() {
+            "BORRADOR" => Ok(EstadoOrdenCompra::Borrador),
+            "EMITIDA" => Ok(EstadoOrdenCompra::Emitida),
+            "ANULADA" => Ok(EstadoOrdenCompra::Anulada),
+            "RECIBIDA_PARCIAL" => Ok(EstadoOrdenCompra::RecibidaParcial),
+            "RECIBIDA" => Ok(EstadoOrdenCompra::Recibida),
+            otro => Err(format!("Estado de orden de compra desconocido: {}", otro)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct OrdenCompra {
     pub id: String,
@@ -45,6 +90,10 @@ pub struct OrdenCompra {
     pub subtotal: f64,
     pub iva: f64,
     pub total: f64,
+    #[sqlx(try_from = "String")]
+    pub estado: EstadoOrdenCompra,
+    pub motivo_anulacion: Option<String>,
+    pub fecha_anulacion: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,6 +105,20 @@ pub struct CreateOrdenCompra {
     pub plazo_entrega: Option<String>,
     pub es_iva_inscripto: bool,
     pub renglones: Vec<CreateRenglon>,
+    /// Guardar como BORRADOR (se emite después); si no, queda EMITIDA
+    #[serde(default)]
+    pub borrador: bool,
+}
+
+/// Nuevos datos de una OC en borrador: los renglones reemplazan a los anteriores
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateOrdenCompra {
+    pub destino: Option<String>,
+    pub resolucion_nro: Option<String>,
+    pub forma_pago: String,
+    pub plazo_entrega: Option<String>,
+    pub es_iva_inscripto: bool,
+    pub renglones: Vec<CreateRenglon>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -77,6 +140,33 @@ pub struct CreateRenglon {
     pub valor_unitario: f64,
 }
 
+/// Cantidad de un renglón entregada por el proveedor (un remito puede cubrir varios renglones)
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct OrdenCompraEntrega {
+    pub id: String,
+    pub oc_id: String,
+    pub renglon_id: String,
+    pub cantidad: f64,
+    pub fecha: String,
+    pub remito: Option<String>,
+    pub observaciones: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateEntrega {
+    /// Fecha de recepción (YYYY-MM-DD); por defecto hoy
+    pub fecha: Option<String>,
+    pub remito: Option<String>,
+    pub observaciones: Option<String>,
+    pub renglones: Vec<CreateEntregaRenglon>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateEntregaRenglon {
+    pub renglon_id: String,
+    pub cantidad: f64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrdenCompraCompleta {
     pub orden: OrdenCompra,
@@ -84,6 +174,85 @@ pub struct OrdenCompraCompleta {
     pub total_en_letras: String,
 }
 
+/// Ejemplares de la OC: el original va al proveedor, el duplicado a Contaduría y el triplicado al archivo
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Copia {
+    Original,
+    Duplicado,
+    Triplicado,
+}
+
+impl Copia {
+    pub const TODAS: [Copia; 3] = [Copia::Original, Copia::Duplicado, Copia::Triplicado];
+
+    /// Leyenda que se imprime en el encabezado de la copia
+    pub fn etiqueta(self) -> &'static str {
+        match self {
+            Copia::Original => "ORIGINAL",
+            Copia::Duplicado => "DUPLICADO",
+            Copia::Triplicado => "TRIPLICADO",
+        }
+    }
+
+    /// Las primeras `cantidad` copias (1 = sólo el original)
+    pub fn primeras(cantidad: u8) -> Vec<Copia> {
+        Copia::TODAS.iter().copied().take(cantidad.max(1) as usize).collect()
+    }
+}
+
+/// Cómo se entregan las copias pedidas
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SalidaCopias {
+    /// Un solo archivo con una hoja (o un grupo de hojas) por copia
+    #[default]
+    UnDocumento,
+    /// Un archivo por copia (OC-15-2026-DUPLICADO-...)
+    ArchivoPorCopia,
+}
+
+/// Copias a generar con generar_pdf / generar_excel
+/// Lo que no se indica se toma de la configuración (cantidad según el tipo de contratación, marca de agua)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OpcionesCopias {
+    pub copias: Option<Vec<Copia>>,
+    #[serde(default)]
+    pub salida: SalidaCopias,
+    pub marca_agua: Option<bool>,
+}
+
+impl OpcionesCopias {
+    /// Copias pedidas (sin repetir, en el orden indicado) o las configuradas para el tipo de contratación
+    pub fn copias(&self, tipo_contratacion: &str, impresion: &Impresion) -> Vec<Copia> {
+        match &self.copias {
+            Some(pedidas) => {
+                let mut copias = Vec::new();
+                for copia in pedidas {
+                    if !copias.contains(copia) {
+                        copias.push(*copia);
+                    }
+                }
+                copias
+            }
+            None => Copia::primeras(impresion.copias_para(tipo_contratacion)),
+        }
+    }
+
+    /// Copias agrupadas por archivo a generar
+    pub fn archivos(&self, tipo_contratacion: &str, impresion: &Impresion) -> Vec<Vec<Copia>> {
+        let copias = self.copias(tipo_contratacion, impresion);
+        match self.salida {
+            SalidaCopias::UnDocumento => vec![copias],
+            SalidaCopias::ArchivoPorCopia => copias.into_iter().map(|copia| vec![copia]).collect(),
+        }
+    }
+
+    pub fn marca_agua(&self, impresion: &Impresion) -> bool {
+        self.marca_agua.unwrap_or(impresion.marca_agua)
+    }
+}
+
 /// Determina el tipo de contratación según el monto total
 pub fn determinar_tipo_contratacion(monto: f64, topes: &[ConfigTope]) -> String {
     let mut topes_ordenados = topes.to_vec();
@@ -102,40 +271,91 @@ pub fn determinar_tipo_contratacion(monto: f64, topes: &[ConfigTope]) -> String
         .unwrap_or_else(|| "Licitación pública de mayor monto".to_string())
 }
 
-/// Genera el siguiente número de OC con formato XX/YYYY
-pub fn generar_numero_oc(ultima_oc: Option<String>, año_actual: i32) -> String {
-    match ultima_oc {
-        Some(ultimo) => {
-            // Formato: "15/2025"
-            let partes: Vec<&str> = ultimo.split('/').collect();
-            if partes.len() == 2 {
-                let numero = partes[0].parse::<i32>().unwrap_or(0);
-                let año = partes[1].parse::<i32>().unwrap_or(año_actual - 1);
-
-                if año == año_actual {
-                    // Mismo año, incrementar número
-                    format!("{:02}/{}", numero + 1, año_actual)
-                } else {
-                    // Nuevo año, resetear a 01
-                    format!("01/{}", año_actual)
-                }
-            } else {
-                format!("01/{}", año_actual)
-            }
+/// Número de OC con formato XX/YYYY (el número sale de la secuencia de la zona y el año, ver secuencias_oc)
+pub fn formatear_numero_oc(numero: i32, año: i32) -> String {
+    format!("{:02}/{}", numero, año)
+}
+
+/// Número y año de un número de OC ("15/2026" -> (15, 2026)); None si no tiene el formato XX/YYYY
+pub fn parsear_numero_oc(numero_oc: &str) -> Option<(i32, i32)> {
+    let (numero, año) = numero_oc.trim().split_once('/')?;
+    let numero = numero.trim().parse::<i32>().ok().filter(|n| *n > 0)?;
+    let año = año.trim();
+    if año.len() != 4 {
+        return None;
+    }
+    Some((numero, año.parse().ok()?))
+}
+
+/// Numeración de un año en el informe de conciliación
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConciliacionNumeracion {
+    pub año: i32,
+    pub ultimo_numero: i32,
+    /// Números entre 1 y el último que no tiene ninguna OC
+    pub faltantes: Vec<i32>,
+    pub duplicados: Vec<NumeroDuplicado>,
+}
+
+/// Número usado por más de una OC ("01/2026" y "1/2026" cuentan como el mismo)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NumeroDuplicado {
+    pub numero: i32,
+    /// (id, numero_oc tal como está guardado)
+    pub ordenes: Vec<(String, String)>,
+}
+
+/// Informe de conciliación de la numeración de una zona
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InformeNumeracion {
+    pub años: Vec<ConciliacionNumeracion>,
+    /// OC cuyo número no tiene el formato XX/YYYY: (id, numero_oc)
+    pub invalidos: Vec<(String, String)>,
+}
+
+/// Conciliar la numeración de las OC de una zona: faltantes y duplicados por año
+/// Las anuladas cuentan: su número quedó usado
+pub fn conciliar_numeracion(ordenes: &[(String, String)]) -> InformeNumeracion {
+    let mut por_año: BTreeMap<i32, BTreeMap<i32, Vec<(String, String)>>> = BTreeMap::new();
+    let mut invalidos = Vec::new();
+
+    for (id, numero_oc) in ordenes {
+        match parsear_numero_oc(numero_oc) {
+            Some((numero, año)) => por_año
+                .entry(año)
+                .or_default()
+                .entry(numero)
+                .or_default()
+                .push((id.clone(), numero_oc.clone())),
+            None => invalidos.push((id.clone(), numero_oc.clone())),
         }
-        None => format!("01/{}", año_actual),
     }
+
+    let años = por_año
+        .into_iter()
+        .map(|(año, numeros)| {
+            let ultimo_numero = numeros.keys().next_back().copied().unwrap_or(0);
+            let faltantes = (1..ultimo_numero).filter(|n| !numeros.contains_key(n)).collect();
+            let duplicados = numeros
+                .into_iter()
+                .filter(|(_, ordenes)| ordenes.len() > 1)
+                .map(|(numero, ordenes)| NumeroDuplicado { numero, ordenes })
+                .collect();
+            ConciliacionNumeracion { año, ultimo_numero, faltantes, duplicados }
+        })
+        .collect();
+
+    InformeNumeracion { años, invalidos }
 }
 
-/// Calcula subtotal, IVA y total
-pub fn calcular_totales(renglones: &[CreateRenglon], es_iva_inscripto: bool) -> (f64, f64, f64) {
+/// Calcula subtotal, IVA y total con las alícuotas configuradas
+pub fn calcular_totales(renglones: &[CreateRenglon], es_iva_inscripto: bool, tasas: &TasasIva) -> (f64, f64, f64) {
     let subtotal: f64 = renglones
         .iter()
         .map(|r| r.cantidad * r.valor_unitario)
         .sum();
 
-    let tasa_iva = if es_iva_inscripto { 0.21 } else { 0.105 };
-    let iva = subtotal * tasa_iva;
+    let iva = subtotal * tasas.tasa(es_iva_inscripto);
     let total = subtotal + iva;
 
     (subtotal, iva, total)
@@ -243,10 +463,40 @@ mod tests {
     }
 
     #[test]
-    fn test_generar_numero_oc() {
-        assert_eq!(generar_numero_oc(Some("15/2025".to_string()), 2026), "01/2026");
-        assert_eq!(generar_numero_oc(Some("15/2026".to_string()), 2026), "16/2026");
-        assert_eq!(generar_numero_oc(None, 2026), "01/2026");
+    fn test_formatear_y_parsear_numero_oc() {
+        assert_eq!(formatear_numero_oc(1, 2026), "01/2026");
+        assert_eq!(formatear_numero_oc(16, 2026), "16/2026");
+        assert_eq!(formatear_numero_oc(125, 2026), "125/2026");
+        assert_eq!(parsear_numero_oc("16/2026"), Some((16, 2026)));
+        assert_eq!(parsear_numero_oc(" 1/2025 "), Some((1, 2025)));
+        assert_eq!(parsear_numero_oc("16-2026"), None);
+        assert_eq!(parsear_numero_oc("0/2026"), None);
+        assert_eq!(parsear_numero_oc("16/26"), None);
+    }
+
+    #[test]
+    fn test_conciliar_numeracion() {
+        let oc = |id: &str, numero: &str| (id.to_string(), numero.to_string());
+        let informe = conciliar_numeracion(&[
+            oc("a", "01/2026"),
+            oc("b", "02/2026"),
+            oc("c", "05/2026"),
+            oc("d", "2/2026"),
+            oc("e", "01/2025"),
+            oc("f", "OC-3"),
+        ]);
+
+        assert_eq!(informe.años.len(), 2);
+        assert_eq!(informe.años[0], ConciliacionNumeracion { año: 2025, ultimo_numero: 1, faltantes: vec![], duplicados: vec![] });
+
+        let año = &informe.años[1];
+        assert_eq!(año.ultimo_numero, 5);
+        assert_eq!(año.faltantes, vec![3, 4]);
+        assert_eq!(
+            año.duplicados,
+            vec![NumeroDuplicado { numero: 2, ordenes: vec![oc("b", "02/2026"), oc("d", "2/2026")] }]
+        );
+        assert_eq!(informe.invalidos, vec![oc("f", "OC-3")]);
     }
 
     #[test]
@@ -266,12 +516,41 @@ mod tests {
             },
         ];
         
-        let (subtotal, iva, total) = calcular_totales(&renglones, true);
+        let (subtotal, iva, total) = calcular_totales(&renglones, true, &TasasIva::default());
         assert_eq!(subtotal, 20000.0);
         assert_eq!(iva, 4200.0);
         assert_eq!(total, 24200.0);
     }
 
+    #[test]
+    fn test_copias_primeras() {
+        assert_eq!(Copia::primeras(1), vec![Copia::Original]);
+        assert_eq!(Copia::primeras(0), vec![Copia::Original]);
+        assert_eq!(Copia::primeras(9), Copia::TODAS.to_vec());
+    }
+
+    #[test]
+    fn test_opciones_copias() {
+        let mut impresion = Impresion::default();
+        impresion.copias_por_tipo.insert("Contratación directa".to_string(), 2);
+
+        let configuradas = OpcionesCopias::default();
+        assert_eq!(configuradas.archivos("Contratación directa", &impresion), vec![vec![Copia::Original, Copia::Duplicado]]);
+        assert_eq!(configuradas.copias("Licitación pública de menor monto", &impresion), Copia::TODAS.to_vec());
+        assert!(!configuradas.marca_agua(&impresion));
+
+        let pedidas = OpcionesCopias {
+            copias: Some(vec![Copia::Triplicado, Copia::Original, Copia::Triplicado]),
+            salida: SalidaCopias::ArchivoPorCopia,
+            marca_agua: Some(true),
+        };
+        assert_eq!(
+            pedidas.archivos("Contratación directa", &impresion),
+            vec![vec![Copia::Triplicado], vec![Copia::Original]]
+        );
+        assert!(pedidas.marca_agua(&impresion));
+    }
+
     #[test]
     fn test_determinar_tipo_contratacion() {
         let topes = vec![
@@ -1,18 +1,17 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
-use uuid::Uuid;
 
 /// Modelo de Ticket de Combustible
 /// Representa una carga de combustible para un vehículo
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Ticket {
     /// ID único (UUID v4)
-    pub id: Uuid,
+    pub id: String,
     
     /// Relaciones
-    pub vehiculo_id: Uuid,
-    pub agente_id: Uuid, // Quien cargó el combustible
+    pub vehiculo_id: String,
+    pub agente_id: Option<String>, // Quien cargó el combustible (vacío en tickets anteriores a la carga de agentes)
     
     /// Datos del ticket
     pub fecha_carga: DateTime<Utc>,
@@ -53,8 +52,8 @@ pub enum TipoCombustible {
 /// Datos para crear un ticket
 #[derive(Debug, Deserialize)]
 pub struct CreateTicket {
-    pub vehiculo_id: Uuid,
-    pub agente_id: Uuid,
+    pub vehiculo_id: String,
+    pub agente_id: String,
     pub fecha_carga: DateTime<Utc>,
     pub numero_ticket: Option<String>,
     pub tipo_combustible: TipoCombustible,
@@ -69,7 +68,7 @@ pub struct CreateTicket {
 /// Estadísticas de rendimiento calculadas
 #[derive(Debug, Serialize)]
 pub struct RendimientoVehiculo {
-    pub vehiculo_id: Uuid,
+    pub vehiculo_id: String,
     pub patente: String,
     pub total_litros: f64,
     pub total_gastado: f64,
@@ -1,14 +1,13 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
-use uuid::Uuid;
 
 /// Modelo de Vehículo
 /// Representa un vehículo de la flota institucional
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Vehiculo {
     /// ID único (UUID v4)
-    pub id: Uuid,
+    pub id: String,
     
     /// Identificación del vehículo
     pub patente: String,
@@ -36,7 +35,7 @@ pub struct Vehiculo {
     
     /// Asignación
     pub area_asignada: Option<String>,
-    pub agente_asignado_id: Option<Uuid>,
+    pub agente_asignado_id: Option<String>,
     
     /// Observaciones
     pub observaciones: Option<String>,
@@ -84,6 +83,6 @@ pub struct UpdateVehiculo {
     pub vencimiento_vtv: Option<DateTime<Utc>>,
     pub vencimiento_habilitacion: Option<DateTime<Utc>>,
     pub area_asignada: Option<String>,
-    pub agente_asignado_id: Option<Uuid>,
+    pub agente_asignado_id: Option<String>,
     pub observaciones: Option<String>,
 }
@@ -0,0 +1,27 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Zona creada por la migración: todo lo cargado antes de que existieran las zonas queda en ella
+pub const ZONA_PREDETERMINADA: &str = "00000000-0000-0000-0000-000000000001";
+
+/// Jefatura de zona de riego
+/// La base central guarda varias; cada instalación trabaja sólo con la configurada (zona.id)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Zona {
+    pub id: String,
+    /// Código corto y único (MALARGUE, TUNUYAN_SUPERIOR)
+    pub codigo: String,
+    pub nombre: String,
+    pub activa: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub synced_at: Option<DateTime<Utc>>,
+}
+
+/// Datos para crear una zona
+#[derive(Debug, Deserialize)]
+pub struct CreateZona {
+    pub codigo: String,
+    pub nombre: String,
+}
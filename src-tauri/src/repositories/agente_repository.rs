@@ -0,0 +1,124 @@
+// Repositorio de Agentes (Personal)
+// Maneja todas las operaciones de base de datos para agentes
+
+use sqlx::{Pool, Sqlite};
+use uuid::Uuid;
+use chrono::Utc;
+
+use crate::models::agente::{Agente, CreateAgente, UpdateAgente};
+use crate::error::{Result, AppError};
+
+pub struct AgenteRepository;
+
+impl AgenteRepository {
+    /// Obtener los agentes activos de una zona ordenados por apellido y nombre
+    pub async fn get_activos(pool: &Pool<Sqlite>, zona_id: &str) -> Result<Vec<Agente>> {
+        let agentes = sqlx::query_as::<_, Agente>(
+            "SELECT * FROM agentes WHERE activo = 1 AND zona_id = ? ORDER BY apellido, nombre ASC"
+        )
+        .bind(zona_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(agentes)
+    }
+
+    /// Obtener un agente de la zona por ID
+    pub async fn get_by_id(pool: &Pool<Sqlite>, zona_id: &str, id: &str) -> Result<Agente> {
+        sqlx::query_as::<_, Agente>("SELECT * FROM agentes WHERE id = ? AND zona_id = ?")
+            .bind(id)
+            .bind(zona_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Agente con ID {}", id)))
+    }
+
+    /// Crear un nuevo agente en una zona
+    pub async fn create(pool: &Pool<Sqlite>, zona_id: &str, data: CreateAgente) -> Result<Agente> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now();
+
+        sqlx::query(
+            r#"
+            INSERT INTO agentes (
+                id, dni, nombre, apellido, email, telefono,
+                legajo, cargo, area, fecha_ingreso, activo,
+                licencia_conducir, vencimiento_licencia,
+                zona_id, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 1, ?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(&id)
+        .bind(&data.dni)
+        .bind(&data.nombre)
+        .bind(&data.apellido)
+        .bind(&data.email)
+        .bind(&data.telefono)
+        .bind(&data.legajo)
+        .bind(&data.cargo)
+        .bind(&data.area)
+        .bind(data.fecha_ingreso)
+        .bind(&data.licencia_conducir)
+        .bind(data.vencimiento_licencia)
+        .bind(zona_id)
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        Self::get_by_id(pool, zona_id, &id).await
+    }
+
+    /// Actualizar un agente de la zona (sólo los campos presentes)
+    pub async fn update(pool: &Pool<Sqlite>, zona_id: &str, id: &str, data: UpdateAgente) -> Result<Agente> {
+        sqlx::query(
+            r#"
+            UPDATE agentes SET
+                nombre = COALESCE(?, nombre),
+                apellido = COALESCE(?, apellido),
+                email = COALESCE(?, email),
+                telefono = COALESCE(?, telefono),
+                cargo = COALESCE(?, cargo),
+                area = COALESCE(?, area),
+                activo = COALESCE(?, activo),
+                licencia_conducir = COALESCE(?, licencia_conducir),
+                vencimiento_licencia = COALESCE(?, vencimiento_licencia),
+                updated_at = ?
+            WHERE id = ? AND zona_id = ?
+            "#
+        )
+        .bind(data.nombre)
+        .bind(data.apellido)
+        .bind(data.email)
+        .bind(data.telefono)
+        .bind(data.cargo)
+        .bind(data.area)
+        .bind(data.activo)
+        .bind(data.licencia_conducir)
+        .bind(data.vencimiento_licencia)
+        .bind(Utc::now())
+        .bind(id)
+        .bind(zona_id)
+        .execute(pool)
+        .await?;
+
+        Self::get_by_id(pool, zona_id, id).await
+    }
+
+    /// Baja lógica de un agente de la zona
+    pub async fn delete(pool: &Pool<Sqlite>, zona_id: &str, id: &str) -> Result<()> {
+        let result = sqlx::query("UPDATE agentes SET activo = 0, updated_at = ? WHERE id = ? AND zona_id = ?")
+            .bind(Utc::now())
+            .bind(id)
+            .bind(zona_id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Agente con ID {}", id)));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,331 @@
+// Repositorio del historial de capturas de InfoGov
+// Cada fila capturada queda registrada con su texto y el expediente antes y después, para poder deshacerla
+
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, Pool, Sqlite, SqliteConnection};
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+use crate::models::captura::{
+    AccionCaptura, CapturaDeshecha, CapturaInfoGov, CapturaOmitida, OrigenCaptura, ResultadoDeshacer,
+};
+use crate::models::expediente::Expediente;
+use crate::utils::infogov_parser::InfoGovExpediente;
+
+/// Fila de capturas_infogov tal como está en SQLite (los datos van serializados en JSON)
+#[derive(Debug, Clone, FromRow)]
+struct FilaCaptura {
+    id: String,
+    lote_id: String,
+    fila: i64,
+    origen: OrigenCaptura,
+    texto: String,
+    parseado: Option<String>,
+    error: Option<String>,
+    accion: AccionCaptura,
+    expediente_id: Option<String>,
+    movimiento_id: Option<String>,
+    antes: Option<String>,
+    despues: Option<String>,
+    created_at: DateTime<Utc>,
+    deshecha_at: Option<DateTime<Utc>>,
+}
+
+impl FilaCaptura {
+    fn a_captura(self) -> Result<CapturaInfoGov> {
+        Ok(CapturaInfoGov {
+            id: self.id,
+            lote_id: self.lote_id,
+            fila: self.fila,
+            origen: self.origen,
+            texto: self.texto,
+            parseado: desde_json(self.parseado.as_deref())?,
+            error: self.error,
+            accion: self.accion,
+            expediente_id: self.expediente_id,
+            movimiento_id: self.movimiento_id,
+            antes: desde_json(self.antes.as_deref())?,
+            despues: desde_json(self.despues.as_deref())?,
+            created_at: self.created_at,
+            deshecha_at: self.deshecha_at,
+        })
+    }
+}
+
+/// Datos de una fila capturada para registrar
+#[derive(Debug, Clone)]
+pub struct CreateCaptura {
+    pub lote_id: String,
+    pub fila: usize,
+    pub origen: OrigenCaptura,
+    pub texto: String,
+    pub parseado: Option<InfoGovExpediente>,
+    pub error: Option<String>,
+    pub accion: AccionCaptura,
+    pub expediente_id: Option<String>,
+    pub movimiento_id: Option<String>,
+    pub antes: Option<Expediente>,
+    pub despues: Option<Expediente>,
+}
+
+pub struct CapturaRepository;
+
+impl CapturaRepository {
+    /// Registrar una fila capturada dentro de la transacción de la captura
+    pub async fn registrar_en(conn: &mut SqliteConnection, data: CreateCaptura) -> Result<String> {
+        let id = Uuid::new_v4().to_string();
+
+        sqlx::query(
+            r#"
+            INSERT INTO capturas_infogov (
+                id, lote_id, fila, origen, texto, parseado, error, accion,
+                expediente_id, movimiento_id, antes, despues, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(&id)
+        .bind(&data.lote_id)
+        .bind(data.fila as i64)
+        .bind(data.origen)
+        .bind(&data.texto)
+        .bind(a_json(data.parseado.as_ref())?)
+        .bind(&data.error)
+        .bind(data.accion)
+        .bind(&data.expediente_id)
+        .bind(&data.movimiento_id)
+        .bind(a_json(data.antes.as_ref())?)
+        .bind(a_json(data.despues.as_ref())?)
+        .bind(Utc::now())
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(id)
+    }
+
+    /// Capturas más recientes primero (pantalla de revisión)
+    pub async fn recientes(pool: &Pool<Sqlite>, limite: i64) -> Result<Vec<CapturaInfoGov>> {
+        let filas = sqlx::query_as::<_, FilaCaptura>(
+            "SELECT * FROM capturas_infogov ORDER BY created_at DESC, rowid DESC LIMIT ?"
+        )
+        .bind(limite)
+        .fetch_all(pool)
+        .await?;
+
+        filas.into_iter().map(FilaCaptura::a_captura).collect()
+    }
+
+    /// Deshace las últimas `cantidad` capturas que modificaron expedientes, de la más nueva a la más vieja
+    /// Si el expediente cambió después de la captura (edición manual u otra captura) se omite
+    /// - CREADO: borra el expediente y su historial
+    /// - ACTUALIZADO: vuelve a los valores anteriores y borra el pase que registró
+    pub async fn deshacer(pool: &Pool<Sqlite>, cantidad: i64) -> Result<ResultadoDeshacer> {
+        let mut resultado = ResultadoDeshacer::default();
+        let mut tx = pool.begin().await?;
+
+        let filas = sqlx::query_as::<_, FilaCaptura>(
+            r#"
+            SELECT * FROM capturas_infogov
+            WHERE deshecha_at IS NULL AND accion != 'ERROR'
+            ORDER BY created_at DESC, rowid DESC
+            LIMIT ?
+            "#
+        )
+        .bind(cantidad)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        for fila in filas {
+            let captura = fila.a_captura()?;
+            let omitir = |motivo: &str| CapturaOmitida {
+                id: captura.id.clone(),
+                expediente_id: captura.expediente_id.clone(),
+                motivo: motivo.to_string(),
+            };
+
+            let (Some(expediente_id), Some(despues)) = (&captura.expediente_id, &captura.despues) else {
+                resultado.omitidas.push(omitir("La captura no guardó el estado del expediente"));
+                continue;
+            };
+
+            let actual = sqlx::query_as::<_, Expediente>("SELECT * FROM expedientes WHERE id = ?")
+                .bind(expediente_id)
+                .fetch_optional(&mut *tx)
+                .await?;
+            match actual {
+                None => {
+                    resultado.omitidas.push(omitir("El expediente ya no existe"));
+                    continue;
+                }
+                Some(actual) if actual.updated_at != despues.updated_at => {
+                    resultado.omitidas.push(omitir("El expediente se modificó después de la captura"));
+                    continue;
+                }
+                Some(_) => {}
+            }
+
+            match (captura.accion, &captura.antes) {
+                (AccionCaptura::Creado, _) => {
+                    sqlx::query("DELETE FROM expediente_movimientos WHERE expediente_id = ?")
+                        .bind(expediente_id)
+                        .execute(&mut *tx)
+                        .await?;
+                    sqlx::query("DELETE FROM expedientes WHERE id = ?")
+                        .bind(expediente_id)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+                (AccionCaptura::Actualizado, Some(antes)) => {
+                    // updated_at también vuelve atrás: así una captura anterior del mismo expediente
+                    // sigue reconociéndolo como propio y se puede deshacer a continuación
+                    sqlx::query(
+                        r#"
+                        UPDATE expedientes
+                        SET nro_infogov = ?, nro_gde = ?, fecha_pase = ?, estado = ?, estado_infogov = ?,
+                            fecha_finalizacion = ?, oficina = ?, resumen = ?, updated_at = ?
+                        WHERE id = ?
+                        "#
+                    )
+                    .bind(&antes.nro_infogov)
+                    .bind(&antes.nro_gde)
+                    .bind(antes.fecha_pase)
+                    .bind(&antes.estado)
+                    .bind(&antes.estado_infogov)
+                    .bind(antes.fecha_finalizacion)
+                    .bind(&antes.oficina)
+                    .bind(&antes.resumen)
+                    .bind(antes.updated_at)
+                    .bind(expediente_id)
+                    .execute(&mut *tx)
+                    .await?;
+
+                    if let Some(movimiento_id) = &captura.movimiento_id {
+                        sqlx::query("DELETE FROM expediente_movimientos WHERE id = ?")
+                            .bind(movimiento_id)
+                            .execute(&mut *tx)
+                            .await?;
+                    }
+                }
+                _ => {
+                    resultado.omitidas.push(omitir("La captura no guardó el estado del expediente"));
+                    continue;
+                }
+            }
+
+            sqlx::query("UPDATE capturas_infogov SET deshecha_at = ? WHERE id = ?")
+                .bind(Utc::now())
+                .bind(&captura.id)
+                .execute(&mut *tx)
+                .await?;
+
+            resultado.deshechas.push(CapturaDeshecha {
+                id: captura.id.clone(),
+                expediente_id: expediente_id.clone(),
+                accion: captura.accion,
+            });
+        }
+
+        tx.commit().await?;
+        Ok(resultado)
+    }
+}
+
+fn a_json<T: serde::Serialize>(valor: Option<&T>) -> Result<Option<String>> {
+    valor
+        .map(serde_json::to_string)
+        .transpose()
+        .map_err(|e| AppError::Internal(format!("No se pudo serializar la captura: {}", e)))
+}
+
+fn desde_json<T: serde::de::DeserializeOwned>(valor: Option<&str>) -> Result<Option<T>> {
+    valor
+        .map(serde_json::from_str)
+        .transpose()
+        .map_err(|e| AppError::Internal(format!("Captura con datos inválidos: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_util::pool_en_memoria;
+    use crate::models::zona::ZONA_PREDETERMINADA;
+    use crate::models::expediente::{EstadoExpediente, UpdateExpediente};
+    use crate::repositories::{ExpedienteRepository, MovimientoRepository};
+    use crate::services::EstadoExpedienteService;
+
+    async fn capturar(pool: &Pool<Sqlite>, texto: &str) -> crate::models::expediente::ResumenCapturaInfoGov {
+        let filas = InfoGovExpediente::lote_desde_portapapeles(texto);
+        ExpedienteRepository::upsert_lote_infogov(pool, filas, OrigenCaptura::Atajo).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_historial_y_deshacer() {
+        let pool = pool_en_memoria().await;
+
+        let creado = capturar(&pool, "1 1 2026 Compra repuestos 12/1/2026 EX-2026-00000001-GDEMZA-DGIRR Iniciado").await;
+        let id = creado.creados[0].id.clone();
+        capturar(&pool, "1 1 2026 Compra repuestos 20/1/2026 EX-2026-00000001-GDEMZA-DGIRR Contratación Directa").await;
+        // Mensaje de chat que empieza con tres números: queda registrado como error
+        capturar(&pool, "10 15 2026 nos vemos mañana").await;
+
+        let capturas = CapturaRepository::recientes(&pool, 10).await.unwrap();
+        assert_eq!(capturas.len(), 3);
+        assert_eq!(capturas[0].accion, AccionCaptura::Error);
+        assert_eq!(capturas[0].texto, "10 15 2026 nos vemos mañana");
+        assert_eq!(capturas[1].accion, AccionCaptura::Actualizado);
+        assert_eq!(capturas[1].antes.as_ref().unwrap().estado, EstadoExpediente::Iniciado);
+        assert_eq!(capturas[1].despues.as_ref().unwrap().estado, EstadoExpediente::EnProceso);
+        assert!(capturas[1].movimiento_id.is_some());
+        assert_eq!(capturas[2].parseado.as_ref().unwrap().nro_infogov, "1-1-2026");
+
+        // Deshacer la actualización: vuelve el estado anterior y se borra su pase
+        let resultado = CapturaRepository::deshacer(&pool, 1).await.unwrap();
+        assert_eq!(resultado.deshechas.len(), 1);
+        let exp = ExpedienteRepository::get_by_id(&pool, ZONA_PREDETERMINADA, &id).await.unwrap();
+        assert_eq!(exp.estado, EstadoExpediente::Iniciado);
+        assert_eq!(exp.estado_infogov.as_deref(), Some("Iniciado"));
+        assert_eq!(MovimientoRepository::get_by_expediente(&pool, &id).await.unwrap().len(), 1);
+
+        // Deshacer la creación borra el expediente
+        let resultado = CapturaRepository::deshacer(&pool, 5).await.unwrap();
+        assert_eq!(resultado.deshechas.len(), 1);
+        assert!(ExpedienteRepository::get_by_id(&pool, ZONA_PREDETERMINADA, &id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_deshacer_finalizado_quita_fecha_finalizacion() {
+        let pool = pool_en_memoria().await;
+
+        let creado = capturar(&pool, "1 1 2026 Compra repuestos 12/1/2026 EX-2026-00000001-GDEMZA-DGIRR En proceso").await;
+        let id = creado.creados[0].id.clone();
+        capturar(&pool, "1 1 2026 Compra repuestos 20/1/2026 EX-2026-00000001-GDEMZA-DGIRR Finalizado").await;
+
+        let exp = ExpedienteRepository::get_by_id(&pool, ZONA_PREDETERMINADA, &id).await.unwrap();
+        assert_eq!(exp.estado, EstadoExpediente::Finalizado);
+        assert!(exp.fecha_finalizacion.is_some());
+
+        let resultado = CapturaRepository::deshacer(&pool, 1).await.unwrap();
+        assert_eq!(resultado.deshechas.len(), 1);
+        let exp = ExpedienteRepository::get_by_id(&pool, ZONA_PREDETERMINADA, &id).await.unwrap();
+        assert_eq!(exp.estado, EstadoExpediente::EnProceso);
+        assert!(exp.fecha_finalizacion.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_no_deshace_expediente_editado() {
+        let pool = pool_en_memoria().await;
+
+        let creado = capturar(&pool, "1 1 2026 Compra repuestos 12/1/2026 EX-2026-00000001-GDEMZA-DGIRR Iniciado").await;
+        let id = creado.creados[0].id.clone();
+        EstadoExpedienteService::actualizar(&pool, ZONA_PREDETERMINADA, &id, UpdateExpediente {
+            hacer: Some("Pedir presupuesto".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+        let resultado = CapturaRepository::deshacer(&pool, 1).await.unwrap();
+        assert!(resultado.deshechas.is_empty());
+        assert_eq!(resultado.omitidas.len(), 1);
+        assert!(ExpedienteRepository::get_by_id(&pool, ZONA_PREDETERMINADA, &id).await.is_ok());
+    }
+}
@@ -0,0 +1,77 @@
+// Repositorio de la configuración de la instalación (tabla clave/valor)
+// La interpretación de los valores está en SettingsService
+
+use std::collections::HashMap;
+
+use chrono::Utc;
+use sqlx::{Pool, Sqlite, SqliteConnection};
+
+use crate::error::Result;
+use crate::models::configuracion::{AccionAtajo, AtajoTeclado};
+
+pub struct ConfiguracionRepository;
+
+impl ConfiguracionRepository {
+    /// Valor guardado para una clave (None si no está configurada)
+    pub async fn get_en(conn: &mut SqliteConnection, clave: &str) -> Result<Option<String>> {
+        let valor = sqlx::query_scalar::<_, String>("SELECT valor FROM configuracion WHERE clave = ?")
+            .bind(clave)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+        Ok(valor)
+    }
+
+    /// Todas las claves guardadas
+    pub async fn get_all_en(conn: &mut SqliteConnection) -> Result<HashMap<String, String>> {
+        let filas = sqlx::query_as::<_, (String, String)>("SELECT clave, valor FROM configuracion")
+            .fetch_all(&mut *conn)
+            .await?;
+
+        Ok(filas.into_iter().collect())
+    }
+
+    /// Guardar el valor de una clave
+    pub async fn set_en(conn: &mut SqliteConnection, clave: &str, valor: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO configuracion (clave, valor, updated_at) VALUES (?, ?, ?)
+            ON CONFLICT(clave) DO UPDATE SET valor = excluded.valor, updated_at = excluded.updated_at
+            "#
+        )
+        .bind(clave)
+        .bind(valor)
+        .bind(Utc::now())
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Atajos de teclado globales, uno por acción
+    /// Una acción sin clave guardada usa la combinación de fábrica
+    pub async fn atajos(pool: &Pool<Sqlite>) -> Result<Vec<AtajoTeclado>> {
+        let mut conn = pool.acquire().await?;
+        let mut atajos = Vec::new();
+
+        for accion in AccionAtajo::TODAS {
+            let combinacion = Self::get_en(&mut conn, accion.clave())
+                .await?
+                .unwrap_or_else(|| accion.combinacion_de_fabrica().to_string());
+            atajos.push(AtajoTeclado { accion, combinacion });
+        }
+
+        Ok(atajos)
+    }
+
+    /// Guardar los atajos (ya validados por atajos::validar)
+    pub async fn guardar_atajos(pool: &Pool<Sqlite>, atajos: &[AtajoTeclado]) -> Result<()> {
+        let mut tx = pool.begin().await?;
+        for atajo in atajos {
+            Self::set_en(&mut tx, atajo.accion.clave(), &atajo.combinacion).await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+}
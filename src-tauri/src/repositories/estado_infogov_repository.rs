@@ -0,0 +1,306 @@
+// Repositorio del mapeo de estados de InfoGov
+// Traduce el estado libre de InfoGov al EstadoExpediente y lleva la cola de estados sin mapear
+
+use chrono::Utc;
+use sqlx::{Pool, Sqlite, SqliteConnection};
+
+use crate::error::{AppError, Result};
+use crate::models::estado_infogov::{normalizar_estado_infogov, EstadoInfoGovPendiente, MapeoEstadoInfoGov};
+use crate::models::expediente::{EstadoExpediente, Expediente};
+use crate::services::EstadoExpedienteService;
+
+pub struct EstadoInfoGovRepository;
+
+impl EstadoInfoGovRepository {
+    /// Listar el mapeo configurado
+    pub async fn get_all(pool: &Pool<Sqlite>) -> Result<Vec<MapeoEstadoInfoGov>> {
+        let mapeos = sqlx::query_as::<_, MapeoEstadoInfoGov>(
+            "SELECT * FROM infogov_estados ORDER BY estado_infogov"
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(mapeos)
+    }
+
+    /// Crea o modifica la equivalencia de un estado de InfoGov
+    /// Los expedientes que esperaban ese estado en la cola de revisión se actualizan en el momento
+    pub async fn guardar(
+        pool: &Pool<Sqlite>,
+        estado_infogov: &str,
+        estado: EstadoExpediente,
+    ) -> Result<MapeoEstadoInfoGov> {
+        let clave = normalizar_estado_infogov(estado_infogov);
+        if clave.is_empty() {
+            return Err(AppError::Validation("El estado de InfoGov no puede estar vacío".to_string()));
+        }
+
+        let now = Utc::now();
+        sqlx::query(
+            r#"
+            INSERT INTO infogov_estados (estado_infogov, estado, created_at, updated_at)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT(estado_infogov) DO UPDATE SET estado = excluded.estado, updated_at = excluded.updated_at
+            "#
+        )
+        .bind(&clave)
+        .bind(&estado)
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        Self::reconciliar(pool).await?;
+
+        let mapeo = sqlx::query_as::<_, MapeoEstadoInfoGov>(
+            "SELECT * FROM infogov_estados WHERE estado_infogov = ?"
+        )
+        .bind(&clave)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(mapeo)
+    }
+
+    /// Eliminar una equivalencia (las próximas capturas con ese estado vuelven a la cola)
+    pub async fn delete(pool: &Pool<Sqlite>, estado_infogov: &str) -> Result<()> {
+        let result = sqlx::query("DELETE FROM infogov_estados WHERE estado_infogov = ?")
+            .bind(normalizar_estado_infogov(estado_infogov))
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Estado de InfoGov sin mapeo: {}", estado_infogov)));
+        }
+
+        Ok(())
+    }
+
+    /// Estados de InfoGov pendientes de revisión, los más frecuentes primero
+    /// Antes de listar descarta los que ya tienen equivalencia
+    pub async fn pendientes(pool: &Pool<Sqlite>) -> Result<Vec<EstadoInfoGovPendiente>> {
+        Self::reconciliar(pool).await?;
+
+        let pendientes = sqlx::query_as::<_, EstadoInfoGovPendiente>(
+            "SELECT * FROM infogov_estados_pendientes ORDER BY apariciones DESC, ultima_vez DESC"
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(pendientes)
+    }
+
+    /// Traduce un estado de InfoGov dentro de una transacción abierta
+    /// Sin mapeo configurado acepta los nombres internos (ENPROCESO, "En proceso", ...)
+    pub async fn resolver_en(conn: &mut SqliteConnection, estado_infogov: &str) -> Result<Option<EstadoExpediente>> {
+        let clave = normalizar_estado_infogov(estado_infogov);
+        if clave.is_empty() {
+            return Ok(None);
+        }
+
+        let mapeado = sqlx::query_scalar::<_, EstadoExpediente>(
+            "SELECT estado FROM infogov_estados WHERE estado_infogov = ?"
+        )
+        .bind(&clave)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(mapeado.or_else(|| estado_interno(&clave)))
+    }
+
+    /// Agrega (o vuelve a contar) un estado sin equivalencia en la cola de revisión
+    pub async fn encolar_en(conn: &mut SqliteConnection, estado_infogov: &str, expediente_id: &str) -> Result<()> {
+        let now = Utc::now();
+        sqlx::query(
+            r#"
+            INSERT INTO infogov_estados_pendientes (estado_infogov, apariciones, ultimo_expediente_id, primera_vez, ultima_vez)
+            VALUES (?, 1, ?, ?, ?)
+            ON CONFLICT(estado_infogov) DO UPDATE SET
+                apariciones = apariciones + 1,
+                ultimo_expediente_id = excluded.ultimo_expediente_id,
+                ultima_vez = excluded.ultima_vez
+            "#
+        )
+        .bind(estado_infogov.trim())
+        .bind(expediente_id)
+        .bind(now)
+        .bind(now)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Aplica el mapeo a los expedientes de los estados en cola que ya tienen equivalencia
+    /// y los saca de la cola. Devuelve la cantidad de expedientes actualizados
+    /// Respeta la tabla de transiciones: un expediente que no puede pasar al estado mapeado lo conserva
+    pub async fn reconciliar(pool: &Pool<Sqlite>) -> Result<u64> {
+        let mut tx = pool.begin().await?;
+        let pendientes: Vec<String> = sqlx::query_scalar("SELECT estado_infogov FROM infogov_estados_pendientes")
+            .fetch_all(&mut *tx)
+            .await?;
+
+        let mut actualizados = 0;
+        for pendiente in pendientes {
+            let Some(estado) = Self::resolver_en(&mut tx, &pendiente).await? else {
+                continue;
+            };
+
+            let expedientes = sqlx::query_as::<_, Expediente>(
+                "SELECT * FROM expedientes WHERE estado_infogov = ? AND estado != ?"
+            )
+            .bind(&pendiente)
+            .bind(&estado)
+            .fetch_all(&mut *tx)
+            .await?;
+
+            for expediente in expedientes {
+                let Some(cambio) = EstadoExpedienteService::cambio_desde_infogov(&expediente, estado.clone()) else {
+                    continue;
+                };
+
+                sqlx::query("UPDATE expedientes SET estado = ?, fecha_finalizacion = ?, updated_at = ? WHERE id = ?")
+                    .bind(&cambio.estado)
+                    .bind(cambio.fecha_finalizacion)
+                    .bind(Utc::now())
+                    .bind(&expediente.id)
+                    .execute(&mut *tx)
+                    .await?;
+                actualizados += 1;
+            }
+
+            sqlx::query("DELETE FROM infogov_estados_pendientes WHERE estado_infogov = ?")
+                .bind(&pendiente)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(actualizados)
+    }
+}
+
+/// Estado interno escrito tal cual (ENPROCESO, EN_PROCESO, "en proceso")
+fn estado_interno(clave: &str) -> Option<EstadoExpediente> {
+    match clave.replace(' ', "").as_str() {
+        "iniciado" => Some(EstadoExpediente::Iniciado),
+        "enproceso" => Some(EstadoExpediente::EnProceso),
+        "enrevision" => Some(EstadoExpediente::EnRevision),
+        "observado" => Some(EstadoExpediente::Observado),
+        "finalizado" => Some(EstadoExpediente::Finalizado),
+        "archivado" => Some(EstadoExpediente::Archivado),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_util::{self, pool_en_memoria};
+    use crate::models::zona::ZONA_PREDETERMINADA;
+    use crate::repositories::ExpedienteRepository;
+    use crate::utils::infogov_parser::InfoGovExpediente;
+
+    fn captura(nro: &str, estado: &str) -> InfoGovExpediente {
+        test_util::captura(nro, "2026-02-18", estado)
+    }
+
+    #[tokio::test]
+    async fn test_captura_con_estado_mapeado() {
+        let pool = pool_en_memoria().await;
+
+        let exp = ExpedienteRepository::upsert_from_infogov(&pool, captura("1-1-2026", "Contratación Directa"))
+            .await
+            .unwrap();
+        assert_eq!(exp.estado, EstadoExpediente::EnProceso);
+        assert_eq!(exp.estado_infogov.as_deref(), Some("Contratación Directa"));
+        assert!(EstadoInfoGovRepository::pendientes(&pool).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_estado_desconocido_va_a_revision() {
+        let pool = pool_en_memoria().await;
+
+        let exp = ExpedienteRepository::upsert_from_infogov(&pool, captura("1-1-2026", "Giro a Contaduría"))
+            .await
+            .unwrap();
+        assert_eq!(exp.estado, EstadoExpediente::Iniciado);
+        ExpedienteRepository::upsert_from_infogov(&pool, captura("2-1-2026", "Giro a Contaduría"))
+            .await
+            .unwrap();
+
+        let pendientes = EstadoInfoGovRepository::pendientes(&pool).await.unwrap();
+        assert_eq!(pendientes.len(), 1);
+        assert_eq!(pendientes[0].estado_infogov, "Giro a Contaduría");
+        assert_eq!(pendientes[0].apariciones, 2);
+
+        // Al mapearlo se actualizan los expedientes y sale de la cola
+        EstadoInfoGovRepository::guardar(&pool, "GIRO A CONTADURIA", EstadoExpediente::EnProceso)
+            .await
+            .unwrap();
+        assert!(EstadoInfoGovRepository::pendientes(&pool).await.unwrap().is_empty());
+        let exp = ExpedienteRepository::get_by_id(&pool, ZONA_PREDETERMINADA, &exp.id).await.unwrap();
+        assert_eq!(exp.estado, EstadoExpediente::EnProceso);
+    }
+
+    #[tokio::test]
+    async fn test_mapeo_respeta_transiciones() {
+        let pool = pool_en_memoria().await;
+
+        let archivado = ExpedienteRepository::upsert_from_infogov(&pool, captura("1-1-2026", "Giro a Contaduría"))
+            .await
+            .unwrap();
+        let en_proceso = ExpedienteRepository::upsert_from_infogov(&pool, captura("2-1-2026", "Contratación Directa"))
+            .await
+            .unwrap();
+        sqlx::query("UPDATE expedientes SET estado = 'ARCHIVADO' WHERE id = ?")
+            .bind(&archivado.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        ExpedienteRepository::upsert_from_infogov(&pool, captura("2-1-2026", "Giro a Contaduría"))
+            .await
+            .unwrap();
+
+        // Un archivado no vuelve atrás; el que puede finalizar registra la fecha
+        EstadoInfoGovRepository::guardar(&pool, "Giro a Contaduría", EstadoExpediente::Finalizado)
+            .await
+            .unwrap();
+        let archivado = ExpedienteRepository::get_by_id(&pool, ZONA_PREDETERMINADA, &archivado.id).await.unwrap();
+        assert_eq!(archivado.estado, EstadoExpediente::Archivado);
+        let finalizado = ExpedienteRepository::get_by_id(&pool, ZONA_PREDETERMINADA, &en_proceso.id).await.unwrap();
+        assert_eq!(finalizado.estado, EstadoExpediente::Finalizado);
+        assert!(finalizado.fecha_finalizacion.is_some());
+
+        // Una captura tampoco saca al expediente del archivo
+        let capturado = ExpedienteRepository::upsert_from_infogov(&pool, captura("1-1-2026", "Contratación Directa"))
+            .await
+            .unwrap();
+        assert_eq!(capturado.estado, EstadoExpediente::Archivado);
+    }
+
+    #[tokio::test]
+    async fn test_migracion_rescata_estados_crudos() {
+        let pool = pool_en_memoria().await;
+        let exp = ExpedienteRepository::upsert_from_infogov(&pool, captura("1-1-2026", "Iniciado"))
+            .await
+            .unwrap();
+
+        // Fila escrita por versiones anteriores, con el estado crudo de InfoGov
+        sqlx::query("UPDATE expedientes SET estado = 'CONTRATACIóN DIRECTA', estado_infogov = NULL WHERE id = ?")
+            .bind(&exp.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(crate::db::migrations::RESCATAR_ESTADOS_INFOGOV)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let exp = ExpedienteRepository::get_by_id(&pool, ZONA_PREDETERMINADA, &exp.id).await.unwrap();
+        assert_eq!(exp.estado_infogov.as_deref(), Some("CONTRATACIóN DIRECTA"));
+        assert!(EstadoInfoGovRepository::pendientes(&pool).await.unwrap().is_empty());
+        let exp = ExpedienteRepository::get_by_id(&pool, ZONA_PREDETERMINADA, &exp.id).await.unwrap();
+        assert_eq!(exp.estado, EstadoExpediente::EnProceso);
+    }
+}
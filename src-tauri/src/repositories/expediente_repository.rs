@@ -1,44 +1,165 @@
 // Repositorio de Expedientes
 // Maneja todas las operaciones de base de datos para expedientes
 
-use sqlx::{Pool, Sqlite};
+use sqlx::{Pool, QueryBuilder, Sqlite, SqliteConnection};
 use uuid::Uuid;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 
-use crate::models::expediente::{Expediente, CreateExpediente, UpdateExpediente};
+use crate::models::expediente::{
+    Expediente, CreateExpediente, UpdateExpediente, ResultadoBusqueda, EstadoExpediente,
+    ExpedienteFilter, CampoOrdenExpediente, PaginaExpedientes,
+    ResumenCapturaInfoGov, ExpedienteCapturado, ErrorCaptura,
+    VistaPreviaImportacion, CambiosImportacion, CampoImportado,
+};
+use crate::models::movimiento::{CreateMovimiento, OrigenMovimiento};
+use crate::models::NumeroGde;
 use crate::error::{Result, AppError};
-use crate::utils::infogov_parser::InfoGovExpediente;
+use crate::models::captura::{AccionCaptura, OrigenCaptura};
+use crate::repositories::captura_repository::CreateCaptura;
+use crate::models::configuracion::FechaInicioInfoGov;
+use crate::repositories::{CapturaRepository, EstadoInfoGovRepository, MovimientoRepository};
+use crate::services::{AltaInfoGovService, EstadoExpedienteService, SettingsService};
+use crate::utils::fts_query::construir_consulta_fts;
+use crate::utils::infogov_parser::{FilaInfoGov, InfoGovExpediente};
 
 
 pub struct ExpedienteRepository;
 
 impl ExpedienteRepository {
-    /// Obtener todos los expedientes
-    pub async fn get_all(pool: &Pool<Sqlite>) -> Result<Vec<Expediente>> {
+    /// Obtener todos los expedientes de una zona
+    pub async fn get_all(pool: &Pool<Sqlite>, zona_id: &str) -> Result<Vec<Expediente>> {
         let expedientes = sqlx::query_as::<_, Expediente>(
-            "SELECT * FROM expedientes ORDER BY created_at DESC"
+            "SELECT * FROM expedientes WHERE zona_id = ? ORDER BY created_at DESC"
         )
+        .bind(zona_id)
         .fetch_all(pool)
         .await?;
         
         Ok(expedientes)
     }
     
-    /// Obtener un expediente por ID
-    pub async fn get_by_id(pool: &Pool<Sqlite>, id: &str) -> Result<Expediente> {
+    /// Listar expedientes de una zona con filtros, orden y paginación
+    /// La consulta se arma con parámetros enlazados; devuelve también el total de coincidencias
+    pub async fn filtrar(pool: &Pool<Sqlite>, zona_id: &str, filtro: &ExpedienteFilter) -> Result<PaginaExpedientes> {
+        let por_pagina = filtro.por_pagina.unwrap_or(50).clamp(1, 500);
+        let pagina = filtro.pagina.unwrap_or(1).max(1);
+
+        // Total de coincidencias
+        let mut count_query = QueryBuilder::<Sqlite>::new("SELECT COUNT(*) FROM expedientes e");
+        Self::aplicar_filtro(&mut count_query, zona_id, filtro);
+        let total: i64 = count_query.build_query_scalar().fetch_one(pool).await?;
+
+        // Página solicitada
+        let mut query = QueryBuilder::<Sqlite>::new("SELECT e.* FROM expedientes e");
+        Self::aplicar_filtro(&mut query, zona_id, filtro);
+
+        query.push(" ORDER BY ");
+        if filtro.orden.is_empty() {
+            query.push("julianday(e.created_at) DESC");
+        } else {
+            for (i, orden) in filtro.orden.iter().enumerate() {
+                if i > 0 {
+                    query.push(", ");
+                }
+                query.push(columna_orden(orden.campo));
+                query.push(if orden.descendente { " DESC" } else { " ASC" });
+            }
+        }
+        // Desempate estable para que la paginación no repita ni saltee filas
+        query.push(", e.id ASC");
+
+        query.push(" LIMIT ").push_bind(por_pagina);
+        query.push(" OFFSET ").push_bind((pagina - 1) * por_pagina);
+
+        let items = query.build_query_as::<Expediente>().fetch_all(pool).await?;
+
+        Ok(PaginaExpedientes { items, total, pagina, por_pagina })
+    }
+
+    /// Agrega las condiciones WHERE del filtro a la consulta
+    fn aplicar_filtro(query: &mut QueryBuilder<'_, Sqlite>, zona_id: &str, filtro: &ExpedienteFilter) {
+        query.push(" WHERE e.zona_id = ").push_bind(zona_id.to_string());
+
+        if let Some(tipo) = &filtro.tipo {
+            query.push(" AND e.tipo = ").push_bind(tipo.clone());
+        }
+        if !filtro.estados.is_empty() {
+            query.push(" AND e.estado IN (");
+            let mut estados = query.separated(", ");
+            for estado in &filtro.estados {
+                estados.push_bind(estado.clone());
+            }
+            estados.push_unseparated(")");
+        }
+        if let Some(prioridad) = &filtro.prioridad {
+            query.push(" AND e.prioridad = ").push_bind(prioridad.clone());
+        }
+        if let Some(area) = &filtro.area_responsable {
+            query.push(" AND e.area_responsable = ").push_bind(area.clone());
+        }
+        if let Some(agente_id) = &filtro.agente_responsable_id {
+            query.push(" AND e.agente_responsable_id = ").push_bind(agente_id.clone());
+        }
+
+        // Las fechas se comparan con julianday() porque conviven formatos
+        // "YYYY-MM-DD HH:MM:SS" (CURRENT_TIMESTAMP) y RFC 3339
+        let rangos = [
+            ("e.fecha_inicio", filtro.fecha_inicio_desde, filtro.fecha_inicio_hasta),
+            ("e.fecha_pase", filtro.fecha_pase_desde, filtro.fecha_pase_hasta),
+            ("e.fecha_vencimiento", filtro.fecha_vencimiento_desde, filtro.fecha_vencimiento_hasta),
+        ];
+        for (columna, desde, hasta) in rangos {
+            if let Some(desde) = desde {
+                query.push(format!(" AND julianday({}) >= julianday(", columna)).push_bind(desde).push(")");
+            }
+            if let Some(hasta) = hasta {
+                query.push(format!(" AND julianday({}) <= julianday(", columna)).push_bind(hasta).push(")");
+            }
+        }
+
+        if let Some(vehiculo_id) = &filtro.vehiculo_id {
+            query.push(" AND e.vehiculo_id = ").push_bind(vehiculo_id.clone());
+        }
+        if let Some(categoria) = &filtro.categoria_gasto {
+            query.push(" AND e.categoria_gasto = ").push_bind(categoria.clone());
+        }
+        match filtro.tiene_oc {
+            Some(true) => {
+                query.push(" AND EXISTS (SELECT 1 FROM ordenes_compra oc WHERE oc.expediente_id = e.id)");
+            }
+            Some(false) => {
+                query.push(" AND NOT EXISTS (SELECT 1 FROM ordenes_compra oc WHERE oc.expediente_id = e.id)");
+            }
+            None => {}
+        }
+    }
+
+    /// Obtener un expediente de la zona por ID
+    pub async fn get_by_id(pool: &Pool<Sqlite>, zona_id: &str, id: &str) -> Result<Expediente> {
         let expediente = sqlx::query_as::<_, Expediente>(
-            "SELECT * FROM expedientes WHERE id = ?"
+            "SELECT * FROM expedientes WHERE id = ? AND zona_id = ?"
         )
         .bind(id)
+        .bind(zona_id)
         .fetch_optional(pool)
         .await?
         .ok_or_else(|| AppError::NotFound(format!("Expediente {} no encontrado", id)))?;
         
         Ok(expediente)
     }
+
+    /// Obtener un expediente de la zona por ID dentro de una transacción abierta
+    pub(crate) async fn get_by_id_en(conn: &mut SqliteConnection, zona_id: &str, id: &str) -> Result<Expediente> {
+        sqlx::query_as::<_, Expediente>("SELECT * FROM expedientes WHERE id = ? AND zona_id = ?")
+            .bind(id)
+            .bind(zona_id)
+            .fetch_optional(conn)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Expediente {} no encontrado", id)))
+    }
     
-    /// Crear un nuevo expediente
-    pub async fn create(pool: &Pool<Sqlite>, data: CreateExpediente) -> Result<Expediente> {
+    /// Crear un nuevo expediente en una zona
+    pub async fn create(pool: &Pool<Sqlite>, zona_id: &str, data: CreateExpediente) -> Result<Expediente> {
         let id = Uuid::new_v4().to_string();
         let now = Utc::now();
         
@@ -53,8 +174,8 @@ impl ExpedienteRepository {
                 asunto, descripcion, area_responsable, prioridad, estado,
                 fecha_inicio, fecha_vencimiento, agente_responsable_id,
                 oc_señor, oc_domicilio, oc_cuit, oc_descripcion_zona, oc_forma_pago, oc_plazo_entrega,
-                created_at, updated_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                zona_id, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#
         )
         .bind(&id)
@@ -62,7 +183,7 @@ impl ExpedienteRepository {
         .bind(data.año)
         .bind(tipo_str)
         .bind(&data.nro_infogov)
-        .bind(&data.nro_gde)
+        .bind(data.nro_gde.as_ref().map(NumeroGde::to_string))
         .bind(&data.caratula)
         .bind(&data.resolucion_nro)
         .bind(&data.asunto)
@@ -79,21 +200,35 @@ impl ExpedienteRepository {
         .bind(&data.oc_descripcion_zona)
         .bind(&data.oc_forma_pago)
         .bind(&data.oc_plazo_entrega)
+        .bind(zona_id)
         .bind(now)
         .bind(now)
         .execute(pool)
         .await?;
         
-        Self::get_by_id(pool, &id).await
+        Self::get_by_id(pool, zona_id, &id).await
     }
     
-    /// Actualizar un expediente existente
-    pub async fn update(pool: &Pool<Sqlite>, id: &str, data: UpdateExpediente) -> Result<Expediente> {
-        // Verificar que el expediente existe
-        let _ = Self::get_by_id(pool, id).await?;
+    /// Actualizar un expediente dentro de una transacción abierta
+    /// Si cambia el pase (fecha, oficina o estado) se registra un movimiento en el historial
+    /// No valida el cambio de estado: se llega sólo a través de EstadoExpedienteService::actualizar
+    pub(crate) async fn update_en(conn: &mut SqliteConnection, zona_id: &str, id: &str, data: UpdateExpediente) -> Result<()> {
+        // Verificar que el expediente existe en la zona
+        let actual = Self::get_by_id_en(&mut *conn, zona_id, id).await?;
+
+        // Datos del pase resultante (para el historial)
+        let registra_pase = data.fecha_pase.is_some() || data.oficina.is_some() || data.estado.is_some();
+        let pase_fecha = data.fecha_pase.or(actual.fecha_pase).unwrap_or_else(Utc::now);
+        let pase_oficina = data.oficina.clone().or_else(|| actual.oficina.clone());
+        let pase_estado = data
+            .estado
+            .as_ref()
+            .map(|estado| format!("{:?}", estado).to_uppercase())
+            .unwrap_or_else(|| format!("{:?}", actual.estado).to_uppercase());
+        let pase_observaciones = data.observaciones.clone();
         
         // Construir la query de actualización dinámicamente
-        let mut query = "UPDATE expedientes SET updated_at = ? WHERE id = ?".to_string();
+        let mut query = "UPDATE expedientes SET updated_at = ? WHERE id = ? AND zona_id = ?".to_string();
         
         if data.asunto.is_some() {
             query = query.replace("WHERE", ", asunto = ? WHERE");
@@ -119,6 +254,12 @@ impl ExpedienteRepository {
         if data.estado.is_some() {
             query = query.replace("WHERE", ", estado = ? WHERE");
         }
+        if data.fecha_pase.is_some() {
+            query = query.replace("WHERE", ", fecha_pase = ? WHERE");
+        }
+        if data.oficina.is_some() {
+            query = query.replace("WHERE", ", oficina = ? WHERE");
+        }
         if data.fecha_vencimiento.is_some() {
             query = query.replace("WHERE", ", fecha_vencimiento = ? WHERE");
         }
@@ -132,7 +273,8 @@ impl ExpedienteRepository {
             query = query.replace("WHERE", ", observaciones = ? WHERE");
         }
         
-        let mut query_builder = sqlx::query(&query).bind(Utc::now()).bind(id);
+        // Los parámetros se enlazan en el mismo orden en que aparecen en la query (id al final)
+        let mut query_builder = sqlx::query(&query).bind(Utc::now());
         
         if let Some(asunto) = data.asunto {
             query_builder = query_builder.bind(asunto);
@@ -144,7 +286,7 @@ impl ExpedienteRepository {
             query_builder = query_builder.bind(nro_infogov);
         }
         if let Some(nro_gde) = data.nro_gde {
-            query_builder = query_builder.bind(nro_gde);
+            query_builder = query_builder.bind(nro_gde.to_string());
         }
         if let Some(caratula) = data.caratula {
             query_builder = query_builder.bind(caratula);
@@ -158,6 +300,12 @@ impl ExpedienteRepository {
         if let Some(estado) = data.estado {
             query_builder = query_builder.bind(format!("{:?}", estado).to_uppercase());
         }
+        if let Some(fecha_pase) = data.fecha_pase {
+            query_builder = query_builder.bind(fecha_pase);
+        }
+        if let Some(oficina) = data.oficina {
+            query_builder = query_builder.bind(oficina);
+        }
         if let Some(fecha_venc) = data.fecha_vencimiento {
             query_builder = query_builder.bind(fecha_venc);
         }
@@ -170,16 +318,33 @@ impl ExpedienteRepository {
         if let Some(obs) = data.observaciones {
             query_builder = query_builder.bind(obs);
         }
-        
-        query_builder.execute(pool).await?;
-        
-        Self::get_by_id(pool, id).await
+        query_builder = query_builder.bind(id).bind(zona_id);
+
+        query_builder.execute(&mut *conn).await?;
+
+        if registra_pase {
+            Self::registrar_pase_si_cambia(
+                conn,
+                id,
+                actual.oficina.clone(),
+                pase_oficina,
+                pase_fecha,
+                pase_estado,
+                None,
+                OrigenMovimiento::Manual,
+                pase_observaciones,
+            )
+            .await?;
+        }
+
+        Ok(())
     }
     
-    /// Eliminar un expediente
-    pub async fn delete(pool: &Pool<Sqlite>, id: &str) -> Result<()> {
-        let result = sqlx::query("DELETE FROM expedientes WHERE id = ?")
+    /// Eliminar un expediente de la zona
+    pub async fn delete(pool: &Pool<Sqlite>, zona_id: &str, id: &str) -> Result<()> {
+        let result = sqlx::query("DELETE FROM expedientes WHERE id = ? AND zona_id = ?")
             .bind(id)
+            .bind(zona_id)
             .execute(pool)
             .await?;
         
@@ -190,15 +355,16 @@ impl ExpedienteRepository {
         Ok(())
     }
     
-    /// Buscar expedientes por texto (número o asunto)
-    pub async fn search(pool: &Pool<Sqlite>, query: &str) -> Result<Vec<Expediente>> {
+    /// Buscar expedientes de una zona por texto (número o asunto)
+    pub async fn search(pool: &Pool<Sqlite>, zona_id: &str, query: &str) -> Result<Vec<Expediente>> {
         let search_pattern = format!("%{}%", query);
         
         let expedientes = sqlx::query_as::<_, Expediente>(
             "SELECT * FROM expedientes 
-             WHERE numero LIKE ? OR asunto LIKE ?
+             WHERE zona_id = ? AND (numero LIKE ? OR asunto LIKE ?)
              ORDER BY created_at DESC"
         )
+        .bind(zona_id)
         .bind(&search_pattern)
         .bind(&search_pattern)
         .fetch_all(pool)
@@ -207,102 +373,440 @@ impl ExpedienteRepository {
         Ok(expedientes)
     }
 
+    /// Búsqueda de texto completo sobre el índice FTS5
+    /// Acepta términos libres (por prefijo), frases y filtros por campo (gde:GDEMZA, prov:"Estudio ABC")
+    /// Los resultados se ordenan por relevancia (bm25) e incluyen un fragmento con las coincidencias marcadas
+    pub async fn buscar_texto_completo(
+        pool: &Pool<Sqlite>,
+        zona_id: &str,
+        query: &str,
+        limite: i64,
+    ) -> Result<Vec<ResultadoBusqueda>> {
+        let Some(consulta) = construir_consulta_fts(query) else {
+            return Ok(Vec::new());
+        };
+
+        // Pesos bm25 por columna (expediente_id, numero, asunto, tema, resumen, caratula,
+        // nro_infogov, nro_gde, observaciones, proveedor): los identificadores pesan más
+        let resultados = sqlx::query_as::<_, ResultadoBusqueda>(
+            r#"
+            SELECT e.*,
+                   bm25(expedientes_fts, 0.0, 10.0, 5.0, 5.0, 2.0, 3.0, 10.0, 10.0, 1.0, 3.0) AS relevancia,
+                   snippet(expedientes_fts, -1, '<mark>', '</mark>', '…', 12) AS fragmento
+            FROM expedientes_fts
+            JOIN expedientes e ON e.id = expedientes_fts.expediente_id
+            WHERE expedientes_fts MATCH ? AND e.zona_id = ?
+            ORDER BY relevancia
+            LIMIT ?
+            "#
+        )
+        .bind(consulta)
+        .bind(zona_id)
+        .bind(limite)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(resultados)
+    }
+
     /// UPSERT: Inserta o actualiza un expediente desde datos de InfoGov
-    /// Si el nro_infogov ya existe, actualiza fecha_pase, estado y resumen
+    /// Si el nro_infogov ya existe, actualiza fecha_pase, estado, oficina y resumen
     /// Si no existe, crea un nuevo registro
+    /// En ambos casos el pase queda registrado en el historial de movimientos
     pub async fn upsert_from_infogov(
         pool: &Pool<Sqlite>,
         infogov_exp: InfoGovExpediente,
     ) -> Result<Expediente> {
-        let id = Uuid::new_v4().to_string();
-        let now = Utc::now();
+        let mut tx = pool.begin().await?;
+        let (id, _) = Self::upsert_infogov_en(&mut tx, &infogov_exp).await?;
+        tx.commit().await?;
+
+        // Recuperar el expediente creado/actualizado (la captura trabaja en la zona de la instalación)
+        let zona_id = SettingsService::zona_activa(pool).await?;
+        Self::get_by_id(pool, &zona_id, &id).await
+    }
 
-        // Iniciar transacción
+    /// Guarda todas las filas de una captura múltiple de InfoGov en una sola transacción
+    /// Cada fila corre en su propio savepoint: un error en una fila no descarta las demás
+    /// Todas las filas (también las fallidas) quedan en el historial de capturas
+    pub async fn upsert_lote_infogov(
+        pool: &Pool<Sqlite>,
+        filas: Vec<FilaInfoGov>,
+        origen: OrigenCaptura,
+    ) -> Result<ResumenCapturaInfoGov> {
+        let mut resumen = ResumenCapturaInfoGov::default();
+        let lote_id = Uuid::new_v4().to_string();
         let mut tx = pool.begin().await?;
 
-        // Verificar si el expediente ya existe
-        let existing = sqlx::query_as::<_, Expediente>(
-            "SELECT * FROM expedientes WHERE nro_infogov = ?"
+        for fila in filas {
+            let mut captura = CreateCaptura {
+                lote_id: lote_id.clone(),
+                fila: fila.fila,
+                origen,
+                texto: fila.texto.clone(),
+                parseado: None,
+                error: None,
+                accion: AccionCaptura::Error,
+                expediente_id: None,
+                movimiento_id: None,
+                antes: None,
+                despues: None,
+            };
+
+            let infogov_exp = match fila.resultado {
+                Ok(exp) => exp,
+                Err(error) => {
+                    captura.error = Some(error.clone());
+                    CapturaRepository::registrar_en(&mut tx, captura).await?;
+                    resumen.errores.push(ErrorCaptura { fila: fila.fila, texto: fila.texto, error });
+                    continue;
+                }
+            };
+            captura.parseado = Some(infogov_exp.clone());
+
+            let mut savepoint = sqlx::Connection::begin(&mut *tx).await?;
+            match Self::upsert_infogov_registrado(&mut savepoint, &infogov_exp, &mut captura).await {
+                Ok((id, creado)) => {
+                    savepoint.commit().await?;
+                    CapturaRepository::registrar_en(&mut tx, captura).await?;
+                    let capturado = ExpedienteCapturado {
+                        fila: fila.fila,
+                        id,
+                        nro_infogov: infogov_exp.nro_infogov,
+                        resumen: infogov_exp.resumen,
+                    };
+                    if creado {
+                        resumen.creados.push(capturado);
+                    } else {
+                        resumen.actualizados.push(capturado);
+                    }
+                }
+                Err(e) => {
+                    savepoint.rollback().await?;
+                    captura.accion = AccionCaptura::Error;
+                    captura.error = Some(e.to_string());
+                    captura.expediente_id = None;
+                    captura.movimiento_id = None;
+                    captura.despues = None;
+                    CapturaRepository::registrar_en(&mut tx, captura).await?;
+                    resumen.errores.push(ErrorCaptura { fila: fila.fila, texto: fila.texto, error: e.to_string() });
+                }
+            }
+        }
+
+        tx.commit().await?;
+        Ok(resumen)
+    }
+
+    /// Upsert de una fila tomando el expediente antes y después (y el pase nuevo) para el historial
+    async fn upsert_infogov_registrado(
+        conn: &mut SqliteConnection,
+        infogov_exp: &InfoGovExpediente,
+        captura: &mut CreateCaptura,
+    ) -> Result<(String, bool)> {
+        let zona_id = SettingsService::cargar_en(&mut *conn).await?.zona.id;
+        captura.antes = Self::buscar_para_infogov(&mut *conn, &zona_id, infogov_exp).await?;
+        let ultimo_antes = match &captura.antes {
+            Some(antes) => MovimientoRepository::get_ultimo(&mut *conn, &antes.id).await?.map(|m| m.id),
+            None => None,
+        };
+
+        let (id, creado) = Self::upsert_infogov_en(&mut *conn, infogov_exp).await?;
+
+        let ultimo = MovimientoRepository::get_ultimo(&mut *conn, &id).await?.map(|m| m.id);
+        captura.movimiento_id = ultimo.filter(|m| Some(m) != ultimo_antes.as_ref());
+        captura.despues = sqlx::query_as::<_, Expediente>("SELECT * FROM expedientes WHERE id = ?")
+            .bind(&id)
+            .fetch_optional(&mut *conn)
+            .await?;
+        captura.expediente_id = Some(id.clone());
+        captura.accion = if creado { AccionCaptura::Creado } else { AccionCaptura::Actualizado };
+
+        Ok((id, creado))
+    }
+
+    /// Calcula qué haría la importación de un lote sin escribir nada (dry-run)
+    /// Compara cada fila con el expediente que actualizaría, con las mismas reglas que el upsert
+    pub async fn previsualizar_lote_infogov(
+        pool: &Pool<Sqlite>,
+        filas: Vec<FilaInfoGov>,
+    ) -> Result<VistaPreviaImportacion> {
+        let mut vista = VistaPreviaImportacion::default();
+        let mut conn = pool.acquire().await?;
+        let zona_id = SettingsService::cargar_en(&mut conn).await?.zona.id;
+
+        for fila in filas {
+            let infogov_exp = match fila.resultado {
+                Ok(exp) => exp,
+                Err(error) => {
+                    vista.errores.push(ErrorCaptura { fila: fila.fila, texto: fila.texto, error });
+                    continue;
+                }
+            };
+
+            let estado_infogov = Some(infogov_exp.estado.trim()).filter(|e| !e.is_empty());
+            let estado = match estado_infogov {
+                Some(e) => EstadoInfoGovRepository::resolver_en(&mut conn, e).await?,
+                None => None,
+            };
+            if let (None, Some(e)) = (&estado, estado_infogov) {
+                if !vista.estados_sin_mapeo.iter().any(|s| s == e) {
+                    vista.estados_sin_mapeo.push(e.to_string());
+                }
+            }
+
+            let fecha_pase = infogov_exp.fecha_pase_utc().map(|f| f.format("%d/%m/%Y").to_string());
+            let nombre_estado = |e: &EstadoExpediente| format!("{:?}", e).to_uppercase();
+            let mut cambios = Vec::new();
+            let mut anotar = |campo: &str, antes: Option<String>, despues: Option<String>| {
+                if despues.is_some() && antes != despues {
+                    cambios.push(CampoImportado { campo: campo.to_string(), antes, despues });
+                }
+            };
+
+            match Self::buscar_para_infogov(&mut conn, &zona_id, &infogov_exp).await? {
+                Some(existente) => {
+                    if existente.nro_infogov.is_none() {
+                        anotar("nro_infogov", None, Some(infogov_exp.nro_infogov.clone()));
+                    }
+                    if existente.nro_gde.is_none() {
+                        anotar("nro_gde", None, Some(infogov_exp.nro_gde.clone()));
+                    }
+                    anotar(
+                        "fecha_pase",
+                        existente.fecha_pase.map(|f| f.format("%d/%m/%Y").to_string()),
+                        fecha_pase,
+                    );
+                    anotar("estado", Some(nombre_estado(&existente.estado)), estado.as_ref().map(nombre_estado));
+                    anotar("estado_infogov", existente.estado_infogov.clone(), estado_infogov.map(str::to_string));
+                    anotar("oficina", existente.oficina.clone(), infogov_exp.oficina.clone());
+                    anotar("resumen", existente.resumen.clone(), Some(infogov_exp.resumen.clone()));
+
+                    if cambios.is_empty() {
+                        vista.sin_cambios += 1;
+                    } else {
+                        vista.modificados.push(CambiosImportacion {
+                            fila: fila.fila,
+                            id: Some(existente.id),
+                            nro_infogov: infogov_exp.nro_infogov,
+                            cambios,
+                        });
+                    }
+                }
+                None => {
+                    anotar("nro_infogov", None, Some(infogov_exp.nro_infogov.clone()));
+                    anotar("nro_gde", None, Some(infogov_exp.nro_gde.clone()));
+                    anotar("asunto", None, Some(infogov_exp.tema.clone()));
+                    anotar("fecha_pase", None, fecha_pase);
+                    anotar(
+                        "estado",
+                        None,
+                        Some(nombre_estado(estado.as_ref().unwrap_or(&EstadoExpediente::Iniciado))),
+                    );
+                    anotar("estado_infogov", None, estado_infogov.map(str::to_string));
+                    anotar("oficina", None, infogov_exp.oficina.clone());
+
+                    vista.nuevos.push(CambiosImportacion {
+                        fila: fila.fila,
+                        id: None,
+                        nro_infogov: infogov_exp.nro_infogov,
+                        cambios,
+                    });
+                }
+            }
+        }
+
+        Ok(vista)
+    }
+
+    /// Expediente de la zona que corresponde a una captura de InfoGov
+    /// Primero por nro_infogov; si no hay, un expediente cargado solo con el nro_gde
+    async fn buscar_para_infogov(
+        conn: &mut SqliteConnection,
+        zona_id: &str,
+        infogov_exp: &InfoGovExpediente,
+    ) -> Result<Option<Expediente>> {
+        let por_infogov = sqlx::query_as::<_, Expediente>(
+            "SELECT * FROM expedientes WHERE zona_id = ? AND nro_infogov = ?"
         )
+        .bind(zona_id)
         .bind(&infogov_exp.nro_infogov)
-        .fetch_optional(&mut *tx)
+        .fetch_optional(&mut *conn)
         .await?;
 
+        if por_infogov.is_some() {
+            return Ok(por_infogov);
+        }
+
+        let por_gde = sqlx::query_as::<_, Expediente>(
+            "SELECT * FROM expedientes WHERE zona_id = ? AND nro_infogov IS NULL AND nro_gde = ? ORDER BY created_at LIMIT 1"
+        )
+        .bind(zona_id)
+        .bind(&infogov_exp.nro_gde)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(por_gde)
+    }
+
+    /// Upsert de una captura dentro de una transacción abierta
+    /// Devuelve el id del expediente y si fue creado (true) o actualizado (false)
+    async fn upsert_infogov_en(
+        conn: &mut SqliteConnection,
+        infogov_exp: &InfoGovExpediente,
+    ) -> Result<(String, bool)> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now();
+        let fecha_pase = infogov_exp.fecha_pase_utc().unwrap_or(now);
+        let settings = SettingsService::cargar_en(&mut *conn).await?;
+
+        // Verificar si el expediente ya existe en la zona
+        let existing = Self::buscar_para_infogov(&mut *conn, &settings.zona.id, infogov_exp).await?;
+
+        // El estado de InfoGov es texto libre: se traduce con el mapeo y se guarda crudo en estado_infogov
+        let estado_infogov = Some(infogov_exp.estado.trim()).filter(|e| !e.is_empty());
+        let estado = match estado_infogov {
+            Some(e) => EstadoInfoGovRepository::resolver_en(&mut *conn, e).await?,
+            None => None,
+        };
+
         let result = if let Some(existing_exp) = existing {
-            // ACTUALIZAR: Solo fecha_pase, estado y resumen
+            // ACTUALIZAR: Solo fecha_pase, estado, oficina y resumen (y los números que falten)
+            // Sin equivalencia para el estado de InfoGov, o si la transición no está permitida, se conserva el estado interno
             println!("📝 Actualizando expediente existente: {}", infogov_exp.nro_infogov);
+            let cambio = estado
+                .clone()
+                .and_then(|nuevo| EstadoExpedienteService::cambio_desde_infogov(&existing_exp, nuevo))
+                .unwrap_or_default();
             
             sqlx::query(
                 r#"
                 UPDATE expedientes 
-                SET fecha_pase = ?, estado = ?, resumen = ?, updated_at = ?
+                SET nro_infogov = COALESCE(nro_infogov, ?), nro_gde = COALESCE(nro_gde, ?),
+                    fecha_pase = ?, estado = COALESCE(?, estado), fecha_finalizacion = COALESCE(?, fecha_finalizacion),
+                    estado_infogov = COALESCE(?, estado_infogov),
+                    oficina = COALESCE(?, oficina), resumen = ?, updated_at = ?
                 WHERE id = ?
                 "#
             )
-            .bind(&infogov_exp.fecha_pase)
-            .bind(&infogov_exp.estado)
+            .bind(&infogov_exp.nro_infogov)
+            .bind(&infogov_exp.nro_gde)
+            .bind(fecha_pase)
+            .bind(&cambio.estado)
+            .bind(cambio.fecha_finalizacion)
+            .bind(estado_infogov)
+            .bind(&infogov_exp.oficina)
             .bind(&infogov_exp.resumen)
             .bind(now)
             .bind(&existing_exp.id)
-            .execute(&mut *tx)
+            .execute(&mut *conn)
+            .await?;
+
+            // Registrar el pase solo si cambió respecto del último movimiento
+            // El historial guarda el estado interno resultante; el texto de InfoGov va aparte
+            let estado_resultante = cambio.estado.as_ref().unwrap_or(&existing_exp.estado);
+            Self::registrar_pase_si_cambia(
+                &mut *conn,
+                &existing_exp.id,
+                existing_exp.oficina.clone(),
+                infogov_exp.oficina.clone().or(existing_exp.oficina.clone()),
+                fecha_pase,
+                format!("{:?}", estado_resultante).to_uppercase(),
+                estado_infogov.map(str::to_string),
+                OrigenMovimiento::InfoGov,
+                None,
+            )
             .await?;
 
-            existing_exp.id
+            if estado.is_none() {
+                if let Some(e) = estado_infogov {
+                    EstadoInfoGovRepository::encolar_en(&mut *conn, e, &existing_exp.id).await?;
+                }
+            }
+
+            (existing_exp.id, false)
         } else {
             // INSERTAR: Nuevo expediente completo
             println!("✨ Creando nuevo expediente: {}", infogov_exp.nro_infogov);
+
+            // numero/año/tipo se derivan de los identificadores; lo que InfoGov no informa sale de la configuración
+            let identificacion = AltaInfoGovService::identificar(infogov_exp);
+            let defectos = &settings.infogov;
+            let fecha_inicio = match defectos.fecha_inicio {
+                FechaInicioInfoGov::FechaPase => fecha_pase,
+                FechaInicioInfoGov::Hoy => now,
+            };
             
             sqlx::query(
                 r#"
                 INSERT INTO expedientes (
                     id, numero, año, tipo, nro_infogov, nro_gde, 
                     asunto, descripcion, area_responsable, prioridad, estado,
-                    fecha_inicio, fecha_pase, oficina, buzon_grupal, hacer,
-                    resumen, created_at, updated_at
-                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                    estado_infogov, fecha_inicio, fecha_pase, oficina, buzon_grupal, hacer,
+                    resumen, categoria_gasto, zona_id, created_at, updated_at
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
                 "#
             )
             .bind(&id)
-            .bind("") // numero (vacío - será completado manualmente si es necesario)
-            .bind(0) // año (extraído del nro_infogov)
-            .bind("OTRO") // tipo por defecto
+            .bind(&identificacion.numero) // numero derivado del nro_infogov (o del nro_gde)
+            .bind(identificacion.año) // año derivado del nro_infogov (o del nro_gde)
+            .bind(&identificacion.tipo) // INFOGOV, GDE o PAGO
             .bind(&infogov_exp.nro_infogov)
             .bind(&infogov_exp.nro_gde)
             .bind(&infogov_exp.tema) // asunto = tema
             .bind("") // descripción vacía
-            .bind("InfoGov") // área responsable
-            .bind("MEDIA") // prioridad por defecto
-            .bind(&infogov_exp.estado) // estado capturado de InfoGov
-            .bind(now.format("%Y-%m-%d").to_string()) // fecha_inicio = hoy
-            .bind(&infogov_exp.fecha_pase) // fecha_pase capturada
+            .bind(&defectos.area_responsable) // área responsable configurada
+            .bind(&defectos.prioridad) // prioridad configurada
+            .bind(estado.as_ref().unwrap_or(&EstadoExpediente::Iniciado)) // estado mapeado (INICIADO si no hay equivalencia)
+            .bind(estado_infogov) // estado crudo de InfoGov
+            .bind(fecha_inicio) // fecha del pase o de hoy, según la configuración
+            .bind(fecha_pase) // fecha_pase capturada
             .bind(&infogov_exp.oficina) // oficina extraída del nro_gde
             .bind("") // buzón grupal vacío (completar manualmente)
             .bind("") // hacer vacío (completar manualmente)
             .bind(&infogov_exp.resumen) // resumen calculado
+            .bind(&identificacion.categoria_gasto) // categoría detectada por el clasificador
+            .bind(&settings.zona.id) // zona de la instalación
             .bind(now)
             .bind(now)
-            .execute(&mut *tx)
+            .execute(&mut *conn)
             .await
             .map_err(|e| {
                 eprintln!("❌ Error en INSERT: {}", e);
                 e
             })?;
 
-            id
-        };
+            // Primer movimiento del historial: ingreso desde InfoGov
+            MovimientoRepository::registrar(&mut *conn, CreateMovimiento {
+                expediente_id: id.clone(),
+                oficina_origen: None,
+                oficina_destino: infogov_exp.oficina.clone(),
+                fecha_pase,
+                estado: Some(format!("{:?}", estado.as_ref().unwrap_or(&EstadoExpediente::Iniciado)).to_uppercase()),
+                estado_infogov: estado_infogov.map(str::to_string),
+                origen: OrigenMovimiento::InfoGov,
+                observaciones: None,
+            })
+            .await?;
 
-        // Confirmar transacción
-        tx.commit().await?;
+            if estado.is_none() {
+                if let Some(e) = estado_infogov {
+                    EstadoInfoGovRepository::encolar_en(&mut *conn, e, &id).await?;
+                }
+            }
 
-        // Recuperar el expediente creado/actualizado
-        Self::get_by_id(pool, &result).await
+            (id, true)
+        };
+
+        Ok(result)
     }
 
-    /// Obtener expedientes vinculados a un vehículo
-    pub async fn get_by_vehiculo_id(pool: &Pool<Sqlite>, vehiculo_id: &str) -> Result<Vec<Expediente>> {
+    /// Obtener expedientes de una zona vinculados a un vehículo
+    pub async fn get_by_vehiculo_id(pool: &Pool<Sqlite>, zona_id: &str, vehiculo_id: &str) -> Result<Vec<Expediente>> {
         let expedientes = sqlx::query_as::<_, Expediente>(
-            "SELECT * FROM expedientes WHERE vehiculo_id = ? AND tipo = 'PAGO' ORDER BY created_at DESC"
+            "SELECT * FROM expedientes WHERE zona_id = ? AND vehiculo_id = ? AND tipo = 'PAGO' ORDER BY created_at DESC"
         )
+        .bind(zona_id)
         .bind(vehiculo_id)
         .fetch_all(pool)
         .await?;
@@ -310,15 +814,287 @@ impl ExpedienteRepository {
         Ok(expedientes)
     }
 
-    /// Obtener expedientes de pago con categoría de gasto específica
-    pub async fn get_by_categoria_gasto(pool: &Pool<Sqlite>, categoria: &str) -> Result<Vec<Expediente>> {
+    /// Obtener expedientes de pago de una zona con categoría de gasto específica
+    pub async fn get_by_categoria_gasto(pool: &Pool<Sqlite>, zona_id: &str, categoria: &str) -> Result<Vec<Expediente>> {
         let expedientes = sqlx::query_as::<_, Expediente>(
-            "SELECT * FROM expedientes WHERE categoria_gasto = ? AND tipo = 'PAGO' ORDER BY created_at DESC"
+            "SELECT * FROM expedientes WHERE zona_id = ? AND categoria_gasto = ? AND tipo = 'PAGO' ORDER BY created_at DESC"
         )
+        .bind(zona_id)
         .bind(categoria)
         .fetch_all(pool)
         .await?;
         
         Ok(expedientes)
     }
+
+    /// Registra un movimiento si el pase difiere del último registrado
+    /// Evita duplicados cuando se captura varias veces el mismo pase
+    #[allow(clippy::too_many_arguments)]
+    async fn registrar_pase_si_cambia(
+        conn: &mut SqliteConnection,
+        expediente_id: &str,
+        oficina_anterior: Option<String>,
+        oficina_destino: Option<String>,
+        fecha_pase: DateTime<Utc>,
+        estado: String,
+        estado_infogov: Option<String>,
+        origen: OrigenMovimiento,
+        observaciones: Option<String>,
+    ) -> Result<()> {
+        let ultimo = MovimientoRepository::get_ultimo(&mut *conn, expediente_id).await?;
+
+        if let Some(ultimo) = &ultimo {
+            if ultimo.fecha_pase == fecha_pase
+                && ultimo.oficina_destino == oficina_destino
+                && ultimo.estado.as_deref() == Some(estado.as_str())
+                && (estado_infogov.is_none() || ultimo.estado_infogov == estado_infogov)
+            {
+                return Ok(());
+            }
+        }
+
+        let oficina_origen = match ultimo {
+            Some(ultimo) => ultimo.oficina_destino,
+            None => oficina_anterior,
+        };
+
+        MovimientoRepository::registrar(conn, CreateMovimiento {
+            expediente_id: expediente_id.to_string(),
+            oficina_origen,
+            oficina_destino,
+            fecha_pase,
+            estado: Some(estado),
+            estado_infogov,
+            origen,
+            observaciones,
+        })
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// Expresión SQL para cada columna de ordenamiento (lista cerrada, nunca texto del usuario)
+fn columna_orden(campo: CampoOrdenExpediente) -> &'static str {
+    match campo {
+        CampoOrdenExpediente::Numero => "e.numero",
+        CampoOrdenExpediente::Año => "e.año",
+        CampoOrdenExpediente::Asunto => "e.asunto",
+        CampoOrdenExpediente::Prioridad => {
+            "CASE e.prioridad WHEN 'BAJA' THEN 1 WHEN 'MEDIA' THEN 2 WHEN 'ALTA' THEN 3 WHEN 'URGENTE' THEN 4 END"
+        }
+        CampoOrdenExpediente::Estado => "e.estado",
+        CampoOrdenExpediente::FechaInicio => "julianday(e.fecha_inicio)",
+        CampoOrdenExpediente::FechaPase => "julianday(e.fecha_pase)",
+        CampoOrdenExpediente::FechaVencimiento => "julianday(e.fecha_vencimiento)",
+        CampoOrdenExpediente::CreatedAt => "julianday(e.created_at)",
+        CampoOrdenExpediente::UpdatedAt => "julianday(e.updated_at)",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_util::{captura, pool_en_memoria};
+    use crate::models::configuracion::DefectosInfoGov;
+    use crate::models::zona::{CreateZona, ZONA_PREDETERMINADA};
+    use crate::repositories::ZonaRepository;
+    use crate::models::expediente::{EstadoExpediente, OrdenExpediente, Prioridad, TipoExpediente};
+
+    #[tokio::test]
+    async fn test_busqueda_texto_completo() {
+        let pool = pool_en_memoria().await;
+
+        let exp = ExpedienteRepository::upsert_from_infogov(&pool, InfoGovExpediente {
+            nro_infogov: "817619-30-2026".to_string(),
+            tema: "Reparación embrague Toyota Hilux".to_string(),
+            nro_gde: "EX-2026-01216856-GDEMZA-DGIRR".to_string(),
+            fecha_pase: "2026-02-18".to_string(),
+            estado: "ENPROCESO".to_string(),
+            oficina: Some("GDEMZA".to_string()),
+            resumen: "817619-30-2026 - Reparación embrague Toyota Hilux".to_string(),
+        })
+        .await
+        .unwrap();
+
+        // Sin acentos, en minúsculas y por prefijo
+        let resultados = ExpedienteRepository::buscar_texto_completo(&pool, ZONA_PREDETERMINADA, "reparacion hil", 10).await.unwrap();
+        assert_eq!(resultados.len(), 1);
+        assert_eq!(resultados[0].expediente.id, exp.id);
+        assert!(resultados[0].fragmento.contains("<mark>"));
+
+        // Filtro por campo
+        let resultados = ExpedienteRepository::buscar_texto_completo(&pool, ZONA_PREDETERMINADA, "gde:gdemza", 10).await.unwrap();
+        assert_eq!(resultados.len(), 1);
+        let resultados = ExpedienteRepository::buscar_texto_completo(&pool, ZONA_PREDETERMINADA, "tema:gdemza", 10).await.unwrap();
+        assert!(resultados.is_empty());
+
+        // El índice se mantiene al eliminar
+        ExpedienteRepository::delete(&pool, ZONA_PREDETERMINADA, &exp.id).await.unwrap();
+        let resultados = ExpedienteRepository::buscar_texto_completo(&pool, ZONA_PREDETERMINADA, "hilux", 10).await.unwrap();
+        assert!(resultados.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_filtrar_con_paginacion() {
+        let pool = pool_en_memoria().await;
+
+        ExpedienteRepository::upsert_from_infogov(&pool, captura("1-1-2026", "2026-01-10", "INICIADO")).await.unwrap();
+        ExpedienteRepository::upsert_from_infogov(&pool, captura("2-1-2026", "2026-02-10", "ENPROCESO")).await.unwrap();
+        ExpedienteRepository::upsert_from_infogov(&pool, captura("3-1-2026", "2026-03-10", "ENPROCESO")).await.unwrap();
+        ExpedienteRepository::upsert_from_infogov(&pool, captura("4-1-2026", "2026-04-10", "FINALIZADO")).await.unwrap();
+
+        let filtro = ExpedienteFilter {
+            estados: vec![EstadoExpediente::EnProceso, EstadoExpediente::Finalizado],
+            orden: vec![OrdenExpediente { campo: CampoOrdenExpediente::FechaPase, descendente: true }],
+            por_pagina: Some(2),
+            pagina: Some(2),
+            ..Default::default()
+        };
+        let pagina = ExpedienteRepository::filtrar(&pool, ZONA_PREDETERMINADA, &filtro).await.unwrap();
+        assert_eq!(pagina.total, 3);
+        assert_eq!(pagina.items.len(), 1);
+        assert_eq!(pagina.items[0].nro_infogov.as_deref(), Some("2-1-2026"));
+
+        let filtro = ExpedienteFilter {
+            fecha_pase_desde: Some("2026-02-01T00:00:00Z".parse().unwrap()),
+            fecha_pase_hasta: Some("2026-03-31T23:59:59Z".parse().unwrap()),
+            tiene_oc: Some(false),
+            ..Default::default()
+        };
+        let pagina = ExpedienteRepository::filtrar(&pool, ZONA_PREDETERMINADA, &filtro).await.unwrap();
+        assert_eq!(pagina.total, 2);
+    }
+
+    #[tokio::test]
+    async fn test_previsualizar_e_importar_bandeja() {
+        let pool = pool_en_memoria().await;
+        let encabezado = || vec!["Nro InfoGov".into(), "Tema".into(), "Fecha Pase".into(), "Nro GDE".into(), "Estado".into()];
+        let previas = crate::utils::infogov_html::lote_desde_celdas(vec![
+            encabezado(),
+            vec!["1-1-2026".into(), "Expediente 1-1-2026".into(), "10/01/2026".into(), "EX-2026-01216856-GDEMZA-DGIRR".into(), "Iniciado".into()],
+            vec!["2-1-2026".into(), "Expediente 2-1-2026".into(), "10/01/2026".into(), "EX-2026-01216856-GDEMZA-DGIRR".into(), "Iniciado".into()],
+        ])
+        .unwrap();
+        ExpedienteRepository::upsert_lote_infogov(&pool, previas, OrigenCaptura::Archivo).await.unwrap();
+
+        // Expediente cargado a mano solo con el nro_gde
+        sqlx::query(
+            "INSERT INTO expedientes (id, numero, año, tipo, asunto, nro_gde) VALUES ('manual', '7', 2026, 'PAGO', 'Cubiertas', 'EX-2026-00000007-GDEMZA-DGIRR')"
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let filas = crate::utils::infogov_html::lote_desde_celdas(vec![
+            encabezado(),
+            vec!["1-1-2026".into(), "Expediente 1-1-2026".into(), "10/01/2026".into(), "EX-2026-01216856-GDEMZA-DGIRR".into(), "Iniciado".into()],
+            vec!["2-1-2026".into(), "Expediente 2-1-2026".into(), "15/01/2026".into(), "EX-2026-01216856-GDEMZA-DGIRR".into(), "Contratación Directa".into()],
+            vec!["7-30-2026".into(), "Cubiertas".into(), "16/01/2026".into(), "EX-2026-00000007-GDEMZA-DGIRR".into(), "Giro a Contaduría".into()],
+            vec!["8-30-2026".into(), "Nuevo".into(), "16/01/2026".into(), "EX-2026-00000008-GDEMZA-DGIRR".into(), "".into()],
+            vec!["9-30-2026".into(), "Sin fecha".into(), "".into(), "EX-2026-00000009-GDEMZA-DGIRR".into(), "".into()],
+        ])
+        .unwrap();
+
+        // La vista previa no escribe nada
+        let vista = ExpedienteRepository::previsualizar_lote_infogov(&pool, filas.clone()).await.unwrap();
+        assert_eq!(vista.sin_cambios, 1);
+        assert_eq!(vista.modificados.len(), 2);
+        let campos: Vec<&str> = vista.modificados[0].cambios.iter().map(|c| c.campo.as_str()).collect();
+        assert_eq!(campos, vec!["fecha_pase", "estado", "estado_infogov"]);
+        assert_eq!(vista.modificados[1].id.as_deref(), Some("manual"));
+        assert_eq!(vista.modificados[1].cambios[0].campo, "nro_infogov");
+        assert_eq!(vista.nuevos.len(), 1);
+        assert_eq!(vista.errores.len(), 1);
+        assert_eq!(vista.estados_sin_mapeo, vec!["Giro a Contaduría"]);
+        let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM expedientes").fetch_one(&pool).await.unwrap();
+        assert_eq!(total, 3);
+
+        let resumen = ExpedienteRepository::upsert_lote_infogov(&pool, filas, OrigenCaptura::Portapapeles).await.unwrap();
+        assert_eq!(resumen.actualizados.len(), 3);
+        assert_eq!(resumen.creados.len(), 1);
+        let manual = ExpedienteRepository::get_by_id(&pool, ZONA_PREDETERMINADA, "manual").await.unwrap();
+        assert_eq!(manual.nro_infogov.as_deref(), Some("7-30-2026"));
+        assert_eq!(manual.estado_infogov.as_deref(), Some("Giro a Contaduría"));
+    }
+
+    #[tokio::test]
+    async fn test_upsert_lote_infogov() {
+        let pool = pool_en_memoria().await;
+        ExpedienteRepository::upsert_from_infogov(&pool, captura("1-1-2026", "2026-01-10", "INICIADO")).await.unwrap();
+
+        let filas = InfoGovExpediente::lote_desde_portapapeles(concat!(
+            "1 1 2026 Compra repuestos 12/1/2026 EX-2026-00000001-GDEMZA-DGIRR Contratación Directa\n",
+            "2 1 2026 Service camioneta 13/1/2026 EX-2026-00000002-GDEMZA-DGIRR Contratación Directa\n",
+            "3 1 2026 Sin datos\n",
+        ));
+        let resumen = ExpedienteRepository::upsert_lote_infogov(&pool, filas, OrigenCaptura::Portapapeles).await.unwrap();
+
+        assert_eq!(resumen.actualizados.iter().map(|e| e.fila).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(resumen.creados.len(), 1);
+        assert_eq!(resumen.creados[0].nro_infogov, "2-1-2026");
+        assert_eq!(resumen.errores.len(), 1);
+        assert_eq!(resumen.errores[0].fila, 3);
+        let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM expedientes").fetch_one(&pool).await.unwrap();
+        assert_eq!(total, 2);
+    }
+
+    #[tokio::test]
+    async fn test_alta_infogov_deriva_identificacion_y_defectos() {
+        let pool = pool_en_memoria().await;
+
+        let creado = ExpedienteRepository::upsert_from_infogov(&pool, captura("817619-30-2026", "2026-02-18", "INICIADO")).await.unwrap();
+        assert_eq!(creado.numero, "817619-30");
+        assert_eq!(creado.año, 2026);
+        assert!(matches!(creado.tipo, TipoExpediente::InfoGov));
+        assert_eq!(creado.area_responsable, "InfoGov");
+        assert_eq!(creado.fecha_inicio, creado.fecha_pase.unwrap());
+
+        let mut settings = SettingsService::cargar(&pool).await.unwrap();
+        settings.infogov = DefectosInfoGov {
+            fecha_inicio: FechaInicioInfoGov::Hoy,
+            area_responsable: "Compras".to_string(),
+            prioridad: Prioridad::Alta,
+        };
+        SettingsService::guardar(&pool, &settings).await.unwrap();
+
+        let creado = ExpedienteRepository::upsert_from_infogov(&pool, captura("12-30-2026", "2026-02-18", "Contratación Directa")).await.unwrap();
+        assert!(matches!(creado.tipo, TipoExpediente::Pago));
+        assert_eq!(creado.area_responsable, "Compras");
+        assert!(matches!(creado.prioridad, Prioridad::Alta));
+        assert!(creado.fecha_inicio > creado.fecha_pase.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_expedientes_por_zona() {
+        let pool = pool_en_memoria().await;
+        ExpedienteRepository::upsert_from_infogov(&pool, captura("1-1-2026", "2026-01-10", "INICIADO")).await.unwrap();
+
+        // Otra jefatura: no ve los expedientes de la primera y la misma captura crea el suyo
+        let tunuyan = ZonaRepository::create(&pool, CreateZona {
+            codigo: "tunuyan superior".to_string(),
+            nombre: "Tunuyán Superior".to_string(),
+        })
+        .await
+        .unwrap();
+        assert_eq!(tunuyan.codigo, "TUNUYAN_SUPERIOR");
+        let mut settings = SettingsService::cargar(&pool).await.unwrap();
+        settings.zona.id = tunuyan.id.clone();
+        SettingsService::guardar(&pool, &settings).await.unwrap();
+
+        assert!(ExpedienteRepository::get_all(&pool, &tunuyan.id).await.unwrap().is_empty());
+        let creado = ExpedienteRepository::upsert_from_infogov(&pool, captura("1-1-2026", "2026-01-12", "INICIADO")).await.unwrap();
+
+        let propios = ExpedienteRepository::filtrar(&pool, &tunuyan.id, &ExpedienteFilter::default()).await.unwrap();
+        assert_eq!(propios.total, 1);
+        assert_eq!(propios.items[0].id, creado.id);
+        let ajenos = ExpedienteRepository::search(&pool, ZONA_PREDETERMINADA, "1-1-2026").await.unwrap();
+        assert_eq!(ajenos.len(), 1);
+        assert_ne!(ajenos[0].id, creado.id);
+
+        // Por ID tampoco se alcanza un expediente de otra zona
+        assert!(ExpedienteRepository::get_by_id(&pool, &tunuyan.id, &ajenos[0].id).await.is_err());
+        assert!(ExpedienteRepository::delete(&pool, &tunuyan.id, &ajenos[0].id).await.is_err());
+        assert!(ExpedienteRepository::get_by_id(&pool, ZONA_PREDETERMINADA, &ajenos[0].id).await.is_ok());
+    }
 }
@@ -1,6 +1,22 @@
 // Módulo de repositorios
 // Contiene la lógica de acceso a datos para cada entidad
 
+pub mod agente_repository;
+pub mod captura_repository;
+pub mod configuracion_repository;
+pub mod estado_infogov_repository;
 pub mod expediente_repository;
+pub mod movimiento_repository;
+pub mod ticket_repository;
+pub mod vehiculo_repository;
+pub mod zona_repository;
 
+pub use agente_repository::AgenteRepository;
+pub use captura_repository::CapturaRepository;
+pub use configuracion_repository::ConfiguracionRepository;
+pub use estado_infogov_repository::EstadoInfoGovRepository;
 pub use expediente_repository::ExpedienteRepository;
+pub use movimiento_repository::MovimientoRepository;
+pub use ticket_repository::TicketRepository;
+pub use vehiculo_repository::VehiculoRepository;
+pub use zona_repository::ZonaRepository;
@@ -0,0 +1,193 @@
+// Repositorio de Movimientos de Expedientes
+// Registra cada pase de un expediente entre oficinas (historial inmutable)
+
+use sqlx::{Pool, Sqlite, SqliteConnection};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+use crate::models::movimiento::{ExpedienteMovimiento, CreateMovimiento};
+use crate::error::{Result, AppError};
+
+
+pub struct MovimientoRepository;
+
+impl MovimientoRepository {
+    /// Registrar un nuevo movimiento (pase) de un expediente
+    /// Recibe una conexión para poder participar de la transacción del llamador
+    pub async fn registrar(conn: &mut SqliteConnection, data: CreateMovimiento) -> Result<ExpedienteMovimiento> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now();
+        let capturado_por = usuario_sistema();
+
+        sqlx::query(
+            r#"
+            INSERT INTO expediente_movimientos (
+                id, expediente_id, oficina_origen, oficina_destino, fecha_pase,
+                estado, estado_infogov, origen, capturado_por, observaciones, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(&id)
+        .bind(&data.expediente_id)
+        .bind(&data.oficina_origen)
+        .bind(&data.oficina_destino)
+        .bind(data.fecha_pase)
+        .bind(&data.estado)
+        .bind(&data.estado_infogov)
+        .bind(&data.origen)
+        .bind(&capturado_por)
+        .bind(&data.observaciones)
+        .bind(now)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(ExpedienteMovimiento {
+            id,
+            expediente_id: data.expediente_id,
+            oficina_origen: data.oficina_origen,
+            oficina_destino: data.oficina_destino,
+            fecha_pase: data.fecha_pase,
+            estado: data.estado,
+            estado_infogov: data.estado_infogov,
+            origen: data.origen,
+            capturado_por,
+            observaciones: data.observaciones,
+            created_at: now,
+        })
+    }
+
+    /// Obtener el último movimiento registrado de un expediente
+    pub async fn get_ultimo(conn: &mut SqliteConnection, expediente_id: &str) -> Result<Option<ExpedienteMovimiento>> {
+        let movimiento = sqlx::query_as::<_, ExpedienteMovimiento>(
+            r#"
+            SELECT * FROM expediente_movimientos
+            WHERE expediente_id = ?
+            ORDER BY fecha_pase DESC, created_at DESC
+            LIMIT 1
+            "#
+        )
+        .bind(expediente_id)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(movimiento)
+    }
+
+    /// Obtener la línea de tiempo completa de un expediente (más antiguo primero)
+    pub async fn get_by_expediente(pool: &Pool<Sqlite>, expediente_id: &str) -> Result<Vec<ExpedienteMovimiento>> {
+        let movimientos = sqlx::query_as::<_, ExpedienteMovimiento>(
+            "SELECT * FROM expediente_movimientos WHERE expediente_id = ? ORDER BY fecha_pase ASC, created_at ASC"
+        )
+        .bind(expediente_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(movimientos)
+    }
+
+    /// Obtener el último pase vigente a una fecha dada
+    /// Responde "¿dónde estaba este expediente el día X?"
+    pub async fn get_ubicacion_en_fecha(
+        pool: &Pool<Sqlite>,
+        expediente_id: &str,
+        fecha: DateTime<Utc>,
+    ) -> Result<ExpedienteMovimiento> {
+        let movimiento = sqlx::query_as::<_, ExpedienteMovimiento>(
+            r#"
+            SELECT * FROM expediente_movimientos
+            WHERE expediente_id = ? AND fecha_pase <= ?
+            ORDER BY fecha_pase DESC, created_at DESC
+            LIMIT 1
+            "#
+        )
+        .bind(expediente_id)
+        .bind(fecha)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!(
+            "El expediente {} no registra pases al {}",
+            expediente_id,
+            fecha.format("%d/%m/%Y")
+        )))?;
+
+        Ok(movimiento)
+    }
+}
+
+/// Usuario del sistema operativo que realiza la captura
+fn usuario_sistema() -> Option<String> {
+    std::env::var("USERNAME")
+        .or_else(|_| std::env::var("USER"))
+        .ok()
+        .filter(|u| !u.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_util::pool_en_memoria;
+    use chrono::NaiveDateTime;
+    use crate::models::expediente::{EstadoExpediente, UpdateExpediente};
+    use crate::models::zona::ZONA_PREDETERMINADA;
+    use crate::repositories::ExpedienteRepository;
+    use crate::services::EstadoExpedienteService;
+    use crate::utils::infogov_parser::InfoGovExpediente;
+
+    fn captura(fecha_pase: &str, oficina: &str) -> InfoGovExpediente {
+        InfoGovExpediente {
+            nro_infogov: "817619-30-2026".to_string(),
+            tema: "Reparación embrague".to_string(),
+            nro_gde: format!("EX-2026-01216856-{}-DGIRR", oficina),
+            fecha_pase: fecha_pase.to_string(),
+            estado: "ENPROCESO".to_string(),
+            oficina: Some(oficina.to_string()),
+            resumen: "817619-30-2026 - Reparación embrague".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_historial_desde_infogov() {
+        let pool = pool_en_memoria().await;
+
+        let exp = ExpedienteRepository::upsert_from_infogov(&pool, captura("2026-03-01", "GDEMZA")).await.unwrap();
+        // Capturar el mismo pase dos veces no duplica el historial
+        ExpedienteRepository::upsert_from_infogov(&pool, captura("2026-03-01", "GDEMZA")).await.unwrap();
+        ExpedienteRepository::upsert_from_infogov(&pool, captura("2026-03-10", "DGIRRI")).await.unwrap();
+
+        let historial = MovimientoRepository::get_by_expediente(&pool, &exp.id).await.unwrap();
+        assert_eq!(historial.len(), 2);
+        assert_eq!(historial[1].oficina_origen.as_deref(), Some("GDEMZA"));
+        assert_eq!(historial[1].oficina_destino.as_deref(), Some("DGIRRI"));
+
+        let fecha = NaiveDateTime::parse_from_str("2026-03-03 23:59:59", "%Y-%m-%d %H:%M:%S").unwrap().and_utc();
+        let ubicacion = MovimientoRepository::get_ubicacion_en_fecha(&pool, &exp.id, fecha).await.unwrap();
+        assert_eq!(ubicacion.oficina_destino.as_deref(), Some("GDEMZA"));
+    }
+
+    #[tokio::test]
+    async fn test_historial_guarda_estado_interno() {
+        let pool = pool_en_memoria().await;
+
+        let mut infogov = captura("2026-03-01", "GDEMZA");
+        infogov.estado = "En proceso".to_string();
+        let exp = ExpedienteRepository::upsert_from_infogov(&pool, infogov).await.unwrap();
+
+        // Estado sin equivalencia: el expediente conserva el suyo y el texto de InfoGov queda aparte
+        let mut infogov = captura("2026-03-10", "DGIRRI");
+        infogov.estado = "Contratación Directa".to_string();
+        ExpedienteRepository::upsert_from_infogov(&pool, infogov).await.unwrap();
+
+        EstadoExpedienteService::actualizar(&pool, ZONA_PREDETERMINADA, &exp.id, UpdateExpediente {
+            estado: Some(EstadoExpediente::Finalizado),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+        let historial = MovimientoRepository::get_by_expediente(&pool, &exp.id).await.unwrap();
+        let estados: Vec<_> = historial.iter().map(|m| m.estado.as_deref()).collect();
+        assert_eq!(estados, vec![Some("ENPROCESO"), Some("ENPROCESO"), Some("FINALIZADO")]);
+        let crudos: Vec<_> = historial.iter().map(|m| m.estado_infogov.as_deref()).collect();
+        assert_eq!(crudos, vec![Some("En proceso"), Some("Contratación Directa"), None]);
+    }
+}
@@ -0,0 +1,214 @@
+// Repositorio de Tickets de Combustible
+// Maneja todas las operaciones de base de datos para tickets
+
+use sqlx::{Pool, Sqlite};
+use uuid::Uuid;
+use chrono::Utc;
+
+use crate::models::ticket::{Ticket, CreateTicket, RendimientoVehiculo};
+use crate::error::{Result, AppError};
+
+pub struct TicketRepository;
+
+impl TicketRepository {
+    /// Obtener los tickets de los vehículos de una zona, del más reciente al más antiguo
+    pub async fn get_all(pool: &Pool<Sqlite>, zona_id: &str) -> Result<Vec<Ticket>> {
+        let tickets = sqlx::query_as::<_, Ticket>(
+            r#"
+            SELECT t.* FROM tickets_combustible t
+            JOIN vehiculos v ON v.id = t.vehiculo_id
+            WHERE v.zona_id = ?
+            ORDER BY julianday(t.fecha_carga) DESC
+            "#
+        )
+        .bind(zona_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(tickets)
+    }
+
+    /// Obtener los tickets de un vehículo de la zona
+    pub async fn get_by_vehiculo(pool: &Pool<Sqlite>, zona_id: &str, vehiculo_id: &str) -> Result<Vec<Ticket>> {
+        let tickets = sqlx::query_as::<_, Ticket>(
+            r#"
+            SELECT t.* FROM tickets_combustible t
+            JOIN vehiculos v ON v.id = t.vehiculo_id
+            WHERE t.vehiculo_id = ? AND v.zona_id = ?
+            ORDER BY julianday(t.fecha_carga) DESC
+            "#
+        )
+        .bind(vehiculo_id)
+        .bind(zona_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(tickets)
+    }
+
+    /// Obtener un ticket de un vehículo de la zona por ID
+    pub async fn get_by_id(pool: &Pool<Sqlite>, zona_id: &str, id: &str) -> Result<Ticket> {
+        sqlx::query_as::<_, Ticket>(
+            r#"
+            SELECT t.* FROM tickets_combustible t
+            JOIN vehiculos v ON v.id = t.vehiculo_id
+            WHERE t.id = ? AND v.zona_id = ?
+            "#
+        )
+        .bind(id)
+        .bind(zona_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Ticket con ID {}", id)))
+    }
+
+    /// Registrar una carga de combustible de un vehículo de la zona
+    /// Calcula el monto, toma el kilometraje anterior del último ticket y actualiza el del vehículo
+    pub async fn create(pool: &Pool<Sqlite>, zona_id: &str, data: CreateTicket) -> Result<Ticket> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now();
+        let monto_total = data.litros * data.precio_por_litro;
+
+        let mut tx = pool.begin().await?;
+
+        // Actualizar primero el vehículo: si no es de la zona no se registra nada
+        let result = sqlx::query("UPDATE vehiculos SET kilometraje_actual = ?, updated_at = ? WHERE id = ? AND zona_id = ?")
+            .bind(data.kilometraje)
+            .bind(now)
+            .bind(&data.vehiculo_id)
+            .bind(zona_id)
+            .execute(&mut *tx)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Vehículo con ID {}", data.vehiculo_id)));
+        }
+
+        let kilometraje_anterior: Option<f64> = sqlx::query_scalar(
+            r#"
+            SELECT kilometraje
+            FROM tickets_combustible
+            WHERE vehiculo_id = ?
+            ORDER BY julianday(fecha_carga) DESC
+            LIMIT 1
+            "#
+        )
+        .bind(&data.vehiculo_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO tickets_combustible (
+                id, vehiculo_id, agente_id, fecha_carga, numero_ticket,
+                tipo_combustible, litros, precio_por_litro, monto_total,
+                kilometraje, kilometraje_anterior, estacion_servicio,
+                localidad, observaciones, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(&id)
+        .bind(&data.vehiculo_id)
+        .bind(&data.agente_id)
+        .bind(data.fecha_carga)
+        .bind(&data.numero_ticket)
+        .bind(&data.tipo_combustible)
+        .bind(data.litros)
+        .bind(data.precio_por_litro)
+        .bind(monto_total)
+        .bind(data.kilometraje)
+        .bind(kilometraje_anterior)
+        .bind(&data.estacion_servicio)
+        .bind(&data.localidad)
+        .bind(&data.observaciones)
+        .bind(now)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Self::get_by_id(pool, zona_id, &id).await
+    }
+
+    /// Consumo, gasto y rendimiento acumulados de un vehículo de la zona
+    pub async fn calcular_rendimiento(pool: &Pool<Sqlite>, zona_id: &str, vehiculo_id: &str) -> Result<RendimientoVehiculo> {
+        let patente: String = sqlx::query_scalar("SELECT patente FROM vehiculos WHERE id = ? AND zona_id = ?")
+            .bind(vehiculo_id)
+            .bind(zona_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Vehículo con ID {}", vehiculo_id)))?;
+
+        let (total_litros, total_gastado, cantidad_cargas): (f64, f64, i64) = sqlx::query_as(
+            r#"
+            SELECT
+                COALESCE(SUM(litros), 0.0) as total_litros,
+                COALESCE(SUM(monto_total), 0.0) as total_gastado,
+                COUNT(*) as cantidad_cargas
+            FROM tickets_combustible
+            WHERE vehiculo_id = ?
+            "#
+        )
+        .bind(vehiculo_id)
+        .fetch_one(pool)
+        .await?;
+
+        let (km_inicial, km_final): (Option<f64>, Option<f64>) = sqlx::query_as(
+            r#"
+            SELECT
+                (SELECT kilometraje FROM tickets_combustible WHERE vehiculo_id = ?1 ORDER BY julianday(fecha_carga) ASC LIMIT 1) as km_inicial,
+                (SELECT kilometraje FROM tickets_combustible WHERE vehiculo_id = ?1 ORDER BY julianday(fecha_carga) DESC LIMIT 1) as km_final
+            "#
+        )
+        .bind(vehiculo_id)
+        .fetch_one(pool)
+        .await?;
+
+        let kilometros_recorridos = match (km_inicial, km_final) {
+            (Some(inicial), Some(final_km)) => final_km - inicial,
+            _ => 0.0,
+        };
+
+        let rendimiento_promedio = if total_litros > 0.0 {
+            kilometros_recorridos / total_litros
+        } else {
+            0.0
+        };
+
+        let costo_por_km = if kilometros_recorridos > 0.0 {
+            total_gastado / kilometros_recorridos
+        } else {
+            0.0
+        };
+
+        Ok(RendimientoVehiculo {
+            vehiculo_id: vehiculo_id.to_string(),
+            patente,
+            total_litros,
+            total_gastado,
+            kilometros_recorridos,
+            rendimiento_promedio,
+            costo_por_km,
+            cantidad_cargas: cantidad_cargas as i32,
+        })
+    }
+
+    /// Eliminar un ticket de un vehículo de la zona
+    pub async fn delete(pool: &Pool<Sqlite>, zona_id: &str, id: &str) -> Result<()> {
+        let result = sqlx::query(
+            "DELETE FROM tickets_combustible WHERE id = ? AND vehiculo_id IN (SELECT id FROM vehiculos WHERE zona_id = ?)"
+        )
+        .bind(id)
+        .bind(zona_id)
+        .execute(pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Ticket con ID {}", id)));
+        }
+
+        Ok(())
+    }
+}
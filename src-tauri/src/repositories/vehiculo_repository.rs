@@ -0,0 +1,105 @@
+// Repositorio de Vehículos
+// Maneja todas las operaciones de base de datos para vehículos
+
+use sqlx::{Pool, Sqlite};
+use uuid::Uuid;
+use chrono::Utc;
+
+use crate::models::vehiculo::{Vehiculo, CreateVehiculo};
+use crate::error::{Result, AppError};
+
+pub struct VehiculoRepository;
+
+impl VehiculoRepository {
+    /// Obtener los vehículos activos de una zona ordenados por patente
+    pub async fn get_activos(pool: &Pool<Sqlite>, zona_id: &str) -> Result<Vec<Vehiculo>> {
+        let vehiculos = sqlx::query_as::<_, Vehiculo>(
+            "SELECT * FROM vehiculos WHERE activo = 1 AND zona_id = ? ORDER BY patente ASC"
+        )
+        .bind(zona_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(vehiculos)
+    }
+
+    /// Obtener un vehículo de la zona por ID
+    pub async fn get_by_id(pool: &Pool<Sqlite>, zona_id: &str, id: &str) -> Result<Vehiculo> {
+        sqlx::query_as::<_, Vehiculo>("SELECT * FROM vehiculos WHERE id = ? AND zona_id = ?")
+            .bind(id)
+            .bind(zona_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Vehículo con ID {}", id)))
+    }
+
+    /// Crear un nuevo vehículo en una zona
+    pub async fn create(pool: &Pool<Sqlite>, zona_id: &str, data: CreateVehiculo) -> Result<Vehiculo> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now();
+
+        sqlx::query(
+            r#"
+            INSERT INTO vehiculos (
+                id, patente, marca, modelo, año, tipo,
+                numero_motor, numero_chasis, color,
+                activo, kilometraje_actual, capacidad_tanque,
+                area_asignada, zona_id, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, 1, ?, ?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(&id)
+        .bind(&data.patente)
+        .bind(&data.marca)
+        .bind(&data.modelo)
+        .bind(data.año)
+        .bind(&data.tipo)
+        .bind(&data.numero_motor)
+        .bind(&data.numero_chasis)
+        .bind(&data.color)
+        .bind(data.kilometraje_actual)
+        .bind(data.capacidad_tanque)
+        .bind(&data.area_asignada)
+        .bind(zona_id)
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        Self::get_by_id(pool, zona_id, &id).await
+    }
+
+    /// Actualizar el kilometraje de un vehículo de la zona
+    pub async fn update_kilometraje(pool: &Pool<Sqlite>, zona_id: &str, id: &str, kilometraje: f64) -> Result<()> {
+        let result = sqlx::query("UPDATE vehiculos SET kilometraje_actual = ?, updated_at = ? WHERE id = ? AND zona_id = ?")
+            .bind(kilometraje)
+            .bind(Utc::now())
+            .bind(id)
+            .bind(zona_id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Vehículo con ID {}", id)));
+        }
+
+        Ok(())
+    }
+
+    /// Baja lógica de un vehículo de la zona
+    pub async fn delete(pool: &Pool<Sqlite>, zona_id: &str, id: &str) -> Result<()> {
+        let result = sqlx::query("UPDATE vehiculos SET activo = 0, updated_at = ? WHERE id = ? AND zona_id = ?")
+            .bind(Utc::now())
+            .bind(id)
+            .bind(zona_id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Vehículo con ID {}", id)));
+        }
+
+        Ok(())
+    }
+}
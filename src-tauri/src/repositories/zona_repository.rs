@@ -0,0 +1,74 @@
+// Repositorio de Zonas de riego
+// Las zonas se sincronizan con el servidor: cada instalación ve todas pero trabaja con una
+
+use chrono::Utc;
+use sqlx::{Pool, Sqlite, SqliteConnection};
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+use crate::models::zona::{CreateZona, Zona};
+
+pub struct ZonaRepository;
+
+impl ZonaRepository {
+    /// Obtener las zonas activas ordenadas por nombre
+    pub async fn get_activas(pool: &Pool<Sqlite>) -> Result<Vec<Zona>> {
+        let zonas = sqlx::query_as::<_, Zona>(
+            "SELECT * FROM zonas WHERE activa = 1 ORDER BY nombre ASC"
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(zonas)
+    }
+
+    pub async fn get_by_id_en(conn: &mut SqliteConnection, id: &str) -> Result<Zona> {
+        sqlx::query_as::<_, Zona>("SELECT * FROM zonas WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&mut *conn)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Zona con ID {}", id)))
+    }
+
+    /// Obtener una zona por ID
+    pub async fn get_by_id(pool: &Pool<Sqlite>, id: &str) -> Result<Zona> {
+        let mut conn = pool.acquire().await?;
+        Self::get_by_id_en(&mut conn, id).await
+    }
+
+    /// Crear una zona (el código se guarda en mayúsculas)
+    pub async fn create(pool: &Pool<Sqlite>, data: CreateZona) -> Result<Zona> {
+        let codigo = data.codigo.trim().to_uppercase().replace(' ', "_");
+        let nombre = data.nombre.trim();
+        if codigo.is_empty() || nombre.is_empty() {
+            return Err(AppError::Validation("La zona necesita código y nombre".to_string()));
+        }
+
+        let existe: Option<String> = sqlx::query_scalar("SELECT id FROM zonas WHERE codigo = ?")
+            .bind(&codigo)
+            .fetch_optional(pool)
+            .await?;
+        if existe.is_some() {
+            return Err(AppError::Validation(format!("Ya existe una zona con código {}", codigo)));
+        }
+
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now();
+
+        sqlx::query(
+            r#"
+            INSERT INTO zonas (id, codigo, nombre, activa, created_at, updated_at)
+            VALUES (?, ?, ?, 1, ?, ?)
+            "#
+        )
+        .bind(&id)
+        .bind(&codigo)
+        .bind(nombre)
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        Self::get_by_id(pool, &id).await
+    }
+}
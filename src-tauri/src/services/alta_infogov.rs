@@ -0,0 +1,121 @@
+use crate::models::estado_infogov::normalizar_estado_infogov;
+use crate::models::expediente::{CategoriaGasto, TipoExpediente};
+use crate::models::NumeroGde;
+use crate::services::GastoClassifier;
+use crate::utils::infogov_parser::InfoGovExpediente;
+
+/// Estados de InfoGov que indican un trámite de compra o de pago
+const ESTADOS_DE_PAGO: &[&str] = &[
+    "contratacion",
+    "licitacion",
+    "concurso de precios",
+    "compra",
+    "pago",
+    "liquidacion",
+    "factura",
+];
+
+/// Identificación de un expediente nuevo creado desde InfoGov
+#[derive(Debug, Clone)]
+pub struct IdentificacionInfoGov {
+    pub numero: String,
+    pub año: i32,
+    pub tipo: TipoExpediente,
+    pub categoria_gasto: Option<CategoriaGasto>,
+}
+
+/// Deriva número, año y tipo de los expedientes que se dan de alta desde una captura de InfoGov
+pub struct AltaInfoGovService;
+
+impl AltaInfoGovService {
+    /// - numero/año: del nro_infogov (817619-30-2026 → "817619-30", 2026); si no sirve, del nro_gde
+    /// - tipo: PAGO si el estado es de compra/pago o el tema es un gasto reconocido;
+    ///   si no, INFOGOV o GDE según de qué número se tomó la identificación
+    pub fn identificar(exp: &InfoGovExpediente) -> IdentificacionInfoGov {
+        let gde = NumeroGde::buscar(&exp.nro_gde);
+
+        let (numero, año, tipo) = match (numero_infogov(&exp.nro_infogov), &gde) {
+            (Some((numero, año)), _) => (numero, año, TipoExpediente::InfoGov),
+            (None, Some(gde)) => (format!("{:08}", gde.numero), i32::from(gde.año), TipoExpediente::Gde),
+            (None, None) => (exp.nro_infogov.clone(), 0, TipoExpediente::InfoGov),
+        };
+
+        let categoria_gasto = GastoClassifier::classify(&exp.tema, None, None).map(|c| c.categoria);
+        let estado = normalizar_estado_infogov(&exp.estado);
+        let estado_de_pago = ESTADOS_DE_PAGO.iter().any(|e| estado.contains(e));
+
+        let tipo = if estado_de_pago || categoria_gasto.is_some() {
+            TipoExpediente::Pago
+        } else {
+            tipo
+        };
+
+        IdentificacionInfoGov {
+            numero,
+            año,
+            tipo,
+            categoria_gasto,
+        }
+    }
+}
+
+/// Número y año de un nro_infogov NÚMERO-CÓDIGO-AÑO (el año debe ser plausible)
+fn numero_infogov(nro_infogov: &str) -> Option<(String, i32)> {
+    let partes: Vec<&str> = nro_infogov.split('-').map(str::trim).collect();
+    let [numero, codigo, año] = partes.as_slice() else {
+        return None;
+    };
+
+    let año: i32 = año.parse().ok().filter(|a| (2000..=2099).contains(a))?;
+    if numero.is_empty() || codigo.is_empty() {
+        return None;
+    }
+
+    Some((format!("{}-{}", numero, codigo), año))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_util;
+
+    fn captura(nro_infogov: &str, tema: &str, estado: &str) -> InfoGovExpediente {
+        InfoGovExpediente {
+            tema: tema.to_string(),
+            ..test_util::captura(nro_infogov, "2026-02-18", estado)
+        }
+    }
+
+    #[test]
+    fn test_numero_y_año_del_nro_infogov() {
+        let id = AltaInfoGovService::identificar(&captura("817619-30-2026", "Informe de gestión", "Iniciado"));
+        assert_eq!(id.numero, "817619-30");
+        assert_eq!(id.año, 2026);
+        assert!(matches!(id.tipo, TipoExpediente::InfoGov));
+    }
+
+    #[test]
+    fn test_numero_y_año_del_nro_gde() {
+        // Año distinto al de la fecha de pase: tiene que salir del nro_gde
+        let id = AltaInfoGovService::identificar(&InfoGovExpediente {
+            nro_gde: "EX-2025-01216856-GDEMZA-DGIRR".to_string(),
+            ..captura("817619-30-26", "Informe de gestión", "")
+        });
+        assert_eq!(id.numero, "01216856");
+        assert_eq!(id.año, 2025);
+        assert!(matches!(id.tipo, TipoExpediente::Gde));
+    }
+
+    #[test]
+    fn test_tipo_pago() {
+        // Por el estado de InfoGov
+        let id = AltaInfoGovService::identificar(&captura("1-30-2026", "Adquisición de mobiliario", "Contratación Directa"));
+        assert!(matches!(id.tipo, TipoExpediente::Pago));
+        assert!(id.categoria_gasto.is_none());
+
+        // Por el tema (gasto reconocido por el clasificador)
+        let id = AltaInfoGovService::identificar(&captura("2-30-2026", "Compra de nafta YPF", "Iniciado"));
+        assert!(matches!(id.tipo, TipoExpediente::Pago));
+        assert!(matches!(id.categoria_gasto, Some(CategoriaGasto::Combustible)));
+    }
+}
@@ -0,0 +1,190 @@
+use chrono::Utc;
+use sqlx::{Pool, Sqlite};
+
+use crate::error::{AppError, Result};
+use crate::models::expediente::{EstadoExpediente, Expediente, UpdateExpediente};
+use crate::repositories::ExpedienteRepository;
+
+/// Máquina de estados del ciclo de vida de un expediente
+/// Centraliza qué cambios de estado son válidos y qué efectos tienen
+pub struct EstadoExpedienteService;
+
+impl EstadoExpedienteService {
+    /// Tabla de transiciones permitidas desde cada estado
+    /// Mantener el mismo estado siempre está permitido
+    pub fn transiciones_permitidas(desde: &EstadoExpediente) -> &'static [EstadoExpediente] {
+        use EstadoExpediente::*;
+
+        match desde {
+            Iniciado => &[EnProceso, Observado, Archivado],
+            EnProceso => &[EnRevision, Observado, Finalizado],
+            EnRevision => &[EnProceso, Observado, Finalizado],
+            Observado => &[EnProceso, EnRevision],
+            Finalizado => &[Archivado],
+            Archivado => &[],
+        }
+    }
+
+    /// Indica si el expediente puede pasar de `desde` a `hacia`
+    pub fn puede_transicionar(desde: &EstadoExpediente, hacia: &EstadoExpediente) -> bool {
+        desde == hacia || Self::transiciones_permitidas(desde).contains(hacia)
+    }
+
+    /// Valida el cambio de estado pedido y completa los campos que exige el nuevo estado
+    /// - FINALIZADO: registra fecha_finalizacion si no se informó
+    /// - OBSERVADO: exige observaciones no vacías
+    pub fn validar_transicion(actual: &EstadoExpediente, data: &mut UpdateExpediente) -> Result<()> {
+        let nuevo = match &data.estado {
+            Some(estado) if estado != actual => estado.clone(),
+            _ => return Ok(()),
+        };
+
+        if !Self::puede_transicionar(actual, &nuevo) {
+            return Err(AppError::Validation(format!(
+                "No se puede pasar el expediente de {} a {}",
+                nombre_estado(actual),
+                nombre_estado(&nuevo)
+            )));
+        }
+
+        match nuevo {
+            EstadoExpediente::Finalizado => {
+                data.fecha_finalizacion.get_or_insert_with(Utc::now);
+            }
+            EstadoExpediente::Observado => {
+                let tiene_observaciones = data
+                    .observaciones
+                    .as_deref()
+                    .map(|obs| !obs.trim().is_empty())
+                    .unwrap_or(false);
+
+                if !tiene_observaciones {
+                    return Err(AppError::Validation(
+                        "Para marcar el expediente como OBSERVADO se deben cargar las observaciones".to_string(),
+                    ));
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Cambio de estado informado por InfoGov (captura o mapeo nuevo) con las mismas reglas que el manual
+    /// Devuelve el estado a escribir, con fecha_finalizacion al finalizar, o None si se conserva el actual
+    pub fn cambio_desde_infogov(actual: &Expediente, nuevo: EstadoExpediente) -> Option<UpdateExpediente> {
+        if actual.estado == nuevo {
+            return None;
+        }
+
+        let mut data = UpdateExpediente {
+            estado: Some(nuevo),
+            observaciones: actual.observaciones.clone(),
+            fecha_finalizacion: actual.fecha_finalizacion,
+            ..Default::default()
+        };
+        Self::validar_transicion(&actual.estado, &mut data).ok()?;
+        Some(data)
+    }
+
+    /// Actualiza un expediente aplicando las reglas de la máquina de estados
+    /// La lectura del estado y la escritura van en la misma transacción: si una captura de InfoGov
+    /// cambia el expediente en el medio, SQLite rechaza la escritura en lugar de saltear la validación
+    pub async fn actualizar(pool: &Pool<Sqlite>, zona_id: &str, id: &str, mut data: UpdateExpediente) -> Result<Expediente> {
+        let mut tx = pool.begin().await?;
+        let actual = ExpedienteRepository::get_by_id_en(&mut tx, zona_id, id).await?;
+
+        Self::validar_transicion(&actual.estado, &mut data)?;
+
+        ExpedienteRepository::update_en(&mut tx, zona_id, id, data).await?;
+        tx.commit().await?;
+
+        ExpedienteRepository::get_by_id(pool, zona_id, id).await
+    }
+}
+
+/// Nombre del estado tal como se guarda en la base de datos
+fn nombre_estado(estado: &EstadoExpediente) -> String {
+    format!("{:?}", estado).to_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use EstadoExpediente::*;
+
+    #[test]
+    fn test_tabla_de_transiciones() {
+        let casos = [
+            (Iniciado, EnProceso, true),
+            (Iniciado, Observado, true),
+            (Iniciado, Archivado, true),
+            (Iniciado, Finalizado, false),
+            (EnProceso, EnRevision, true),
+            (EnProceso, Finalizado, true),
+            (EnProceso, Iniciado, false),
+            (EnRevision, EnProceso, true),
+            (EnRevision, Finalizado, true),
+            (Observado, EnProceso, true),
+            (Observado, Finalizado, false),
+            (Finalizado, Archivado, true),
+            (Finalizado, EnProceso, false),
+            (Archivado, Iniciado, false),
+            (Archivado, EnProceso, false),
+            (Archivado, Archivado, true),
+        ];
+
+        for (desde, hacia, esperado) in casos {
+            assert_eq!(
+                EstadoExpedienteService::puede_transicionar(&desde, &hacia),
+                esperado,
+                "{:?} -> {:?}",
+                desde,
+                hacia
+            );
+        }
+    }
+
+    #[test]
+    fn test_finalizado_registra_fecha() {
+        let mut data = UpdateExpediente {
+            estado: Some(Finalizado),
+            ..Default::default()
+        };
+
+        EstadoExpedienteService::validar_transicion(&EnRevision, &mut data).unwrap();
+        assert!(data.fecha_finalizacion.is_some());
+    }
+
+    #[test]
+    fn test_observado_requiere_observaciones() {
+        let mut sin_obs = UpdateExpediente {
+            estado: Some(Observado),
+            observaciones: Some("   ".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            EstadoExpedienteService::validar_transicion(&EnProceso, &mut sin_obs),
+            Err(AppError::Validation(_))
+        ));
+
+        let mut con_obs = UpdateExpediente {
+            estado: Some(Observado),
+            observaciones: Some("Falta factura original".to_string()),
+            ..Default::default()
+        };
+        assert!(EstadoExpedienteService::validar_transicion(&EnProceso, &mut con_obs).is_ok());
+    }
+
+    #[test]
+    fn test_transicion_invalida() {
+        let mut data = UpdateExpediente {
+            estado: Some(Iniciado),
+            ..Default::default()
+        };
+        assert!(matches!(
+            EstadoExpedienteService::validar_transicion(&Archivado, &mut data),
+            Err(AppError::Validation(_))
+        ));
+    }
+}
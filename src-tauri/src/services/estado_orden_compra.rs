@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+
+use crate::error::{AppError, Result};
+use crate::models::orden_compra::{CreateEntrega, EstadoOrdenCompra, OrdenCompraRenglon};
+
+/// Tolerancia al comparar cantidades (se guardan como REAL / DECIMAL(10, 2))
+const TOLERANCIA_CANTIDAD: f64 = 0.005;
+
+/// Máquina de estados del ciclo de vida de una orden de compra
+/// Las consultas viven en los comandos (PostgreSQL con respaldo en SQLite); acá sólo las reglas
+pub struct EstadoOrdenCompraService;
+
+impl EstadoOrdenCompraService {
+    /// Tabla de transiciones permitidas desde cada estado
+    /// Mantener el mismo estado siempre está permitido
+    pub fn transiciones_permitidas(desde: &EstadoOrdenCompra) -> &'static [EstadoOrdenCompra] {
+        use EstadoOrdenCompra::*;
+
+        match desde {
+            Borrador => &[Emitida, Anulada],
+            Emitida => &[Anulada, RecibidaParcial, Recibida],
+            RecibidaParcial => &[Recibida],
+            Anulada => &[],
+            Recibida => &[],
+        }
+    }
+
+    /// Indica si la OC puede pasar de `desde` a `hacia`
+    pub fn puede_transicionar(desde: &EstadoOrdenCompra, hacia: &EstadoOrdenCompra) -> bool {
+        desde == hacia || Self::transiciones_permitidas(desde).contains(hacia)
+    }
+
+    /// Sólo los borradores se modifican: una OC emitida se anula y se emite otra
+    pub fn validar_edicion(actual: &EstadoOrdenCompra) -> Result<()> {
+        if *actual != EstadoOrdenCompra::Borrador {
+            return Err(AppError::Validation(format!(
+                "La OC está {}: sólo se pueden modificar los borradores (anule la OC y emita una nueva)",
+                actual.as_str()
+            )));
+        }
+        Ok(())
+    }
+
+    /// BORRADOR -> EMITIDA, con al menos un renglón
+    pub fn validar_emision(actual: &EstadoOrdenCompra, cantidad_renglones: usize) -> Result<()> {
+        Self::validar_cambio(actual, &EstadoOrdenCompra::Emitida)?;
+        if *actual == EstadoOrdenCompra::Emitida {
+            return Err(AppError::Validation("La OC ya fue emitida".to_string()));
+        }
+        if cantidad_renglones == 0 {
+            return Err(AppError::Validation("No se puede emitir una OC sin renglones".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Valida la anulación y devuelve el motivo sin espacios sobrantes (obligatorio)
+    pub fn validar_anulacion(actual: &EstadoOrdenCompra, motivo: &str) -> Result<String> {
+        if *actual == EstadoOrdenCompra::Anulada {
+            return Err(AppError::Validation("La OC ya está anulada".to_string()));
+        }
+        Self::validar_cambio(actual, &EstadoOrdenCompra::Anulada)?;
+
+        let motivo = motivo.trim();
+        if motivo.is_empty() {
+            return Err(AppError::Validation("Para anular la OC se debe indicar el motivo".to_string()));
+        }
+        Ok(motivo.to_string())
+    }
+
+    /// Valida una entrega contra lo pedido y lo ya recibido (por id de renglón) y devuelve el nuevo estado:
+    /// RECIBIDA cuando cada renglón quedó completo, si no RECIBIDA_PARCIAL
+    pub fn estado_tras_entrega(
+        actual: &EstadoOrdenCompra,
+        renglones: &[OrdenCompraRenglon],
+        recibido: &HashMap<String, f64>,
+        entrega: &CreateEntrega,
+    ) -> Result<EstadoOrdenCompra> {
+        if !matches!(actual, EstadoOrdenCompra::Emitida | EstadoOrdenCompra::RecibidaParcial) {
+            return Err(AppError::Validation(format!(
+                "No se pueden registrar entregas de una OC {}",
+                actual.as_str()
+            )));
+        }
+        if entrega.renglones.is_empty() {
+            return Err(AppError::Validation("La entrega no tiene renglones".to_string()));
+        }
+
+        let mut total = recibido.clone();
+        for item in &entrega.renglones {
+            let renglon = renglones
+                .iter()
+                .find(|r| r.id == item.renglon_id)
+                .ok_or_else(|| AppError::Validation(format!("El renglón {} no es de esta OC", item.renglon_id)))?;
+
+            if item.cantidad <= 0.0 {
+                return Err(AppError::Validation(format!(
+                    "La cantidad entregada del renglón {} debe ser mayor que cero",
+                    renglon.renglon_nro
+                )));
+            }
+
+            let acumulado = total.entry(renglon.id.clone()).or_insert(0.0);
+            *acumulado += item.cantidad;
+            if *acumulado > renglon.cantidad + TOLERANCIA_CANTIDAD {
+                return Err(AppError::Validation(format!(
+                    "El renglón {} pide {} y se recibirían {}",
+                    renglon.renglon_nro, renglon.cantidad, acumulado
+                )));
+            }
+        }
+
+        let completa = renglones
+            .iter()
+            .all(|r| total.get(&r.id).copied().unwrap_or(0.0) >= r.cantidad - TOLERANCIA_CANTIDAD);
+        Ok(if completa {
+            EstadoOrdenCompra::Recibida
+        } else {
+            EstadoOrdenCompra::RecibidaParcial
+        })
+    }
+
+    fn validar_cambio(actual: &EstadoOrdenCompra, nuevo: &EstadoOrdenCompra) -> Result<()> {
+        if !Self::puede_transicionar(actual, nuevo) {
+            return Err(AppError::Validation(format!(
+                "No se puede pasar la OC de {} a {}",
+                actual.as_str(),
+                nuevo.as_str()
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::orden_compra::CreateEntregaRenglon;
+    use EstadoOrdenCompra::*;
+
+    fn renglon(id: &str, nro: i32, cantidad: f64) -> OrdenCompraRenglon {
+        OrdenCompraRenglon {
+            id: id.to_string(),
+            oc_id: "oc".to_string(),
+            renglon_nro: nro,
+            cantidad,
+            detalle: "Caño PVC 110 mm".to_string(),
+            marca: None,
+            valor_unitario: 100.0,
+        }
+    }
+
+    fn entrega(items: &[(&str, f64)]) -> CreateEntrega {
+        CreateEntrega {
+            fecha: None,
+            remito: Some("0001-00001234".to_string()),
+            observaciones: None,
+            renglones: items
+                .iter()
+                .map(|(id, cantidad)| CreateEntregaRenglon { renglon_id: id.to_string(), cantidad: *cantidad })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_tabla_de_transiciones() {
+        let casos = [
+            (Borrador, Emitida, true),
+            (Borrador, Anulada, true),
+            (Borrador, Recibida, false),
+            (Emitida, Anulada, true),
+            (Emitida, RecibidaParcial, true),
+            (Emitida, Recibida, true),
+            (Emitida, Borrador, false),
+            (RecibidaParcial, Recibida, true),
+            (RecibidaParcial, Anulada, false),
+            (Anulada, Emitida, false),
+            (Anulada, Borrador, false),
+            (Recibida, Anulada, false),
+            (Recibida, Recibida, true),
+        ];
+
+        for (desde, hacia, esperado) in casos {
+            assert_eq!(
+                EstadoOrdenCompraService::puede_transicionar(&desde, &hacia),
+                esperado,
+                "{:?} -> {:?}",
+                desde,
+                hacia
+            );
+        }
+    }
+
+    #[test]
+    fn test_edicion_y_emision() {
+        assert!(EstadoOrdenCompraService::validar_edicion(&Borrador).is_ok());
+        assert!(matches!(EstadoOrdenCompraService::validar_edicion(&Emitida), Err(AppError::Validation(_))));
+
+        assert!(EstadoOrdenCompraService::validar_emision(&Borrador, 2).is_ok());
+        assert!(EstadoOrdenCompraService::validar_emision(&Borrador, 0).is_err());
+        assert!(EstadoOrdenCompraService::validar_emision(&Emitida, 2).is_err());
+        assert!(EstadoOrdenCompraService::validar_emision(&Anulada, 2).is_err());
+    }
+
+    #[test]
+    fn test_anulacion_requiere_motivo() {
+        assert!(matches!(
+            EstadoOrdenCompraService::validar_anulacion(&Emitida, "   "),
+            Err(AppError::Validation(_))
+        ));
+        assert_eq!(
+            EstadoOrdenCompraService::validar_anulacion(&Emitida, " Proveedor sin stock ").unwrap(),
+            "Proveedor sin stock"
+        );
+        assert!(EstadoOrdenCompraService::validar_anulacion(&Borrador, "Cargada por error").is_ok());
+        assert!(EstadoOrdenCompraService::validar_anulacion(&Anulada, "Otra vez").is_err());
+        assert!(EstadoOrdenCompraService::validar_anulacion(&Recibida, "Tarde").is_err());
+    }
+
+    #[test]
+    fn test_entregas_parciales_y_completa() {
+        let renglones = vec![renglon("r1", 1, 10.0), renglon("r2", 2, 4.0)];
+        let mut recibido = HashMap::new();
+
+        let estado =
+            EstadoOrdenCompraService::estado_tras_entrega(&Emitida, &renglones, &recibido, &entrega(&[("r1", 6.0)]))
+                .unwrap();
+        assert_eq!(estado, RecibidaParcial);
+
+        recibido.insert("r1".to_string(), 6.0);
+        let estado = EstadoOrdenCompraService::estado_tras_entrega(
+            &RecibidaParcial,
+            &renglones,
+            &recibido,
+            &entrega(&[("r1", 4.0), ("r2", 4.0)]),
+        )
+        .unwrap();
+        assert_eq!(estado, Recibida);
+    }
+
+    #[test]
+    fn test_entrega_invalida() {
+        let renglones = vec![renglon("r1", 1, 10.0)];
+        let recibido = HashMap::from([("r1".to_string(), 8.0)]);
+        let casos = [
+            (Emitida, entrega(&[("r1", 3.0)])),
+            (Emitida, entrega(&[("r1", 1.5), ("r1", 1.5)])),
+            (Emitida, entrega(&[("r1", 0.0)])),
+            (Emitida, entrega(&[("otro", 1.0)])),
+            (Emitida, entrega(&[])),
+            (Borrador, entrega(&[("r1", 1.0)])),
+            (Anulada, entrega(&[("r1", 1.0)])),
+        ];
+
+        for (actual, entrega) in casos {
+            assert!(
+                matches!(
+                    EstadoOrdenCompraService::estado_tras_entrega(&actual, &renglones, &recibido, &entrega),
+                    Err(AppError::Validation(_))
+                ),
+                "{:?} {:?}",
+                actual,
+                entrega.renglones
+            );
+        }
+    }
+}
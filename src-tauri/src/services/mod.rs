@@ -1,3 +1,11 @@
+pub mod alta_infogov;
 pub mod classifier;
+pub mod estado_expediente;
+pub mod estado_orden_compra;
+pub mod settings;
 
+pub use alta_infogov::AltaInfoGovService;
 pub use classifier::{GastoClassifier, ExpenseClassification};
+pub use estado_expediente::EstadoExpedienteService;
+pub use estado_orden_compra::EstadoOrdenCompraService;
+pub use settings::SettingsService;
@@ -0,0 +1,596 @@
+// Conflictos de sincronización: la misma fila se editó localmente y en el servidor
+// Cada fila guarda en sync_version la versión del servidor sobre la que se basa;
+// si al enviarla el servidor tiene otra versión, el cambio no se aplica y se registra acá
+// También se registran las filas que repiten el valor único de otra (el número de OC)
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{FromRow, SqlitePool};
+use uuid::Uuid;
+
+use crate::commands::ordenes_compra::TOMAR_NUMERO_SQLITE;
+use crate::error::{AppError, Result};
+use crate::models::orden_compra::formatear_numero_oc;
+use crate::sync::push::columnas_locales;
+use crate::sync::tablas::{tabla_sincronizada, valor_a_local};
+
+/// Columnas de control que no se comparan ni se copian entre versiones
+const COLUMNAS_CONTROL: &[&str] = &["id", "synced_at", "sync_version"];
+
+/// Fila de sync_conflicts tal como está en SQLite
+#[derive(Debug, Clone, FromRow)]
+struct FilaConflicto {
+    id: String,
+    tabla: String,
+    registro_id: String,
+    version_local: i64,
+    version_remota: i64,
+    datos_locales: String,
+    datos_remotos: Option<String>,
+    detectado_at: DateTime<Utc>,
+}
+
+/// Conflicto pendiente, con ambas versiones de la fila para mostrar en la interfaz
+#[derive(Debug, Clone, Serialize)]
+pub struct ConflictoSincronizacion {
+    pub id: String,
+    pub tabla: String,
+    pub registro_id: String,
+    pub version_local: i64,
+    pub version_remota: i64,
+    pub datos_locales: Value,
+    /// None si la fila ya no existe en el servidor
+    pub datos_remotos: Option<Value>,
+    /// Columnas cuyo valor difiere entre ambas versiones
+    pub campos_en_conflicto: Vec<String>,
+    /// La fila del servidor es otra que ya tiene el valor único de la local (mismo número de OC)
+    pub valor_repetido: bool,
+    pub detectado_at: DateTime<Utc>,
+}
+
+/// Datos para registrar un conflicto
+#[derive(Debug, Clone)]
+pub struct CreateConflicto {
+    pub tabla: String,
+    pub registro_id: String,
+    pub version_local: i64,
+    pub version_remota: i64,
+    pub datos_locales: Value,
+    pub datos_remotos: Option<Value>,
+}
+
+/// Origen de un campo al combinar versiones
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OrigenCampo {
+    Local,
+    Remoto,
+}
+
+/// Cómo resolver un conflicto
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "tipo", rename_all = "snake_case")]
+pub enum ResolucionConflicto {
+    /// Se vuelve a enviar la versión local sobre la versión actual del servidor
+    MantenerLocal,
+    /// Se descarta el cambio local y se adopta la versión del servidor
+    MantenerRemoto,
+    /// Se parte de la versión del servidor y se toman de la local los campos indicados
+    Combinar { campos: HashMap<String, OrigenCampo> },
+    /// La OC local toma el siguiente número libre de la secuencia y se vuelve a enviar
+    Renumerar,
+}
+
+impl ResolucionConflicto {
+    fn como_texto(&self) -> &'static str {
+        match self {
+            ResolucionConflicto::MantenerLocal => "MANTENER_LOCAL",
+            ResolucionConflicto::MantenerRemoto => "MANTENER_REMOTO",
+            ResolucionConflicto::Combinar { .. } => "COMBINAR",
+            ResolucionConflicto::Renumerar => "RENUMERAR",
+        }
+    }
+}
+
+impl FilaConflicto {
+    fn a_conflicto(self) -> Result<ConflictoSincronizacion> {
+        let datos_locales: Value = serde_json::from_str(&self.datos_locales)
+            .map_err(|e| AppError::Internal(format!("Datos locales inválidos: {}", e)))?;
+        let datos_remotos: Option<Value> = self
+            .datos_remotos
+            .as_deref()
+            .map(serde_json::from_str)
+            .transpose()
+            .map_err(|e| AppError::Internal(format!("Datos remotos inválidos: {}", e)))?;
+
+        let campos_en_conflicto = campos_en_conflicto(&self.tabla, &datos_locales, datos_remotos.as_ref());
+        let valor_repetido = datos_remotos
+            .as_ref()
+            .and_then(|d| d.get("id"))
+            .and_then(Value::as_str)
+            .is_some_and(|id| id != self.registro_id);
+
+        Ok(ConflictoSincronizacion {
+            id: self.id,
+            tabla: self.tabla,
+            registro_id: self.registro_id,
+            version_local: self.version_local,
+            version_remota: self.version_remota,
+            datos_locales,
+            datos_remotos,
+            campos_en_conflicto,
+            valor_repetido,
+            detectado_at: self.detectado_at,
+        })
+    }
+}
+
+/// Columnas cuyo valor difiere entre la versión local y la remota
+/// Compara valores normalizados (números, fechas con o sin zona horaria, booleanos 1/0)
+pub fn campos_en_conflicto(tabla: &str, locales: &Value, remotos: Option<&Value>) -> Vec<String> {
+    let Some(locales) = locales.as_object() else {
+        return Vec::new();
+    };
+
+    let mut campos: Vec<String> = locales
+        .iter()
+        .filter(|(columna, _)| !COLUMNAS_CONTROL.contains(&columna.as_str()))
+        .filter(|(columna, valor)| {
+            let local = valor.as_str().map(normalizar);
+            let remoto = remotos
+                .and_then(|r| r.get(columna.as_str()))
+                .and_then(|v| valor_a_local(tabla, columna, v))
+                .map(|v| normalizar(&v));
+            local != remoto
+        })
+        .map(|(columna, _)| columna.clone())
+        .collect();
+
+    campos.sort();
+    campos
+}
+
+/// Representación canónica de un valor para comparar ambas bases
+fn normalizar(valor: &str) -> String {
+    let valor = valor.trim();
+
+    if let Ok(numero) = valor.parse::<f64>() {
+        return numero.to_string();
+    }
+    if let Ok(fecha) = DateTime::parse_from_rfc3339(valor) {
+        return fecha.with_timezone(&Utc).naive_utc().to_string();
+    }
+    if let Ok(fecha) = DateTime::parse_from_str(valor, "%Y-%m-%d %H:%M:%S%.f%:z") {
+        return fecha.with_timezone(&Utc).naive_utc().to_string();
+    }
+    for formato in ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"] {
+        if let Ok(fecha) = NaiveDateTime::parse_from_str(valor, formato) {
+            return fecha.to_string();
+        }
+    }
+
+    match valor {
+        "true" => "1".to_string(),
+        "false" => "0".to_string(),
+        _ => valor.to_string(),
+    }
+}
+
+pub struct ConflictoRepository;
+
+impl ConflictoRepository {
+    /// Registra un conflicto, reemplazando el pendiente que hubiera para la misma fila
+    pub async fn registrar(pool: &SqlitePool, data: CreateConflicto) -> Result<String> {
+        let id = Uuid::new_v4().to_string();
+        let mut tx = pool.begin().await?;
+
+        sqlx::query("DELETE FROM sync_conflicts WHERE tabla = ? AND registro_id = ? AND estado = 'PENDIENTE'")
+            .bind(&data.tabla)
+            .bind(&data.registro_id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO sync_conflicts (
+                id, tabla, registro_id, version_local, version_remota,
+                datos_locales, datos_remotos, estado, detectado_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, 'PENDIENTE', ?)
+            "#
+        )
+        .bind(&id)
+        .bind(&data.tabla)
+        .bind(&data.registro_id)
+        .bind(data.version_local)
+        .bind(data.version_remota)
+        .bind(data.datos_locales.to_string())
+        .bind(data.datos_remotos.as_ref().map(|d| d.to_string()))
+        .bind(Utc::now())
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(id)
+    }
+
+    /// Conflictos pendientes de resolver, del más antiguo al más reciente
+    pub async fn pendientes(pool: &SqlitePool) -> Result<Vec<ConflictoSincronizacion>> {
+        let filas = sqlx::query_as::<_, FilaConflicto>(
+            r#"
+            SELECT id, tabla, registro_id, version_local, version_remota,
+                   datos_locales, datos_remotos, detectado_at
+            FROM sync_conflicts
+            WHERE estado = 'PENDIENTE'
+            ORDER BY detectado_at ASC
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        filas.into_iter().map(FilaConflicto::a_conflicto).collect()
+    }
+
+    /// Resuelve un conflicto pendiente
+    /// La fila local queda basada en la versión remota; si el resultado difiere del servidor se vuelve a encolar
+    pub async fn resolver(pool: &SqlitePool, id: &str, resolucion: &ResolucionConflicto) -> Result<()> {
+        let fila = sqlx::query_as::<_, FilaConflicto>(
+            r#"
+            SELECT id, tabla, registro_id, version_local, version_remota,
+                   datos_locales, datos_remotos, detectado_at
+            FROM sync_conflicts
+            WHERE id = ? AND estado = 'PENDIENTE'
+            "#
+        )
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Conflicto pendiente {}", id)))?;
+
+        let conflicto = fila.a_conflicto()?;
+        let tabla = tabla_sincronizada(&conflicto.tabla)
+            .ok_or_else(|| AppError::Sync(format!("Tabla no sincronizada: {}", conflicto.tabla)))?;
+        let columnas = columnas_locales(pool, tabla.local).await?;
+
+        // La fila del servidor es otra: adoptarla o combinarla pisaría (o borraría) la local
+        match resolucion {
+            ResolucionConflicto::MantenerRemoto | ResolucionConflicto::Combinar { .. } if conflicto.valor_repetido => {
+                return Err(AppError::Validation(
+                    "La fila del servidor es otra con el mismo valor único: corrija la local o renumérela".to_string(),
+                ));
+            }
+            ResolucionConflicto::Renumerar if !conflicto.valor_repetido || tabla.local != "ordenes_compra" => {
+                return Err(AppError::Validation(
+                    "Sólo se renumera una OC cuyo número ya usa otra OC del servidor".to_string(),
+                ));
+            }
+            _ => {}
+        }
+
+        let mut tx = pool.begin().await?;
+
+        match resolucion {
+            ResolucionConflicto::MantenerLocal => {
+                // El trigger de modificación vuelve a encolar la fila con la nueva versión base
+                sqlx::query(&format!("UPDATE {} SET sync_version = ? WHERE id = ?", tabla.local))
+                    .bind(conflicto.version_remota)
+                    .bind(&conflicto.registro_id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            ResolucionConflicto::MantenerRemoto => {
+                match &conflicto.datos_remotos {
+                    Some(remotos) => {
+                        let valores = valores_locales(tabla.local, &columnas, remotos, None, &conflicto.datos_locales);
+                        // synced_at con valor: el trigger no encola, la fila queda igual al servidor
+                        escribir_fila(&mut tx, tabla.local, &conflicto.registro_id, &valores, conflicto.version_remota, Some(Utc::now())).await?;
+                    }
+                    None => {
+                        // La fila ya no existe en el servidor
+                        sqlx::query(&format!("DELETE FROM {} WHERE id = ?", tabla.local))
+                            .bind(&conflicto.registro_id)
+                            .execute(&mut *tx)
+                            .await?;
+                    }
+                }
+
+                sqlx::query("DELETE FROM sync_outbox WHERE tabla = ? AND registro_id = ?")
+                    .bind(tabla.local)
+                    .bind(&conflicto.registro_id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            ResolucionConflicto::Combinar { campos } => {
+                let remotos = conflicto.datos_remotos.as_ref().ok_or_else(|| {
+                    AppError::Validation("No se puede combinar: la fila ya no existe en el servidor".to_string())
+                })?;
+
+                if let Some(desconocido) = campos.keys().find(|c| !columnas.contains(c)) {
+                    return Err(AppError::Validation(format!("Campo desconocido: {}", desconocido)));
+                }
+
+                let valores = valores_locales(tabla.local, &columnas, remotos, Some(campos), &conflicto.datos_locales);
+                // synced_at NULL: el trigger encola la versión combinada para enviarla
+                escribir_fila(&mut tx, tabla.local, &conflicto.registro_id, &valores, conflicto.version_remota, None).await?;
+            }
+            ResolucionConflicto::Renumerar => {
+                let (zona_id, año): (String, Option<i32>) =
+                    sqlx::query_as("SELECT zona_id, año FROM ordenes_compra WHERE id = ?")
+                        .bind(&conflicto.registro_id)
+                        .fetch_optional(&mut *tx)
+                        .await?
+                        .ok_or_else(|| AppError::NotFound(format!("Orden de compra {}", conflicto.registro_id)))?;
+                let año = año.ok_or_else(|| {
+                    AppError::Validation("La OC no tiene año en el número: corríjala a mano".to_string())
+                })?;
+
+                let (numero, pedido_nro): (i32, i32) = sqlx::query_as(TOMAR_NUMERO_SQLITE)
+                    .bind(&zona_id)
+                    .bind(año)
+                    .fetch_one(&mut *tx)
+                    .await?;
+
+                // El trigger de modificación vuelve a encolar la OC con el número nuevo
+                sqlx::query(
+                    "UPDATE ordenes_compra SET numero_oc = ?, pedido_nro = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+                )
+                .bind(formatear_numero_oc(numero, año))
+                .bind(pedido_nro)
+                .bind(&conflicto.registro_id)
+                .execute(&mut *tx)
+                .await?;
+
+                // La OC del servidor no se pudo descargar mientras el número estaba tomado: se vuelve a pedir
+                sqlx::query("DELETE FROM sync_pull_estado WHERE tabla = ?")
+                    .bind(tabla.local)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+
+        sqlx::query("UPDATE sync_conflicts SET estado = 'RESUELTO', resolucion = ?, resuelto_at = ? WHERE id = ?")
+            .bind(resolucion.como_texto())
+            .bind(Utc::now())
+            .bind(&conflicto.id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+/// Valores a escribir en la fila local: parte de la versión remota y toma de la local los campos elegidos
+pub(crate) fn valores_locales(
+    tabla: &str,
+    columnas: &[String],
+    remotos: &Value,
+    campos: Option<&HashMap<String, OrigenCampo>>,
+    locales: &Value,
+) -> Vec<(String, Option<String>)> {
+    columnas
+        .iter()
+        .filter(|c| !COLUMNAS_CONTROL.contains(&c.as_str()))
+        .filter_map(|columna| {
+            let origen = campos
+                .and_then(|c| c.get(columna))
+                .copied()
+                .unwrap_or(OrigenCampo::Remoto);
+
+            match origen {
+                OrigenCampo::Local => {
+                    let valor = locales.get(columna.as_str())?;
+                    Some((columna.clone(), valor.as_str().map(str::to_string)))
+                }
+                OrigenCampo::Remoto => {
+                    let valor = remotos.get(columna.as_str())?;
+                    Some((columna.clone(), valor_a_local(tabla, columna, valor)))
+                }
+            }
+        })
+        .collect()
+}
+
+/// Actualiza (o vuelve a crear) la fila local con los valores dados, la versión base y synced_at
+/// No usa INSERT ... ON CONFLICT: su cláusula de conflicto anularía el INSERT OR REPLACE de los triggers
+pub(crate) async fn escribir_fila(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    tabla: &str,
+    id: &str,
+    valores: &[(String, Option<String>)],
+    version: i64,
+    synced_at: Option<DateTime<Utc>>,
+) -> Result<()> {
+    let mut nombres: Vec<String> = valores.iter().map(|(c, _)| format!("\"{}\"", c)).collect();
+    nombres.push("\"sync_version\"".to_string());
+    nombres.push("\"synced_at\"".to_string());
+
+    let asignaciones = nombres
+        .iter()
+        .map(|n| format!("{} = ?", n))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let sql = format!("UPDATE {} SET {} WHERE id = ?", tabla, asignaciones);
+    let mut query = sqlx::query(&sql);
+    for (_, valor) in valores {
+        query = query.bind(valor.clone());
+    }
+    let result = query
+        .bind(version)
+        .bind(synced_at)
+        .bind(id)
+        .execute(&mut **tx)
+        .await?;
+
+    if result.rows_affected() > 0 {
+        return Ok(());
+    }
+
+    // La fila se borró localmente después del conflicto
+    let sql = format!(
+        "INSERT INTO {} (\"id\", {}) VALUES (?, {})",
+        tabla,
+        nombres.join(", "),
+        vec!["?"; nombres.len()].join(", ")
+    );
+    let mut query = sqlx::query(&sql).bind(id);
+    for (_, valor) in valores {
+        query = query.bind(valor.clone());
+    }
+    query
+        .bind(version)
+        .bind(synced_at)
+        .execute(&mut **tx)
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_util::{captura, pool_en_memoria};
+    use crate::models::zona::ZONA_PREDETERMINADA;
+    use crate::repositories::ExpedienteRepository;
+    use crate::sync::pull::{aplicar_fila_remota, Aplicacion};
+    use crate::sync::{tabla_sincronizada, OutboxRepository};
+
+    /// Proveedor editado localmente y en el servidor a la vez
+    async fn conflicto_proveedor(pool: &SqlitePool) -> String {
+        sqlx::query("INSERT INTO proveedores (id, nombre, cuit, domicilio) VALUES ('p1', 'Estudio ABC', '20-1-3', 'San Rafael')")
+            .execute(pool)
+            .await
+            .unwrap();
+
+        ConflictoRepository::registrar(pool, CreateConflicto {
+            tabla: "proveedores".to_string(),
+            registro_id: "p1".to_string(),
+            version_local: 1,
+            version_remota: 2,
+            datos_locales: serde_json::json!({
+                "id": "p1", "nombre": "Estudio ABC", "cuit": "20-1-3", "domicilio": "San Rafael", "sync_version": "1"
+            }),
+            datos_remotos: Some(serde_json::json!({
+                "id": "p1", "nombre": "Estudio ABC SRL", "cuit": "20-1-3", "domicilio": "Malargüe", "sync_version": 2
+            })),
+        })
+        .await
+        .unwrap()
+    }
+
+    async fn proveedor(pool: &SqlitePool) -> (String, String, i64) {
+        sqlx::query_as("SELECT nombre, domicilio, sync_version FROM proveedores WHERE id = 'p1'")
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_resolver_manteniendo_remoto() {
+        let pool = pool_en_memoria().await;
+        let id = conflicto_proveedor(&pool).await;
+
+        let pendientes = ConflictoRepository::pendientes(&pool).await.unwrap();
+        assert_eq!(pendientes.len(), 1);
+        assert_eq!(pendientes[0].campos_en_conflicto, vec!["domicilio", "nombre"]);
+
+        ConflictoRepository::resolver(&pool, &id, &ResolucionConflicto::MantenerRemoto).await.unwrap();
+
+        assert_eq!(proveedor(&pool).await, ("Estudio ABC SRL".to_string(), "Malargüe".to_string(), 2));
+        assert!(OutboxRepository::pendientes(&pool, Utc::now(), 10).await.unwrap().is_empty());
+        assert!(ConflictoRepository::pendientes(&pool).await.unwrap().is_empty());
+
+        // Un conflicto ya resuelto no se puede volver a resolver
+        assert!(ConflictoRepository::resolver(&pool, &id, &ResolucionConflicto::MantenerLocal).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_resolver_combinando_campos() {
+        let pool = pool_en_memoria().await;
+        let id = conflicto_proveedor(&pool).await;
+
+        let campos = HashMap::from([("domicilio".to_string(), OrigenCampo::Local)]);
+        ConflictoRepository::resolver(&pool, &id, &ResolucionConflicto::Combinar { campos }).await.unwrap();
+
+        // Nombre del servidor, domicilio local, y la fila queda encolada para enviarse
+        assert_eq!(proveedor(&pool).await, ("Estudio ABC SRL".to_string(), "San Rafael".to_string(), 2));
+        let pendientes = OutboxRepository::pendientes(&pool, Utc::now(), 10).await.unwrap();
+        assert_eq!(pendientes.len(), 1);
+        assert_eq!(pendientes[0].registro_id, "p1");
+    }
+
+    /// Dos puestos sin conexión emitieron la OC 01/2026: la del servidor no se puede descargar
+    /// y la local se renumera sin perderla
+    #[tokio::test]
+    async fn test_resolver_oc_con_numero_repetido_renumerando() {
+        let pool = pool_en_memoria().await;
+        let exp = ExpedienteRepository::upsert_from_infogov(&pool, captura("1-1-2026", "2026-01-10", "INICIADO")).await.unwrap();
+        sqlx::query(
+            "INSERT INTO ordenes_compra (id, numero_oc, pedido_nro, fecha, expediente_id, forma_pago, tipo_contratacion, \
+             subtotal, iva, total, zona_id, estado, año) VALUES ('oc-local', '01/2026', 1, '2026-03-01', ?, 'Transferencia', \
+             'Contratación directa', 100, 21, 121, ?, 'EMITIDA', 2026)",
+        )
+        .bind(&exp.id)
+        .bind(ZONA_PREDETERMINADA)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO sync_pull_estado (tabla, ultimo_updated_at, ultimo_id) VALUES ('ordenes_compra', '2026-03-02 00:00:00+00', 'x')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let remota = serde_json::json!({
+            "id": "oc-remota", "numero_oc": "01/2026", "pedido_nro": 1, "destino": "ZONA RIEGO MALARGUE",
+            "fecha": "2026-03-01", "expediente_id": exp.id, "resolucion_nro": null, "forma_pago": "Transferencia",
+            "plazo_entrega": "-", "es_iva_inscripto": true, "tipo_contratacion": "Contratación directa",
+            "subtotal": 50, "iva": 10.5, "total": 60.5, "zona_id": ZONA_PREDETERMINADA, "estado": "EMITIDA",
+            "año": 2026, "numero_repetido": false, "sync_version": 1,
+            "created_at": "2026-03-01T10:00:00+00:00", "updated_at": "2026-03-01T10:00:00+00:00"
+        });
+        let tabla = tabla_sincronizada("ordenes_compra").unwrap();
+        let columnas = columnas_locales(&pool, tabla.local).await.unwrap();
+
+        // La descarga no se traba: queda un conflicto sobre la OC local
+        let aplicada = aplicar_fila_remota(&pool, tabla, &columnas, "oc-remota", &remota).await.unwrap();
+        assert_eq!(aplicada, Aplicacion::Conflicto);
+        let pendientes = ConflictoRepository::pendientes(&pool).await.unwrap();
+        assert_eq!(pendientes.len(), 1);
+        assert_eq!(pendientes[0].registro_id, "oc-local");
+        assert!(pendientes[0].valor_repetido);
+
+        // Adoptar la del servidor borraría la OC local
+        assert!(ConflictoRepository::resolver(&pool, &pendientes[0].id, &ResolucionConflicto::MantenerRemoto).await.is_err());
+
+        ConflictoRepository::resolver(&pool, &pendientes[0].id, &ResolucionConflicto::Renumerar).await.unwrap();
+
+        let (numero, total): (String, f64) = sqlx::query_as("SELECT numero_oc, total FROM ordenes_compra WHERE id = 'oc-local'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!((numero.as_str(), total), ("02/2026", 121.0));
+        let encolados = OutboxRepository::pendientes(&pool, Utc::now(), 10).await.unwrap();
+        assert!(encolados.iter().any(|e| e.tabla == "ordenes_compra" && e.registro_id == "oc-local"));
+        assert!(ConflictoRepository::pendientes(&pool).await.unwrap().is_empty());
+
+        // Con el número libre, la OC del servidor baja en la próxima pasada
+        let marcas: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM sync_pull_estado WHERE tabla = 'ordenes_compra'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(marcas, 0);
+        let aplicada = aplicar_fila_remota(&pool, tabla, &columnas, "oc-remota", &remota).await.unwrap();
+        assert_eq!(aplicada, Aplicacion::Insertada);
+    }
+
+    #[test]
+    fn test_normalizar_valores() {
+        assert_eq!(normalizar("10.50"), normalizar("10.5"));
+        assert_eq!(normalizar("2024-03-01 10:00:00"), normalizar("2024-03-01T10:00:00+00:00"));
+        assert_eq!(normalizar("true"), "1");
+    }
+}
@@ -0,0 +1,17 @@
+// Módulo de sincronización SQLite (local) <-> PostgreSQL (remoto)
+// Las escrituras locales se encolan en sync_outbox mediante triggers y un worker las envía;
+// el mismo worker descarga los cambios remotos de forma incremental
+
+pub mod conflictos;
+pub mod outbox;
+pub mod pull;
+pub mod push;
+pub mod tablas;
+pub mod worker;
+
+pub use conflictos::{ConflictoRepository, ConflictoSincronizacion, ResolucionConflicto};
+pub use outbox::{OutboxRepository, ResumenOutbox};
+pub use pull::{descargar_cambios, reiniciar_marcas, ResumenPull};
+pub use push::{procesar_outbox, ResultadoPush};
+pub use tablas::{tabla_sincronizada, TablaSincronizada, TABLAS_SINCRONIZADAS};
+pub use worker::{iniciar_worker_sincronizacion, reintentar_pendientes, sincronizar, ResultadoSincronizacion};
@@ -0,0 +1,219 @@
+// Cola de salida (outbox) de cambios locales pendientes de sincronizar
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use sqlx::{FromRow, SqlitePool};
+
+use crate::error::{AppError, Result};
+
+/// Cambio local pendiente de enviar al servidor
+#[derive(Debug, Clone, FromRow)]
+pub struct OutboxEntry {
+    pub id: i64,
+    pub tabla: String,
+    pub registro_id: String,
+    pub operacion: String,
+    pub intentos: i64,
+    pub ultimo_error: Option<String>,
+}
+
+/// Resumen de pendientes por tabla (para mostrar en la interfaz)
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct ResumenOutbox {
+    pub tabla: String,
+    pub pendientes: i64,
+    pub con_error: i64,
+    pub ultimo_error: Option<String>,
+}
+
+/// Espera mínima y máxima entre reintentos
+const ESPERA_BASE_SEGUNDOS: i64 = 5;
+const ESPERA_MAXIMA_SEGUNDOS: i64 = 3600;
+
+/// Backoff exponencial: 5s, 10s, 20s, ... hasta 1 hora
+pub fn calcular_espera(intentos: i64) -> Duration {
+    let exponente = (intentos.max(1) - 1).min(20) as u32;
+    let segundos = ESPERA_BASE_SEGUNDOS.saturating_mul(2_i64.pow(exponente));
+    Duration::seconds(segundos.min(ESPERA_MAXIMA_SEGUNDOS))
+}
+
+pub struct OutboxRepository;
+
+impl OutboxRepository {
+    /// Entradas listas para enviar (sin reintento programado o con la espera cumplida)
+    pub async fn pendientes(pool: &SqlitePool, ahora: DateTime<Utc>, limite: i64) -> Result<Vec<OutboxEntry>> {
+        let entradas = sqlx::query_as::<_, OutboxEntry>(
+            r#"
+            SELECT id, tabla, registro_id, operacion, intentos, ultimo_error
+            FROM sync_outbox
+            WHERE proximo_intento IS NULL OR julianday(proximo_intento) <= julianday(?)
+            ORDER BY id ASC
+            LIMIT ?
+            "#
+        )
+        .bind(ahora)
+        .bind(limite)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(entradas)
+    }
+
+    /// Marca la entrada como enviada: actualiza synced_at de la fila y la quita de la cola
+    /// `version` es la versión que asignó el servidor (None si la tabla remota no la lleva)
+    /// Si la fila cambió mientras se enviaba, la entrada fue reemplazada (otro id) y queda en cola
+    pub async fn confirmar(
+        pool: &SqlitePool,
+        entrada: &OutboxEntry,
+        version: Option<i64>,
+        ahora: DateTime<Utc>,
+    ) -> Result<()> {
+        let mut tx = pool.begin().await?;
+
+        if entrada.operacion == "UPSERT" {
+            let tabla = crate::sync::tabla_sincronizada(&entrada.tabla)
+                .ok_or_else(|| AppError::Sync(format!("Tabla no sincronizada: {}", entrada.tabla)))?;
+
+            let sql = format!(
+                "UPDATE {} SET synced_at = ?, sync_version = COALESCE(?, sync_version) WHERE id = ?",
+                tabla.local
+            );
+            sqlx::query(&sql)
+                .bind(ahora)
+                .bind(version)
+                .bind(&entrada.registro_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        sqlx::query("DELETE FROM sync_outbox WHERE id = ?")
+            .bind(entrada.id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Registra un fallo y programa el próximo reintento con backoff exponencial
+    pub async fn registrar_error(
+        pool: &SqlitePool,
+        entrada: &OutboxEntry,
+        error: &str,
+        ahora: DateTime<Utc>,
+    ) -> Result<()> {
+        let intentos = entrada.intentos + 1;
+        let proximo = ahora + calcular_espera(intentos);
+
+        sqlx::query(
+            "UPDATE sync_outbox SET intentos = ?, ultimo_error = ?, proximo_intento = ? WHERE id = ?"
+        )
+        .bind(intentos)
+        .bind(error)
+        .bind(proximo)
+        .bind(entrada.id)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Quita una entrada de la cola sin marcar la fila (ej. la fila ya no existe)
+    pub async fn descartar(pool: &SqlitePool, entrada: &OutboxEntry) -> Result<()> {
+        sqlx::query("DELETE FROM sync_outbox WHERE id = ?")
+            .bind(entrada.id)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Cantidad de pendientes y errores por tabla
+    pub async fn resumen(pool: &SqlitePool) -> Result<Vec<ResumenOutbox>> {
+        let resumen = sqlx::query_as::<_, ResumenOutbox>(
+            r#"
+            SELECT tabla,
+                   COUNT(*) AS pendientes,
+                   SUM(CASE WHEN ultimo_error IS NOT NULL THEN 1 ELSE 0 END) AS con_error,
+                   MAX(ultimo_error) AS ultimo_error
+            FROM sync_outbox
+            GROUP BY tabla
+            ORDER BY tabla
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(resumen)
+    }
+
+    /// Reprograma todas las entradas con error para reintentarlas ya
+    pub async fn reintentar_todo(pool: &SqlitePool) -> Result<u64> {
+        let result = sqlx::query("UPDATE sync_outbox SET proximo_intento = NULL WHERE proximo_intento IS NOT NULL")
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_util::pool_en_memoria;
+
+    #[tokio::test]
+    async fn test_triggers_encolan_cambios() {
+        let pool = pool_en_memoria().await;
+        let ahora = Utc::now();
+
+        sqlx::query("INSERT INTO proveedores (id, nombre, cuit, domicilio) VALUES ('p1', 'Estudio ABC', '20-1-3', 'Malargüe')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let pendientes = OutboxRepository::pendientes(&pool, ahora, 10).await.unwrap();
+        assert_eq!(pendientes.len(), 1);
+        assert_eq!(pendientes[0].operacion, "UPSERT");
+
+        // Confirmar marca synced_at sin volver a encolar
+        OutboxRepository::confirmar(&pool, &pendientes[0], Some(1), ahora).await.unwrap();
+        assert!(OutboxRepository::pendientes(&pool, ahora, 10).await.unwrap().is_empty());
+        let synced: Option<String> = sqlx::query_scalar("SELECT synced_at FROM proveedores WHERE id = 'p1'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert!(synced.is_some());
+
+        // Una edición local vuelve a encolar y limpia synced_at
+        sqlx::query("UPDATE proveedores SET domicilio = 'San Rafael' WHERE id = 'p1'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let pendientes = OutboxRepository::pendientes(&pool, ahora, 10).await.unwrap();
+        assert_eq!(pendientes.len(), 1);
+        let synced: Option<String> = sqlx::query_scalar("SELECT synced_at FROM proveedores WHERE id = 'p1'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert!(synced.is_none());
+
+        // Un error posterga la entrada según el backoff
+        OutboxRepository::registrar_error(&pool, &pendientes[0], "sin conexión", ahora).await.unwrap();
+        assert!(OutboxRepository::pendientes(&pool, ahora, 10).await.unwrap().is_empty());
+
+        // La baja reemplaza la entrada pendiente
+        sqlx::query("DELETE FROM proveedores WHERE id = 'p1'").execute(&pool).await.unwrap();
+        let pendientes = OutboxRepository::pendientes(&pool, ahora, 10).await.unwrap();
+        assert_eq!(pendientes.len(), 1);
+        assert_eq!(pendientes[0].operacion, "DELETE");
+    }
+
+    #[test]
+    fn test_backoff_exponencial() {
+        assert_eq!(calcular_espera(1), Duration::seconds(5));
+        assert_eq!(calcular_espera(2), Duration::seconds(10));
+        assert_eq!(calcular_espera(4), Duration::seconds(40));
+        assert_eq!(calcular_espera(30), Duration::seconds(3600));
+    }
+}
@@ -0,0 +1,386 @@
+// Descarga incremental de cambios remotos (PostgreSQL -> SQLite)
+// Cada tabla guarda en sync_pull_estado el último (updated_at, id) recibido del servidor
+
+use chrono::Utc;
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::{PgPool, Row, SqlitePool};
+
+use crate::error::{AppError, Result};
+use crate::services::SettingsService;
+use crate::sync::conflictos::{escribir_fila, valores_locales, ConflictoRepository, CreateConflicto};
+use crate::sync::push::{columnas_locales, es_error_de_conexion, leer_fila_local, version_base};
+use crate::sync::tablas::{clave_unica, TablaSincronizada, TABLAS_SINCRONIZADAS};
+
+/// Filas remotas pedidas por página
+const LOTE_DESCARGA: i64 = 500;
+
+/// Margen hacia atrás al retomar la descarga: cubre transacciones que confirmaron
+/// después de otras con updated_at posterior (NOW() es la hora de inicio de la transacción)
+const MARGEN_SEGUNDOS: i64 = 60;
+
+/// Resultado de la descarga de una tabla
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ResumenPull {
+    pub tabla: String,
+    pub recibidos: usize,
+    pub insertados: usize,
+    pub actualizados: usize,
+    /// Filas que ya estaban al día localmente
+    pub omitidos: usize,
+    /// Filas con cambios locales sin enviar (quedan en sync_conflicts)
+    pub conflictos: usize,
+    /// Error que cortó la descarga de la tabla (se reintenta desde la última fila aplicada)
+    pub error: Option<String>,
+}
+
+/// Qué se hizo con una fila remota
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aplicacion {
+    Insertada,
+    Actualizada,
+    Omitida,
+    Conflicto,
+}
+
+/// Descarga los cambios remotos de todas las tablas sincronizadas, en orden de dependencias
+/// Sólo se descargan las filas de la zona de la instalación (y las tablas compartidas)
+/// Los errores de conexión cortan la descarga; los de datos se informan por tabla
+pub async fn descargar_cambios(sqlite: &SqlitePool, postgres: &PgPool) -> Result<Vec<ResumenPull>> {
+    let zona_id = SettingsService::cargar(sqlite).await?.zona.id;
+    let mut resumen = Vec::new();
+
+    for tabla in TABLAS_SINCRONIZADAS {
+        let mut resultado = ResumenPull {
+            tabla: tabla.local.to_string(),
+            ..Default::default()
+        };
+
+        if let Err(e) = descargar_tabla(sqlite, postgres, *tabla, &zona_id, &mut resultado).await {
+            if es_error_de_conexion(&e) {
+                return Err(e);
+            }
+            eprintln!("⚠️ Error descargando {}: {}", tabla.local, e);
+            resultado.error = Some(e.to_string());
+        }
+
+        resumen.push(resultado);
+    }
+
+    Ok(resumen)
+}
+
+/// Descarga una tabla página por página, avanzando la marca después de cada fila aplicada
+async fn descargar_tabla(
+    sqlite: &SqlitePool,
+    postgres: &PgPool,
+    tabla: TablaSincronizada,
+    zona_id: &str,
+    resultado: &mut ResumenPull,
+) -> Result<()> {
+    let columnas = columnas_locales(sqlite, tabla.local).await?;
+
+    let marca: Option<(Option<String>, Option<String>)> = sqlx::query_as(
+        "SELECT ultimo_updated_at, ultimo_id FROM sync_pull_estado WHERE tabla = ?"
+    )
+    .bind(tabla.local)
+    .fetch_optional(sqlite)
+    .await?;
+
+    // Se retoma un poco antes de la última marca: las filas repetidas se omiten por versión
+    let (mut desde, mut desde_id, mut margen) = match marca {
+        Some((Some(updated_at), _)) => (updated_at, String::new(), MARGEN_SEGUNDOS),
+        _ => ("-infinity".to_string(), String::new(), 0),
+    };
+
+    loop {
+        let pagina = leer_pagina_remota(postgres, tabla, zona_id, &desde, &desde_id, margen).await?;
+        margen = 0;
+
+        for (datos, updated_at, id) in &pagina {
+            resultado.recibidos += 1;
+            match aplicar_fila_remota(sqlite, tabla, &columnas, id, datos).await? {
+                Aplicacion::Insertada => resultado.insertados += 1,
+                Aplicacion::Actualizada => resultado.actualizados += 1,
+                Aplicacion::Omitida => resultado.omitidos += 1,
+                Aplicacion::Conflicto => resultado.conflictos += 1,
+            }
+
+            guardar_marca(sqlite, tabla, updated_at, id).await?;
+            desde = updated_at.clone();
+            desde_id = id.clone();
+        }
+
+        if (pagina.len() as i64) < LOTE_DESCARGA {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Página de filas remotas posteriores a (desde - margen, desde_id), ordenadas por (updated_at, id)
+/// Devuelve (fila como JSON, updated_at como texto UTC, id)
+async fn leer_pagina_remota(
+    postgres: &PgPool,
+    tabla: TablaSincronizada,
+    zona_id: &str,
+    desde: &str,
+    desde_id: &str,
+    margen_segundos: i64,
+) -> Result<Vec<(Value, String, String)>> {
+    let sql = format!(
+        r#"
+        SELECT row_to_json(r)::text AS datos,
+               r.updated_at::timestamptz::text AS marca,
+               r.id::text AS rid
+        FROM "{}" r
+        WHERE (r.updated_at::timestamptz, r.id::text)
+              > ($1::text::timestamptz - make_interval(secs => $2), $3)
+          AND {}
+        ORDER BY r.updated_at::timestamptz, r.id::text
+        LIMIT $4
+        "#,
+        tabla.remota,
+        tabla.filtro_zona.unwrap_or("TRUE")
+    );
+
+    let mut tx = postgres.begin().await?;
+
+    // Fechas en UTC tanto en la marca como en el JSON (SQLite guarda UTC sin zona)
+    sqlx::query("SET LOCAL TIME ZONE 'UTC'").execute(&mut *tx).await?;
+
+    let mut query = sqlx::query(&sql)
+        .bind(desde)
+        .bind(margen_segundos as f64)
+        .bind(desde_id)
+        .bind(LOTE_DESCARGA);
+    if tabla.filtro_zona.is_some() {
+        query = query.bind(zona_id);
+    }
+    let filas = query.fetch_all(&mut *tx).await?;
+
+    tx.commit().await?;
+
+    filas
+        .iter()
+        .map(|f| {
+            let datos: String = f.try_get("datos")?;
+            let datos = serde_json::from_str(&datos).map_err(|e| AppError::Internal(e.to_string()))?;
+            Ok((datos, f.try_get("marca")?, f.try_get("rid")?))
+        })
+        .collect()
+}
+
+/// Aplica una fila remota en SQLite
+/// - Si la fila local ya está en esa versión (o posterior) no se toca
+/// - Si tiene cambios sin enviar, se registra un conflicto en lugar de pisarlos
+/// - Si no, se escribe con synced_at para que los triggers no la vuelvan a encolar
+/// - Si repite el valor único de otra fila local (la OC con el número que tomó este puesto sin conexión),
+///   se registra un conflicto sobre la fila local y la remota baja cuando se resuelva
+pub(crate) async fn aplicar_fila_remota(
+    sqlite: &SqlitePool,
+    tabla: TablaSincronizada,
+    columnas: &[String],
+    id: &str,
+    datos: &Value,
+) -> Result<Aplicacion> {
+    let version_remota = datos.get("sync_version").and_then(Value::as_i64);
+
+    let version_local: Option<i64> = sqlx::query_scalar(&format!(
+        "SELECT sync_version FROM {} WHERE id = ?",
+        tabla.local
+    ))
+    .bind(id)
+    .fetch_optional(sqlite)
+    .await?;
+
+    let pendiente: Option<String> = sqlx::query_scalar(
+        "SELECT operacion FROM sync_outbox WHERE tabla = ? AND registro_id = ?"
+    )
+    .bind(tabla.local)
+    .bind(id)
+    .fetch_optional(sqlite)
+    .await?;
+
+    if let (Some(local), Some(remota)) = (version_local, version_remota) {
+        if local >= remota {
+            return Ok(Aplicacion::Omitida);
+        }
+    }
+
+    match pendiente.as_deref() {
+        // Borrada localmente: la baja se enviará en el próximo push
+        Some("DELETE") => return Ok(Aplicacion::Omitida),
+        Some(_) if version_local.is_some() => {
+            let Some(fila) = leer_fila_local(sqlite, tabla, id).await? else {
+                return Ok(Aplicacion::Omitida);
+            };
+
+            ConflictoRepository::registrar(sqlite, CreateConflicto {
+                tabla: tabla.local.to_string(),
+                registro_id: id.to_string(),
+                version_local: version_local.unwrap_or_default(),
+                version_remota: version_remota.unwrap_or_default(),
+                datos_locales: serde_json::to_value(&fila).map_err(|e| AppError::Internal(e.to_string()))?,
+                datos_remotos: Some(datos.clone()),
+            })
+            .await?;
+
+            sqlx::query("DELETE FROM sync_outbox WHERE tabla = ? AND registro_id = ?")
+                .bind(tabla.local)
+                .bind(id)
+                .execute(sqlite)
+                .await?;
+
+            return Ok(Aplicacion::Conflicto);
+        }
+        _ => {}
+    }
+
+    let valores = valores_locales(tabla.local, columnas, datos, None, &Value::Null);
+
+    let mut tx = sqlite.begin().await?;
+    match escribir_fila(&mut tx, tabla.local, id, &valores, version_remota.unwrap_or_default(), Some(Utc::now())).await {
+        Err(AppError::Database(sqlx::Error::Database(e))) if e.is_unique_violation() => {
+            tx.rollback().await?;
+            return registrar_valor_repetido(sqlite, tabla, id, datos, &valores).await;
+        }
+        resultado => resultado?,
+    }
+    tx.commit().await?;
+
+    Ok(if version_local.is_some() {
+        Aplicacion::Actualizada
+    } else {
+        Aplicacion::Insertada
+    })
+}
+
+/// Registra el conflicto entre la fila local que ya tiene el valor único y la remota que no se pudo escribir
+async fn registrar_valor_repetido(
+    sqlite: &SqlitePool,
+    tabla: TablaSincronizada,
+    id: &str,
+    datos: &Value,
+    valores: &[(String, Option<String>)],
+) -> Result<Aplicacion> {
+    let repetido = || AppError::Sync(format!("{} {} repite un valor único local", tabla.local, id));
+    let clave = clave_unica(tabla.local).ok_or_else(repetido)?;
+
+    let condiciones = clave
+        .iter()
+        .map(|c| format!("\"{}\" = ?", c))
+        .collect::<Vec<_>>()
+        .join(" AND ");
+    let sql = format!("SELECT id FROM {} WHERE {} AND id <> ? LIMIT 1", tabla.local, condiciones);
+    let mut query = sqlx::query_scalar::<_, String>(&sql);
+    for columna in clave {
+        let valor = valores.iter().find(|(c, _)| c == columna).and_then(|(_, v)| v.clone());
+        query = query.bind(valor);
+    }
+    let local_id = query.bind(id).fetch_optional(sqlite).await?.ok_or_else(repetido)?;
+
+    let Some(fila) = leer_fila_local(sqlite, tabla, &local_id).await? else {
+        return Err(repetido());
+    };
+
+    ConflictoRepository::registrar(sqlite, CreateConflicto {
+        tabla: tabla.local.to_string(),
+        registro_id: local_id.clone(),
+        version_local: version_base(&fila),
+        version_remota: datos.get("sync_version").and_then(Value::as_i64).unwrap_or_default(),
+        datos_locales: serde_json::to_value(&fila).map_err(|e| AppError::Internal(e.to_string()))?,
+        datos_remotos: Some(datos.clone()),
+    })
+    .await?;
+
+    // El envío de la fila local chocaría igual en el servidor: queda en el conflicto
+    sqlx::query("DELETE FROM sync_outbox WHERE tabla = ? AND registro_id = ?")
+        .bind(tabla.local)
+        .bind(&local_id)
+        .execute(sqlite)
+        .await?;
+
+    Ok(Aplicacion::Conflicto)
+}
+
+/// Guarda la última fila aplicada de la tabla
+async fn guardar_marca(sqlite: &SqlitePool, tabla: TablaSincronizada, updated_at: &str, id: &str) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO sync_pull_estado (tabla, ultimo_updated_at, ultimo_id, ultima_descarga)
+        VALUES (?, ?, ?, ?)
+        ON CONFLICT(tabla) DO UPDATE SET
+            ultimo_updated_at = excluded.ultimo_updated_at,
+            ultimo_id = excluded.ultimo_id,
+            ultima_descarga = excluded.ultima_descarga
+        "#
+    )
+    .bind(tabla.local)
+    .bind(updated_at)
+    .bind(id)
+    .bind(Utc::now())
+    .execute(sqlite)
+    .await?;
+
+    Ok(())
+}
+
+/// Olvida las marcas de descarga: el próximo pull trae todo de nuevo
+/// (al cambiar de zona, las filas de la zona nueva pueden ser anteriores a las marcas)
+pub async fn reiniciar_marcas(sqlite: &SqlitePool) -> Result<()> {
+    sqlx::query("DELETE FROM sync_pull_estado").execute(sqlite).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_util::pool_en_memoria;
+    use crate::sync::{tabla_sincronizada, OutboxRepository};
+
+    fn proveedor_remoto(domicilio: &str, version: i64) -> Value {
+        serde_json::json!({
+            "id": "p1", "nombre": "Estudio ABC", "cuit": "20-1-3", "domicilio": domicilio,
+            "activo": true, "sync_version": version,
+            "created_at": "2026-03-01T10:00:00+00:00", "updated_at": "2026-03-01T10:00:00+00:00"
+        })
+    }
+
+    #[tokio::test]
+    async fn test_aplicar_filas_remotas() {
+        let pool = pool_en_memoria().await;
+        let tabla = tabla_sincronizada("proveedores").unwrap();
+        let columnas = columnas_locales(&pool, tabla.local).await.unwrap();
+        let ahora = Utc::now();
+
+        // Alta remota: se inserta ya sincronizada, sin encolar
+        let aplicada = aplicar_fila_remota(&pool, tabla, &columnas, "p1", &proveedor_remoto("Malargüe", 1)).await.unwrap();
+        assert_eq!(aplicada, Aplicacion::Insertada);
+        assert!(OutboxRepository::pendientes(&pool, ahora, 10).await.unwrap().is_empty());
+
+        // La misma versión se omite; una posterior actualiza
+        let aplicada = aplicar_fila_remota(&pool, tabla, &columnas, "p1", &proveedor_remoto("Malargüe", 1)).await.unwrap();
+        assert_eq!(aplicada, Aplicacion::Omitida);
+        let aplicada = aplicar_fila_remota(&pool, tabla, &columnas, "p1", &proveedor_remoto("San Rafael", 2)).await.unwrap();
+        assert_eq!(aplicada, Aplicacion::Actualizada);
+        let (domicilio, version): (String, i64) = sqlx::query_as("SELECT domicilio, sync_version FROM proveedores WHERE id = 'p1'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!((domicilio.as_str(), version), ("San Rafael", 2));
+        assert!(OutboxRepository::pendientes(&pool, ahora, 10).await.unwrap().is_empty());
+
+        // Con una edición local sin enviar, la versión remota queda como conflicto
+        sqlx::query("UPDATE proveedores SET domicilio = 'General Alvear' WHERE id = 'p1'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let aplicada = aplicar_fila_remota(&pool, tabla, &columnas, "p1", &proveedor_remoto("Tunuyán", 3)).await.unwrap();
+        assert_eq!(aplicada, Aplicacion::Conflicto);
+        let conflictos = ConflictoRepository::pendientes(&pool).await.unwrap();
+        assert_eq!(conflictos.len(), 1);
+        assert_eq!(conflictos[0].campos_en_conflicto, vec!["domicilio"]);
+    }
+}
@@ -0,0 +1,390 @@
+// Envío de cambios locales (outbox) a PostgreSQL
+
+use std::collections::HashMap;
+
+use chrono::Utc;
+use serde::Serialize;
+use sqlx::{PgPool, Row, SqlitePool};
+
+use crate::error::{AppError, Result};
+use crate::sync::conflictos::{ConflictoRepository, CreateConflicto};
+use crate::sync::outbox::{OutboxEntry, OutboxRepository};
+use crate::sync::tablas::{clave_unica, tabla_sincronizada, valor_a_remoto, TablaSincronizada};
+
+/// Columna de una tabla remota con su tipo (udt_name de information_schema)
+#[derive(Debug, Clone)]
+pub struct ColumnaRemota {
+    pub nombre: String,
+    pub tipo: String,
+}
+
+/// Resultado de procesar un lote de la outbox
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ResultadoPush {
+    pub enviados: usize,
+    pub fallidos: usize,
+    /// Cambios rechazados porque la fila se modificó en el servidor o repite un valor único (quedan en sync_conflicts)
+    pub conflictos: usize,
+    /// true si se cortó el lote por falta de conexión con el servidor
+    pub sin_conexion: bool,
+}
+
+/// Resultado de enviar una entrada
+enum Envio {
+    /// Aplicada en el servidor, con la versión que asignó (None si la tabla no lleva versión)
+    Enviado(Option<i64>),
+    /// La fila local ya no existe
+    NoExiste,
+    /// El servidor tiene una versión distinta a la base local, u otra fila con el mismo valor
+    /// en una columna única: se registró un conflicto
+    Conflicto,
+}
+
+/// Procesa un lote de cambios pendientes
+/// Los errores de datos se registran en la entrada (con backoff); los de conexión cortan el lote
+pub async fn procesar_outbox(sqlite: &SqlitePool, postgres: &PgPool, limite: i64) -> Result<ResultadoPush> {
+    let entradas = OutboxRepository::pendientes(sqlite, Utc::now(), limite).await?;
+    let mut esquemas: HashMap<&'static str, Vec<ColumnaRemota>> = HashMap::new();
+    let mut resultado = ResultadoPush::default();
+
+    for entrada in entradas {
+        match enviar_entrada(sqlite, postgres, &entrada, &mut esquemas).await {
+            Ok(Envio::Enviado(version)) => {
+                OutboxRepository::confirmar(sqlite, &entrada, version, Utc::now()).await?;
+                resultado.enviados += 1;
+            }
+            Ok(Envio::NoExiste) => {
+                // La fila local ya no existe: el DELETE correspondiente está (o estará) en cola
+                OutboxRepository::descartar(sqlite, &entrada).await?;
+            }
+            Ok(Envio::Conflicto) => {
+                // El cambio queda en sync_conflicts hasta que el usuario lo resuelva
+                OutboxRepository::descartar(sqlite, &entrada).await?;
+                resultado.conflictos += 1;
+            }
+            Err(e) if es_error_de_conexion(&e) => {
+                resultado.sin_conexion = true;
+                break;
+            }
+            Err(e) => {
+                eprintln!("⚠️ Error sincronizando {} {}: {}", entrada.tabla, entrada.registro_id, e);
+                OutboxRepository::registrar_error(sqlite, &entrada, &e.to_string(), Utc::now()).await?;
+                resultado.fallidos += 1;
+            }
+        }
+    }
+
+    Ok(resultado)
+}
+
+/// Envía una entrada al servidor
+async fn enviar_entrada(
+    sqlite: &SqlitePool,
+    postgres: &PgPool,
+    entrada: &OutboxEntry,
+    esquemas: &mut HashMap<&'static str, Vec<ColumnaRemota>>,
+) -> Result<Envio> {
+    let tabla = tabla_sincronizada(&entrada.tabla)
+        .ok_or_else(|| AppError::Sync(format!("Tabla no sincronizada: {}", entrada.tabla)))?;
+
+    if entrada.operacion == "DELETE" {
+        sqlx::query(&format!("DELETE FROM \"{}\" WHERE id::text = $1", tabla.remota))
+            .bind(&entrada.registro_id)
+            .execute(postgres)
+            .await?;
+        return Ok(Envio::Enviado(None));
+    }
+
+    let Some(fila) = leer_fila_local(sqlite, tabla, &entrada.registro_id).await? else {
+        return Ok(Envio::NoExiste);
+    };
+
+    if !esquemas.contains_key(tabla.remota) {
+        let columnas = columnas_remotas(postgres, tabla.remota).await?;
+        esquemas.insert(tabla.remota, columnas);
+    }
+    let columnas = &esquemas[tabla.remota];
+
+    if columnas.is_empty() {
+        return Err(AppError::Sync(format!("La tabla remota {} no existe", tabla.remota)));
+    }
+
+    let (envio, repetido) = match upsert_remoto(postgres, tabla, columnas, &fila).await {
+        // Otra fila del servidor ya tiene el valor de una columna única (p. ej. el número de OC que
+        // tomaron dos puestos sin conexión): reintentar no lo arregla, queda como conflicto
+        Err(AppError::Database(sqlx::Error::Database(e))) if e.is_unique_violation() => {
+            eprintln!(
+                "⚠️ {} {} repite un valor único en el servidor ({}): se registra como conflicto",
+                entrada.tabla,
+                entrada.registro_id,
+                e.constraint().unwrap_or("sin nombre")
+            );
+            (Envio::Conflicto, true)
+        }
+        resultado => (resultado?, false),
+    };
+
+    match envio {
+        Envio::Conflicto => {
+            // En un valor repetido la fila del servidor es la otra, no la del mismo id (que no existe)
+            let datos_remotos = if repetido {
+                leer_fila_remota_repetida(postgres, tabla, &fila).await?
+            } else {
+                leer_fila_remota(postgres, tabla, &entrada.registro_id).await?
+            };
+            let version_remota = datos_remotos
+                .as_ref()
+                .and_then(|d| d.get("sync_version"))
+                .and_then(|v| v.as_i64())
+                .unwrap_or_default();
+
+            ConflictoRepository::registrar(sqlite, CreateConflicto {
+                tabla: tabla.local.to_string(),
+                registro_id: entrada.registro_id.clone(),
+                version_local: version_base(&fila),
+                version_remota,
+                datos_locales: serde_json::to_value(&fila)
+                    .map_err(|e| AppError::Internal(e.to_string()))?,
+                datos_remotos,
+            })
+            .await?;
+
+            Ok(Envio::Conflicto)
+        }
+        envio => Ok(envio),
+    }
+}
+
+/// Versión del servidor sobre la que se basa la fila local
+pub(crate) fn version_base(fila: &HashMap<String, Option<String>>) -> i64 {
+    fila.get("sync_version")
+        .cloned()
+        .flatten()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or_default()
+}
+
+/// Lee la fila remota completa como JSON (None si no existe)
+pub async fn leer_fila_remota(
+    postgres: &PgPool,
+    tabla: TablaSincronizada,
+    id: &str,
+) -> Result<Option<serde_json::Value>> {
+    let texto: Option<String> = sqlx::query_scalar(&format!(
+        "SELECT row_to_json(r)::text FROM \"{}\" r WHERE r.id::text = $1",
+        tabla.remota
+    ))
+    .bind(id)
+    .fetch_optional(postgres)
+    .await?;
+
+    texto
+        .map(|t| serde_json::from_str(&t).map_err(|e| AppError::Internal(e.to_string())))
+        .transpose()
+}
+
+/// Lee la fila remota con la que choca una fila local por su clave única (None si la tabla no la define)
+async fn leer_fila_remota_repetida(
+    postgres: &PgPool,
+    tabla: TablaSincronizada,
+    fila: &HashMap<String, Option<String>>,
+) -> Result<Option<serde_json::Value>> {
+    let Some(clave) = clave_unica(tabla.local) else {
+        return Ok(None);
+    };
+
+    let condiciones = clave
+        .iter()
+        .enumerate()
+        .map(|(i, c)| format!("r.\"{}\"::text = ${}", c, i + 1))
+        .collect::<Vec<_>>()
+        .join(" AND ");
+    let sql = format!(
+        "SELECT row_to_json(r)::text FROM \"{}\" r WHERE {} AND r.id::text <> ${} LIMIT 1",
+        tabla.remota,
+        condiciones,
+        clave.len() + 1
+    );
+
+    let mut query = sqlx::query_scalar::<_, String>(&sql);
+    for columna in clave {
+        query = query.bind(fila.get(*columna).cloned().flatten());
+    }
+    let texto = query
+        .bind(fila.get("id").cloned().flatten())
+        .fetch_optional(postgres)
+        .await?;
+
+    texto
+        .map(|t| serde_json::from_str(&t).map_err(|e| AppError::Internal(e.to_string())))
+        .transpose()
+}
+
+/// Lee una fila local con todas sus columnas como texto
+pub async fn leer_fila_local(
+    sqlite: &SqlitePool,
+    tabla: TablaSincronizada,
+    id: &str,
+) -> Result<Option<HashMap<String, Option<String>>>> {
+    let columnas = columnas_locales(sqlite, tabla.local).await?;
+
+    let select = columnas
+        .iter()
+        .map(|c| format!("CAST(\"{c}\" AS TEXT) AS \"{c}\"", c = c))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let fila = sqlx::query(&format!("SELECT {} FROM {} WHERE id = ?", select, tabla.local))
+        .bind(id)
+        .fetch_optional(sqlite)
+        .await?;
+
+    let Some(fila) = fila else {
+        return Ok(None);
+    };
+
+    let mut valores = HashMap::new();
+    for columna in columnas {
+        let valor: Option<String> = fila.try_get(columna.as_str())?;
+        valores.insert(columna, valor);
+    }
+
+    Ok(Some(valores))
+}
+
+/// Nombres de columnas de una tabla local
+pub async fn columnas_locales(sqlite: &SqlitePool, tabla: &str) -> Result<Vec<String>> {
+    let filas = sqlx::query(&format!("PRAGMA table_info({})", tabla))
+        .fetch_all(sqlite)
+        .await?;
+
+    filas
+        .iter()
+        .map(|f| f.try_get::<String, _>("name").map_err(AppError::from))
+        .collect()
+}
+
+/// Columnas y tipos de una tabla remota
+pub async fn columnas_remotas(postgres: &PgPool, tabla: &str) -> Result<Vec<ColumnaRemota>> {
+    let filas = sqlx::query(
+        r#"
+        SELECT column_name::text AS nombre, udt_name::text AS tipo
+        FROM information_schema.columns
+        WHERE table_schema = current_schema() AND table_name = $1
+        ORDER BY ordinal_position
+        "#
+    )
+    .bind(tabla)
+    .fetch_all(postgres)
+    .await?;
+
+    filas
+        .iter()
+        .map(|f| {
+            Ok(ColumnaRemota {
+                nombre: f.try_get("nombre")?,
+                tipo: f.try_get("tipo")?,
+            })
+        })
+        .collect()
+}
+
+/// INSERT ... ON CONFLICT (id) DO UPDATE con las columnas comunes a ambos esquemas
+/// Cada valor viaja como texto y se convierte al tipo remoto (uuid, timestamptz, enums, ...)
+/// Si la tabla remota lleva sync_version, sólo se actualiza cuando coincide con la versión base local:
+/// si el servidor tiene otra versión no se modifica nada y se devuelve Envio::Conflicto
+async fn upsert_remoto(
+    postgres: &PgPool,
+    tabla: TablaSincronizada,
+    columnas: &[ColumnaRemota],
+    fila: &HashMap<String, Option<String>>,
+) -> Result<Envio> {
+    let comunes: Vec<&ColumnaRemota> = columnas
+        .iter()
+        .filter(|c| c.nombre != "synced_at" && c.nombre != "sync_version" && fila.contains_key(&c.nombre))
+        .collect();
+    let versionada = columnas.iter().any(|c| c.nombre == "sync_version");
+
+    let mut nombres = comunes.iter().map(|c| format!("\"{}\"", c.nombre)).collect::<Vec<_>>();
+    let mut valores = comunes
+        .iter()
+        .enumerate()
+        .map(|(i, c)| format!("${}::text::\"{}\"", i + 1, c.tipo))
+        .collect::<Vec<_>>();
+    let mut actualizaciones = nombres
+        .iter()
+        .filter(|n| n.as_str() != "\"id\"")
+        .map(|n| format!("{n} = EXCLUDED.{n}", n = n))
+        .collect::<Vec<_>>();
+
+    if columnas.iter().any(|c| c.nombre == "synced_at") {
+        nombres.push("\"synced_at\"".to_string());
+        valores.push("NOW()".to_string());
+        actualizaciones.push("\"synced_at\" = NOW()".to_string());
+    }
+
+    let mut sql = format!(
+        "INSERT INTO \"{}\" AS r ({}",
+        tabla.remota,
+        nombres.join(", ")
+    );
+    if versionada {
+        // Alta: versión 1. Modificación: sólo si el servidor sigue en la versión base local
+        sql.push_str(&format!(
+            ", \"sync_version\") VALUES ({}, 1) ON CONFLICT (id) DO UPDATE SET {}, \"sync_version\" = r.sync_version + 1 \
+             WHERE r.sync_version = ${} RETURNING r.sync_version",
+            valores.join(", "),
+            actualizaciones.join(", "),
+            comunes.len() + 1
+        ));
+    } else {
+        sql.push_str(&format!(
+            ") VALUES ({}) ON CONFLICT (id) DO UPDATE SET {}",
+            valores.join(", "),
+            actualizaciones.join(", ")
+        ));
+    }
+
+    let mut tx = postgres.begin().await?;
+
+    // Las fechas locales sin zona horaria están en UTC (CURRENT_TIMESTAMP de SQLite)
+    sqlx::query("SET LOCAL TIME ZONE 'UTC'").execute(&mut *tx).await?;
+
+    let mut query = sqlx::query(&sql);
+    for columna in &comunes {
+        let valor = fila
+            .get(&columna.nombre)
+            .cloned()
+            .flatten()
+            .map(|v| valor_a_remoto(tabla.local, &columna.nombre, v));
+        query = query.bind(valor);
+    }
+
+    let resultado = if versionada {
+        let fila_version = query
+            .bind(version_base(fila) as i32)
+            .fetch_optional(&mut *tx)
+            .await?;
+        match fila_version {
+            Some(f) => Envio::Enviado(Some(f.try_get::<i32, _>(0)? as i64)),
+            None => Envio::Conflicto,
+        }
+    } else {
+        query.execute(&mut *tx).await?;
+        Envio::Enviado(None)
+    };
+
+    tx.commit().await?;
+    Ok(resultado)
+}
+
+/// Errores que indican que el servidor no está disponible (no son culpa del dato)
+pub fn es_error_de_conexion(error: &AppError) -> bool {
+    matches!(
+        error,
+        AppError::Database(sqlx::Error::Io(_))
+            | AppError::Database(sqlx::Error::PoolTimedOut)
+            | AppError::Database(sqlx::Error::PoolClosed)
+            | AppError::Database(sqlx::Error::Tls(_))
+    )
+}
@@ -0,0 +1,102 @@
+// Tablas que participan de la sincronización SQLite <-> PostgreSQL
+
+/// Tabla sincronizada: nombre local (SQLite) y remoto (PostgreSQL)
+#[derive(Debug, Clone, Copy)]
+pub struct TablaSincronizada {
+    pub local: &'static str,
+    pub remota: &'static str,
+    /// Condición sobre la fila remota `r` que limita la descarga a la zona de la instalación ($5)
+    /// None: la tabla se comparte entre todas las zonas
+    pub filtro_zona: Option<&'static str>,
+}
+
+/// Filas de la zona ($5) en tablas con zona_id
+const DE_LA_ZONA: &str = "r.zona_id::text = $5";
+
+/// Tablas sincronizadas, en orden de dependencias (padres antes que hijos)
+pub const TABLAS_SINCRONIZADAS: &[TablaSincronizada] = &[
+    TablaSincronizada { local: "zonas", remota: "zonas", filtro_zona: None },
+    TablaSincronizada { local: "agentes", remota: "agentes", filtro_zona: Some(DE_LA_ZONA) },
+    TablaSincronizada { local: "vehiculos", remota: "vehiculos", filtro_zona: Some(DE_LA_ZONA) },
+    TablaSincronizada { local: "proveedores", remota: "proveedores", filtro_zona: None },
+    TablaSincronizada { local: "expedientes", remota: "expedientes", filtro_zona: Some(DE_LA_ZONA) },
+    TablaSincronizada {
+        local: "tickets_combustible",
+        remota: "tickets",
+        filtro_zona: Some("r.vehiculo_id IN (SELECT v.id FROM vehiculos v WHERE v.zona_id::text = $5)"),
+    },
+    TablaSincronizada { local: "ordenes_compra", remota: "ordenes_compra", filtro_zona: Some(DE_LA_ZONA) },
+    TablaSincronizada {
+        local: "orden_compra_renglones",
+        remota: "orden_compra_renglones",
+        filtro_zona: Some("r.oc_id IN (SELECT oc.id FROM ordenes_compra oc WHERE oc.zona_id::text = $5)"),
+    },
+    TablaSincronizada {
+        local: "orden_compra_entregas",
+        remota: "orden_compra_entregas",
+        filtro_zona: Some("r.oc_id IN (SELECT oc.id FROM ordenes_compra oc WHERE oc.zona_id::text = $5)"),
+    },
+];
+
+/// Busca la configuración de una tabla por su nombre local
+pub fn tabla_sincronizada(local: &str) -> Option<TablaSincronizada> {
+    TABLAS_SINCRONIZADAS.iter().copied().find(|t| t.local == local)
+}
+
+/// Columnas únicas (además del id) de una tabla: con ellas se busca la otra fila que repite el valor
+/// cuando un alta choca con el índice único, en el servidor o en la base local
+pub fn clave_unica(tabla: &str) -> Option<&'static [&'static str]> {
+    match tabla {
+        "proveedores" => Some(&["cuit"]),
+        "ordenes_compra" => Some(&["zona_id", "año", "numero_oc"]),
+        _ => None,
+    }
+}
+
+/// Adapta un valor local al formato que espera PostgreSQL
+/// SQLite guarda los estados sin guión bajo (ENPROCESO) y el enum de PG los define con él (EN_PROCESO)
+pub fn valor_a_remoto(tabla: &str, columna: &str, valor: String) -> String {
+    match (tabla, columna, valor.as_str()) {
+        ("expedientes", "estado", "ENPROCESO") => "EN_PROCESO".to_string(),
+        ("expedientes", "estado", "ENREVISION") => "EN_REVISION".to_string(),
+        _ => valor,
+    }
+}
+
+/// Adapta un valor remoto (JSON de row_to_json) al formato local de SQLite
+/// Devuelve None para NULL; booleanos como 1/0 y objetos JSON serializados
+pub fn valor_a_local(tabla: &str, columna: &str, valor: &serde_json::Value) -> Option<String> {
+    use serde_json::Value;
+
+    match valor {
+        Value::Null => None,
+        Value::Bool(b) => Some(if *b { "1" } else { "0" }.to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::String(s) => Some(match (tabla, columna, s.as_str()) {
+            ("expedientes", "estado", "EN_PROCESO") => "ENPROCESO".to_string(),
+            ("expedientes", "estado", "EN_REVISION") => "ENREVISION".to_string(),
+            _ => s.clone(),
+        }),
+        otro => Some(otro.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adaptar_estado_expediente() {
+        assert_eq!(valor_a_remoto("expedientes", "estado", "ENPROCESO".to_string()), "EN_PROCESO");
+        assert_eq!(valor_a_remoto("expedientes", "estado", "INICIADO".to_string()), "INICIADO");
+        assert_eq!(valor_a_remoto("vehiculos", "estado", "ENPROCESO".to_string()), "ENPROCESO");
+    }
+
+    #[test]
+    fn test_adaptar_valores_remotos() {
+        let estado = serde_json::json!("EN_REVISION");
+        assert_eq!(valor_a_local("expedientes", "estado", &estado).as_deref(), Some("ENREVISION"));
+        assert_eq!(valor_a_local("ordenes_compra", "es_iva_inscripto", &serde_json::json!(true)).as_deref(), Some("1"));
+        assert_eq!(valor_a_local("agentes", "email", &serde_json::Value::Null), None);
+    }
+}
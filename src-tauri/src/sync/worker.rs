@@ -0,0 +1,278 @@
+// Tarea en segundo plano que sincroniza con PostgreSQL cuando hay conexión:
+// primero envía la outbox y después descarga los cambios remotos
+
+use std::time::Duration;
+
+use serde::Serialize;
+use sqlx::PgPool;
+
+use crate::db::DatabasePool;
+use crate::error::Result;
+use crate::sync::outbox::OutboxRepository;
+use crate::sync::pull::{descargar_cambios, ResumenPull};
+use crate::sync::push::{procesar_outbox, ResultadoPush};
+
+/// Intervalo entre pasadas del worker
+const INTERVALO_SINCRONIZACION: Duration = Duration::from_secs(30);
+
+/// Cantidad máxima de cambios enviados por pasada
+const LOTE_SINCRONIZACION: i64 = 200;
+
+/// Resultado de una pasada completa de sincronización
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ResultadoSincronizacion {
+    pub enviados: ResultadoPush,
+    /// Cambios descargados por tabla (vacío si se cortó el envío por falta de conexión)
+    pub recibidos: Vec<ResumenPull>,
+}
+
+/// Envía los cambios locales y descarga los remotos, con el turno de sincronización tomado
+/// Se envía primero para que los cambios propios no se detecten como conflicto al descargar
+pub async fn sincronizar(pools: &DatabasePool, postgres: &PgPool, limite: i64) -> Result<ResultadoSincronizacion> {
+    let _turno = pools.turno_sincronizacion().await;
+    let sqlite = pools.get_sqlite();
+
+    let enviados = procesar_outbox(sqlite, postgres, limite).await?;
+    if enviados.sin_conexion {
+        return Ok(ResultadoSincronizacion { enviados, recibidos: Vec::new() });
+    }
+
+    let recibidos = descargar_cambios(sqlite, postgres).await?;
+    Ok(ResultadoSincronizacion { enviados, recibidos })
+}
+
+/// Reintenta ya todos los cambios pendientes (ignora el backoff), con el turno de sincronización tomado
+pub async fn reintentar_pendientes(pools: &DatabasePool, postgres: &PgPool) -> Result<ResultadoPush> {
+    let _turno = pools.turno_sincronizacion().await;
+
+    OutboxRepository::reintentar_todo(pools.get_sqlite()).await?;
+    procesar_outbox(pools.get_sqlite(), postgres, i64::MAX).await
+}
+
+/// Lanza el worker de sincronización
+/// La outbox y las marcas de descarga viven en SQLite, así que todo se retoma después de un reinicio
+pub fn iniciar_worker_sincronizacion(pools: DatabasePool) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            if let Some(postgres) = pools.get_postgres() {
+                match sincronizar(&pools, &postgres, LOTE_SINCRONIZACION).await {
+                    Ok(resultado) => {
+                        let enviados = &resultado.enviados;
+                        if enviados.enviados > 0 || enviados.fallidos > 0 || enviados.conflictos > 0 {
+                            println!(
+                                "🔄 Sincronización: {} enviados, {} con error, {} en conflicto",
+                                enviados.enviados, enviados.fallidos, enviados.conflictos
+                            );
+                        }
+                        for tabla in resultado.recibidos.iter().filter(|t| t.insertados + t.actualizados > 0) {
+                            println!(
+                                "⬇️ {}: {} nuevos, {} actualizados",
+                                tabla.tabla, tabla.insertados, tabla.actualizados
+                            );
+                        }
+                        if enviados.sin_conexion {
+                            eprintln!("⚠️ Sincronización interrumpida: PostgreSQL no responde");
+                        }
+                    }
+                    Err(e) => eprintln!("❌ Error en sincronización: {}", e),
+                }
+            }
+
+            tokio::time::sleep(INTERVALO_SINCRONIZACION).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::init_databases;
+    use crate::db::test_util::captura;
+    use crate::repositories::ExpedienteRepository;
+    use crate::services::SettingsService;
+    use crate::sync::{ConflictoRepository, ResolucionConflicto};
+    use chrono::Utc;
+
+    /// El worker y "sincronizar ya" a la vez: cada cambio se envía una sola vez, sin conflictos falsos
+    #[tokio::test]
+    #[ignore = "necesita un PostgreSQL de prueba en TEST_POSTGRES_URL"]
+    async fn test_pasadas_simultaneas_no_duplican_envios() {
+        let url = std::env::var("TEST_POSTGRES_URL").expect("TEST_POSTGRES_URL");
+        let ruta = std::env::temp_dir().join(format!("gestor-test-sync-{}.db", uuid::Uuid::new_v4()));
+        let pools = DatabasePool::new(ruta.to_str().unwrap(), Some(&url)).await.unwrap();
+        init_databases(&pools).await.unwrap();
+        let postgres = pools.get_postgres().expect("PostgreSQL conectado");
+
+        let ids: Vec<String> = (0..20).map(|_| uuid::Uuid::new_v4().to_string()).collect();
+        for (i, id) in ids.iter().enumerate() {
+            sqlx::query("INSERT INTO proveedores (id, nombre, cuit, domicilio) VALUES (?, ?, ?, 'Malargüe')")
+                .bind(id)
+                .bind(format!("Proveedor {}", i))
+                .bind(format!("20-{}-3", &id[..8]))
+                .execute(pools.get_sqlite())
+                .await
+                .unwrap();
+        }
+
+        let (a, b) = tokio::join!(
+            sincronizar(&pools, &postgres, i64::MAX),
+            sincronizar(&pools, &postgres, i64::MAX)
+        );
+        let (a, b) = (a.unwrap(), b.unwrap());
+
+        assert_eq!(a.enviados.enviados + b.enviados.enviados, ids.len());
+        assert_eq!(a.enviados.conflictos + b.enviados.conflictos, 0);
+        assert!(ConflictoRepository::pendientes(pools.get_sqlite()).await.unwrap().is_empty());
+        assert!(OutboxRepository::pendientes(pools.get_sqlite(), Utc::now(), 100).await.unwrap().is_empty());
+
+        let remotos: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM proveedores WHERE id::text = ANY($1)")
+            .bind(&ids)
+            .fetch_one(&postgres)
+            .await
+            .unwrap();
+        assert_eq!(remotos, ids.len() as i64);
+
+        sqlx::query("DELETE FROM proveedores WHERE id::text = ANY($1)")
+            .bind(&ids)
+            .execute(&postgres)
+            .await
+            .unwrap();
+        pools.sqlite.close().await;
+        let _ = std::fs::remove_file(&ruta);
+    }
+
+    /// Un valor único que ya usa otra fila del servidor (como el número de OC tomado por dos puestos
+    /// sin conexión) queda como conflicto en vez de reintentarse para siempre
+    #[tokio::test]
+    #[ignore = "necesita un PostgreSQL de prueba en TEST_POSTGRES_URL"]
+    async fn test_valor_unico_repetido_queda_en_conflicto() {
+        let url = std::env::var("TEST_POSTGRES_URL").expect("TEST_POSTGRES_URL");
+        let ruta = std::env::temp_dir().join(format!("gestor-test-sync-{}.db", uuid::Uuid::new_v4()));
+        let pools = DatabasePool::new(ruta.to_str().unwrap(), Some(&url)).await.unwrap();
+        init_databases(&pools).await.unwrap();
+        let postgres = pools.get_postgres().expect("PostgreSQL conectado");
+
+        let remoto = uuid::Uuid::new_v4().to_string();
+        let local = uuid::Uuid::new_v4().to_string();
+        let cuit = format!("20-{}-3", &remoto[..8]);
+        sqlx::query("INSERT INTO proveedores (id, nombre, cuit, domicilio) VALUES ($1::uuid, 'Otro puesto', $2, 'Malargüe')")
+            .bind(&remoto)
+            .bind(&cuit)
+            .execute(&postgres)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO proveedores (id, nombre, cuit, domicilio) VALUES (?, 'Este puesto', ?, 'Malargüe')")
+            .bind(&local)
+            .bind(&cuit)
+            .execute(pools.get_sqlite())
+            .await
+            .unwrap();
+
+        let resultado = sincronizar(&pools, &postgres, i64::MAX).await.unwrap();
+
+        assert_eq!(resultado.enviados.fallidos, 0);
+        assert_eq!(resultado.enviados.conflictos, 1);
+        let conflictos = ConflictoRepository::pendientes(pools.get_sqlite()).await.unwrap();
+        assert_eq!(conflictos.len(), 1);
+        assert_eq!(conflictos[0].registro_id, local);
+        assert!(OutboxRepository::pendientes(pools.get_sqlite(), Utc::now(), 100).await.unwrap().is_empty());
+
+        sqlx::query("DELETE FROM proveedores WHERE id = $1::uuid")
+            .bind(&remoto)
+            .execute(&postgres)
+            .await
+            .unwrap();
+        pools.sqlite.close().await;
+        let _ = std::fs::remove_file(&ruta);
+    }
+
+    /// La OC que repite el número de otra del servidor no traba la descarga y, renumerada, se envía
+    #[tokio::test]
+    #[ignore = "necesita un PostgreSQL de prueba en TEST_POSTGRES_URL"]
+    async fn test_oc_con_numero_repetido_se_renumera() {
+        let url = std::env::var("TEST_POSTGRES_URL").expect("TEST_POSTGRES_URL");
+        let ruta = std::env::temp_dir().join(format!("gestor-test-sync-{}.db", uuid::Uuid::new_v4()));
+        let pools = DatabasePool::new(ruta.to_str().unwrap(), Some(&url)).await.unwrap();
+        init_databases(&pools).await.unwrap();
+        let postgres = pools.get_postgres().expect("PostgreSQL conectado");
+        let sqlite = pools.get_sqlite();
+        let zona_id = SettingsService::zona_activa(sqlite).await.unwrap();
+
+        // Año sin OC reales: los números de la prueba no chocan con otras
+        sqlx::query("DELETE FROM ordenes_compra WHERE zona_id = $1::uuid AND año = 1999")
+            .bind(&zona_id)
+            .execute(&postgres)
+            .await
+            .unwrap();
+        let nro = format!("{}-1-1999", &uuid::Uuid::new_v4().simple().to_string()[..6]);
+        let exp = ExpedienteRepository::upsert_from_infogov(sqlite, captura(&nro, "1999-03-01", "INICIADO")).await.unwrap();
+        sincronizar(&pools, &postgres, i64::MAX).await.unwrap();
+
+        let remoto = uuid::Uuid::new_v4().to_string();
+        let local = uuid::Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO ordenes_compra (id, numero_oc, expediente_id, forma_pago, tipo_contratacion, subtotal, iva, total, zona_id, año) \
+             VALUES ($1::uuid, '01/1999', $2::uuid, 'Transferencia', 'Contratación directa', 50, 10.5, 60.5, $3::uuid, 1999)",
+        )
+        .bind(&remoto)
+        .bind(&exp.id)
+        .bind(&zona_id)
+        .execute(&postgres)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO ordenes_compra (id, numero_oc, pedido_nro, fecha, expediente_id, forma_pago, tipo_contratacion, \
+             subtotal, iva, total, zona_id, año) VALUES (?, '01/1999', 1, '1999-03-01', ?, 'Transferencia', \
+             'Contratación directa', 100, 21, 121, ?, 1999)",
+        )
+        .bind(&local)
+        .bind(&exp.id)
+        .bind(&zona_id)
+        .execute(sqlite)
+        .await
+        .unwrap();
+
+        let resultado = sincronizar(&pools, &postgres, i64::MAX).await.unwrap();
+        assert_eq!(resultado.enviados.conflictos, 1);
+        let descarga_oc = resultado.recibidos.iter().find(|t| t.tabla == "ordenes_compra").unwrap();
+        assert_eq!(descarga_oc.error, None);
+        let conflictos = ConflictoRepository::pendientes(sqlite).await.unwrap();
+        assert_eq!(conflictos.len(), 1);
+        assert_eq!(conflictos[0].registro_id, local);
+        assert!(conflictos[0].valor_repetido);
+        assert_eq!(conflictos[0].datos_remotos.as_ref().unwrap()["id"], remoto.as_str());
+
+        ConflictoRepository::resolver(sqlite, &conflictos[0].id, &ResolucionConflicto::Renumerar).await.unwrap();
+        let resultado = sincronizar(&pools, &postgres, i64::MAX).await.unwrap();
+        assert_eq!((resultado.enviados.conflictos, resultado.enviados.fallidos), (0, 0));
+
+        // Las dos OC quedan en ambas bases, cada una con su número
+        let remotas: Vec<(String, String)> = sqlx::query_as(
+            "SELECT id::text, numero_oc FROM ordenes_compra WHERE zona_id = $1::uuid AND año = 1999 ORDER BY numero_oc",
+        )
+        .bind(&zona_id)
+        .fetch_all(&postgres)
+        .await
+        .unwrap();
+        assert_eq!(remotas, vec![(remoto.clone(), "01/1999".to_string()), (local.clone(), "02/1999".to_string())]);
+        let locales: Vec<(String, String)> =
+            sqlx::query_as("SELECT id, numero_oc FROM ordenes_compra WHERE año = 1999 ORDER BY numero_oc")
+                .fetch_all(sqlite)
+                .await
+                .unwrap();
+        assert_eq!(locales, remotas);
+
+        sqlx::query("DELETE FROM ordenes_compra WHERE zona_id = $1::uuid AND año = 1999")
+            .bind(&zona_id)
+            .execute(&postgres)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM expedientes WHERE id = $1::uuid")
+            .bind(&exp.id)
+            .execute(&postgres)
+            .await
+            .unwrap();
+        pools.sqlite.close().await;
+        let _ = std::fs::remove_file(&ruta);
+    }
+}
@@ -0,0 +1,309 @@
+// Conversión de la orden de compra a PDF
+// Dos motores intercambiables: LibreOffice (convierte la planilla completada) y el renderizador nativo (printpdf)
+// El motor se elige en tiempo de ejecución según la configuración (impresion.motor_pdf)
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use crate::error::{AppError, Result};
+use crate::models::configuracion::{MotorPdf, Settings};
+use crate::utils::excel_generator::OCExcelData;
+use crate::utils::pdf_generator::{self, OCPDFData};
+
+/// Tiempo máximo de una conversión con LibreOffice (la primera ejecución crea el perfil y tarda más)
+const LIMITE_LIBREOFFICE: Duration = Duration::from_secs(120);
+
+/// Motor que produce el PDF de una OC
+pub trait ConversorPdf: Send + Sync {
+    /// Nombre para mensajes y registros
+    fn nombre(&self) -> &'static str;
+
+    /// Generar `pdf` a partir de la planilla ya completada (`excel`) y de los datos de la OC
+    /// Cada motor usa la fuente que necesita: LibreOffice la planilla, el nativo los datos
+    fn convertir(&self, excel: &Path, datos: &OCExcelData, pdf: &Path) -> Result<()>;
+}
+
+/// Elegir el motor configurado
+/// En modo automático se usa LibreOffice si está instalado; si no, el renderizador nativo
+pub fn elegir_conversor(settings: &Settings) -> Result<Box<dyn ConversorPdf>> {
+    let ruta = settings.impresion.ruta_soffice.as_deref();
+    match settings.impresion.motor_pdf {
+        MotorPdf::LibreOffice => Ok(Box::new(LibreOffice::detectar(ruta)?)),
+        MotorPdf::Nativo => Ok(Box::new(RenderizadorNativo::new(settings))),
+        MotorPdf::Automatico => match LibreOffice::detectar(ruta) {
+            Ok(libreoffice) => Ok(Box::new(libreoffice)),
+            Err(_) => Ok(Box::new(RenderizadorNativo::new(settings))),
+        },
+    }
+}
+
+/// soffice --headless --convert-to pdf
+pub struct LibreOffice {
+    ejecutable: PathBuf,
+}
+
+impl LibreOffice {
+    const NOMBRE: &'static str = "LibreOffice";
+
+    /// Ubicar soffice: la ruta configurada o, sin ella, el PATH y las rutas de instalación habituales
+    pub fn detectar(ruta: Option<&str>) -> Result<Self> {
+        if let Some(ruta) = ruta {
+            let ejecutable = PathBuf::from(ruta);
+            if !ejecutable.is_file() {
+                return Err(AppError::ConversorNoDisponible {
+                    conversor: Self::NOMBRE,
+                    mensaje: format!("no existe {}", ejecutable.display()),
+                });
+            }
+            return Ok(LibreOffice { ejecutable });
+        }
+
+        let nombre = if cfg!(windows) { "soffice.exe" } else { "soffice" };
+        let en_path = std::env::var_os("PATH")
+            .map(|path| std::env::split_paths(&path).map(|dir| dir.join(nombre)).collect::<Vec<_>>())
+            .unwrap_or_default();
+        let habituales = [
+            "/usr/bin/soffice",
+            "/usr/lib/libreoffice/program/soffice",
+            "/opt/libreoffice/program/soffice",
+            "/Applications/LibreOffice.app/Contents/MacOS/soffice",
+            r"C:\Program Files\LibreOffice\program\soffice.exe",
+            r"C:\Program Files (x86)\LibreOffice\program\soffice.exe",
+        ];
+
+        en_path
+            .into_iter()
+            .chain(habituales.iter().map(PathBuf::from))
+            .find(|candidato| candidato.is_file())
+            .map(|ejecutable| LibreOffice { ejecutable })
+            .ok_or_else(|| AppError::ConversorNoDisponible {
+                conversor: Self::NOMBRE,
+                mensaje: "no se encontró soffice (instale LibreOffice o configure su ruta)".to_string(),
+            })
+    }
+
+    fn error(mensaje: String) -> AppError {
+        AppError::ConversionPdf { conversor: Self::NOMBRE, mensaje }
+    }
+}
+
+impl ConversorPdf for LibreOffice {
+    fn nombre(&self) -> &'static str {
+        Self::NOMBRE
+    }
+
+    fn convertir(&self, excel: &Path, _datos: &OCExcelData, pdf: &Path) -> Result<()> {
+        // Directorio y perfil propios: con LibreOffice abierto por el usuario la conversión no haría nada
+        let trabajo = std::env::temp_dir().join(format!("gestor-soffice-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&trabajo)?;
+        let resultado = self.convertir_en(excel, &trabajo, pdf);
+        let _ = std::fs::remove_dir_all(&trabajo);
+        resultado
+    }
+}
+
+impl LibreOffice {
+    fn convertir_en(&self, excel: &Path, trabajo: &Path, pdf: &Path) -> Result<()> {
+        let mut proceso = Command::new(&self.ejecutable)
+            .arg(format!("-env:UserInstallation={}", url_de_archivo(&trabajo.join("perfil"))))
+            .args(["--headless", "--norestore", "--convert-to", "pdf", "--outdir"])
+            .arg(trabajo)
+            .arg(excel)
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| AppError::ConversorNoDisponible {
+                conversor: Self::NOMBRE,
+                mensaje: format!("no se pudo ejecutar {}: {}", self.ejecutable.display(), e),
+            })?;
+
+        let inicio = Instant::now();
+        let estado = loop {
+            if let Some(estado) = proceso.try_wait()? {
+                break estado;
+            }
+            if inicio.elapsed() > LIMITE_LIBREOFFICE {
+                let _ = proceso.kill();
+                let _ = proceso.wait();
+                return Err(Self::error(format!(
+                    "la conversión superó los {} segundos",
+                    LIMITE_LIBREOFFICE.as_secs()
+                )));
+            }
+            std::thread::sleep(Duration::from_millis(100));
+        };
+
+        if !estado.success() {
+            let mut stderr = String::new();
+            if let Some(mut salida) = proceso.stderr.take() {
+                let _ = std::io::Read::read_to_string(&mut salida, &mut stderr);
+            }
+            return Err(Self::error(format!("soffice terminó con {}: {}", estado, stderr.trim())));
+        }
+
+        // soffice nombra la salida como la planilla con extensión .pdf
+        let generado = excel
+            .file_stem()
+            .map(|stem| trabajo.join(stem).with_extension("pdf"))
+            .filter(|generado| generado.is_file())
+            .ok_or_else(|| Self::error("soffice no generó el PDF".to_string()))?;
+
+        // rename falla entre discos distintos: se copia
+        std::fs::copy(&generado, pdf)?;
+        Ok(())
+    }
+}
+
+/// URL file:// para -env:UserInstallation (en Windows, file:///C:/...)
+fn url_de_archivo(ruta: &Path) -> String {
+    let ruta = ruta.to_string_lossy().replace('\\', "/");
+    if ruta.starts_with('/') {
+        format!("file://{}", ruta)
+    } else {
+        format!("file:///{}", ruta)
+    }
+}
+
+/// Renderizador propio: dibuja la OC con printpdf sin programas externos
+pub struct RenderizadorNativo {
+    settings: Settings,
+}
+
+impl RenderizadorNativo {
+    const NOMBRE: &'static str = "renderizador nativo";
+
+    pub fn new(settings: &Settings) -> Self {
+        RenderizadorNativo { settings: settings.clone() }
+    }
+}
+
+impl ConversorPdf for RenderizadorNativo {
+    fn nombre(&self) -> &'static str {
+        Self::NOMBRE
+    }
+
+    fn convertir(&self, _excel: &Path, datos: &OCExcelData, pdf: &Path) -> Result<()> {
+        let datos_pdf = OCPDFData {
+            numero_oc: datos.numero_oc.clone(),
+            pedido_nro: datos.pedido_nro,
+            destino: datos.destino.clone(),
+            fecha: datos.fecha.clone(),
+            expediente_numero: datos.expediente_numero.clone(),
+            expediente_año: datos.expediente_año,
+            resolucion_nro: datos.resolucion_nro.clone(),
+            tipo_contratacion: datos.tipo_contratacion.clone(),
+            señor: datos.señor.clone(),
+            domicilio: datos.domicilio.clone(),
+            cuit: datos.cuit.clone(),
+            descripcion_zona: datos.descripcion_zona.clone(),
+            renglones: datos
+                .renglones
+                .iter()
+                .map(|r| pdf_generator::OCRenglon {
+                    numero: r.numero,
+                    cantidad: r.cantidad,
+                    concepto: r.concepto.clone(),
+                    marca: r.marca.clone(),
+                    valor_unitario: r.valor_unitario,
+                    total: r.total,
+                })
+                .collect(),
+            subtotal: datos.subtotal,
+            iva: datos.iva,
+            total: datos.total,
+            total_en_letras: datos.total_en_letras.clone(),
+            forma_pago: datos.forma_pago.clone(),
+            plazo_entrega: datos.plazo_entrega.clone(),
+            es_iva_inscripto: datos.es_iva_inscripto,
+            institucion: self.settings.institucion.clone(),
+            zona: self.settings.zona.clone(),
+            tasas_iva: self.settings.iva.clone(),
+            anulada: datos.anulada,
+        };
+
+        pdf_generator::generar_pdf_oc(&datos_pdf, &datos.copias, datos.marca_agua, pdf)
+            .map_err(|mensaje| AppError::ConversionPdf { conversor: Self::NOMBRE, mensaje })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::orden_compra::Copia;
+    use crate::utils::excel_generator::OCRenglon;
+
+    fn datos() -> OCExcelData {
+        OCExcelData {
+            numero_oc: "125".to_string(),
+            pedido_nro: 7,
+            destino: "ZONA RIEGO MALARGUE".to_string(),
+            fecha: "Mendoza, 11 de febrero de 2026".to_string(),
+            expediente_numero: "1234-?-26".to_string(),
+            expediente_año: 2026,
+            resolucion_nro: None,
+            tipo_contratacion: "Contratación directa".to_string(),
+            señor: "Ferretería Sur".to_string(),
+            domicilio: "San Martín 100".to_string(),
+            cuit: "30-12345678-9".to_string(),
+            descripcion_zona: "Zona de riego".to_string(),
+            renglones: vec![OCRenglon {
+                numero: 1,
+                cantidad: 2.0,
+                concepto: "Caño PVC 110".to_string(),
+                marca: None,
+                valor_unitario: 50.0,
+                total: 100.0,
+            }],
+            subtotal: 100.0,
+            iva: 21.0,
+            total: 121.0,
+            total_en_letras: "ciento veintiuno".to_string(),
+            forma_pago: "Contado".to_string(),
+            plazo_entrega: "Inmediato".to_string(),
+            es_iva_inscripto: true,
+            alicuota_iva: "21%".to_string(),
+            copias: vec![Copia::Original, Copia::Duplicado],
+            marca_agua: true,
+            anulada: true,
+        }
+    }
+
+    #[test]
+    fn test_elegir_conversor() {
+        let mut settings = Settings::default();
+        settings.impresion.motor_pdf = MotorPdf::Nativo;
+        assert_eq!(elegir_conversor(&settings).unwrap().nombre(), RenderizadorNativo::NOMBRE);
+
+        // LibreOffice pedido explícitamente y ausente: error tipado, sin caer al nativo
+        settings.impresion.motor_pdf = MotorPdf::LibreOffice;
+        settings.impresion.ruta_soffice = Some("/no/existe/soffice".to_string());
+        assert!(matches!(
+            elegir_conversor(&settings),
+            Err(AppError::ConversorNoDisponible { conversor: "LibreOffice", .. })
+        ));
+
+        settings.impresion.motor_pdf = MotorPdf::Automatico;
+        assert_eq!(elegir_conversor(&settings).unwrap().nombre(), RenderizadorNativo::NOMBRE);
+    }
+
+    #[test]
+    fn test_renderizador_nativo_genera_pdf() {
+        let dir = std::env::temp_dir().join(format!("gestor-test-pdf-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let pdf = dir.join("OC-125.pdf");
+
+        RenderizadorNativo::new(&Settings::default())
+            .convertir(&dir.join("sin-planilla.xlsx"), &datos(), &pdf)
+            .unwrap();
+        let contenido = std::fs::read(&pdf).unwrap();
+        assert!(contenido.starts_with(b"%PDF"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_url_de_archivo() {
+        assert_eq!(url_de_archivo(Path::new("/tmp/perfil")), "file:///tmp/perfil");
+    }
+}
@@ -1,7 +1,13 @@
-use std::fs;
-use std::path::PathBuf;
+// Planilla de la orden de compra: se completa la plantilla MODELO_ORDEN_DE_COMPRA.xlsx con umya-spreadsheet
+// (la misma planilla se entrega como Excel o se convierte a PDF, ver conversor_pdf)
+// Cada copia pedida (ORIGINAL, DUPLICADO, TRIPLICADO) es una hoja del libro con su leyenda
+use std::path::Path;
 use serde::Serialize;
 
+use crate::error::{AppError, Result};
+use crate::models::orden_compra::Copia;
+
+#[derive(Clone)]
 pub struct OCExcelData {
     pub numero_oc: String,
     pub pedido_nro: i32,
@@ -23,9 +29,17 @@ pub struct OCExcelData {
     pub forma_pago: String,
     pub plazo_entrega: String,
     pub es_iva_inscripto: bool,
+    /// Alícuota configurada ("21%", "10.50%")
+    pub alicuota_iva: String,
+    /// Copias a incluir, en orden (al menos una)
+    pub copias: Vec<Copia>,
+    /// Leyenda de la copia repetida como marca de agua
+    pub marca_agua: bool,
+    /// OC anulada: se imprime igual, con el sello ANULADA
+    pub anulada: bool,
 }
 
-#[derive(Serialize)]
+#[derive(Clone, Serialize)]
 pub struct OCRenglon {
     pub numero: usize,
     pub cantidad: f64,
@@ -35,658 +49,306 @@ pub struct OCRenglon {
     pub total: f64,
 }
 
-pub fn generar_excel_oc(data: OCExcelData, template_path: &str, output_path: &str) -> Result<(), String> {
-    println!("📋 Copiando plantilla: {} -> {}", template_path, output_path);
-    
-    // Copiar la plantilla al archivo de salida
-    fs::copy(template_path, output_path)
-        .map_err(|e| format!("Error al copiar plantilla Excel: {}", e))?;
-    
-    println!("📖 Leyendo Excel con umya-spreadsheet...");
-    
-    // Leer y modificar usando umya-spreadsheet
-    let template_path_obj = std::path::Path::new(output_path);
-    let mut book = umya_spreadsheet::reader::xlsx::read(template_path_obj)
-        .map_err(|e| format!("Error al leer Excel: {:?}", e))?;
-    
-    println!("📊 Verificando hojas...");
-    
-    if book.get_sheet(0).is_err() {
-        return Err("La plantilla Excel no contiene hojas".to_string());
+/// Fila de la plantilla con el formato de los renglones (la primera de la tabla)
+const FILA_PRIMER_RENGLON: u32 = 26;
+
+/// Última fila del bloque ORIGINAL; debajo la plantilla trae un DUPLICADO y un TRIPLICADO fijos
+/// que se descartan: cada copia se arma como una hoja a partir del original
+const FIN_BLOQUE_ORIGINAL: u32 = 51;
+
+/// Completar en el lugar la planilla copiada de la plantilla
+/// Con más de un renglón se insertan filas debajo de la primera y se les copia su formato
+pub fn llenar_plantilla_oc(excel_path: &Path, data: &OCExcelData) -> Result<()> {
+    if data.copias.is_empty() {
+        return Err(AppError::Plantilla("no se pidió ninguna copia".to_string()));
     }
+
+    let mut book = umya_spreadsheet::reader::xlsx::read(excel_path)
+        .map_err(|e| AppError::Plantilla(format!("no se pudo leer {}: {:?}", excel_path.display(), e)))?;
+
+    let titulo = book
+        .get_sheet(0)
+        .map_err(|_| AppError::Plantilla("la plantilla no contiene hojas".to_string()))?
+        .get_title()
+        .to_string();
+
+    descartar_copias_de_plantilla(book.get_sheet_mut(0));
+
+    let renglones = data.renglones.len().max(1) as u32;
+    if renglones > 1 {
+        book.insert_new_row(&titulo, FILA_PRIMER_RENGLON + 1, renglones - 1);
+    }
+
     let sheet = book.get_sheet_mut(0);
-    
-    println!("✏️ Inyectando datos en celdas...");
-    
-    // Inyectar datos en las celdas correctas usando la notación de coordenadas
-    // Encabezado
-    set_cell(sheet, 2, 7, &data.numero_oc);
-    set_cell(sheet, 2, 8, &format!("{} / NRO.", data.pedido_nro));
-    set_cell(sheet, 3, 7, &data.destino);
-    set_cell(sheet, 4, 7, &data.fecha);
-    
-    // Expediente
-    set_cell(sheet, 7, 3, &format!("{}-{}", data.expediente_numero, data.expediente_año));
-    set_cell(sheet, 8, 3, &data.resolucion_nro.unwrap_or_default());
-    set_cell(sheet, 9, 3, &data.tipo_contratacion);
-    
+
+    // Cabecera
+    set_cell(sheet, 7, 2, &data.numero_oc);
+    set_cell(sheet, 7, 3, &data.pedido_nro.to_string());
+    set_cell(sheet, 6, 4, &data.destino.to_uppercase());
+    sheet.get_style_by_column_and_row_mut(6, 4).get_font_mut().set_bold(true);
+    set_cell(sheet, 5, 6, &data.fecha);
+
+    // Datos administrativos
+    set_cell(sheet, 1, 7, &format!("Expte. Nº {}", data.expediente_numero));
+    set_cell(
+        sheet,
+        1,
+        8,
+        &format!("Resolución interna Nº {}", data.resolucion_nro.as_deref().unwrap_or("S/N")),
+    );
+    set_cell(sheet, 1, 9, &data.tipo_contratacion);
+
     // Proveedor
-    set_cell(sheet, 11, 5, &data.señor);
-    set_cell(sheet, 12, 5, &data.domicilio);
-    set_cell(sheet, 13, 5, &data.cuit);
-    
-    // Descripción de zona
-    set_cell(sheet, 16, 3, &data.descripcion_zona);
-    
-    // Renglones (empiezan en fila 27)
-    let mut current_row = 27;
-    for renglon in &data.renglones {
-        set_cell(sheet, current_row, 2, &renglon.numero.to_string());
-        set_cell(sheet, current_row, 3, &format!("{:.2}", renglon.cantidad));
-        set_cell(sheet, current_row, 4, &renglon.concepto);
-        set_cell(sheet, current_row, 8, &renglon.marca.clone().unwrap_or_else(|| "-".to_string()));
-        set_cell(sheet, current_row, 9, &format!("{:.2}", renglon.valor_unitario));
-        set_cell(sheet, current_row, 10, &format!("{:.2}", renglon.total));
-        
-        current_row += 1;
+    set_cell(sheet, 3, 11, &data.señor.to_uppercase());
+    set_cell(sheet, 3, 12, &data.domicilio);
+    set_cell(sheet, 3, 13, &data.cuit);
+
+    // Renglones: las filas insertadas toman el formato de la primera
+    for fila in FILA_PRIMER_RENGLON + 1..FILA_PRIMER_RENGLON + renglones {
+        for col in 1..=6 {
+            let estilo = sheet.get_style_by_column_and_row(col, FILA_PRIMER_RENGLON).clone();
+            sheet.get_cell_by_column_and_row_mut(col, fila).set_style(estilo);
+        }
+        let alto = sheet.get_row_dimension(&FILA_PRIMER_RENGLON).map(|r| *r.get_height()).unwrap_or(0.0);
+        if alto > 0.0 {
+            sheet.get_row_dimension_mut(&fila).set_height(alto);
+        }
     }
-    
-    // Totales
-    let total_row = current_row + 2;
-    set_cell(sheet, total_row, 9, &format!("{:.2}", data.total));
-    
-    // Son pesos
-    set_cell(sheet, total_row + 2, 3, &data.total_en_letras);
-    
-    // Forma de pago y plazo
-    set_cell(sheet, total_row + 2, 5, &data.forma_pago);
-    set_cell(sheet, total_row + 3, 5, &data.plazo_entrega);
-    
-    // IVA
-    let iva_row = total_row + 6;
-    set_cell(sheet, iva_row, 9, &format!("{:.2}", data.subtotal));
-    
-    let iva_pct = if data.es_iva_inscripto { "21%" } else { "10.50%" };
-    set_cell(sheet, iva_row + 1, 3, iva_pct);
-    set_cell(sheet, iva_row + 1, 9, &format!("{:.2}", data.iva));
-    
-    set_cell(sheet, iva_row + 2, 9, &format!("{:.2}", data.total));
-    
-    println!("💾 Guardando Excel modificado...");
-    
-    // Guardar el archivo modificado
-    let output_path_obj = std::path::Path::new(output_path);
-    umya_spreadsheet::writer::xlsx::write(&book, output_path_obj)
-        .map_err(|e| format!("Error al guardar Excel: {:?}", e))?;
-    
-    // Verificar que el archivo existe y tiene contenido
-    let metadata = fs::metadata(output_path)
-        .map_err(|e| format!("Error al verificar Excel generado: {}", e))?;
-    
-    println!("✅ Excel guardado correctamente ({} bytes)", metadata.len());
-    
-    if metadata.len() < 1000 {
-        return Err(format!("El Excel generado es sospechosamente pequeño: {} bytes", metadata.len()));
+
+    // Importes como valores (no fórmulas): los conversores no siempre recalculan al abrir
+    for (i, renglon) in data.renglones.iter().enumerate() {
+        let fila = FILA_PRIMER_RENGLON + i as u32;
+        set_cell(sheet, 1, fila, &renglon.numero.to_string());
+        set_cell(sheet, 2, fila, &renglon.cantidad.to_string());
+        set_cell(sheet, 3, fila, &renglon.concepto);
+        sheet
+            .get_style_by_column_and_row_mut(3, fila)
+            .get_alignment_mut()
+            .set_wrap_text(true);
+        set_cell(sheet, 4, fila, renglon.marca.as_deref().unwrap_or("-"));
+        set_cell(sheet, 5, fila, &format!("{:.2}", renglon.valor_unitario));
+        set_cell(sheet, 6, fila, &format!("{:.2}", renglon.total));
     }
-    
-    Ok(())
-}
 
-fn set_cell(sheet: &mut umya_spreadsheet::Worksheet, row: u32, col: u32, value: &str) {
-    let cell_ref = format!("{}{}", column_to_letter(col), row);
-    let cell = sheet.get_cell_mut(cell_ref);
-    cell.set_value(value);
-}
+    // Totales (las filas debajo de la tabla se corrieron con los renglones insertados)
+    let desplazamiento = renglones - 1;
+    set_cell(sheet, 6, 27 + desplazamiento, &format!("{:.2}", data.total));
+    set_cell(sheet, 3, 29 + desplazamiento, &data.total_en_letras);
+    set_cell(sheet, 3, 30 + desplazamiento, &data.forma_pago);
+    set_cell(sheet, 3, 31 + desplazamiento, &data.plazo_entrega);
 
-fn column_to_letter(col: u32) -> String {
-    let mut col = col;
-    let mut result = String::new();
-    
-    while col > 0 {
-        col -= 1;
-        result.insert(0, (b'A' + (col % 26) as u8) as char);
-        col /= 26;
+    // Cuadro de IVA
+    set_cell(sheet, 5, 36 + desplazamiento, &format!("{:.2}", data.subtotal));
+    set_cell(sheet, 3, 37 + desplazamiento, &format!("I.V.A. {} ………………………………….", data.alicuota_iva));
+    set_cell(sheet, 5, 37 + desplazamiento, &format!("{:.2}", data.iva));
+    set_cell(sheet, 5, 38 + desplazamiento, &format!("{:.2}", data.total));
+
+    // Una hoja por copia: las demás se clonan de la primera ya completada
+    let modelo = sheet.clone();
+    for (i, copia) in data.copias.iter().enumerate() {
+        let hoja = if i == 0 {
+            book.get_sheet_mut(0)
+        } else {
+            book.add_sheet(modelo.clone())
+                .map_err(|e| AppError::Plantilla(format!("no se pudo agregar la hoja {}: {}", copia.etiqueta(), e)))?
+        };
+        rotular_copia(hoja, *copia, data, desplazamiento);
     }
-    
-    result
+
+    umya_spreadsheet::writer::xlsx::write(&book, excel_path)
+        .map_err(|e| AppError::Plantilla(format!("no se pudo guardar {}: {:?}", excel_path.display(), e)))?;
+
+    Ok(())
 }
 
-pub fn crear_script_powershell(excel_path: &str, pdf_path: &str) -> Result<PathBuf, String> {
-    let temp_dir = std::env::temp_dir();
-    let script_path = temp_dir.join("convert_to_pdf.ps1");
-    let log_path = temp_dir.join("convert_to_pdf.log");
-    
-    // Crear el script PowerShell que convierte Excel a PDF
-    let script_content = format!(
-        r#"$ErrorActionPreference = 'Stop'
-$excelFile = "{}"
-$pdfFile = "{}"
-$logFile = "{}"
-
-"[{{0}}] Inicio" -f (Get-Date) | Out-File -FilePath $logFile -Encoding UTF8
-"Excel: $excelFile" | Out-File -FilePath $logFile -Append -Encoding UTF8
-"PDF: $pdfFile" | Out-File -FilePath $logFile -Append -Encoding UTF8
-
-try {{
-    if (-Not (Test-Path $excelFile)) {{ throw "No existe el Excel temporal" }}
-    
-    $excelFileInfo = Get-Item $excelFile
-    "Tamaño Excel: {{0}} bytes" -f $excelFileInfo.Length | Out-File -FilePath $logFile -Append -Encoding UTF8
-    
-    $pdfDir = Split-Path -Parent $pdfFile
-    if (-Not (Test-Path $pdfDir)) {{ New-Item -ItemType Directory -Path $pdfDir | Out-Null }}
-
-    $excel = New-Object -ComObject Excel.Application
-    "Excel COM creado" | Out-File -FilePath $logFile -Append -Encoding UTF8
-    
-    $excel.Visible = $false
-    $excel.DisplayAlerts = $false
-    
-    $fullPath = (Resolve-Path $excelFile).Path
-    "Abriendo: $fullPath" | Out-File -FilePath $logFile -Append -Encoding UTF8
-    
-    # Parámetros explícitos para Open
-    $UpdateLinks = 0
-    $ReadOnly = $false
-    $Format = 5  # xlOpenXMLWorkbook
-    $Password = [Type]::Missing
-    $WriteResPassword = [Type]::Missing
-    $IgnoreReadOnlyRecommended = $true
-    $Origin = [Type]::Missing
-    $Delimiter = [Type]::Missing
-    $Editable = $false
-    $Notify = $false
-    $Converter = [Type]::Missing
-    $AddToMru = $false
-    $Local = $false
-    $CorruptLoad = [Type]::Missing
-    
-    $workbook = $excel.Workbooks.Open(
-        $fullPath,
-        $UpdateLinks,
-        $ReadOnly,
-        $Format,
-        $Password,
-        $WriteResPassword,
-        $IgnoreReadOnlyRecommended,
-        $Origin,
-        $Delimiter,
-        $Editable,
-        $Notify,
-        $Converter,
-        $AddToMru,
-        $Local,
-        $CorruptLoad
-    )
-    "Workbook abierto" | Out-File -FilePath $logFile -Append -Encoding UTF8
-    
-    $workbook.ExportAsFixedFormat(0, $pdfFile, 0, $true, $true)
-    "PDF exportado" | Out-File -FilePath $logFile -Append -Encoding UTF8
-    
-    $workbook.Close($false)
-    $excel.Quit()
-    [System.Runtime.InteropServices.Marshal]::ReleaseComObject($workbook) | Out-Null
-    [System.Runtime.InteropServices.Marshal]::ReleaseComObject($excel) | Out-Null
-    [System.GC]::Collect()
-    [System.GC]::WaitForPendingFinalizers()
-
-    Start-Sleep -Milliseconds 1500
-    if (-Not (Test-Path $pdfFile)) {{
-        throw "El PDF no fue generado correctamente"
-    }}
-
-    Remove-Item $excelFile -Force -ErrorAction SilentlyContinue
-    "[{{0}}] OK" -f (Get-Date) | Out-File -FilePath $logFile -Append -Encoding UTF8
-}} catch {{
-    "[{{0}}] ERROR: {{1}}" -f (Get-Date), $_.Exception.Message | Out-File -FilePath $logFile -Append -Encoding UTF8
-    "Exception Type: {{0}}" -f $_.Exception.GetType().FullName | Out-File -FilePath $logFile -Append -Encoding UTF8
-    if ($excel) {{
-        try {{ $excel.Quit() }} catch {{}}
-        [System.Runtime.InteropServices.Marshal]::ReleaseComObject($excel) | Out-Null
-    }}
-    Write-Error $_.Exception.Message
-    exit 1
-}}
-"#,
-        excel_path,
-        pdf_path,
-        log_path.to_string_lossy()
-    );
-    
-    fs::write(&script_path, script_content)
-        .map_err(|e| format!("Error al crear script PowerShell: {}", e))?;
-    
-    Ok(script_path)
+/// Dejar en la hoja sólo el bloque ORIGINAL (celdas, combinaciones, imágenes y saltos de página de las copias fijas)
+/// Las copias fijas son lo último de la hoja: no hay nada que correr, sólo se descarta
+/// (remove_row de umya no corre bien las combinaciones ni el alto de las filas)
+fn descartar_copias_de_plantilla(sheet: &mut umya_spreadsheet::Worksheet) {
+    sheet
+        .get_cell_collection_mut()
+        .retain(|celda| *celda.get_coordinate().get_row_num() <= FIN_BLOQUE_ORIGINAL);
+    sheet
+        .get_row_dimensions_mut()
+        .retain(|fila| *fila.get_row_num() <= FIN_BLOQUE_ORIGINAL);
+    sheet.get_merge_cells_mut().retain(|rango| {
+        rango
+            .get_coordinate_start_row()
+            .as_ref()
+            .is_none_or(|fila| *fila.get_num() <= FIN_BLOQUE_ORIGINAL)
+    });
+    sheet.get_row_breaks_mut().get_break_list_mut().clear();
+    // Las anclas de las imágenes cuentan las filas desde 0. umya sólo lee imágenes en anclas de dos celdas
+    // (las de una celda se pierden al abrir): por eso el logo de la plantilla está anclado de esa forma
+    let dibujo = sheet.get_worksheet_drawing_mut();
+    dibujo
+        .get_one_cell_anchor_collection_mut()
+        .retain(|ancla| (*ancla.get_from_marker().get_row() as u32) < FIN_BLOQUE_ORIGINAL - 1);
+    dibujo
+        .get_two_cell_anchor_collection_mut()
+        .retain(|ancla| (*ancla.get_from_marker().get_row() as u32) < FIN_BLOQUE_ORIGINAL - 1);
 }
 
-pub fn ejecutar_conversion_excel_a_pdf_con_datos(excel_path: &str, pdf_path: &str, data: OCExcelData) -> Result<(), String> {
-    use std::process::Command;
-    
-    let log_path = std::env::temp_dir().join("convert_to_pdf.log");
-    let script_path = crear_script_powershell_con_datos(excel_path, pdf_path, data)?;
-    
-    // Ejecutar PowerShell de forma oculta
-    let output = Command::new("powershell.exe")
-        .arg("-NoProfile")
-        .arg("-WindowStyle")
-        .arg("Hidden")
-        .arg("-ExecutionPolicy")
-        .arg("Bypass")
-        .arg("-File")
-        .arg(script_path.to_str().ok_or("Ruta de script inválida")?)
-        .output()
-        .map_err(|e| format!("Error al ejecutar PowerShell: {}", e))?;
-
-    if !output.status.success() {
-        let error_msg = String::from_utf8_lossy(&output.stderr);
-        let output_msg = String::from_utf8_lossy(&output.stdout);
-        let log_details = if log_path.exists() {
-            match fs::read_to_string(&log_path) {
-                Ok(content) => format!("\nLog PowerShell:\n{}", content),
-                Err(_) => "".to_string(),
-            }
-        } else {
-            "".to_string()
-        };
-        return Err(format!("Error en PowerShell: {}{}{}", error_msg, output_msg, log_details));
+/// Leyenda de la copia (C1), nombre de la hoja y, fuera del original, las líneas de firma del acuse de recibo
+fn rotular_copia(sheet: &mut umya_spreadsheet::Worksheet, copia: Copia, data: &OCExcelData, desplazamiento: u32) {
+    sheet.set_title(copia.etiqueta());
+    if data.anulada {
+        set_cell(sheet, 3, 1, &format!("{} - ANULADA", copia.etiqueta()));
+    } else {
+        set_cell(sheet, 3, 1, copia.etiqueta());
     }
 
-    let final_pdf_path = std::path::PathBuf::from(pdf_path);
-    let mut found = false;
-    for _ in 0..10 {
-        if final_pdf_path.exists() {
-            found = true;
-            break;
-        }
-        std::thread::sleep(std::time::Duration::from_millis(500));
+    if copia != Copia::Original {
+        set_cell(sheet, 1, 43 + desplazamiento, "FIRMA:");
+        set_cell(sheet, 1, 45 + desplazamiento, "ACLARACIÓN:");
+        set_cell(sheet, 1, 47 + desplazamiento, "D.N.I.:");
     }
 
-    if !found {
-        return Err("El PDF no fue generado correctamente".to_string());
+    // La planilla no admite una marca de agua girada: la leyenda va en el encabezado de página,
+    // en gris, o el sello ANULADA en rojo (tiene prioridad)
+    if data.anulada {
+        sheet
+            .get_header_footer_mut()
+            .get_odd_header_mut()
+            .set_value("&C&\"-,Bold\"&48&KC00000ANULADA");
+    } else if data.marca_agua {
+        sheet
+            .get_header_footer_mut()
+            .get_odd_header_mut()
+            .set_value(format!("&C&\"-,Bold\"&36&KBFBFBF{}", copia.etiqueta()));
     }
-
-    Ok(())
 }
 
-pub fn crear_script_powershell_con_datos(excel_path: &str, pdf_path: &str, data: OCExcelData) -> Result<PathBuf, String> {
-    let temp_dir = std::env::temp_dir();
-    let script_path = temp_dir.join("convert_to_pdf_with_data.ps1");
-    let log_path = temp_dir.join("convert_to_pdf.log");
-    
-    // Crear JSON de renglones para PowerShell
-    let mut renglones_ps = String::new();
-    for (idx, r) in data.renglones.iter().enumerate() {
-        renglones_ps.push_str(&format!(
-            "@{{num={}; cant={}; detalle='{}'; marca='{}'; precio={}; total={}}},\n        ",
-            idx + 1,
-            r.cantidad,
-            r.concepto.replace("'", "''"),
-            r.marca.as_ref().unwrap_or(&"-".to_string()).replace("'", "''"),
-            r.valor_unitario,
-            r.total
-        ));
-    }
-    renglones_ps = renglones_ps.trim_end_matches(",\n        ").to_string();
-    
-    let script_content = format!(
-        r#"# Configurar codificación UTF-8
-[Console]::OutputEncoding = [System.Text.Encoding]::UTF8
-$OutputEncoding = [System.Text.Encoding]::UTF8
-$PSDefaultParameterValues['*:Encoding'] = 'utf8'
-
-$ErrorActionPreference = 'Stop'
-$excelFile = "{}"
-$pdfFile = "{}"
-$logFile = "{}"
-
-"[{{0}}] Inicio" -f (Get-Date) | Out-File -FilePath $logFile -Encoding UTF8
-
-try {{
-    if (-Not (Test-Path $excelFile)) {{ throw "No existe el Excel temporal" }}
-    
-    $excel = New-Object -ComObject Excel.Application
-    "Excel COM creado" | Out-File -FilePath $logFile -Append -Encoding UTF8
-    
-    $excel.Visible = $false
-    $excel.DisplayAlerts = $false
-    
-    $fullPath = (Resolve-Path $excelFile).Path
-    "Abriendo: $fullPath" | Out-File -FilePath $logFile -Append -Encoding UTF8
-    
-    $workbook = $excel.Workbooks.Open($fullPath)
-    "Workbook abierto" | Out-File -FilePath $logFile -Append -Encoding UTF8
-    
-    $sheet = $workbook.Worksheets.Item(1)
-    "Sheet obtenido" | Out-File -FilePath $logFile -Append -Encoding UTF8
-    
-    # CABECERA
-    $sheet.Cells(2, 7) = "{}"  # G2: Solo el número
-    $sheet.Cells(3, 7) = "{}"  # G3: Nro Pedido
-    $sheet.Cells(4, 6) = "{}".ToUpper()  # F4: Destino en MAYÚSCULAS
-    $sheet.Cells(4, 6).Font.Bold = $true  # F4: Negrita
-    $sheet.Cells(6, 5) = "{}"  # E6: Fecha completa en español
-    
-    # DATOS ADMINISTRATIVOS
-    $sheet.Cells(7, 1) = "Expte. Nº {}"  # A7: Expediente completo
-    $sheet.Cells(8, 1) = "Resolución interna Nº {}"  # A8: Resolución
-    $sheet.Cells(9, 1) = "{}"  # A9: Tipo Contratación (sin prefijo)
-    
-    # PROVEEDOR
-    $sheet.Cells(11, 3) = "{}"  # C11: Señor (mayúsculas)
-    $sheet.Cells(12, 3) = "{}"  # C12: Domicilio
-    $sheet.Cells(13, 3) = "{}"  # C13: CUIT
-    
-    "Datos básicos escritos" | Out-File -FilePath $logFile -Append -Encoding UTF8
-    
-    # RENGLONES (tabla comienza en fila 26)
-    $renglones = @(
-        {}
-    )
-    
-    $numRenglones = $renglones.Count
-    $firstRenglon = 26
-    
-    # Si hay más de 1 renglón, necesitamos copiar la fila template
-    if ($numRenglones -gt 1) {{
-        # Copiar la fila 26 (template) para cada renglón adicional
-        for ($i = 1; $i -lt $numRenglones; $i++) {{
-            $targetRow = $firstRenglon + $i
-            # Copiar toda la fila 26 a la fila destino
-            $sheet.Rows($firstRenglon).Copy() | Out-Null
-            $sheet.Rows($targetRow).Insert(-4121) | Out-Null  # xlShiftDown = -4121
-        }}
-    }}
-    
-    # Normalizar bordes de todas las filas de renglones para que tengan el mismo grosor
-    $lastRenglon = $firstRenglon + $numRenglones - 1
-    for ($row = $firstRenglon; $row -le $lastRenglon; $row++) {{
-        for ($col = 1; $col -le 6; $col++) {{
-            $cell = $sheet.Cells($row, $col)
-            # Aplicar bordes uniformes a TODOS los lados (Weight = 2 es fino/normal)
-            $cell.Borders.Item(7).Weight = 2    # xlEdgeLeft
-            $cell.Borders.Item(8).Weight = 2    # xlEdgeTop
-            $cell.Borders.Item(9).Weight = 2    # xlEdgeBottom
-            $cell.Borders.Item(10).Weight = 2   # xlEdgeRight
-            $cell.Borders.Item(11).Weight = 2   # xlInsideVertical
-            $cell.Borders.Item(12).Weight = 2   # xlInsideHorizontal
-        }}
-    }}
-    
-    # Normalizar bordes del rango completo de la tabla
-    $tableRange = $sheet.Range($sheet.Cells($firstRenglon, 1), $sheet.Cells($lastRenglon, 6))
-    $tableRange.Borders.Weight = 2  # Normalizar todos los bordes del rango
-    
-    $currentRow = 26
-    foreach ($item in $renglones) {{
-        $sheet.Cells($currentRow, 1) = $item.num          # A: Índice
-        $sheet.Cells($currentRow, 2) = $item.cant         # B: Cantidad
-        $sheet.Cells($currentRow, 3) = $item.detalle      # C: Detalle
-        $sheet.Cells($currentRow, 3).WrapText = $true
-        $sheet.Cells($currentRow, 4) = $item.marca        # D: Marca
-        $sheet.Cells($currentRow, 5) = $item.precio       # E: Precio unitario
-        # La celda F ya tiene la fórmula del template, solo actualizarla
-        $fila = $currentRow
-        $sheet.Cells($currentRow, 6).Formula = "=B$fila*E$fila"
-        $currentRow++
-    }}
-    
-    "Renglones escritos" | Out-File -FilePath $logFile -Append -Encoding UTF8
-    
-    # TOTALES - Escribir en la fila después del último renglón
-    $totalRow = 26 + $numRenglones
-    $sheet.Cells($totalRow, 6) = {}  # Total numérico
-    $sheet.Cells($totalRow + 2, 3) = "{}"  # Total en letras
-    
-    "Totales escritos" | Out-File -FilePath $logFile -Append -Encoding UTF8
-    
-    $workbook.ExportAsFixedFormat(0, $pdfFile)
-    "PDF exportado" | Out-File -FilePath $logFile -Append -Encoding UTF8
-    
-    $workbook.Close($false)
-    $excel.Quit()
-    [System.Runtime.InteropServices.Marshal]::ReleaseComObject($workbook) | Out-Null
-    [System.Runtime.InteropServices.Marshal]::ReleaseComObject($sheet) | Out-Null
-    [System.Runtime.InteropServices.Marshal]::ReleaseComObject($excel) | Out-Null
-    [System.GC]::Collect()
-
-    if (-Not (Test-Path $pdfFile)) {{
-        throw "El PDF no fue generado"
-    }}
-
-    Remove-Item $excelFile -Force -ErrorAction SilentlyContinue
-    "[{{0}}] OK" -f (Get-Date) | Out-File -FilePath $logFile -Append -Encoding UTF8
-}} catch {{
-    "[{{0}}] ERROR: {{1}}" -f (Get-Date), $_.Exception.Message | Out-File -FilePath $logFile -Append -Encoding UTF8
-    if ($excel) {{
-        try {{ $excel.Quit() }} catch {{}}
-        [System.Runtime.InteropServices.Marshal]::ReleaseComObject($excel) | Out-Null
-    }}
-    Write-Error $_.Exception.Message
-    exit 1
-}}
-"#,
-        excel_path,
-        pdf_path,
-        log_path.to_string_lossy(),
-        data.numero_oc.replace("\"", "\"\""),
-        data.pedido_nro,
-        data.destino.replace("\"", "\"\""),
-        data.fecha.replace("\"", "\"\""),  // Fecha ya formateada en español desde el backend
-        data.expediente_numero.replace("\"", "\"\""),  // Expediente ya formateado completo
-        data.resolucion_nro.unwrap_or_else(|| "S/N".to_string()).replace("\"", "\"\""),
-        data.tipo_contratacion.replace("\"", "\"\""),
-        data.señor.to_uppercase().replace("\"", "\"\""),
-        data.domicilio.replace("\"", "\"\""),
-        data.cuit.replace("\"", "\"\""),
-        renglones_ps,
-        data.total,
-        data.total_en_letras.replace("\"", "\"\"")
-    );
-    
-    // Escribir script con UTF-8 BOM
-    let script_bytes = format!("\u{FEFF}{}", script_content);
-    fs::write(&script_path, script_bytes.as_bytes())
-        .map_err(|e| format!("Error al crear script PowerShell: {}", e))?;
-    
-    Ok(script_path)
+fn set_cell(sheet: &mut umya_spreadsheet::Worksheet, col: u32, row: u32, value: &str) {
+    sheet.get_cell_by_column_and_row_mut(col, row).set_value(value);
 }
 
-pub fn crear_script_powershell_excel_con_datos(excel_path: &str, data: OCExcelData) -> Result<PathBuf, String> {
-    let temp_dir = std::env::temp_dir();
-    let script_path = temp_dir.join("fill_excel_with_data.ps1");
-    let log_path = temp_dir.join("fill_excel_with_data.log");
-    
-    // Crear JSON de renglones para PowerShell
-    let mut renglones_ps = String::new();
-    for (idx, r) in data.renglones.iter().enumerate() {
-        renglones_ps.push_str(&format!(
-            "@{{num={}; cant={}; detalle='{}'; marca='{}'; precio={}; total={}}},\n        ",
-            idx + 1,
-            r.cantidad,
-            r.concepto.replace("'", "''"),
-            r.marca.as_ref().unwrap_or(&"-".to_string()).replace("'", "''"),
-            r.valor_unitario,
-            r.total
-        ));
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLANTILLA: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/resources/templates/MODELO_ORDEN_DE_COMPRA.xlsx");
+
+    fn renglon(numero: usize, concepto: &str, total: f64) -> OCRenglon {
+        OCRenglon {
+            numero,
+            cantidad: 1.0,
+            concepto: concepto.to_string(),
+            marca: None,
+            valor_unitario: total,
+            total,
+        }
     }
-    renglones_ps = renglones_ps.trim_end_matches(",\n        ").to_string();
-
-    let script_content = format!(
-        r#"# Configurar codificación UTF-8
-[Console]::OutputEncoding = [System.Text.Encoding]::UTF8
-$OutputEncoding = [System.Text.Encoding]::UTF8
-$PSDefaultParameterValues['*:Encoding'] = 'utf8'
-
-$ErrorActionPreference = 'Stop'
-$excelFile = "{}"
-$logFile = "{}"
-
-"[{{0}}] Inicio" -f (Get-Date) | Out-File -FilePath $logFile -Encoding UTF8
-
-try {{
-    if (-Not (Test-Path $excelFile)) {{ throw "No existe el Excel temporal" }}
-    
-    $excel = New-Object -ComObject Excel.Application
-    "Excel COM creado" | Out-File -FilePath $logFile -Append -Encoding UTF8
-    
-    $excel.Visible = $false
-    $excel.DisplayAlerts = $false
-    
-    $fullPath = (Resolve-Path $excelFile).Path
-    "Abriendo: $fullPath" | Out-File -FilePath $logFile -Append -Encoding UTF8
-    
-    $workbook = $excel.Workbooks.Open($fullPath)
-    "Workbook abierto" | Out-File -FilePath $logFile -Append -Encoding UTF8
-    
-    $sheet = $workbook.Worksheets.Item(1)
-    "Sheet obtenido" | Out-File -FilePath $logFile -Append -Encoding UTF8
-    
-    # CABECERA
-    $sheet.Cells(2, 7) = "{}"  # G2: Solo el número
-    $sheet.Cells(3, 7) = "{}"  # G3: Nro Pedido
-    $sheet.Cells(4, 6) = "{}".ToUpper()  # F4: Destino en MAYÚSCULAS
-    $sheet.Cells(4, 6).Font.Bold = $true  # F4: Negrita
-    $sheet.Cells(6, 5) = "{}"  # E6: Fecha completa en español
-    
-    # DATOS ADMINISTRATIVOS
-    $sheet.Cells(7, 1) = "Expte. Nº {}"  # A7: Expediente completo
-    $sheet.Cells(8, 1) = "Resolución interna Nº {}"  # A8: Resolución
-    $sheet.Cells(9, 1) = "{}"  # A9: Tipo Contratación (sin prefijo)
-    
-    # PROVEEDOR
-    $sheet.Cells(11, 3) = "{}"  # C11: Señor (mayúsculas)
-    $sheet.Cells(12, 3) = "{}"  # C12: Domicilio
-    $sheet.Cells(13, 3) = "{}"  # C13: CUIT
-    
-    "Datos básicos escritos" | Out-File -FilePath $logFile -Append -Encoding UTF8
-    
-    # RENGLONES (tabla comienza en fila 26)
-    $renglones = @(
-        {}
-    )
-    
-    $numRenglones = $renglones.Count
-    $firstRenglon = 26
-    
-    # Si hay más de 1 renglón, necesitamos copiar la fila template
-    if ($numRenglones -gt 1) {{
-        # Copiar la fila 26 (template) para cada renglón adicional
-        for ($i = 1; $i -lt $numRenglones; $i++) {{
-            $targetRow = $firstRenglon + $i
-            # Copiar toda la fila 26 a la fila destino
-            $sheet.Rows($firstRenglon).Copy() | Out-Null
-            $sheet.Rows($targetRow).Insert(-4121) | Out-Null  # xlShiftDown = -4121
-        }}
-    }}
-    
-    # Normalizar bordes de todas las filas de renglones para que tengan el mismo grosor
-    $lastRenglon = $firstRenglon + $numRenglones - 1
-    for ($row = $firstRenglon; $row -le $lastRenglon; $row++) {{
-        for ($col = 1; $col -le 6; $col++) {{
-            $cell = $sheet.Cells($row, $col)
-            # Aplicar bordes uniformes a TODOS los lados (Weight = 2 es fino/normal)
-            $cell.Borders.Item(7).Weight = 2    # xlEdgeLeft
-            $cell.Borders.Item(8).Weight = 2    # xlEdgeTop
-            $cell.Borders.Item(9).Weight = 2    # xlEdgeBottom
-            $cell.Borders.Item(10).Weight = 2   # xlEdgeRight
-            $cell.Borders.Item(11).Weight = 2   # xlInsideVertical
-            $cell.Borders.Item(12).Weight = 2   # xlInsideHorizontal
-        }}
-    }}
-    
-    # Normalizar bordes del rango completo de la tabla
-    $tableRange = $sheet.Range($sheet.Cells($firstRenglon, 1), $sheet.Cells($lastRenglon, 6))
-    $tableRange.Borders.Weight = 2  # Normalizar todos los bordes del rango
-    
-    $currentRow = 26
-    foreach ($item in $renglones) {{
-        $sheet.Cells($currentRow, 1) = $item.num          # A: Índice
-        $sheet.Cells($currentRow, 2) = $item.cant         # B: Cantidad
-        $sheet.Cells($currentRow, 3) = $item.detalle      # C: Detalle
-        $sheet.Cells($currentRow, 3).WrapText = $true
-        $sheet.Cells($currentRow, 4) = $item.marca        # D: Marca
-        $sheet.Cells($currentRow, 5) = $item.precio       # E: Precio unitario
-        # La celda F ya tiene la fórmula del template, solo actualizarla
-        $fila = $currentRow
-        $sheet.Cells($currentRow, 6).Formula = "=B$fila*E$fila"
-        $currentRow++
-    }}
-    
-    "Renglones escritos" | Out-File -FilePath $logFile -Append -Encoding UTF8
-    
-    # TOTALES - Escribir en la fila después del último renglón
-    $totalRow = 26 + $numRenglones
-    $sheet.Cells($totalRow, 6) = {}  # Total numérico
-    $sheet.Cells($totalRow + 2, 3) = "{}"  # Total en letras
-    
-    "Totales escritos" | Out-File -FilePath $logFile -Append -Encoding UTF8
-    
-    $workbook.Save()
-    "Excel guardado" | Out-File -FilePath $logFile -Append -Encoding UTF8
-    
-    $workbook.Close($true)
-    $excel.Quit()
-    [System.Runtime.InteropServices.Marshal]::ReleaseComObject($workbook) | Out-Null
-    [System.Runtime.InteropServices.Marshal]::ReleaseComObject($sheet) | Out-Null
-    [System.Runtime.InteropServices.Marshal]::ReleaseComObject($excel) | Out-Null
-    [System.GC]::Collect()
-
-    "[{{0}}] OK" -f (Get-Date) | Out-File -FilePath $logFile -Append -Encoding UTF8
-}} catch {{
-    "[{{0}}] ERROR: {{1}}" -f (Get-Date), $_.Exception.Message | Out-File -FilePath $logFile -Append -Encoding UTF8
-    if ($excel) {{
-        $excel.Quit()
-        [System.Runtime.InteropServices.Marshal]::ReleaseComObject($excel) | Out-Null
-    }}
-    Write-Error $_.Exception.Message
-    exit 1
-}}
-"#,
-        excel_path,
-        log_path.to_string_lossy(),
-        data.numero_oc.replace("\"", "\"\""),
-        data.pedido_nro,
-        data.destino.replace("\"", "\"\""),
-        data.fecha.replace("\"", "\"\""),
-        data.expediente_numero.replace("\"", "\"\""),
-        data.resolucion_nro.unwrap_or_else(|| "S/N".to_string()).replace("\"", "\"\""),
-        data.tipo_contratacion.replace("\"", "\"\""),
-        data.señor.to_uppercase().replace("\"", "\"\""),
-        data.domicilio.replace("\"", "\"\""),
-        data.cuit.replace("\"", "\"\""),
-        renglones_ps,
-        data.total,
-        data.total_en_letras.replace("\"", "\"\"")
-    );
-    
-    // Escribir script con UTF-8 BOM
-    let script_bytes = format!("\u{FEFF}{}", script_content);
-    fs::write(&script_path, script_bytes.as_bytes())
-        .map_err(|e| format!("Error al crear script PowerShell: {}", e))?;
-    
-    Ok(script_path)
-}
 
-pub fn ejecutar_llenado_excel_con_datos(excel_path: &str, data: OCExcelData) -> Result<(), String> {
-    use std::process::Command;
-    
-    let script_path = crear_script_powershell_excel_con_datos(excel_path, data)?;
-    
-    let output = Command::new("powershell.exe")
-        .arg("-NoProfile")
-        .arg("-WindowStyle")
-        .arg("Hidden")
-        .arg("-ExecutionPolicy")
-        .arg("Bypass")
-        .arg("-File")
-        .arg(script_path.to_str().ok_or("Ruta de script inválida")?)
-        .output()
-        .map_err(|e| format!("Error al ejecutar PowerShell: {}", e))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Error al ejecutar PowerShell: {}", stderr));
+    #[test]
+    fn test_llenar_plantilla_con_varios_renglones() {
+        let dir = std::env::temp_dir().join(format!("gestor-test-xlsx-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let excel = dir.join("OC-125.xlsx");
+        std::fs::copy(PLANTILLA, &excel).unwrap();
+
+        let data = OCExcelData {
+            numero_oc: "125".to_string(),
+            pedido_nro: 7,
+            destino: "Malargüe".to_string(),
+            fecha: "Mendoza, 11 de febrero de 2026".to_string(),
+            expediente_numero: "1234-?-26".to_string(),
+            expediente_año: 2026,
+            resolucion_nro: None,
+            tipo_contratacion: "Contratación directa".to_string(),
+            señor: "Ferretería Sur".to_string(),
+            domicilio: "San Martín 100".to_string(),
+            cuit: "30-12345678-9".to_string(),
+            descripcion_zona: String::new(),
+            renglones: vec![renglon(1, "Caño", 10.0), renglon(2, "Codo", 20.0), renglon(3, "Cupla", 30.0)],
+            subtotal: 60.0,
+            iva: 12.6,
+            total: 72.6,
+            total_en_letras: "setenta y dos con 60/100".to_string(),
+            forma_pago: "Contado".to_string(),
+            plazo_entrega: "Inmediato".to_string(),
+            es_iva_inscripto: true,
+            alicuota_iva: "21%".to_string(),
+            copias: vec![Copia::Original],
+            marca_agua: false,
+            anulada: false,
+        };
+        llenar_plantilla_oc(&excel, &data).unwrap();
+
+        let book = umya_spreadsheet::reader::xlsx::read(&excel).unwrap();
+        let sheet = book.get_sheet(0).unwrap();
+        let valor = |col, fila| sheet.get_cell_by_column_and_row(col, fila).map(|c| c.get_value().to_string()).unwrap_or_default();
+
+        assert_eq!(valor(7, 2), "125");
+        assert_eq!(valor(6, 4), "MALARGÜE");
+        assert_eq!(valor(1, 8), "Resolución interna Nº S/N");
+        assert_eq!(valor(3, 11), "FERRETERÍA SUR");
+        assert_eq!(valor(3, 28), "Cupla");
+        // Las filas del pie se corrieron dos lugares
+        assert_eq!(valor(5, 29), "TOTAL:");
+        assert_eq!(valor(6, 29), "72.60");
+        assert_eq!(valor(1, 31), "Son Pesos:");
+        assert_eq!(valor(3, 31), "setenta y dos con 60/100");
+        assert_eq!(valor(5, 40), "72.60");
+        // Las copias fijas de la plantilla ya no están
+        assert_eq!(book.get_sheet_count(), 1);
+        assert_eq!(valor(3, 54), "");
+        assert_eq!(valor(3, 102), "");
+
+        std::fs::remove_dir_all(&dir).unwrap();
     }
 
-    Ok(())
+    #[test]
+    fn test_una_hoja_por_copia() {
+        let dir = std::env::temp_dir().join(format!("gestor-test-xlsx-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let excel = dir.join("OC-126.xlsx");
+        std::fs::copy(PLANTILLA, &excel).unwrap();
+
+        let data = OCExcelData {
+            numero_oc: "126".to_string(),
+            pedido_nro: 8,
+            destino: "Malargüe".to_string(),
+            fecha: "Mendoza, 12 de febrero de 2026".to_string(),
+            expediente_numero: "1235-?-26".to_string(),
+            expediente_año: 2026,
+            resolucion_nro: None,
+            tipo_contratacion: "Contratación directa".to_string(),
+            señor: "Ferretería Sur".to_string(),
+            domicilio: "San Martín 100".to_string(),
+            cuit: "30-12345678-9".to_string(),
+            descripcion_zona: String::new(),
+            renglones: vec![renglon(1, "Caño", 10.0)],
+            subtotal: 10.0,
+            iva: 2.1,
+            total: 12.1,
+            total_en_letras: "doce con 10/100".to_string(),
+            forma_pago: "Contado".to_string(),
+            plazo_entrega: "Inmediato".to_string(),
+            es_iva_inscripto: true,
+            alicuota_iva: "21%".to_string(),
+            copias: Copia::TODAS.to_vec(),
+            marca_agua: true,
+            anulada: false,
+        };
+        llenar_plantilla_oc(&excel, &data).unwrap();
+
+        let book = umya_spreadsheet::reader::xlsx::read(&excel).unwrap();
+        assert_eq!(book.get_sheet_count(), 3);
+        for (i, copia) in Copia::TODAS.iter().enumerate() {
+            let sheet = book.get_sheet(i).unwrap();
+            let valor = |col, fila| sheet.get_cell_by_column_and_row(col, fila).map(|c| c.get_value().to_string()).unwrap_or_default();
+            assert_eq!(sheet.get_title(), copia.etiqueta());
+            assert_eq!(valor(3, 1), copia.etiqueta());
+            assert_eq!(valor(7, 2), "126");
+            assert_eq!(valor(1, 43) == "FIRMA:", *copia != Copia::Original);
+            assert!(sheet.get_header_footer().get_odd_header().get_value().ends_with(copia.etiqueta()));
+            // El logo de la plantilla (ancla de dos celdas en A1) se conserva en cada copia
+            assert_eq!(sheet.get_image_collection().len(), 1);
+            let logo = sheet.get_worksheet_drawing().get_two_cell_anchor_collection();
+            assert!(logo.iter().any(|ancla| *ancla.get_from_marker().get_row() == 0 && ancla.get_picture().is_some()));
+        }
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -0,0 +1,165 @@
+// Traductor de búsquedas del usuario a consultas FTS5
+// Soporta términos libres, frases entre comillas y filtros por campo (gde:GDEMZA, prov:"Estudio ABC")
+
+/// Columnas indexadas en expedientes_fts y sus alias de búsqueda
+const ALIAS_CAMPOS: &[(&str, &str)] = &[
+    ("numero", "numero"),
+    ("nro", "numero"),
+    ("asunto", "asunto"),
+    ("tema", "tema"),
+    ("resumen", "resumen"),
+    ("caratula", "caratula"),
+    ("infogov", "nro_infogov"),
+    ("nro_infogov", "nro_infogov"),
+    ("gde", "nro_gde"),
+    ("nro_gde", "nro_gde"),
+    ("obs", "observaciones"),
+    ("observaciones", "observaciones"),
+    ("prov", "proveedor"),
+    ("proveedor", "proveedor"),
+    ("señor", "proveedor"),
+    ("senor", "proveedor"),
+];
+
+/// Término individual de la búsqueda
+#[derive(Debug)]
+struct Termino {
+    campo: Option<&'static str>,
+    texto: String,
+    frase: bool,
+}
+
+/// Convierte el texto ingresado por el usuario en una expresión MATCH de FTS5
+/// Los términos se combinan con AND; las palabras sueltas buscan por prefijo
+/// Devuelve None si no queda ningún término buscable
+pub fn construir_consulta_fts(entrada: &str) -> Option<String> {
+    let partes: Vec<String> = separar_terminos(entrada)
+        .into_iter()
+        .filter(|t| t.texto.chars().any(|c| c.is_alphanumeric()))
+        .map(|t| {
+            let texto = format!("\"{}\"", t.texto.replace('"', "\"\""));
+            let texto = if t.frase { texto } else { format!("{}*", texto) };
+
+            match t.campo {
+                Some(campo) => format!("{} : {}", campo, texto),
+                None => texto,
+            }
+        })
+        .collect();
+
+    if partes.is_empty() {
+        None
+    } else {
+        Some(partes.join(" "))
+    }
+}
+
+/// Resuelve un alias de campo (gde, prov, ...) a la columna FTS
+fn resolver_campo(alias: &str) -> Option<&'static str> {
+    let alias = alias.to_lowercase();
+    ALIAS_CAMPOS
+        .iter()
+        .find(|(nombre, _)| *nombre == alias)
+        .map(|(_, columna)| *columna)
+}
+
+/// Separa la entrada en términos respetando comillas y prefijos campo:
+fn separar_terminos(entrada: &str) -> Vec<Termino> {
+    let mut terminos = Vec::new();
+    let mut chars = entrada.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        if chars.peek().is_none() {
+            break;
+        }
+
+        // Leer una palabra hasta espacio, comillas o ':'
+        let mut palabra = String::new();
+        while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != '"' && *c != ':') {
+            palabra.push(c);
+        }
+
+        let mut campo = None;
+        if chars.peek() == Some(&':') {
+            match resolver_campo(&palabra) {
+                Some(columna) => {
+                    chars.next();
+                    campo = Some(columna);
+                    palabra.clear();
+                    while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != '"') {
+                        palabra.push(c);
+                    }
+                }
+                None => {
+                    // No es un campo conocido: el ':' forma parte del término (ej. "12:30")
+                    while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != '"') {
+                        palabra.push(c);
+                    }
+                }
+            }
+        }
+
+        if palabra.is_empty() && chars.peek() == Some(&'"') {
+            chars.next();
+            let mut frase = String::new();
+            for c in chars.by_ref() {
+                if c == '"' {
+                    break;
+                }
+                frase.push(c);
+            }
+            terminos.push(Termino { campo, texto: frase.trim().to_string(), frase: true });
+            continue;
+        }
+
+        if palabra.is_empty() {
+            // Comilla suelta u otro separador: descartar y seguir
+            if campo.is_none() {
+                chars.next();
+            }
+            continue;
+        }
+
+        terminos.push(Termino { campo, texto: palabra, frase: false });
+    }
+
+    terminos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_terminos_libres_con_prefijo() {
+        assert_eq!(
+            construir_consulta_fts("reparacion hilux").unwrap(),
+            "\"reparacion\"* \"hilux\"*"
+        );
+    }
+
+    #[test]
+    fn test_filtros_por_campo() {
+        assert_eq!(
+            construir_consulta_fts("gde:GDEMZA prov:\"Estudio ABC\"").unwrap(),
+            "nro_gde : \"GDEMZA\"* proveedor : \"Estudio ABC\""
+        );
+        // Campo desconocido: se busca el texto completo
+        assert_eq!(construir_consulta_fts("hora:12").unwrap(), "\"hora:12\"*");
+    }
+
+    #[test]
+    fn test_entrada_sin_terminos() {
+        assert_eq!(construir_consulta_fts("   "), None);
+        assert_eq!(construir_consulta_fts("\" - \""), None);
+    }
+
+    #[test]
+    fn test_comilla_sin_cerrar() {
+        assert_eq!(
+            construir_consulta_fts("prov:\"Estudio").unwrap(),
+            "proveedor : \"Estudio\""
+        );
+    }
+}
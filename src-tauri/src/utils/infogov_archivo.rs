@@ -0,0 +1,220 @@
+// Lectura de la bandeja de InfoGov exportada como planilla (CSV o XLSX)
+// Las celdas se devuelven como texto y las columnas se identifican igual que en la grilla HTML
+
+use std::path::Path;
+
+use chrono::{Duration, NaiveDate};
+use umya_spreadsheet::Cell;
+
+use crate::utils::infogov_html::lote_desde_celdas;
+use crate::utils::infogov_parser::FilaInfoGov;
+
+/// Lee la planilla y arma el lote con las columnas detectadas por encabezado
+pub fn lote_desde_archivo(ruta: &Path) -> Result<Vec<FilaInfoGov>, String> {
+    let filas = leer_planilla(ruta)?;
+    lote_desde_celdas(filas).ok_or_else(|| {
+        "No se reconocieron las columnas de la bandeja (se necesitan el nro de InfoGov y el nro GDE)".to_string()
+    })
+}
+
+/// Celdas de la primera hoja (XLSX) o del archivo completo (CSV), según la extensión
+pub fn leer_planilla(ruta: &Path) -> Result<Vec<Vec<String>>, String> {
+    let extension = ruta
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    match extension.as_str() {
+        "xlsx" | "xlsm" => leer_xlsx(ruta),
+        "csv" | "txt" => {
+            let bytes = std::fs::read(ruta).map_err(|e| format!("No se pudo leer {}: {}", ruta.display(), e))?;
+            Ok(separar_csv(&decodificar_texto(&bytes)))
+        }
+        otra => Err(format!("Formato de archivo no soportado: .{} (usar CSV o XLSX)", otra)),
+    }
+}
+
+fn leer_xlsx(ruta: &Path) -> Result<Vec<Vec<String>>, String> {
+    // El lector de umya entra en pánico con algunos atributos vacíos: se informa como error de lectura
+    let book = std::panic::catch_unwind(|| umya_spreadsheet::reader::xlsx::read(ruta))
+        .map_err(|_| format!("No se pudo interpretar {} como planilla XLSX", ruta.display()))?
+        .map_err(|e| format!("No se pudo abrir {}: {:?}", ruta.display(), e))?;
+    let sheet = book.get_sheet(0).map_err(|e| format!("La planilla no tiene hojas: {}", e))?;
+
+    let mut filas: Vec<Vec<String>> = Vec::new();
+    for cell in sheet.get_cell_collection() {
+        let fila = *cell.get_coordinate().get_row_num() as usize;
+        let columna = *cell.get_coordinate().get_col_num() as usize;
+        if fila == 0 || columna == 0 {
+            continue;
+        }
+
+        if filas.len() < fila {
+            filas.resize(fila, Vec::new());
+        }
+        let celdas = &mut filas[fila - 1];
+        if celdas.len() < columna {
+            celdas.resize(columna, String::new());
+        }
+        celdas[columna - 1] = valor_celda(cell);
+    }
+
+    Ok(filas)
+}
+
+/// Texto de la celda; las fechas guardadas como número de serie se pasan a DD/MM/YYYY
+fn valor_celda(cell: &Cell) -> String {
+    let valor = cell.get_value().trim().to_string();
+    let es_fecha = cell
+        .get_style()
+        .get_number_format()
+        .as_ref()
+        .map(|formato| es_formato_fecha(*formato.get_number_format_id(), formato.get_format_code()))
+        .unwrap_or(false);
+
+    match valor.parse::<f64>() {
+        Ok(serie) if es_fecha => fecha_desde_serie(serie).unwrap_or(valor),
+        _ => valor,
+    }
+}
+
+/// Formatos de fecha integrados de Excel (14-22) o códigos con día y año
+fn es_formato_fecha(id: u32, codigo: &str) -> bool {
+    let codigo = codigo.to_lowercase();
+    (14..=22).contains(&id) || (codigo.contains('d') && codigo.contains('y'))
+}
+
+/// Número de serie de Excel (días desde el 30/12/1899) a DD/MM/YYYY
+fn fecha_desde_serie(serie: f64) -> Option<String> {
+    if !(1.0..2_958_466.0).contains(&serie) {
+        return None;
+    }
+    let base = NaiveDate::from_ymd_opt(1899, 12, 30)?;
+    let fecha = base.checked_add_signed(Duration::days(serie.trunc() as i64))?;
+    Some(fecha.format("%d/%m/%Y").to_string())
+}
+
+/// UTF-8 (con o sin BOM); si no lo es, Windows-1252/Latin-1 como exporta Excel en Windows
+fn decodificar_texto(bytes: &[u8]) -> String {
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    match std::str::from_utf8(bytes) {
+        Ok(texto) => texto.to_string(),
+        Err(_) => bytes.iter().map(|&b| b as char).collect(),
+    }
+}
+
+/// Separa un CSV respetando comillas ("" escapa una comilla y los saltos de línea quedan en la celda)
+/// El separador (; , o tabulación) se detecta en la primera línea
+fn separar_csv(texto: &str) -> Vec<Vec<String>> {
+    let separador = detectar_separador(texto);
+    let mut filas = Vec::new();
+    let mut fila = Vec::new();
+    let mut celda = String::new();
+    let mut entre_comillas = false;
+    let mut caracteres = texto.chars().peekable();
+
+    while let Some(c) = caracteres.next() {
+        match c {
+            '"' if entre_comillas && caracteres.peek() == Some(&'"') => {
+                celda.push('"');
+                caracteres.next();
+            }
+            '"' => entre_comillas = !entre_comillas,
+            c if c == separador && !entre_comillas => fila.push(std::mem::take(&mut celda).trim().to_string()),
+            '\r' if !entre_comillas => {}
+            '\n' if !entre_comillas => {
+                fila.push(std::mem::take(&mut celda).trim().to_string());
+                filas.push(std::mem::take(&mut fila));
+            }
+            c => celda.push(c),
+        }
+    }
+
+    if !celda.is_empty() || !fila.is_empty() {
+        fila.push(celda.trim().to_string());
+        filas.push(fila);
+    }
+
+    filas
+}
+
+fn detectar_separador(texto: &str) -> char {
+    let primera = texto.lines().find(|l| !l.trim().is_empty()).unwrap_or_default();
+    [';', '\t', ',']
+        .into_iter()
+        .max_by_key(|s| primera.matches(*s).count())
+        .filter(|s| primera.contains(*s))
+        .unwrap_or(',')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_con_comillas_y_punto_y_coma() {
+        let texto = "Nro InfoGov;Tema;Fecha Pase;Nro GDE\r\n\
+                     817619-30-2026;\"Reparación; embrague\nToyota \"\"Hilux\"\"\";18/02/2026;EX-2026-01216856-GDEMZA-DGIRR\r\n";
+        let filas = separar_csv(texto);
+        assert_eq!(filas.len(), 2);
+        assert_eq!(filas[1][1], "Reparación; embrague\nToyota \"Hilux\"");
+        assert_eq!(filas[1][3], "EX-2026-01216856-GDEMZA-DGIRR");
+    }
+
+    #[test]
+    fn test_csv_latin1() {
+        let bytes = b"Tema,A\xf1o\nReparaci\xf3n,2026\n";
+        let filas = separar_csv(&decodificar_texto(bytes));
+        assert_eq!(filas[0], vec!["Tema", "Año"]);
+        assert_eq!(filas[1][0], "Reparación");
+    }
+
+    #[test]
+    fn test_fecha_desde_serie() {
+        assert_eq!(fecha_desde_serie(46071.0).as_deref(), Some("18/02/2026"));
+        assert_eq!(fecha_desde_serie(46071.75).as_deref(), Some("18/02/2026"));
+        assert_eq!(fecha_desde_serie(-3.0), None);
+    }
+
+    #[test]
+    fn test_leer_xlsx_exportado() {
+        let mut book = umya_spreadsheet::new_file();
+        let sheet = book.get_sheet_mut(0);
+        for (coordenada, valor) in [
+            ("A1", "Nro. InfoGov"),
+            ("B1", "Asunto"),
+            ("C1", "Fecha Pase"),
+            ("D1", "Nro. GDE"),
+            ("E1", "Estado"),
+            ("A2", "817619-30-2026"),
+            ("B2", "Service Hilux"),
+            ("C2", "46071"),
+            ("D2", "EX-2026-01216856-GDEMZA-DGIRR"),
+            ("E2", "Contratación Directa"),
+        ] {
+            sheet.get_cell_mut(coordenada).set_value(valor);
+        }
+        sheet.get_style_mut("C2").get_number_format_mut().set_format_code("dd/mm/yyyy");
+        // umya 0.4 escribe los márgenes vacíos de un libro nuevo y después no los puede leer
+        sheet
+            .get_page_margins_mut()
+            .set_left(0.7)
+            .set_right(0.7)
+            .set_top(0.75)
+            .set_bottom(0.75)
+            .set_header(0.3)
+            .set_footer(0.3);
+
+        let ruta = std::env::temp_dir().join(format!("bandeja_{}.xlsx", uuid::Uuid::new_v4()));
+        umya_spreadsheet::writer::xlsx::write(&book, &ruta).unwrap();
+        let filas = lote_desde_archivo(&ruta);
+        std::fs::remove_file(&ruta).ok();
+
+        let filas = filas.unwrap();
+        assert_eq!(filas.len(), 1);
+        let exp = filas[0].resultado.as_ref().unwrap();
+        assert_eq!(exp.nro_infogov, "817619-30-2026");
+        assert_eq!(exp.fecha_pase, "2026-02-18");
+        assert_eq!(exp.estado, "Contratación Directa");
+    }
+}
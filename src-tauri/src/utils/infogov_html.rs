@@ -0,0 +1,241 @@
+// Parser de la grilla de InfoGov copiada como HTML (text/html del portapapeles)
+// Las columnas se identifican por el texto del encabezado, no por su posición
+// (la misma identificación se usa para las planillas exportadas, ver infogov_archivo)
+
+use regex::Regex;
+
+use crate::utils::infogov_parser::{FilaInfoGov, InfoGovExpediente};
+
+/// Campos de InfoGovExpediente que se pueden leer de una columna
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Columna {
+    NroGde,
+    NroInfoGov,
+    /// Cuando InfoGov separa el número en tres columnas (número, código, año)
+    Numero,
+    Codigo,
+    Anio,
+    FechaPase,
+    Tema,
+    Estado,
+    Oficina,
+}
+
+/// Claves de cada columna en orden de prioridad ("nro gde" debe ganar sobre "nro")
+const ENCABEZADOS: &[(Columna, &[&str])] = &[
+    (Columna::NroGde, &["gde", "nro gde", "expediente gde", "ex gde"]),
+    (Columna::NroInfoGov, &["nro infogov", "infogov", "nro expediente", "expediente", "nro"]),
+    (Columna::Numero, &["numero", "n°", "nº"]),
+    (Columna::Codigo, &["codigo", "cod", "cod."]),
+    (Columna::Anio, &["año", "anio"]),
+    (Columna::FechaPase, &["fecha pase", "fecha de pase", "fecha", "ingreso", "fecha ingreso"]),
+    (Columna::Tema, &["tema", "asunto", "extracto", "caratula", "descripcion"]),
+    (Columna::Estado, &["estado", "situacion"]),
+    (Columna::Oficina, &["oficina", "ubicacion", "bandeja"]),
+];
+
+/// Parsea la primera tabla del HTML con encabezados reconocibles
+/// Devuelve None si no hay una tabla de InfoGov (se usa entonces el parser de texto)
+pub fn lote_desde_html(html: &str) -> Option<Vec<FilaInfoGov>> {
+    let fila_re = Regex::new(r"(?is)<tr\b[^>]*>(.*?)</tr\s*>").expect("regex fila");
+    let celda_re = Regex::new(r"(?is)<t([hd])\b[^>]*>(.*?)</t[hd]\s*>").expect("regex celda");
+
+    let filas: Vec<Vec<String>> = fila_re
+        .captures_iter(html)
+        .map(|fila| celda_re.captures_iter(&fila[1]).map(|c| texto_celda(&c[2])).collect())
+        .collect();
+
+    lote_desde_celdas(filas)
+}
+
+/// Arma el lote a partir de filas de celdas de texto
+/// La primera fila con encabezados reconocibles define las columnas; las vacías se ignoran
+pub fn lote_desde_celdas(filas: Vec<Vec<String>>) -> Option<Vec<FilaInfoGov>> {
+    let filas: Vec<Vec<String>> = filas
+        .into_iter()
+        .filter(|celdas| celdas.iter().any(|c| !c.trim().is_empty()))
+        .collect();
+
+    let posicion = filas.iter().position(|celdas| mapear_encabezados(celdas).is_some())?;
+    let columnas = mapear_encabezados(&filas[posicion])?;
+
+    Some(
+        filas[posicion + 1..]
+            .iter()
+            .enumerate()
+            .map(|(i, celdas)| FilaInfoGov {
+                fila: i + 1,
+                texto: celdas.join("\t"),
+                resultado: expediente_desde_celdas(&columnas, celdas),
+            })
+            .collect(),
+    )
+}
+
+/// Asigna una columna a cada encabezado; exige poder armar el nro_infogov y el nro_gde
+fn mapear_encabezados(celdas: &[String]) -> Option<Vec<Option<Columna>>> {
+    let mut usadas: Vec<Columna> = Vec::new();
+    let columnas: Vec<Option<Columna>> = celdas
+        .iter()
+        .map(|celda| {
+            let encabezado = normalizar(celda);
+            let columna = ENCABEZADOS
+                .iter()
+                .filter(|(columna, _)| !usadas.contains(columna))
+                .find(|(_, claves)| claves.iter().any(|clave| coincide(&encabezado, clave)))
+                .map(|(columna, _)| *columna);
+            if let Some(c) = columna {
+                usadas.push(c);
+            }
+            columna
+        })
+        .collect();
+
+    let tiene_numero = usadas.contains(&Columna::NroInfoGov)
+        || [Columna::Numero, Columna::Codigo, Columna::Anio].iter().all(|c| usadas.contains(c));
+    (tiene_numero && usadas.contains(&Columna::NroGde)).then_some(columnas)
+}
+
+fn expediente_desde_celdas(columnas: &[Option<Columna>], celdas: &[String]) -> Result<InfoGovExpediente, String> {
+    let valor = |buscada: Columna| -> Option<&str> {
+        columnas
+            .iter()
+            .position(|c| *c == Some(buscada))
+            .and_then(|i| celdas.get(i))
+            .map(|s| s.as_str())
+            .filter(|s| !s.is_empty())
+    };
+
+    let nro_infogov = match valor(Columna::NroInfoGov) {
+        Some(nro) => nro.to_string(),
+        None => [Columna::Numero, Columna::Codigo, Columna::Anio]
+            .iter()
+            .map(|c| valor(*c).unwrap_or_default())
+            .collect::<Vec<_>>()
+            .join(" "),
+    };
+
+    InfoGovExpediente::from_campos(
+        &nro_infogov,
+        valor(Columna::Tema).unwrap_or_default(),
+        valor(Columna::FechaPase).ok_or_else(|| "Falta la fecha de pase".to_string())?,
+        valor(Columna::NroGde).ok_or_else(|| "Falta el nro_gde".to_string())?,
+        valor(Columna::Estado),
+        valor(Columna::Oficina),
+    )
+}
+
+/// Texto visible de una celda: sin etiquetas, con entidades decodificadas y espacios colapsados
+fn texto_celda(html: &str) -> String {
+    let saltos = Regex::new(r"(?i)<br\s*/?>|</p>|</div>").expect("regex saltos");
+    let etiquetas = Regex::new(r"(?s)<[^>]*>").expect("regex etiquetas");
+
+    let texto = saltos.replace_all(html, " ");
+    let texto = etiquetas.replace_all(&texto, "");
+    decodificar_entidades(&texto).split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn decodificar_entidades(texto: &str) -> String {
+    let entidad = Regex::new(r"&(#[xX][0-9a-fA-F]+|#\d+|[a-zA-Z]+);").expect("regex entidad");
+    entidad
+        .replace_all(texto, |caps: &regex::Captures| {
+            let nombre = &caps[1];
+            let caracter = match nombre {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ if nombre.starts_with("#x") || nombre.starts_with("#X") => {
+                    u32::from_str_radix(&nombre[2..], 16).ok().and_then(char::from_u32)
+                }
+                _ if nombre.starts_with('#') => nombre[1..].parse().ok().and_then(char::from_u32),
+                _ => None,
+            };
+            caracter.map(String::from).unwrap_or_else(|| caps[0].to_string())
+        })
+        .into_owned()
+}
+
+/// Encabezado en minúsculas, sin acentos ni signos
+fn normalizar(encabezado: &str) -> String {
+    encabezado
+        .to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'á' => 'a',
+            'é' => 'e',
+            'í' => 'i',
+            'ó' => 'o',
+            'ú' | 'ü' => 'u',
+            ':' | '_' | '-' | '.' => ' ',
+            _ => c,
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// La clave coincide con el encabezado completo o con una de sus palabras iniciales
+fn coincide(encabezado: &str, clave: &str) -> bool {
+    let clave = normalizar(clave);
+    encabezado == clave || encabezado.starts_with(&format!("{} ", clave)) || encabezado.ends_with(&format!(" {}", clave))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRILLA: &str = r#"
+        <html><body><!--StartFragment-->
+        <table class="grid">
+          <thead><tr><th>Nro. InfoGov</th><th>Tema</th><th>Fecha Pase</th><th>Nro. GDE</th><th>Estado</th></tr></thead>
+          <tbody>
+            <tr><td>817619-30-2026</td><td>Reparación embrague 4/1/2026 Toyota&nbsp;Hilux</td><td>18/02/2026</td>
+                <td><a href="/expedientes/817619">EX-2026-01216856-GDEMZA-DGIRR</a></td><td>Contratación Directa</td></tr>
+            <tr><td>817620-30-2026</td><td>Cubiertas<br>R&amp;M</td><td>19/02/2026 10:35</td>
+                <td>EX-2026-01216857-GDEMZA-DGIRR</td><td></td></tr>
+            <tr><td>817621-30-2026</td><td>Sin GDE</td><td>19/02/2026</td><td></td><td>Iniciado</td></tr>
+          </tbody>
+        </table><!--EndFragment--></body></html>
+    "#;
+
+    #[test]
+    fn test_mapea_columnas_por_encabezado() {
+        let filas = lote_desde_html(GRILLA).unwrap();
+        assert_eq!(filas.len(), 3);
+
+        // Una fecha dentro del tema ya no corta la columna
+        let primera = filas[0].resultado.as_ref().unwrap();
+        assert_eq!(primera.nro_infogov, "817619-30-2026");
+        assert_eq!(primera.tema, "Reparación embrague 4/1/2026 Toyota Hilux");
+        assert_eq!(primera.fecha_pase, "2026-02-18");
+        assert_eq!(primera.estado, "Contratación Directa");
+        assert_eq!(primera.oficina.as_deref(), Some("GDEMZA"));
+
+        let segunda = filas[1].resultado.as_ref().unwrap();
+        assert_eq!(segunda.tema, "Cubiertas R&M");
+        assert_eq!(segunda.fecha_pase, "2026-02-19");
+
+        assert!(filas[2].resultado.is_err());
+        assert_eq!(filas[2].fila, 3);
+    }
+
+    #[test]
+    fn test_numero_en_tres_columnas() {
+        let html = "<table><tr><td>Número</td><td>Cód.</td><td>Año</td><td>Asunto</td><td>Fecha</td><td>Expediente GDE</td><td>Ubicación</td></tr>\
+                    <tr><td>817619</td><td>30</td><td>2026</td><td>Service</td><td>18/2/2026</td><td>EX-2026-01216856-GDEMZA-DGIRR</td><td>DGIRR</td></tr></table>";
+        let filas = lote_desde_html(html).unwrap();
+        let exp = filas[0].resultado.as_ref().unwrap();
+        assert_eq!(exp.nro_infogov, "817619-30-2026");
+        assert_eq!(exp.oficina.as_deref(), Some("DGIRR"));
+    }
+
+    #[test]
+    fn test_sin_tabla_reconocible() {
+        assert!(lote_desde_html("<p>817619 30 2026 texto</p>").is_none());
+        assert!(lote_desde_html("<table><tr><td>Nombre</td><td>Apellido</td></tr></table>").is_none());
+    }
+}
@@ -1,16 +1,19 @@
 // Parser para expedientes de InfoGov
 // Extrae datos del formato de portapapeles de InfoGov y los estructura para la base de datos
 
+use chrono::{DateTime, NaiveDate, Utc};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 
+use crate::models::NumeroGde;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InfoGovExpediente {
     pub nro_infogov: String,      // 817619-30-2026
     pub tema: String,              // Reparación embrague Toyota Hilux...
     pub nro_gde: String,           // EX-2026-01216856-GDEMZA-DGIRR
     pub fecha_pase: String,        // 2026-02-18 (ISO format)
-    pub estado: String,            // Contratación Directa
+    pub estado: String,            // Contratación Directa (vacío si InfoGov no lo informa)
     pub oficina: Option<String>,   // Extraído de nro_gde (GDEMZA)
     pub resumen: String,           // nro_infogov + ' - ' + tema + ' - ' + nro_gde
 }
@@ -40,12 +43,12 @@ impl InfoGovExpediente {
             &nro_infogov_caps[3]
         );
 
-        // Encontrar la posición final del nro_infogov
-        let after_nro = cleaned
-            .find(&nro_infogov_caps[3])
+        // Posición final del nro_infogov (el año puede repetirse más adelante en el texto)
+        let after_nro = nro_infogov_caps
+            .get(0)
             .ok_or_else(|| "Error al procesar nro_infogov".to_string())?
-            + nro_infogov_caps[3].len();
-        
+            .end();
+
         let remaining = &cleaned[after_nro..].trim_start();
 
         // Extraer tema (hasta encontrar una fecha o el patrón EX-)
@@ -61,16 +64,12 @@ impl InfoGovExpediente {
         // Parsear fecha DD/MM/YYYY a YYYY-MM-DD
         let fecha_pase = parse_fecha_dmy(&fecha_str)?;
 
-        // Encontrar y extraer nro_gde (EX-YYYY-XXXXX-ABCDE-FGHIJ)
-        let nro_gde_regex = Regex::new(r"(EX-\d{4}-\d{8}-[A-Z]{6}-[A-Z]{5})")
-            .map_err(|e| format!("Error en regex nro_gde: {}", e))?;
-        
-        let nro_gde = nro_gde_regex
-            .captures(remaining)
-            .ok_or_else(|| "No se encontró nro_gde con patrón EX-XXXX-...".to_string())?[1]
-            .to_string();
+        // Encontrar y validar el nro_gde (TIPO-AÑO-NÚMERO-ECOSISTEMA-REPARTICIÓN)
+        let numero_gde = NumeroGde::buscar(remaining)
+            .ok_or_else(|| "No se encontró un nro_gde válido (ej: EX-2026-01216856-GDEMZA-DGIRR)".to_string())?;
+        let nro_gde = numero_gde.to_string();
 
-        // Extraer oficina del nro_gde (parte después del segundo guión)
+        // Extraer oficina del nro_gde (ecosistema)
         let oficina = extract_oficina(&nro_gde);
 
         // Extraer estado (último campo, texto hasta fin de línea)
@@ -80,7 +79,7 @@ impl InfoGovExpediente {
         let estado = estado_regex
             .captures(remaining)
             .map(|c| c[1].to_string())
-            .unwrap_or_else(|| "Desconocido".to_string());
+            .unwrap_or_default();
 
         // Generar resumen
         let resumen = format!("{} - {} - {}", nro_infogov, tema, nro_gde);
@@ -95,6 +94,157 @@ impl InfoGovExpediente {
             resumen,
         })
     }
+
+    /// Arma un expediente a partir de campos ya separados (por ejemplo, columnas de la grilla HTML)
+    /// `fecha` admite DD/MM/YYYY seguida opcionalmente de la hora
+    pub fn from_campos(
+        nro_infogov: &str,
+        tema: &str,
+        fecha: &str,
+        nro_gde: &str,
+        estado: Option<&str>,
+        oficina: Option<&str>,
+    ) -> Result<Self, String> {
+        let partes: Vec<&str> = nro_infogov
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|p| !p.is_empty())
+            .collect();
+        if partes.len() != 3 {
+            return Err(format!("nro_infogov inválido: {}", nro_infogov));
+        }
+        let nro_infogov = partes.join("-");
+
+        let fecha_pase = parse_fecha_dmy(fecha.split_whitespace().next().unwrap_or_default())?;
+
+        let numero_gde = NumeroGde::buscar(nro_gde).ok_or_else(|| format!("nro_gde inválido: {}", nro_gde))?;
+        let nro_gde = numero_gde.to_string();
+
+        let oficina = oficina.map(str::to_string).or_else(|| extract_oficina(&nro_gde));
+        let tema = tema.trim().to_string();
+        let resumen = format!("{} - {} - {}", nro_infogov, tema, nro_gde);
+
+        Ok(InfoGovExpediente {
+            nro_infogov,
+            tema,
+            nro_gde,
+            fecha_pase,
+            estado: estado.unwrap_or_default().to_string(),
+            oficina,
+            resumen,
+        })
+    }
+
+    /// Parsea una selección de varias filas de la bandeja de InfoGov
+    /// Las filas empiezan con el nro_infogov; las celdas pueden venir separadas por tabulaciones
+    /// y ocupar varias líneas (entre comillas, como las copia el navegador, o sin ellas)
+    pub fn lote_desde_portapapeles(raw_text: &str) -> Vec<FilaInfoGov> {
+        let inicio_fila = Regex::new(r"^\s*\d+\s+\d+\s+\d{4}(\s|$)").expect("regex inicio de fila");
+        let nro_gde = Regex::new(r"EX-\d{4}-\d+").expect("regex nro_gde");
+
+        let mut registros: Vec<String> = Vec::new();
+        let mut sin_nro: Vec<String> = Vec::new();
+
+        for linea in separar_lineas(raw_text) {
+            if linea.trim().is_empty() {
+                continue;
+            }
+            if inicio_fila.is_match(&linea) {
+                registros.push(linea);
+            } else if let Some(ultimo) = registros.last_mut() {
+                // Continuación de una celda de varias líneas
+                ultimo.push('\n');
+                ultimo.push_str(&linea);
+            } else if nro_gde.is_match(&linea) {
+                // Fila de datos antes de la primera fila reconocible: le falta el nro_infogov
+                sin_nro.push(linea);
+            }
+            // Las demás líneas previas son encabezados de la tabla
+        }
+
+        sin_nro
+            .into_iter()
+            .map(|texto| (texto, false))
+            .chain(registros.into_iter().map(|texto| (texto, true)))
+            .enumerate()
+            .map(|(i, (texto, completa))| {
+                let texto = texto.split_whitespace().collect::<Vec<_>>().join(" ");
+                let resultado = if completa {
+                    Self::from_clipboard(&texto)
+                } else {
+                    Err("La fila no comienza con un nro_infogov".to_string())
+                };
+                FilaInfoGov { fila: i + 1, texto, resultado }
+            })
+            .collect()
+    }
+
+    /// Fecha de pase como DateTime UTC (medianoche del día capturado)
+    pub fn fecha_pase_utc(&self) -> Option<DateTime<Utc>> {
+        NaiveDate::parse_from_str(&self.fecha_pase, "%Y-%m-%d")
+            .ok()
+            .and_then(|fecha| fecha.and_hms_opt(0, 0, 0))
+            .map(|fecha| fecha.and_utc())
+    }
+}
+
+/// Fila de una captura múltiple con su resultado
+#[derive(Debug, Clone)]
+pub struct FilaInfoGov {
+    /// Número de fila dentro de la selección (desde 1)
+    pub fila: usize,
+    /// Texto de la fila normalizado a una sola línea
+    pub texto: String,
+    pub resultado: Result<InfoGovExpediente, String>,
+}
+
+/// Separa el texto en líneas sin cortar las celdas entre comillas (TSV de navegador/Excel)
+/// Las comillas de las celdas se quitan y "" se convierte en "
+fn separar_lineas(raw_text: &str) -> Vec<String> {
+    let mut lineas = Vec::new();
+    let mut actual = String::new();
+    let mut en_comillas = false;
+    let mut inicio_celda = true;
+    let mut chars = raw_text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if en_comillas => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    actual.push('"');
+                } else {
+                    en_comillas = false;
+                }
+            }
+            '"' if inicio_celda => {
+                en_comillas = true;
+                inicio_celda = false;
+            }
+            '\r' => {}
+            '\n' if !en_comillas => {
+                lineas.push(std::mem::take(&mut actual));
+                inicio_celda = true;
+            }
+            '\t' if !en_comillas => {
+                actual.push('\t');
+                inicio_celda = true;
+            }
+            _ => {
+                if !c.is_whitespace() {
+                    inicio_celda = false;
+                }
+                actual.push(c);
+            }
+        }
+    }
+
+    // Comillas sin cerrar: no eran delimitadores de celda, se separa por líneas sin más
+    if en_comillas {
+        return raw_text.lines().map(str::to_string).collect();
+    }
+
+    lineas.push(actual);
+    lineas
 }
 
 /// Parsea una fecha en formato DD/MM/YYYY a YYYY-MM-DD
@@ -123,15 +273,10 @@ fn parse_fecha_dmy(fecha_str: &str) -> Result<String, String> {
     Ok(format!("{:04}-{:02}-{:02}", year, month, day))
 }
 
-/// Extrae la oficina del nro_gde
-/// Patrón: EX-YYYY-XXXXX-OFICINA-SIGLA
+/// Extrae la oficina (ecosistema) del nro_gde
+/// Patrón: TIPO-AÑO-NÚMERO-ECOSISTEMA-REPARTICIÓN
 fn extract_oficina(nro_gde: &str) -> Option<String> {
-    let parts: Vec<&str> = nro_gde.split('-').collect();
-    if parts.len() >= 4 {
-        Some(parts[3].to_string())
-    } else {
-        None
-    }
+    nro_gde.parse::<NumeroGde>().ok().map(|numero| numero.ecosistema)
 }
 
 #[cfg(test)]
@@ -163,6 +308,47 @@ mod tests {
         assert_eq!(exp.oficina, Some("GDEMZA".to_string()));
     }
 
+    #[test]
+    fn test_lote_con_tabulaciones_y_celdas_multilinea() {
+        let raw_text = concat!(
+            "Nro\tTema\tFecha\tGDE\tEstado\r\n",
+            "817619\t30\t2026\tReparación embrague\t18/2/2026\tEX-2026-01216856-GDEMZA-DGIRR\tContratación Directa\r\n",
+            "817620\t30\t2026\t\"Compra de cubiertas\nToyota \"\"Hilux\"\"\"\t19/2/2026\tEX-2026-01216857-GDEMZA-DGIRR\tContratación Directa\n",
+            "817621 30 2026 Service 4x4\n",
+            "camioneta 20/2/2026 EX-2026-01216858-GDEMZA-DGIRR Contratación Directa\n",
+            "817622 30 2026 Sin fecha EX-2026-01216859-GDEMZA-DGIRR\n",
+        );
+
+        let filas = InfoGovExpediente::lote_desde_portapapeles(raw_text);
+        assert_eq!(filas.len(), 4);
+        assert_eq!(filas.iter().map(|f| f.fila).collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+
+        let primera = filas[0].resultado.as_ref().unwrap();
+        assert_eq!(primera.nro_infogov, "817619-30-2026");
+        assert_eq!(primera.tema, "Reparación embrague");
+
+        let segunda = filas[1].resultado.as_ref().unwrap();
+        assert_eq!(segunda.tema, "Compra de cubiertas Toyota \"Hilux\"");
+        assert_eq!(segunda.fecha_pase, "2026-02-19");
+
+        let tercera = filas[2].resultado.as_ref().unwrap();
+        assert_eq!(tercera.tema, "Service 4x4 camioneta");
+        assert_eq!(tercera.nro_gde, "EX-2026-01216858-GDEMZA-DGIRR");
+
+        assert!(filas[3].resultado.is_err());
+        assert!(filas[3].texto.starts_with("817622 30 2026"));
+    }
+
+    #[test]
+    fn test_lote_fila_sin_nro_infogov() {
+        let raw_text = "Reparación 18/2/2026 EX-2026-01216856-GDEMZA-DGIRR Contratación Directa\n\
+                        817619 30 2026 Reparación 18/2/2026 EX-2026-01216856-GDEMZA-DGIRR Contratación Directa";
+        let filas = InfoGovExpediente::lote_desde_portapapeles(raw_text);
+        assert_eq!(filas.len(), 2);
+        assert!(filas[0].resultado.is_err());
+        assert!(filas[1].resultado.is_ok());
+    }
+
     #[test]
     fn test_from_clipboard_empty() {
         let result = InfoGovExpediente::from_clipboard("");
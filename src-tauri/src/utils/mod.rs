@@ -1,3 +1,8 @@
 pub mod pdf_generator;
+pub mod pdf_layout;
 pub mod excel_generator;
+pub mod conversor_pdf;
 pub mod infogov_parser;
+pub mod infogov_html;
+pub mod infogov_archivo;
+pub mod fts_query;
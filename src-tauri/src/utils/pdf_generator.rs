@@ -1,6 +1,12 @@
-use printpdf::*;
-use std::fs::File;
-use std::io::BufWriter;
+// Orden de compra en PDF con el renderizador nativo
+// La plantilla de la OC (medidas, columnas, textos fijos) se maqueta en hojas con pdf_layout:
+// encabezado y pie en cada hoja y tabla de renglones que continúa en la hoja siguiente si no entra
+// Cada copia (ORIGINAL, DUPLICADO, TRIPLICADO) se maqueta completa con su propia numeración de hojas
+use std::path::Path;
+
+use crate::models::configuracion::{Institucion, TasasIva, ZonaRiego};
+use crate::models::orden_compra::Copia;
+use crate::utils::pdf_layout::{self, interlineado, Alineacion, Fuente, Hoja, Metricas};
 
 pub struct OCPDFData {
     pub numero_oc: String,
@@ -23,6 +29,11 @@ pub struct OCPDFData {
     pub forma_pago: String,
     pub plazo_entrega: String,
     pub es_iva_inscripto: bool,
+    /// OC anulada: cada hoja lleva el sello ANULADA
+    pub anulada: bool,
+    pub institucion: Institucion,
+    pub zona: ZonaRiego,
+    pub tasas_iva: TasasIva,
 }
 
 pub struct OCRenglon {
@@ -34,189 +45,569 @@ pub struct OCRenglon {
     pub total: f64,
 }
 
-pub fn generar_pdf_oc(data: OCPDFData, output_path: &str) -> Result<(), String> {
-    let (document, page1, layer1) = PdfDocument::new("Orden de Compra", Mm(210.0), Mm(297.0), "Layer 1");
-    
-    let font = document
-        .add_builtin_font(BuiltinFont::Helvetica)
-        .map_err(|e| format!("Error al cargar fuente: {}", e))?;
-    
-    let font_bold = document
-        .add_builtin_font(BuiltinFont::HelveticaBold)
-        .map_err(|e| format!("Error al cargar fuente bold: {}", e))?;
-    
-    let current_layer = document.get_page(page1).get_layer(layer1);
-    
-    // ==== ENCABEZADO ====
-    // Logo IRRIGACIÓN (izquierda)
-    current_layer.use_text("IRRIGACIÓN", 28.0, Mm(15.0), Mm(280.0), &font_bold);
-    
-    // ORIGINAL (derecha)
-    current_layer.use_text("ORIGINAL", 14.0, Mm(165.0), Mm(280.0), &font_bold);
-    
-    // Recuadro institución (centro)
-    current_layer.use_text("DEPARTAMENTO GENERAL DE IRRIGACIÓN", 9.0, Mm(60.0), Mm(277.0), &font_bold);
-    current_layer.use_text("C.U.I.T. 30-9991963-1", 9.0, Mm(60.0), Mm(273.5), &font_bold);
-    
-    // Datos OC a la derecha
-    current_layer.use_text(&format!("ORDEN DE COMPRA N°: {}", data.numero_oc), 9.0, Mm(140.0), Mm(279.0), &font);
-    current_layer.use_text(&format!("PEDIDO N°: {} / NRO.", data.pedido_nro), 9.0, Mm(140.0), Mm(275.0), &font);
-    current_layer.use_text(&format!("DESTINO: {}", data.destino), 9.0, Mm(140.0), Mm(271.0), &font);
-    current_layer.use_text(&format!("Mendoza, {}", data.fecha), 9.0, Mm(140.0), Mm(267.0), &font);
-    
-    // ==== DATOS DEL EXPEDIENTE ====
-    let mut y = 260.0;
-    current_layer.use_text(
-        &format!("Expte. N°: {} ({}-{})", data.expediente_numero, data.expediente_año, data.expediente_año),
-        8.5,
-        Mm(15.0),
-        Mm(y),
-        &font,
-    );
-    y -= 3.5;
-    current_layer.use_text(
-        &format!("Resolución interna N°: {}", data.resolucion_nro.unwrap_or_default()),
-        8.5,
-        Mm(15.0),
-        Mm(y),
-        &font,
+// ==== PLANTILLA (mm) ====
+const ANCHO_HOJA: f32 = 210.0;
+const ALTO_HOJA: f32 = 297.0;
+const MARGEN: f32 = 15.0;
+const ANCHO_UTIL: f32 = ANCHO_HOJA - 2.0 * MARGEN;
+/// Primera línea del cuerpo, debajo del encabezado
+const INICIO_CUERPO: f32 = 254.0;
+/// Nada del cuerpo baja de acá: debajo va el pie
+const FIN_CUERPO: f32 = 22.0;
+/// Lugar reservado debajo de la tabla para el aviso de continuación
+const RESERVA_CONTINUA: f32 = 6.0;
+const RELLENO_CELDA: f32 = 1.2;
+const GROSOR_LINEA: f32 = 0.5;
+
+const LETRA_CUERPO: f32 = 8.5;
+const LETRA_TABLA: f32 = 7.5;
+const LETRA_NOTAS: f32 = 7.0;
+const LETRA_MARCA_AGUA: f32 = 72.0;
+const LETRA_SELLO: f32 = 96.0;
+/// La primera línea base de una celda queda un tamaño de letra debajo del relleno superior
+const ASCENSO_TABLA: f32 = LETRA_TABLA * 25.4 / 72.0;
+
+pub const AVISO_CONTINUA: &str = "continúa en hoja siguiente";
+
+struct Columna {
+    titulo: &'static str,
+    ancho: f32,
+    alineacion: Alineacion,
+}
+
+/// Columnas de la tabla de renglones (suman el ancho útil)
+const COLUMNAS: [Columna; 6] = [
+    Columna { titulo: "Renglón Nro.", ancho: 16.0, alineacion: Alineacion::Centro },
+    Columna { titulo: "Cantidad", ancho: 18.0, alineacion: Alineacion::Derecha },
+    Columna { titulo: "CONCEPTO / DETALLE", ancho: 80.0, alineacion: Alineacion::Izquierda },
+    Columna { titulo: "Marca", ancho: 22.0, alineacion: Alineacion::Izquierda },
+    Columna { titulo: "Valor Unitario", ancho: 22.0, alineacion: Alineacion::Derecha },
+    Columna { titulo: "Totales", ancho: 22.0, alineacion: Alineacion::Derecha },
+];
+
+/// Un solo PDF con las copias pedidas, una detrás de otra
+pub fn generar_pdf_oc(data: &OCPDFData, copias: &[Copia], marca_agua: bool, output_path: &Path) -> Result<(), String> {
+    let metricas = Metricas::new();
+    let hojas: Vec<Hoja> = copias
+        .iter()
+        .flat_map(|copia| maquetar_oc(data, *copia, marca_agua, &metricas))
+        .collect();
+    pdf_layout::dibujar(&format!("Orden de Compra {}", data.numero_oc), ANCHO_HOJA, ALTO_HOJA, &hojas, output_path)
+}
+
+/// Maquetar una copia de la OC: cuerpo hoja por hoja y, al final, encabezado y pie con el total de hojas
+pub fn maquetar_oc(data: &OCPDFData, copia: Copia, marca_agua: bool, metricas: &Metricas) -> Vec<Hoja> {
+    let mut maqueta = Maqueta { metricas, hojas: vec![Hoja::default()], y: INICIO_CUERPO };
+
+    maqueta.datos_generales(data);
+    maqueta.tabla_renglones(data);
+    maqueta.cierre(data);
+
+    let total = maqueta.hojas.len();
+    for (i, hoja) in maqueta.hojas.iter_mut().enumerate() {
+        encabezado(hoja, metricas, data, copia);
+        pie(hoja, metricas, data, i + 1, total);
+        if marca_agua {
+            hoja.marca_agua(metricas, ANCHO_HOJA, ALTO_HOJA, LETRA_MARCA_AGUA, copia.etiqueta());
+        }
+        if data.anulada {
+            hoja.sello(metricas, ANCHO_HOJA, ALTO_HOJA, LETRA_SELLO, "ANULADA");
+        }
+    }
+    maqueta.hojas
+}
+
+struct Maqueta<'a> {
+    metricas: &'a Metricas,
+    hojas: Vec<Hoja>,
+    /// Línea base donde sigue el cuerpo en la hoja actual
+    y: f32,
+}
+
+impl Maqueta<'_> {
+    fn hoja(&mut self) -> &mut Hoja {
+        self.hojas.last_mut().expect("siempre hay una hoja")
+    }
+
+    fn nueva_hoja(&mut self) {
+        self.hojas.push(Hoja::default());
+        self.y = INICIO_CUERPO;
+    }
+
+    /// Etiqueta en negrita seguida del valor partido en el ancho restante
+    fn campo(&mut self, etiqueta: &str, valor: &str) {
+        let metricas = self.metricas;
+        let y = self.y;
+        let hoja = self.hoja();
+        hoja.texto(MARGEN, y, LETRA_CUERPO, Fuente::Negrita, etiqueta);
+        let y = hoja.parrafo(metricas, MARGEN + 32.0, ANCHO_UTIL - 32.0, y, LETRA_CUERPO, Fuente::Normal, valor);
+        self.y = y - interlineado(LETRA_CUERPO);
+    }
+
+    fn parrafo(&mut self, tamaño: f32, fuente: Fuente, texto: &str) {
+        let metricas = self.metricas;
+        let y = self.y;
+        let y = self.hoja().parrafo(metricas, MARGEN, ANCHO_UTIL, y, tamaño, fuente, texto);
+        self.y = y - interlineado(tamaño);
+    }
+
+    /// Expediente, proveedor y condiciones (sólo en la primera hoja)
+    fn datos_generales(&mut self, data: &OCPDFData) {
+        let resolucion = format!(
+            "Resolución interna Nº {}",
+            data.resolucion_nro.as_deref().filter(|r| !r.trim().is_empty()).unwrap_or("S/N")
+        );
+        self.parrafo(LETRA_CUERPO, Fuente::Normal, &format!("Expte. Nº {}", data.expediente_numero));
+        self.parrafo(LETRA_CUERPO, Fuente::Normal, &resolucion);
+        self.parrafo(LETRA_CUERPO, Fuente::Negrita, &data.tipo_contratacion);
+
+        self.y -= 3.0;
+        self.campo("SEÑOR/ES:", &data.señor.to_uppercase());
+        self.campo("DOMICILIO:", &data.domicilio);
+        self.campo("CUIT:", &data.cuit);
+
+        self.y -= 3.0;
+        self.parrafo(
+            LETRA_NOTAS,
+            Fuente::Normal,
+            "De acuerdo con la propuesta presentada por ustedes y las reservas consignadas en la presente Orden de \
+             Compra, sírvase proveer por cuenta de este Departamento General de Irrigación los artículos que abajo se \
+             detallan, debiendo entregarse en:",
+        );
+        let entrega = if data.descripcion_zona.trim().is_empty() { &data.zona.destino } else { &data.descripcion_zona };
+        self.parrafo(LETRA_CUERPO, Fuente::Negrita, entrega);
+
+        self.y -= 1.5;
+        self.parrafo(
+            LETRA_NOTAS,
+            Fuente::Normal,
+            &format!(
+                "Esta firma deberá presentar la Factura original tipo \"B\" o \"C\" según corresponda en la oficina de \
+                 {}, sito en {}, acompañada del Remito y esta Orden de Compra, con la constancia de haber tributado el \
+                 IMPUESTO DE SELLOS correspondiente según lo establecido por la legislación vigente. En caso de estar \
+                 EXENTO en el pago de dicho impuesto, deberá adjuntar la respectiva constancia de exención, debidamente \
+                 actualizada. Esta documentación y los pagos respectivos quedarán sujetos a lo establecido en el \
+                 Reglamento de Compras.",
+                data.zona.destino, data.zona.domicilio
+            ),
+        );
+        self.y -= 3.0;
+    }
+
+    /// Fila de la tabla: celdas con borde y el texto de cada una partido al ancho de su columna
+    /// `y` es el borde superior; devuelve el borde inferior
+    fn fila(&mut self, y: f32, celdas: &[Vec<String>], fuente: Fuente) -> f32 {
+        let lineas = celdas.iter().map(Vec::len).max().unwrap_or(1).max(1);
+        let alto = lineas as f32 * interlineado(LETRA_TABLA) + 2.0 * RELLENO_CELDA;
+        let metricas = self.metricas;
+        let hoja = self.hoja();
+
+        let mut x = MARGEN;
+        for (columna, celda) in COLUMNAS.iter().zip(celdas) {
+            hoja.rectangulo(x, y - alto, columna.ancho, alto, GROSOR_LINEA);
+            let mut base = y - RELLENO_CELDA - ASCENSO_TABLA;
+            for linea in celda {
+                let alineacion = if fuente == Fuente::Negrita { Alineacion::Centro } else { columna.alineacion };
+                hoja.texto_alineado(
+                    metricas,
+                    x + RELLENO_CELDA,
+                    columna.ancho - 2.0 * RELLENO_CELDA,
+                    base,
+                    LETRA_TABLA,
+                    fuente,
+                    alineacion,
+                    linea,
+                );
+                base -= interlineado(LETRA_TABLA);
+            }
+            x += columna.ancho;
+        }
+        y - alto
+    }
+
+    fn partir_celdas(&self, textos: &[String], fuente: Fuente) -> Vec<Vec<String>> {
+        COLUMNAS
+            .iter()
+            .zip(textos)
+            .map(|(columna, texto)| {
+                self.metricas.partir(texto, fuente, LETRA_TABLA, columna.ancho - 2.0 * RELLENO_CELDA)
+            })
+            .collect()
+    }
+
+    fn encabezado_tabla(&mut self) {
+        let titulos: Vec<String> = COLUMNAS.iter().map(|c| c.titulo.to_string()).collect();
+        let celdas = self.partir_celdas(&titulos, Fuente::Negrita);
+        let y = self.y;
+        self.y = self.fila(y, &celdas, Fuente::Negrita);
+    }
+
+    fn alto_fila(lineas: usize) -> f32 {
+        lineas as f32 * interlineado(LETRA_TABLA) + 2.0 * RELLENO_CELDA
+    }
+
+    /// Cerrar la hoja con el aviso de continuación y seguir la tabla en la siguiente
+    fn continuar_tabla(&mut self) {
+        let metricas = self.metricas;
+        let y = self.y - 4.0;
+        self.hoja()
+            .texto_alineado(metricas, MARGEN, ANCHO_UTIL, y, LETRA_NOTAS, Fuente::Negrita, Alineacion::Derecha, AVISO_CONTINUA);
+        self.nueva_hoja();
+        self.encabezado_tabla();
+    }
+
+    /// Renglones: una fila que no entra pasa entera a la hoja siguiente;
+    /// si no entraría ni en una hoja vacía se corta por líneas
+    fn tabla_renglones(&mut self, data: &OCPDFData) {
+        let inicio = self.y;
+        self.encabezado_tabla();
+        let limite = FIN_CUERPO + RESERVA_CONTINUA;
+        let capacidad_hoja = INICIO_CUERPO - (inicio - self.y) - limite;
+
+        for renglon in &data.renglones {
+            let textos = [
+                renglon.numero.to_string(),
+                format!("{:.2}", renglon.cantidad),
+                renglon.concepto.clone(),
+                renglon.marca.clone().filter(|m| !m.trim().is_empty()).unwrap_or_else(|| "-".to_string()),
+                importe(renglon.valor_unitario),
+                importe(renglon.total),
+            ];
+            let mut celdas = self.partir_celdas(&textos, Fuente::Normal);
+
+            loop {
+                let lineas = celdas.iter().map(Vec::len).max().unwrap_or(1);
+                if self.y - Self::alto_fila(lineas) >= limite {
+                    let y = self.y;
+                    self.y = self.fila(y, &celdas, Fuente::Normal);
+                    break;
+                }
+
+                // ¿Cuántas líneas entran en lo que queda de la hoja?
+                let disponibles = ((self.y - limite - 2.0 * RELLENO_CELDA) / interlineado(LETRA_TABLA)).floor();
+                if Self::alto_fila(lineas) <= capacidad_hoja || disponibles < 1.0 {
+                    self.continuar_tabla();
+                    continue;
+                }
+
+                let entran = disponibles as usize;
+                let resto: Vec<Vec<String>> = celdas
+                    .iter_mut()
+                    .map(|celda| celda.split_off(entran.min(celda.len())))
+                    .collect();
+                let y = self.y;
+                self.y = self.fila(y, &celdas, Fuente::Normal);
+                self.continuar_tabla();
+                celdas = resto;
+            }
+        }
+    }
+
+    /// Total, importe en letras, condiciones y cuadro de IVA; si no entran debajo de la tabla van a otra hoja
+    fn cierre(&mut self, data: &OCPDFData) {
+        let mut prueba = Hoja::default();
+        let alto = self.y - dibujar_cierre(&mut prueba, self.metricas, data, self.y);
+        if self.y - alto < FIN_CUERPO {
+            let metricas = self.metricas;
+            let y = self.y - 4.0;
+            self.hoja()
+                .texto_alineado(metricas, MARGEN, ANCHO_UTIL, y, LETRA_NOTAS, Fuente::Negrita, Alineacion::Derecha, AVISO_CONTINUA);
+            self.nueva_hoja();
+        }
+        let metricas = self.metricas;
+        let y = self.y;
+        self.y = dibujar_cierre(self.hoja(), metricas, data, y);
+    }
+}
+
+/// Dibuja el cierre desde `y` (borde inferior de la tabla) y devuelve dónde termina
+fn dibujar_cierre(hoja: &mut Hoja, metricas: &Metricas, data: &OCPDFData, y: f32) -> f32 {
+    let x_totales = MARGEN + ANCHO_UTIL - COLUMNAS[5].ancho;
+    let x_etiqueta = x_totales - COLUMNAS[4].ancho;
+    let alto_total = interlineado(LETRA_TABLA) + 2.0 * RELLENO_CELDA;
+    let base = y - RELLENO_CELDA - ASCENSO_TABLA;
+
+    hoja.rectangulo(x_totales, y - alto_total, COLUMNAS[5].ancho, alto_total, GROSOR_LINEA);
+    hoja.texto_alineado(
+        metricas,
+        x_etiqueta,
+        COLUMNAS[4].ancho - RELLENO_CELDA,
+        base,
+        LETRA_TABLA,
+        Fuente::Negrita,
+        Alineacion::Derecha,
+        "TOTAL:",
     );
-    y -= 3.5;
-    current_layer.use_text(
-        &format!("Tipo de Contratación: {}", data.tipo_contratacion),
-        8.5,
-        Mm(15.0),
-        Mm(y),
-        &font,
+    hoja.texto_alineado(
+        metricas,
+        x_totales + RELLENO_CELDA,
+        COLUMNAS[5].ancho - 2.0 * RELLENO_CELDA,
+        base,
+        LETRA_TABLA,
+        Fuente::Negrita,
+        Alineacion::Derecha,
+        &importe(data.total),
     );
-    
-    // ==== DATOS DEL PROVEEDOR ====
-    y -= 8.0;
-    current_layer.use_text("SEÑORES:", 8.5, Mm(15.0), Mm(y), &font_bold);
-    current_layer.use_text(&data.señor, 8.5, Mm(45.0), Mm(y), &font);
-    
-    y -= 3.5;
-    current_layer.use_text("DOMICILIO:", 8.5, Mm(15.0), Mm(y), &font_bold);
-    current_layer.use_text(&data.domicilio, 8.5, Mm(45.0), Mm(y), &font);
-    
-    y -= 3.5;
-    current_layer.use_text("CUIT:", 8.5, Mm(15.0), Mm(y), &font_bold);
-    current_layer.use_text(&data.cuit, 8.5, Mm(45.0), Mm(y), &font);
-    
-    // ==== NOTAS ====
-    y -= 6.0;
-    current_layer.use_text(
-        "De acuerdo con la propuesta presentada por ustedes y las reservas consignadas en la presente Orden de Compra, sírvase proveer por cuenta de este Departamento General de Irrigación los artículos que abajo se detallan. Con nuestras más cordiales saludos.",
-        7.0,
-        Mm(15.0),
-        Mm(y),
-        &font,
+
+    let mut y = y - alto_total - 6.0;
+    let mut campo = |etiqueta: &str, valor: &str, y: f32| -> f32 {
+        hoja.texto(MARGEN, y, LETRA_CUERPO, Fuente::Negrita, etiqueta);
+        let fin = hoja.parrafo(metricas, MARGEN + 32.0, ANCHO_UTIL - 32.0, y, LETRA_CUERPO, Fuente::Normal, valor);
+        fin - interlineado(LETRA_CUERPO)
+    };
+    y = campo("Son Pesos:", &data.total_en_letras, y);
+    y = campo("Forma de Pago:", &data.forma_pago, y);
+    y = campo("Plazo de Entrega:", &data.plazo_entrega, y);
+
+    y -= 2.0;
+    y = hoja.parrafo(
+        metricas,
+        MARGEN,
+        ANCHO_UTIL,
+        y,
+        LETRA_NOTAS,
+        Fuente::Normal,
+        "* Se deberá adjuntar con la factura, la Orden de Compra Original sellada y Copia de Ingresos Varios",
     );
-    
-    // ==== ZONA ====
+
+    // Cuadro de IVA
     y -= 8.0;
-    current_layer.use_text(
-        &format!("ZONA: {}", data.descripcion_zona),
-        8.5,
-        Mm(15.0),
-        Mm(y),
-        &font_bold,
-    );
-    
-    y -= 6.0;
-    current_layer.use_text(
-        "Esta firma deberá presentar la Factura original no 'B' o 'C' según corresponda en la oficina de ZONA RIEGO MALARGUE, sito en Avda. San Martín 258 - Malargüe - Mendoza, acompañada del Remito y esta Orden de Compra. Sólo se considerada haberse iniciado el IMPULSO DE SELLOS correspondiente según lo establecido por la legislación vigente. En caso de estar EXENTO en el pago de dicho impuesto, deberá adjuntar la inspectora constancia de exención, debidamente actualizada. Esta documentación y los pagos inspecciones quedarán sujetos a lo establecido en el Reglamento de Compras.",
-        6.5,
-        Mm(15.0),
-        Mm(y),
-        &font,
+    let x_cuadro = MARGEN + 50.0;
+    let ancho_cuadro = ANCHO_UTIL - 50.0;
+    hoja.texto(x_cuadro, y, LETRA_CUERPO, Fuente::Negrita, "IVA RESPONSABLE INSCRIPTO");
+    let alicuotas = format!("I.V.A. {} / {}", data.tasas_iva.texto(true), data.tasas_iva.texto(false));
+    let filas = [
+        ("Importe Neto Gravado", importe(data.subtotal), Fuente::Normal),
+        (alicuotas.as_str(), importe(data.iva), Fuente::Normal),
+        ("TOTAL", importe(data.total), Fuente::Negrita),
+    ];
+    for (etiqueta, valor, fuente) in filas {
+        y -= interlineado(LETRA_CUERPO) + 0.5;
+        hoja.texto(x_cuadro + 4.0, y, LETRA_TABLA, fuente, etiqueta);
+        hoja.texto_alineado(metricas, x_cuadro, ancho_cuadro, y, LETRA_TABLA, fuente, Alineacion::Derecha, &valor);
+    }
+    hoja.linea(x_cuadro, y - 2.0, MARGEN + ANCHO_UTIL, y - 2.0, GROSOR_LINEA);
+
+    y - 2.0
+}
+
+/// Encabezado de cada hoja: logo, leyenda de la copia, institución y datos de la OC
+fn encabezado(hoja: &mut Hoja, metricas: &Metricas, data: &OCPDFData, copia: Copia) {
+    let derecha = MARGEN + ANCHO_UTIL;
+    hoja.logo(MARGEN, 266.0, 40.0);
+    hoja.texto_alineado(metricas, MARGEN, ANCHO_UTIL, 284.0, 12.0, Fuente::Negrita, Alineacion::Derecha, copia.etiqueta());
+
+    hoja.texto(MARGEN, 262.0, LETRA_NOTAS, Fuente::Negrita, &data.institucion.nombre);
+    hoja.texto(MARGEN, 258.5, LETRA_NOTAS, Fuente::Normal, format!("C.U.I.T. {}", data.institucion.cuit));
+
+    let x_datos = 120.0;
+    let ancho_datos = derecha - x_datos;
+    let mut y = 276.0;
+    for (etiqueta, valor) in [
+        ("ORDEN DE COMPRA Nº:", data.numero_oc.clone()),
+        ("PEDIDO Nº:", data.pedido_nro.to_string()),
+        ("DESTINO:", data.destino.to_uppercase()),
+    ] {
+        hoja.texto(x_datos, y, LETRA_CUERPO, Fuente::Negrita, etiqueta);
+        hoja.texto_alineado(metricas, x_datos, ancho_datos, y, LETRA_CUERPO, Fuente::Normal, Alineacion::Derecha, &valor);
+        y -= interlineado(LETRA_CUERPO) + 0.5;
+    }
+    // La fecha llega formateada con la localidad ("Mendoza, 11 de febrero de 2026")
+    hoja.texto_alineado(metricas, x_datos, ancho_datos, y, LETRA_CUERPO, Fuente::Normal, Alineacion::Derecha, &data.fecha);
+
+    hoja.linea(MARGEN, 257.0, derecha, 257.0, GROSOR_LINEA);
+}
+
+/// Pie de cada hoja: número de OC y hoja actual sobre el total
+fn pie(hoja: &mut Hoja, metricas: &Metricas, data: &OCPDFData, numero: usize, total: usize) {
+    let derecha = MARGEN + ANCHO_UTIL;
+    hoja.linea(MARGEN, 17.0, derecha, 17.0, GROSOR_LINEA);
+    hoja.texto(MARGEN, 12.5, LETRA_NOTAS, Fuente::Normal, format!("Orden de Compra Nº {}", data.numero_oc));
+    hoja.texto_alineado(
+        metricas,
+        MARGEN,
+        ANCHO_UTIL,
+        12.5,
+        LETRA_NOTAS,
+        Fuente::Normal,
+        Alineacion::Derecha,
+        &format!("Hoja {} de {}", numero, total),
     );
-    
-    // ==== TABLA DE RENGLONES ====
-    y -= 12.0;
-    
-    // Encabezados de tabla
-    let table_y = y;
-    current_layer.use_text("Renglón Nro.", 8.0, Mm(17.0), Mm(table_y), &font_bold);
-    current_layer.use_text("Cantidad", 8.0, Mm(37.0), Mm(table_y), &font_bold);
-    current_layer.use_text("CONCEPTO / DETALLE", 8.0, Mm(55.0), Mm(table_y), &font_bold);
-    current_layer.use_text("Marca", 8.0, Mm(140.0), Mm(table_y), &font_bold);
-    current_layer.use_text("Valor Unitario", 8.0, Mm(160.0), Mm(table_y), &font_bold);
-    current_layer.use_text("Totales", 8.0, Mm(185.0), Mm(table_y), &font_bold);
-    
-    y -= 4.0;
-    
-    // Renglones
-    for (idx, renglon) in data.renglones.iter().enumerate() {
-        current_layer.use_text(&renglon.numero.to_string(), 7.5, Mm(17.0), Mm(y), &font);
-        current_layer.use_text(&format!("{:.2}", renglon.cantidad), 7.5, Mm(37.0), Mm(y), &font);
-        current_layer.use_text(&renglon.concepto, 6.5, Mm(55.0), Mm(y), &font);
-        current_layer.use_text(
-            &renglon.marca.clone().unwrap_or_else(|| "-".to_string()),
-            7.5,
-            Mm(140.0),
-            Mm(y),
-            &font,
+}
+
+fn importe(valor: f64) -> String {
+    format!("$ {:.2}", valor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::configuracion::Settings;
+    use crate::utils::pdf_layout::{serializar, Operacion};
+
+    fn datos(renglones: Vec<OCRenglon>) -> OCPDFData {
+        let settings = Settings::default();
+        let total: f64 = renglones.iter().map(|r| r.total).sum();
+        OCPDFData {
+            numero_oc: "125".to_string(),
+            pedido_nro: 7,
+            destino: "Malargüe".to_string(),
+            fecha: "Mendoza, 11 de febrero de 2026".to_string(),
+            expediente_numero: "1234-?-26 (EX-2026-00012345-GDEMZA)".to_string(),
+            expediente_año: 2026,
+            resolucion_nro: Some("45/2026".to_string()),
+            tipo_contratacion: "Contratación directa".to_string(),
+            señor: "Ferretería Güemes S.R.L.".to_string(),
+            domicilio: "Av. San Martín 1050 - Malargüe".to_string(),
+            cuit: "30-12345678-9".to_string(),
+            descripcion_zona: "ZONA RIEGO DE LOS RÍOS MALARGÜE, GRANDE, BARRANCAS Y COLORADO - MALARGÜE - MENDOZA".to_string(),
+            subtotal: total,
+            iva: total * 0.21,
+            total: total * 1.21,
+            total_en_letras: "ciento veintiún mil con 00/100".to_string(),
+            forma_pago: "Según pliego".to_string(),
+            plazo_entrega: "Inmediato".to_string(),
+            es_iva_inscripto: true,
+            anulada: false,
+            institucion: settings.institucion,
+            zona: settings.zona,
+            tasas_iva: settings.iva,
+            renglones,
+        }
+    }
+
+    fn renglon(numero: usize, concepto: &str) -> OCRenglon {
+        OCRenglon {
+            numero,
+            cantidad: 2.0,
+            concepto: concepto.to_string(),
+            marca: Some("Tigre".to_string()),
+            valor_unitario: 1250.5,
+            total: 2501.0,
+        }
+    }
+
+    /// Compara la maqueta serializada con tests/golden; ACTUALIZAR_GOLDEN=1 la regenera
+    fn comparar_con_golden(nombre: &str, obtenida: &str) {
+        let ruta = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden").join(nombre);
+        if std::env::var_os("ACTUALIZAR_GOLDEN").is_some() {
+            std::fs::write(&ruta, obtenida).unwrap();
+            return;
+        }
+        let esperada = std::fs::read_to_string(&ruta)
+            .unwrap_or_else(|_| panic!("falta {} (generarlo con ACTUALIZAR_GOLDEN=1)", ruta.display()));
+        assert!(
+            obtenida == esperada,
+            "la maqueta cambió respecto de {} (si el cambio es buscado, regenerar con ACTUALIZAR_GOLDEN=1)",
+            ruta.display()
         );
-        current_layer.use_text(&format!("$ {:.2}", renglon.valor_unitario), 7.5, Mm(160.0), Mm(y), &font);
-        current_layer.use_text(&format!("$ {:.2}", renglon.total), 7.5, Mm(185.0), Mm(y), &font);
-        
-        y -= 4.0;
-        if idx < data.renglones.len() - 1 {
-            y -= 1.0;
+    }
+
+    fn textos(hoja: &Hoja) -> Vec<&str> {
+        hoja.operaciones
+            .iter()
+            .filter_map(|op| match op {
+                Operacion::Texto { texto, .. } => Some(texto.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_golden_una_hoja() {
+        let data = datos(vec![
+            renglon(1, "Caño de PVC 110 mm x 6 m con junta elástica"),
+            renglon(2, "Codo 90° PVC 110 mm"),
+        ]);
+        let hojas = maquetar_oc(&data, Copia::Original, false, &Metricas::new());
+
+        assert_eq!(hojas.len(), 1);
+        comparar_con_golden("oc_una_hoja.txt", &serializar(&hojas));
+    }
+
+    #[test]
+    fn test_golden_varias_hojas() {
+        let largo = "Válvula esclusa de hierro dúctil DN 150 con bridas, vástago no ascendente y volante, \
+                     incluye juntas, bulones y prueba hidráulica en fábrica. "
+            .repeat(3);
+        let renglones = (1..=40)
+            .map(|n| renglon(n, if n % 7 == 0 { &largo } else { "Caño de PVC 110 mm x 6 m" }))
+            .collect();
+        let hojas = maquetar_oc(&datos(renglones), Copia::Original, false, &Metricas::new());
+
+        assert!(hojas.len() > 1);
+        comparar_con_golden("oc_varias_hojas.txt", &serializar(&hojas));
+    }
+
+    #[test]
+    fn test_encabezado_pie_y_continuacion_en_cada_hoja() {
+        let renglones = (1..=60).map(|n| renglon(n, "Manguera de riego reforzada ¾\" x 50 m")).collect();
+        let hojas = maquetar_oc(&datos(renglones), Copia::Original, false, &Metricas::new());
+        let total = hojas.len();
+        assert!(total >= 3);
+
+        for (i, hoja) in hojas.iter().enumerate() {
+            let textos = textos(hoja);
+            assert!(hoja.operaciones.iter().any(|op| matches!(op, Operacion::Logo { .. })));
+            assert!(textos.contains(&"ORDEN DE COMPRA Nº:"));
+            assert!(textos.contains(&format!("Hoja {} de {}", i + 1, total).as_str()));
+            // Todas menos la última avisan que la tabla sigue
+            assert_eq!(textos.contains(&AVISO_CONTINUA), i + 1 < total);
+            // El encabezado de la tabla se repite (la última hoja puede tener sólo el cierre)
+            if i + 1 < total {
+                assert!(textos.contains(&"CONCEPTO / DETALLE"));
+            }
+        }
+        assert!(textos(&hojas[total - 1]).contains(&"Son Pesos:"));
+
+        // Nada del cuerpo invade el pie
+        for hoja in &hojas {
+            for op in &hoja.operaciones {
+                if let Operacion::Rectangulo { y, .. } = op {
+                    assert!(*y >= FIN_CUERPO);
+                }
+            }
         }
     }
-    
-    // Total de tabla
-    y -= 2.0;
-    current_layer.use_text("TOTAL:", 8.5, Mm(160.0), Mm(y), &font_bold);
-    current_layer.use_text(&format!("$ {:.2}", data.total), 8.5, Mm(185.0), Mm(y), &font_bold);
-    
-    // ==== RESUMEN ====
-    y -= 7.0;
-    current_layer.use_text("Son Pesos:", 8.0, Mm(15.0), Mm(y), &font_bold);
-    current_layer.use_text(&data.total_en_letras, 8.0, Mm(45.0), Mm(y), &font);
-    
-    y -= 4.0;
-    current_layer.use_text("Forma de Pago:", 8.0, Mm(15.0), Mm(y), &font_bold);
-    current_layer.use_text(&data.forma_pago, 8.0, Mm(45.0), Mm(y), &font);
-    
-    y -= 4.0;
-    current_layer.use_text("Plazo de Entrega:", 8.0, Mm(15.0), Mm(y), &font_bold);
-    current_layer.use_text(&data.plazo_entrega, 8.0, Mm(45.0), Mm(y), &font);
-    
-    // ==== NOTA ====
-    y -= 6.0;
-    current_layer.use_text(
-        "* Se deberá adjuntar con la factura, la Orden de Compra Original sellada y Copia de Ingresos Varios",
-        7.0,
-        Mm(15.0),
-        Mm(y),
-        &font,
-    );
-    
-    // ==== TABLA IVA ====
-    y -= 8.0;
-    current_layer.use_text("IVA RESPONSABLE INSCRIPTO", 9.0, Mm(70.0), Mm(y), &font_bold);
-    
-    y -= 5.0;
-    current_layer.use_text("Importe Neto Gravado", 7.5, Mm(80.0), Mm(y), &font);
-    current_layer.use_text(&format!("$ {:.2}", data.subtotal), 7.5, Mm(185.0), Mm(y), &font);
-    
-    y -= 4.0;
-    let iva_pct = if data.es_iva_inscripto { "21%" } else { "10.50%" };
-    current_layer.use_text(&format!("IVA {} / 10.50%", iva_pct), 7.5, Mm(80.0), Mm(y), &font);
-    current_layer.use_text(&format!("$ {:.2}", data.iva), 7.5, Mm(185.0), Mm(y), &font);
-    
-    y -= 4.0;
-    current_layer.use_text("TOTAL", 8.0, Mm(80.0), Mm(y), &font_bold);
-    current_layer.use_text(&format!("$ {:.2}", data.total), 8.0, Mm(185.0), Mm(y), &font_bold);
-    
-    document
-        .save(&mut BufWriter::new(
-            File::create(output_path).map_err(|e| format!("Error al crear archivo: {}", e))?
-        ))
-        .map_err(|e| format!("Error al guardar PDF: {}", e))?;
-    
-    Ok(())
+
+    #[test]
+    fn test_copias_con_leyenda_y_marca_de_agua() {
+        let data = datos(vec![renglon(1, "Codo 90° PVC 110 mm")]);
+        let metricas = Metricas::new();
+
+        let duplicado = maquetar_oc(&data, Copia::Duplicado, true, &metricas);
+        assert!(textos(&duplicado[0]).contains(&"DUPLICADO"));
+        assert!(!textos(&duplicado[0]).contains(&"ORIGINAL"));
+        assert!(matches!(
+            &duplicado[0].operaciones[0],
+            Operacion::MarcaAgua { texto, .. } if texto == "DUPLICADO"
+        ));
+
+        let sin_marca = maquetar_oc(&data, Copia::Triplicado, false, &metricas);
+        assert!(textos(&sin_marca[0]).contains(&"TRIPLICADO"));
+        assert!(!sin_marca[0].operaciones.iter().any(|op| matches!(op, Operacion::MarcaAgua { .. })));
+    }
+
+    #[test]
+    fn test_anulada_lleva_sello_en_cada_hoja() {
+        let renglones = (1..=60).map(|n| renglon(n, "Abrazadera de reparación 110 mm")).collect();
+        let data = OCPDFData { anulada: true, ..datos(renglones) };
+
+        let hojas = maquetar_oc(&data, Copia::Original, true, &Metricas::new());
+        assert!(hojas.len() > 1);
+        for hoja in &hojas {
+            // Encima de todo, también de la marca de agua
+            assert!(matches!(hoja.operaciones.last(), Some(Operacion::Sello { texto, .. }) if texto == "ANULADA"));
+            assert!(matches!(hoja.operaciones[0], Operacion::MarcaAgua { .. }));
+        }
+    }
+
+    #[test]
+    fn test_renglon_mas_largo_que_una_hoja_se_corta() {
+        let enorme = "Provisión e instalación de compuerta. ".repeat(400);
+        let hojas = maquetar_oc(&datos(vec![renglon(1, &enorme)]), Copia::Original, false, &Metricas::new());
+
+        assert!(hojas.len() >= 3);
+        assert!(textos(&hojas[0]).contains(&AVISO_CONTINUA));
+    }
 }
@@ -0,0 +1,455 @@
+// Motor de maquetación de PDF
+// La maquetación produce una lista de operaciones por hoja (texto, líneas, logo) independiente de printpdf:
+// se serializa para compararla en los tests (archivos golden) y después se dibuja con printpdf
+
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+use printpdf::{
+    Color, ColorBits, ColorSpace, Greyscale, Image, ImageTransform, ImageXObject, IndirectFontRef, Line, Mm,
+    PdfDocument, Point, Px, Rgb, TextMatrix,
+};
+use ttf_parser::Face;
+
+/// Fuentes embebidas (DejaVu Sans): las fuentes estándar del PDF no garantizan acentos ni la ñ
+static FUENTE_NORMAL: &[u8] = include_bytes!("../../fonts/DejaVuSans.ttf");
+static FUENTE_NEGRITA: &[u8] = include_bytes!("../../fonts/DejaVuSans-Bold.ttf");
+
+/// Logo institucional que encabeza cada hoja
+static LOGO_PNG: &[u8] = include_bytes!("../../../public/irrigacion-negro.png");
+
+const MM_POR_PUNTO: f32 = 25.4 / 72.0;
+
+/// Gris de la marca de agua (0 = negro, 1 = blanco)
+const GRIS_MARCA_AGUA: f32 = 0.85;
+
+/// Rojo del sello (ANULADA)
+const ROJO_SELLO: (f32, f32, f32) = (0.75, 0.0, 0.0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fuente {
+    Normal,
+    Negrita,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Alineacion {
+    Izquierda,
+    Centro,
+    Derecha,
+}
+
+/// Operación de dibujo: medidas en milímetros con el origen abajo a la izquierda (como printpdf),
+/// tamaño de letra y grosor de línea en puntos
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operacion {
+    Texto { x: f32, y: f32, tamaño: f32, fuente: Fuente, texto: String },
+    Linea { x1: f32, y1: f32, x2: f32, y2: f32, grosor: f32 },
+    Rectangulo { x: f32, y: f32, ancho: f32, alto: f32, grosor: f32 },
+    Logo { x: f32, y: f32, ancho: f32, alto: f32 },
+    /// Texto gris girado `angulo` grados (antihorario) desde (x, y), debajo del resto de la hoja
+    MarcaAgua { x: f32, y: f32, tamaño: f32, angulo: f32, texto: String },
+    /// Como la marca de agua pero en rojo y encima del resto de la hoja
+    Sello { x: f32, y: f32, tamaño: f32, angulo: f32, texto: String },
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Hoja {
+    pub operaciones: Vec<Operacion>,
+}
+
+impl Hoja {
+    pub fn texto(&mut self, x: f32, y: f32, tamaño: f32, fuente: Fuente, texto: impl Into<String>) {
+        let texto = texto.into();
+        if !texto.is_empty() {
+            self.operaciones.push(Operacion::Texto { x, y, tamaño, fuente, texto });
+        }
+    }
+
+    pub fn linea(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, grosor: f32) {
+        self.operaciones.push(Operacion::Linea { x1, y1, x2, y2, grosor });
+    }
+
+    /// (x, y) es la esquina inferior izquierda
+    pub fn rectangulo(&mut self, x: f32, y: f32, ancho: f32, alto: f32, grosor: f32) {
+        self.operaciones.push(Operacion::Rectangulo { x, y, ancho, alto, grosor });
+    }
+
+    /// Logo con su esquina inferior izquierda en (x, y) y el alto que corresponde al ancho
+    pub fn logo(&mut self, x: f32, y: f32, ancho: f32) -> f32 {
+        let alto = ancho * proporcion_logo();
+        self.operaciones.push(Operacion::Logo { x, y, ancho, alto });
+        alto
+    }
+
+    /// Marca de agua en diagonal centrada en la hoja; se dibuja antes que el resto para quedar debajo
+    pub fn marca_agua(&mut self, metricas: &Metricas, ancho_hoja: f32, alto_hoja: f32, tamaño: f32, texto: &str) {
+        let (x, y, angulo) = diagonal(metricas, ancho_hoja, alto_hoja, tamaño, texto);
+        self.operaciones.insert(0, Operacion::MarcaAgua { x, y, tamaño, angulo, texto: texto.to_string() });
+    }
+
+    /// Sello en diagonal centrado en la hoja; se dibuja después del resto para quedar encima
+    pub fn sello(&mut self, metricas: &Metricas, ancho_hoja: f32, alto_hoja: f32, tamaño: f32, texto: &str) {
+        let (x, y, angulo) = diagonal(metricas, ancho_hoja, alto_hoja, tamaño, texto);
+        self.operaciones.push(Operacion::Sello { x, y, tamaño, angulo, texto: texto.to_string() });
+    }
+
+    /// Texto alineado dentro de una caja que empieza en x y mide `ancho`
+    #[allow(clippy::too_many_arguments)]
+    pub fn texto_alineado(
+        &mut self,
+        metricas: &Metricas,
+        x: f32,
+        ancho: f32,
+        y: f32,
+        tamaño: f32,
+        fuente: Fuente,
+        alineacion: Alineacion,
+        texto: &str,
+    ) {
+        let sobrante = ancho - metricas.ancho(texto, fuente, tamaño);
+        let x = match alineacion {
+            Alineacion::Izquierda => x,
+            Alineacion::Centro => x + sobrante / 2.0,
+            Alineacion::Derecha => x + sobrante,
+        };
+        self.texto(x, y, tamaño, fuente, texto);
+    }
+
+    /// Párrafo partido al ancho disponible; devuelve la altura de la línea base de la última línea
+    #[allow(clippy::too_many_arguments)]
+    pub fn parrafo(
+        &mut self,
+        metricas: &Metricas,
+        x: f32,
+        ancho: f32,
+        y: f32,
+        tamaño: f32,
+        fuente: Fuente,
+        texto: &str,
+    ) -> f32 {
+        let mut y = y;
+        for (i, linea) in metricas.partir(texto, fuente, tamaño, ancho).into_iter().enumerate() {
+            if i > 0 {
+                y -= interlineado(tamaño);
+            }
+            self.texto(x, y, tamaño, fuente, linea);
+        }
+        y
+    }
+}
+
+/// Distancia entre líneas base para un tamaño de letra (mm)
+pub fn interlineado(tamaño: f32) -> f32 {
+    tamaño * MM_POR_PUNTO * 1.2
+}
+
+/// Métricas de las fuentes embebidas para medir y partir texto
+pub struct Metricas {
+    normal: Face<'static>,
+    negrita: Face<'static>,
+}
+
+impl Default for Metricas {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Metricas {
+    pub fn new() -> Self {
+        Metricas {
+            normal: Face::parse(FUENTE_NORMAL, 0).expect("fuente embebida DejaVuSans"),
+            negrita: Face::parse(FUENTE_NEGRITA, 0).expect("fuente embebida DejaVuSans-Bold"),
+        }
+    }
+
+    /// Ancho del texto en milímetros
+    pub fn ancho(&self, texto: &str, fuente: Fuente, tamaño: f32) -> f32 {
+        let face = match fuente {
+            Fuente::Normal => &self.normal,
+            Fuente::Negrita => &self.negrita,
+        };
+        let unidades: u32 = texto
+            .chars()
+            .map(|c| {
+                face.glyph_index(c)
+                    .and_then(|glifo| face.glyph_hor_advance(glifo))
+                    .unwrap_or(face.units_per_em() / 2) as u32
+            })
+            .sum();
+        unidades as f32 / face.units_per_em() as f32 * tamaño * MM_POR_PUNTO
+    }
+
+    /// Partir el texto en líneas que entren en `ancho` (respeta los saltos de línea propios)
+    /// Una palabra más larga que el ancho se corta donde haga falta
+    pub fn partir(&self, texto: &str, fuente: Fuente, tamaño: f32, ancho: f32) -> Vec<String> {
+        let mut lineas = Vec::new();
+
+        for parrafo in texto.lines() {
+            let mut actual = String::new();
+            for palabra in parrafo.split_whitespace() {
+                let candidata = if actual.is_empty() { palabra.to_string() } else { format!("{} {}", actual, palabra) };
+                if self.ancho(&candidata, fuente, tamaño) <= ancho {
+                    actual = candidata;
+                    continue;
+                }
+
+                if !actual.is_empty() {
+                    lineas.push(std::mem::take(&mut actual));
+                }
+                for c in palabra.chars() {
+                    actual.push(c);
+                    if self.ancho(&actual, fuente, tamaño) > ancho && actual.chars().count() > 1 {
+                        actual.pop();
+                        lineas.push(std::mem::take(&mut actual));
+                        actual.push(c);
+                    }
+                }
+            }
+            lineas.push(actual);
+        }
+
+        if lineas.is_empty() {
+            lineas.push(String::new());
+        }
+        lineas
+    }
+}
+
+/// Alto / ancho del logo (se lee del encabezado del PNG)
+pub fn proporcion_logo() -> f32 {
+    let (ancho, alto) = png::Decoder::new(LOGO_PNG)
+        .read_info()
+        .map(|lector| (lector.info().width, lector.info().height))
+        .expect("logo embebido irrigacion-negro.png");
+    alto as f32 / ancho as f32
+}
+
+/// Origen y ángulo (en grados) de un texto en negrita centrado sobre la diagonal de la hoja
+fn diagonal(metricas: &Metricas, ancho_hoja: f32, alto_hoja: f32, tamaño: f32, texto: &str) -> (f32, f32, f32) {
+    let angulo = (alto_hoja / ancho_hoja).atan();
+    let mitad = metricas.ancho(texto, Fuente::Negrita, tamaño) / 2.0;
+    (
+        ancho_hoja / 2.0 - mitad * angulo.cos(),
+        alto_hoja / 2.0 - mitad * angulo.sin(),
+        angulo.to_degrees(),
+    )
+}
+
+/// Texto estable de las hojas para los tests golden: una operación por línea
+pub fn serializar(hojas: &[Hoja]) -> String {
+    let mut salida = String::new();
+    for (i, hoja) in hojas.iter().enumerate() {
+        let _ = writeln!(salida, "hoja {}", i + 1);
+        for operacion in &hoja.operaciones {
+            let _ = match operacion {
+                Operacion::Texto { x, y, tamaño, fuente, texto } => {
+                    let fuente = match fuente {
+                        Fuente::Normal => "normal",
+                        Fuente::Negrita => "negrita",
+                    };
+                    writeln!(salida, "texto {:.2} {:.2} {:.1} {} {:?}", x, y, tamaño, fuente, texto)
+                }
+                Operacion::Linea { x1, y1, x2, y2, grosor } => {
+                    writeln!(salida, "linea {:.2} {:.2} {:.2} {:.2} {:.1}", x1, y1, x2, y2, grosor)
+                }
+                Operacion::Rectangulo { x, y, ancho, alto, grosor } => {
+                    writeln!(salida, "rectangulo {:.2} {:.2} {:.2} {:.2} {:.1}", x, y, ancho, alto, grosor)
+                }
+                Operacion::Logo { x, y, ancho, alto } => {
+                    writeln!(salida, "logo {:.2} {:.2} {:.2} {:.2}", x, y, ancho, alto)
+                }
+                Operacion::MarcaAgua { x, y, tamaño, angulo, texto } => {
+                    writeln!(salida, "marca_agua {:.2} {:.2} {:.1} {:.1} {:?}", x, y, tamaño, angulo, texto)
+                }
+                Operacion::Sello { x, y, tamaño, angulo, texto } => {
+                    writeln!(salida, "sello {:.2} {:.2} {:.1} {:.1} {:?}", x, y, tamaño, angulo, texto)
+                }
+            };
+        }
+    }
+    salida
+}
+
+/// Dibujar las hojas maquetadas con printpdf (fuentes y logo embebidos en el archivo)
+pub fn dibujar(titulo: &str, ancho: f32, alto: f32, hojas: &[Hoja], salida: &Path) -> Result<(), String> {
+    let (document, primera, capa) = PdfDocument::new(titulo, Mm(ancho), Mm(alto), "Hoja 1");
+
+    let normal = document
+        .add_external_font(FUENTE_NORMAL)
+        .map_err(|e| format!("Error al cargar fuente: {}", e))?;
+    let negrita = document
+        .add_external_font(FUENTE_NEGRITA)
+        .map_err(|e| format!("Error al cargar fuente bold: {}", e))?;
+    let logo = decodificar_logo()?;
+
+    for (i, hoja) in hojas.iter().enumerate() {
+        let layer = if i == 0 {
+            document.get_page(primera).get_layer(capa)
+        } else {
+            let (pagina, capa) = document.add_page(Mm(ancho), Mm(alto), format!("Hoja {}", i + 1));
+            document.get_page(pagina).get_layer(capa)
+        };
+
+        for operacion in &hoja.operaciones {
+            match operacion {
+                Operacion::Texto { x, y, tamaño, fuente, texto } => {
+                    let fuente: &IndirectFontRef = match fuente {
+                        Fuente::Normal => &normal,
+                        Fuente::Negrita => &negrita,
+                    };
+                    layer.use_text(texto.as_str(), *tamaño, Mm(*x), Mm(*y), fuente);
+                }
+                Operacion::Linea { x1, y1, x2, y2, grosor } => {
+                    layer.set_outline_thickness(*grosor);
+                    layer.add_line(Line {
+                        points: vec![(Point::new(Mm(*x1), Mm(*y1)), false), (Point::new(Mm(*x2), Mm(*y2)), false)],
+                        is_closed: false,
+                    });
+                }
+                Operacion::Rectangulo { x, y, ancho, alto, grosor } => {
+                    layer.set_outline_thickness(*grosor);
+                    layer.add_line(Line {
+                        points: vec![
+                            (Point::new(Mm(*x), Mm(*y)), false),
+                            (Point::new(Mm(x + ancho), Mm(*y)), false),
+                            (Point::new(Mm(x + ancho), Mm(y + alto)), false),
+                            (Point::new(Mm(*x), Mm(y + alto)), false),
+                        ],
+                        is_closed: true,
+                    });
+                }
+                Operacion::Logo { x, y, ancho, .. } => {
+                    // A 300 dpi cada píxel mide 25.4 / 300 mm: se escala al ancho pedido
+                    let natural = logo.width.0 as f32 * 25.4 / 300.0;
+                    let escala = ancho / natural;
+                    Image::from(logo.clone()).add_to_layer(
+                        layer.clone(),
+                        ImageTransform {
+                            translate_x: Some(Mm(*x)),
+                            translate_y: Some(Mm(*y)),
+                            scale_x: Some(escala),
+                            scale_y: Some(escala),
+                            dpi: Some(300.0),
+                            ..Default::default()
+                        },
+                    );
+                }
+                Operacion::MarcaAgua { x, y, tamaño, angulo, texto } | Operacion::Sello { x, y, tamaño, angulo, texto } => {
+                    let color = if matches!(operacion, Operacion::Sello { .. }) {
+                        Color::Rgb(Rgb::new(ROJO_SELLO.0, ROJO_SELLO.1, ROJO_SELLO.2, None))
+                    } else {
+                        Color::Greyscale(Greyscale::new(GRIS_MARCA_AGUA, None))
+                    };
+                    layer.begin_text_section();
+                    layer.set_fill_color(color);
+                    layer.set_font(&negrita, *tamaño);
+                    layer.set_text_matrix(TextMatrix::TranslateRotate(Mm(*x).into(), Mm(*y).into(), *angulo));
+                    layer.write_text(texto.as_str(), &negrita);
+                    layer.end_text_section();
+                    layer.set_fill_color(Color::Greyscale(Greyscale::new(0.0, None)));
+                }
+            }
+        }
+    }
+
+    document
+        .save(&mut BufWriter::new(
+            File::create(salida).map_err(|e| format!("Error al crear archivo: {}", e))?,
+        ))
+        .map_err(|e| format!("Error al guardar PDF: {}", e))
+}
+
+/// Logo en RGB de 8 bits: la transparencia se compone sobre fondo blanco
+fn decodificar_logo() -> Result<ImageXObject, String> {
+    let mut decoder = png::Decoder::new(LOGO_PNG);
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut lector = decoder.read_info().map_err(|e| format!("Error al leer el logo: {}", e))?;
+    let mut pixeles = vec![0; lector.output_buffer_size()];
+    let info = lector
+        .next_frame(&mut pixeles)
+        .map_err(|e| format!("Error al leer el logo: {}", e))?;
+    pixeles.truncate(info.buffer_size());
+
+    let sobre_blanco = |valor: u8, alfa: u8| -> u8 {
+        ((valor as u16 * alfa as u16 + 255 * (255 - alfa as u16)) / 255) as u8
+    };
+    let rgb: Vec<u8> = match info.color_type {
+        png::ColorType::Rgb => pixeles,
+        png::ColorType::Rgba => pixeles
+            .chunks_exact(4)
+            .flat_map(|p| [sobre_blanco(p[0], p[3]), sobre_blanco(p[1], p[3]), sobre_blanco(p[2], p[3])])
+            .collect(),
+        png::ColorType::Grayscale => pixeles.iter().flat_map(|&g| [g, g, g]).collect(),
+        png::ColorType::GrayscaleAlpha => pixeles
+            .chunks_exact(2)
+            .flat_map(|p| {
+                let g = sobre_blanco(p[0], p[1]);
+                [g, g, g]
+            })
+            .collect(),
+        png::ColorType::Indexed => return Err("Logo con paleta sin expandir".to_string()),
+    };
+
+    Ok(ImageXObject {
+        width: Px(info.width as usize),
+        height: Px(info.height as usize),
+        color_space: ColorSpace::Rgb,
+        bits_per_component: ColorBits::Bit8,
+        interpolate: true,
+        image_data: rgb,
+        image_filter: None,
+        smask: None,
+        clipping_bbox: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partir_respeta_el_ancho() {
+        let metricas = Metricas::new();
+        let texto = "Caño de PVC de 110 mm para desagüe con junta elástica y accesorios de montaje";
+        let lineas = metricas.partir(texto, Fuente::Normal, 8.0, 40.0);
+
+        assert!(lineas.len() > 1);
+        assert!(lineas.iter().all(|l| metricas.ancho(l, Fuente::Normal, 8.0) <= 40.0));
+        assert_eq!(lineas.join(" "), texto);
+    }
+
+    #[test]
+    fn test_partir_corta_palabras_largas() {
+        let metricas = Metricas::new();
+        let lineas = metricas.partir("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA", Fuente::Negrita, 10.0, 20.0);
+
+        assert!(lineas.len() > 1);
+        assert!(lineas.iter().all(|l| metricas.ancho(l, Fuente::Negrita, 10.0) <= 20.0));
+    }
+
+    #[test]
+    fn test_dibujar_embebe_fuente_y_logo() {
+        let mut hoja = Hoja::default();
+        hoja.logo(15.0, 270.0, 40.0);
+        hoja.texto(15.0, 260.0, 10.0, Fuente::Negrita, "IRRIGACIÓN — Malargüe");
+        hoja.rectangulo(15.0, 200.0, 50.0, 20.0, 0.5);
+        hoja.marca_agua(&Metricas::new(), 210.0, 297.0, 60.0, "DUPLICADO");
+        hoja.sello(&Metricas::new(), 210.0, 297.0, 90.0, "ANULADA");
+        assert!(matches!(hoja.operaciones[0], Operacion::MarcaAgua { .. }));
+        assert!(matches!(hoja.operaciones.last(), Some(Operacion::Sello { .. })));
+
+        let salida = std::env::temp_dir().join(format!("gestor-test-layout-{}.pdf", uuid::Uuid::new_v4()));
+        dibujar("Prueba", 210.0, 297.0, &[hoja.clone(), hoja], &salida).unwrap();
+        let contenido = std::fs::read(&salida).unwrap();
+        std::fs::remove_file(&salida).unwrap();
+
+        assert!(contenido.starts_with(b"%PDF"));
+        let texto = String::from_utf8_lossy(&contenido);
+        assert!(texto.contains("FontFile2"), "la fuente TrueType tiene que quedar embebida");
+        assert!(texto.contains("/Subtype /Image") || texto.contains("/Subtype/Image"));
+    }
+}
@@ -0,0 +1,211 @@
+// Pruebas de integración de los commands de Personal y Movilidades
+// Cada command delega en su repositorio; se ejecutan contra una base temporal
+// inicializada con el mismo pipeline de migraciones que usa la aplicación
+
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use gestor_irrigacion_lib::models::agente::{CreateAgente, UpdateAgente};
+use gestor_irrigacion_lib::models::ticket::{CreateTicket, TipoCombustible};
+use gestor_irrigacion_lib::models::vehiculo::{CreateVehiculo, TipoVehiculo};
+use gestor_irrigacion_lib::models::zona::ZONA_PREDETERMINADA;
+use gestor_irrigacion_lib::repositories::{AgenteRepository, TicketRepository, VehiculoRepository};
+use gestor_irrigacion_lib::{init_databases, AppError, DatabasePool};
+
+/// Base SQLite en un archivo temporal; se borra al terminar la prueba
+struct BaseTemporal {
+    pools: DatabasePool,
+    ruta: std::path::PathBuf,
+}
+
+impl BaseTemporal {
+    async fn nueva() -> Self {
+        let ruta = std::env::temp_dir().join(format!("gestor_test_{}.db", Uuid::new_v4()));
+        Self::abrir(ruta).await
+    }
+
+    async fn abrir(ruta: std::path::PathBuf) -> Self {
+        let pools = DatabasePool::new(ruta.to_str().unwrap(), None).await.unwrap();
+        init_databases(&pools).await.unwrap();
+        BaseTemporal { pools, ruta }
+    }
+}
+
+impl Drop for BaseTemporal {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.ruta);
+    }
+}
+
+fn nuevo_agente(dni: &str, legajo: &str) -> CreateAgente {
+    CreateAgente {
+        dni: dni.to_string(),
+        nombre: "Ana".to_string(),
+        apellido: "Pérez".to_string(),
+        email: None,
+        telefono: None,
+        legajo: legajo.to_string(),
+        cargo: "Chofer".to_string(),
+        area: "Movilidades".to_string(),
+        fecha_ingreso: Utc::now(),
+        licencia_conducir: Some("B1".to_string()),
+        vencimiento_licencia: Some(Utc::now() + Duration::days(10)),
+    }
+}
+
+fn nuevo_vehiculo(patente: &str) -> CreateVehiculo {
+    CreateVehiculo {
+        patente: patente.to_string(),
+        marca: "Toyota".to_string(),
+        modelo: "Hilux".to_string(),
+        año: 2020,
+        tipo: TipoVehiculo::Camioneta,
+        numero_motor: None,
+        numero_chasis: None,
+        color: None,
+        capacidad_tanque: 80.0,
+        kilometraje_actual: 1000.0,
+        area_asignada: None,
+    }
+}
+
+fn carga(vehiculo_id: &str, agente_id: &str, dias: i64, litros: f64, kilometraje: f64) -> CreateTicket {
+    CreateTicket {
+        vehiculo_id: vehiculo_id.to_string(),
+        agente_id: agente_id.to_string(),
+        fecha_carga: Utc::now() - Duration::days(dias),
+        numero_ticket: None,
+        tipo_combustible: TipoCombustible::Diesel,
+        litros,
+        precio_por_litro: 1000.0,
+        kilometraje,
+        estacion_servicio: "YPF".to_string(),
+        localidad: "Mendoza".to_string(),
+        observaciones: None,
+    }
+}
+
+#[tokio::test]
+async fn test_commands_agentes() {
+    let base = BaseTemporal::nueva().await;
+    let pool = base.pools.get_sqlite();
+
+    // create_agente / get_agente
+    let agente = AgenteRepository::create(pool, ZONA_PREDETERMINADA, nuevo_agente("30111222", "L-1")).await.unwrap();
+    assert_eq!(AgenteRepository::get_by_id(pool, ZONA_PREDETERMINADA, &agente.id).await.unwrap().dni, "30111222");
+
+    // update_agente
+    let cambios = UpdateAgente {
+        nombre: None,
+        apellido: None,
+        email: Some("ana@irrigacion.gov.ar".to_string()),
+        telefono: None,
+        cargo: Some("Supervisora".to_string()),
+        area: None,
+        activo: None,
+        licencia_conducir: None,
+        vencimiento_licencia: None,
+    };
+    let agente = AgenteRepository::update(pool, ZONA_PREDETERMINADA, &agente.id, cambios).await.unwrap();
+    assert_eq!(agente.cargo, "Supervisora");
+    assert_eq!(agente.nombre, "Ana");
+
+    // get_all_agentes / delete_agente (baja lógica)
+    assert_eq!(AgenteRepository::get_activos(pool, ZONA_PREDETERMINADA).await.unwrap().len(), 1);
+    AgenteRepository::delete(pool, ZONA_PREDETERMINADA, &agente.id).await.unwrap();
+    assert!(AgenteRepository::get_activos(pool, ZONA_PREDETERMINADA).await.unwrap().is_empty());
+    assert!(!AgenteRepository::get_by_id(pool, ZONA_PREDETERMINADA, &agente.id).await.unwrap().activo);
+
+    assert!(matches!(
+        AgenteRepository::get_by_id(pool, ZONA_PREDETERMINADA, "inexistente").await,
+        Err(AppError::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_commands_vehiculos_y_tickets() {
+    let base = BaseTemporal::nueva().await;
+    let pool = base.pools.get_sqlite();
+
+    let agente = AgenteRepository::create(pool, ZONA_PREDETERMINADA, nuevo_agente("30111222", "L-1")).await.unwrap();
+
+    // create_vehiculo / get_vehiculo / update_kilometraje
+    let vehiculo = VehiculoRepository::create(pool, ZONA_PREDETERMINADA, nuevo_vehiculo("AB123CD")).await.unwrap();
+    assert_eq!(vehiculo.kilometraje_actual, 1000.0);
+    VehiculoRepository::update_kilometraje(pool, ZONA_PREDETERMINADA, &vehiculo.id, 1500.0).await.unwrap();
+    assert_eq!(VehiculoRepository::get_by_id(pool, ZONA_PREDETERMINADA, &vehiculo.id).await.unwrap().kilometraje_actual, 1500.0);
+
+    // create_ticket: toma el kilometraje anterior y actualiza el del vehículo
+    TicketRepository::create(pool, ZONA_PREDETERMINADA, carga(&vehiculo.id, &agente.id, 2, 40.0, 1500.0)).await.unwrap();
+    let ticket = TicketRepository::create(pool, ZONA_PREDETERMINADA, carga(&vehiculo.id, &agente.id, 1, 50.0, 2000.0)).await.unwrap();
+    assert_eq!(ticket.kilometraje_anterior, Some(1500.0));
+    assert_eq!(ticket.monto_total, 50_000.0);
+    assert_eq!(VehiculoRepository::get_by_id(pool, ZONA_PREDETERMINADA, &vehiculo.id).await.unwrap().kilometraje_actual, 2000.0);
+
+    // get_all_tickets / get_tickets_by_vehiculo
+    assert_eq!(TicketRepository::get_all(pool, ZONA_PREDETERMINADA).await.unwrap().len(), 2);
+    let tickets = TicketRepository::get_by_vehiculo(pool, ZONA_PREDETERMINADA, &vehiculo.id).await.unwrap();
+    assert_eq!(tickets[0].id, ticket.id);
+
+    // calcular_rendimiento
+    let rendimiento = TicketRepository::calcular_rendimiento(pool, ZONA_PREDETERMINADA, &vehiculo.id).await.unwrap();
+    assert_eq!(rendimiento.patente, "AB123CD");
+    assert_eq!(rendimiento.cantidad_cargas, 2);
+    assert_eq!(rendimiento.kilometros_recorridos, 500.0);
+    assert_eq!(rendimiento.total_litros, 90.0);
+
+    // delete_ticket / delete_vehiculo
+    TicketRepository::delete(pool, ZONA_PREDETERMINADA, &ticket.id).await.unwrap();
+    assert_eq!(TicketRepository::get_all(pool, ZONA_PREDETERMINADA).await.unwrap().len(), 1);
+    VehiculoRepository::delete(pool, ZONA_PREDETERMINADA, &vehiculo.id).await.unwrap();
+    assert!(VehiculoRepository::get_activos(pool, ZONA_PREDETERMINADA).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_migracion_de_esquema_anterior() {
+    let ruta = std::env::temp_dir().join(format!("gestor_test_{}.db", Uuid::new_v4()));
+
+    // Base creada con el esquema anterior de agentes, vehiculos y tickets
+    {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .connect_with(
+                sqlx::sqlite::SqliteConnectOptions::new()
+                    .filename(&ruta)
+                    .create_if_missing(true),
+            )
+            .await
+            .unwrap();
+        for sql in [
+            "CREATE TABLE agentes (id TEXT PRIMARY KEY, nombre TEXT NOT NULL, apellido TEXT NOT NULL, dni TEXT UNIQUE NOT NULL, legajo TEXT UNIQUE NOT NULL, area TEXT NOT NULL, tipo_licencia TEXT, fecha_vencimiento_licencia DATE, created_at DATETIME DEFAULT CURRENT_TIMESTAMP, updated_at DATETIME DEFAULT CURRENT_TIMESTAMP)",
+            "CREATE TABLE vehiculos (id TEXT PRIMARY KEY, patente TEXT UNIQUE NOT NULL, tipo TEXT NOT NULL, marca TEXT NOT NULL, modelo TEXT NOT NULL, año INTEGER NOT NULL, kilometraje INTEGER DEFAULT 0, estado TEXT DEFAULT 'Activo', created_at DATETIME DEFAULT CURRENT_TIMESTAMP, updated_at DATETIME DEFAULT CURRENT_TIMESTAMP)",
+            "CREATE TABLE tickets_combustible (id TEXT PRIMARY KEY, vehiculo_id TEXT NOT NULL, fecha DATE NOT NULL, litros REAL NOT NULL, precio_total REAL NOT NULL, kilometraje_actual INTEGER NOT NULL, created_at DATETIME DEFAULT CURRENT_TIMESTAMP, updated_at DATETIME DEFAULT CURRENT_TIMESTAMP, FOREIGN KEY (vehiculo_id) REFERENCES vehiculos(id))",
+            "INSERT INTO agentes (id, nombre, apellido, dni, legajo, area, tipo_licencia, fecha_vencimiento_licencia) VALUES ('a1', 'Juan', 'Gómez', '20999888', 'L-9', 'Taller', 'D2', '2030-01-31')",
+            "INSERT INTO vehiculos (id, patente, tipo, marca, modelo, año, kilometraje) VALUES ('v1', 'AA000AA', 'camioneta', 'Ford', 'Ranger', 2015, 120000)",
+            "INSERT INTO tickets_combustible (id, vehiculo_id, fecha, litros, precio_total, kilometraje_actual) VALUES ('t1', 'v1', '2024-05-10', 50, 45000, 120000)",
+        ] {
+            sqlx::query(sql).execute(&pool).await.unwrap();
+        }
+        pool.close().await;
+    }
+
+    let base = BaseTemporal::abrir(ruta).await;
+    let pool = base.pools.get_sqlite();
+
+    let agente = AgenteRepository::get_by_id(pool, ZONA_PREDETERMINADA, "a1").await.unwrap();
+    assert_eq!(agente.licencia_conducir.as_deref(), Some("D2"));
+    assert!(agente.activo);
+
+    let vehiculo = VehiculoRepository::get_by_id(pool, ZONA_PREDETERMINADA, "v1").await.unwrap();
+    assert_eq!(vehiculo.kilometraje_actual, 120000.0);
+    assert!(matches!(vehiculo.tipo, TipoVehiculo::Camioneta));
+
+    let tickets = TicketRepository::get_by_vehiculo(pool, ZONA_PREDETERMINADA, "v1").await.unwrap();
+    assert_eq!(tickets.len(), 1);
+    assert_eq!(tickets[0].precio_por_litro, 900.0);
+    assert!(tickets[0].agente_id.is_none());
+
+    // Los commands funcionan sobre la tabla migrada
+    let nuevo = VehiculoRepository::create(pool, ZONA_PREDETERMINADA, nuevo_vehiculo("AC456EF")).await.unwrap();
+    assert_eq!(VehiculoRepository::get_activos(pool, ZONA_PREDETERMINADA).await.unwrap().len(), 2);
+    assert!(nuevo.activo);
+}
@@ -1,7 +1,13 @@
+import { getCurrentWindow } from "@tauri-apps/api/window";
 import Dashboard from "./components/Dashboard";
+import CapturaRapida from "./components/CapturaRapida";
 import "./App.css";
 
 function App() {
+  // La ventana de captura rápida (atajo de InfoGov) carga el mismo index.html
+  if (getCurrentWindow().label === "captura-rapida") {
+    return <CapturaRapida />;
+  }
   return <Dashboard />;
 }
 
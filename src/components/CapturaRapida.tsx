@@ -0,0 +1,109 @@
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { AtajosService } from "@/services/atajos.service";
+import type { FilaCapturaRapida, InfoGovExpediente } from "@/types/atajos";
+
+// Ventana siempre visible que abre el atajo de captura: muestra lo interpretado
+// y permite corregir tema y estado antes de guardar
+export default function CapturaRapida() {
+  const [filas, setFilas] = useState<FilaCapturaRapida[]>([]);
+  const [saving, setSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    AtajosService.obtenerCapturaRapida().then(setFilas).catch((err) => setError(String(err)));
+
+    // Cada nueva captura reemplaza la anterior
+    const unlisten = listen<FilaCapturaRapida[]>("captura_rapida", (event) => {
+      setFilas(event.payload);
+      setError(null);
+    });
+    return () => {
+      unlisten.then((f) => f());
+    };
+  }, []);
+
+  const updateCampo = (fila: number, campo: keyof Pick<InfoGovExpediente, "tema" | "estado">, valor: string) => {
+    setFilas((prev) =>
+      prev.map((f) => (f.fila === fila && f.expediente ? { ...f, expediente: { ...f.expediente, [campo]: valor } } : f))
+    );
+  };
+
+  const guardar = async () => {
+    try {
+      setSaving(true);
+      setError(null);
+      await AtajosService.guardarCapturaRapida(filas);
+      setFilas([]);
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const descartar = async () => {
+    await AtajosService.descartarCapturaRapida();
+    setFilas([]);
+  };
+
+  const validas = filas.filter((f) => f.expediente).length;
+
+  return (
+    <div className="h-screen flex flex-col bg-white dark:bg-slate-900 text-slate-900 dark:text-slate-100">
+      <header className="px-4 py-3 border-b border-slate-200 dark:border-slate-700">
+        <h1 className="text-base font-semibold">Captura rápida de InfoGov</h1>
+        <p className="text-xs text-slate-500">
+          {validas} de {filas.length} filas reconocidas. Revisá tema y estado antes de guardar.
+        </p>
+      </header>
+
+      <div className="flex-1 overflow-auto p-4 space-y-3">
+        {filas.length === 0 && (
+          <p className="text-sm text-slate-500">Seleccioná expedientes en InfoGov, copialos y presioná el atajo de captura.</p>
+        )}
+
+        {filas.map((fila) =>
+          fila.expediente ? (
+            <div key={fila.fila} className="rounded-lg border border-slate-200 dark:border-slate-700 p-3 space-y-2">
+              <div className="flex justify-between text-xs text-slate-500">
+                <span className="font-mono">{fila.expediente.nro_infogov}</span>
+                <span className="font-mono">{fila.expediente.nro_gde}</span>
+                <span>{fila.expediente.fecha_pase}</span>
+              </div>
+              <Input
+                value={fila.expediente.tema}
+                onChange={(e) => updateCampo(fila.fila, "tema", e.target.value)}
+                placeholder="Tema"
+              />
+              <Input
+                value={fila.expediente.estado}
+                onChange={(e) => updateCampo(fila.fila, "estado", e.target.value)}
+                placeholder="Estado en InfoGov"
+              />
+            </div>
+          ) : (
+            <div key={fila.fila} className="rounded-lg border border-red-200 bg-red-50 dark:bg-red-950 p-3 text-xs">
+              <p className="font-medium text-red-700 dark:text-red-300">Fila {fila.fila}: {fila.error}</p>
+              <p className="text-slate-500 truncate">{fila.texto}</p>
+            </div>
+          )
+        )}
+      </div>
+
+      <footer className="px-4 py-3 border-t border-slate-200 dark:border-slate-700 flex items-center gap-2">
+        {error && <p className="flex-1 text-xs text-red-600">{error}</p>}
+        <div className="ml-auto flex gap-2">
+          <Button variant="outline" onClick={descartar} disabled={saving}>
+            Descartar
+          </Button>
+          <Button onClick={guardar} disabled={saving || validas === 0}>
+            {saving ? "Guardando..." : `Guardar ${validas}`}
+          </Button>
+        </div>
+      </footer>
+    </div>
+  );
+}
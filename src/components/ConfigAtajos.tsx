@@ -0,0 +1,85 @@
+import { useEffect, useState } from "react";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { AtajosService } from "@/services/atajos.service";
+import type { EstadoAtajo } from "@/types/atajos";
+
+export default function ConfigAtajos() {
+  const [atajos, setAtajos] = useState<EstadoAtajo[]>([]);
+  const [loading, setLoading] = useState(true);
+  const [saving, setSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    const load = async () => {
+      try {
+        setLoading(true);
+        setAtajos(await AtajosService.obtenerAtajos());
+      } finally {
+        setLoading(false);
+      }
+    };
+    load();
+  }, []);
+
+  const updateCombinacion = (accion: EstadoAtajo["accion"], combinacion: string) => {
+    setAtajos((prev) => prev.map((a) => (a.accion === accion ? { ...a, combinacion } : a)));
+  };
+
+  const saveAtajos = async () => {
+    try {
+      setSaving(true);
+      setError(null);
+      const actualizados = await AtajosService.actualizarAtajos(
+        atajos.map(({ accion, combinacion }) => ({ accion, combinacion }))
+      );
+      setAtajos(actualizados);
+    } catch (err) {
+      // Conflicto o combinación inválida: los atajos anteriores siguen activos
+      setError(String(err));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  return (
+    <div>
+      <h3 className="text-lg font-semibold text-slate-900 dark:text-white mb-1">Atajos de teclado</h3>
+      <p className="text-sm text-slate-600 dark:text-slate-300 mb-4">
+        Funcionan aunque la aplicación esté minimizada. Ejemplo: Ctrl+Shift+I. Dejar vacío para desactivar.
+      </p>
+
+      {loading ? (
+        <div className="text-sm text-slate-500">Cargando atajos...</div>
+      ) : (
+        <div className="space-y-3">
+          {atajos.map((atajo) => (
+            <div key={atajo.accion} className="grid grid-cols-1 md:grid-cols-12 gap-3 items-center">
+              <div className="md:col-span-6">
+                <Label className="text-slate-700 dark:text-slate-300">{atajo.descripcion}</Label>
+                {atajo.error && (
+                  <p className="text-xs text-red-600 mt-1">No registrado: {atajo.error}</p>
+                )}
+              </div>
+              <div className="md:col-span-6">
+                <Input
+                  value={atajo.combinacion}
+                  placeholder="Sin atajo"
+                  onChange={(e) => updateCombinacion(atajo.accion, e.target.value)}
+                  className="dark:bg-slate-700 dark:border-slate-600 dark:text-slate-100"
+                />
+              </div>
+            </div>
+          ))}
+
+          {error && <p className="text-sm text-red-600">{error}</p>}
+
+          <Button variant="outline" onClick={saveAtajos} disabled={saving}>
+            {saving ? "Guardando..." : "Guardar atajos"}
+          </Button>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,217 @@
+import { useEffect, useState, type ReactNode } from "react";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { ConfiguracionService } from "@/services/configuracion.service";
+import { OrdenCompraService } from "@/services/orden_compra.service";
+import type { Settings, Zona } from "@/types/configuracion";
+import type { Prioridad } from "@/types/expediente";
+
+const inputClass = "dark:bg-slate-700 dark:border-slate-600 dark:text-slate-100";
+const selectClass =
+  "w-full h-9 rounded-md border border-slate-200 bg-white px-3 text-sm dark:bg-slate-700 dark:border-slate-600 dark:text-slate-100";
+
+export default function ConfigInstalacion() {
+  const [settings, setSettings] = useState<Settings | null>(null);
+  const [zonas, setZonas] = useState<Zona[]>([]);
+  const [tiposContratacion, setTiposContratacion] = useState<string[]>([]);
+  const [saving, setSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const [guardado, setGuardado] = useState(false);
+
+  useEffect(() => {
+    ConfiguracionService.obtenerSettings()
+      .then(setSettings)
+      .catch((err) => setError(String(err)));
+    ConfiguracionService.obtenerZonas()
+      .then(setZonas)
+      .catch((err) => setError(String(err)));
+    OrdenCompraService.obtenerConfigTopes()
+      .then((topes) => setTiposContratacion(topes.map((tope) => tope.tipo_contratacion)))
+      .catch((err) => setError(String(err)));
+  }, []);
+
+  const update = <K extends keyof Settings>(seccion: K, campo: keyof Settings[K], valor: Settings[K][keyof Settings[K]]) => {
+    setGuardado(false);
+    setSettings((prev) => (prev ? { ...prev, [seccion]: { ...prev[seccion], [campo]: valor } } : prev));
+  };
+
+  const saveSettings = async () => {
+    if (!settings) return;
+    try {
+      setSaving(true);
+      setError(null);
+      setSettings(await ConfiguracionService.actualizarSettings(settings));
+      setGuardado(true);
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  // Copias por tipo de contratación: "" = usar la cantidad por defecto
+  const updateCopiasPorTipo = (tipo: string, valor: string) => {
+    if (!settings) return;
+    const copiasPorTipo = { ...settings.impresion.copias_por_tipo };
+    if (valor) {
+      copiasPorTipo[tipo] = Number(valor);
+    } else {
+      delete copiasPorTipo[tipo];
+    }
+    update("impresion", "copias_por_tipo", copiasPorTipo);
+  };
+
+  const opcionesCopias = (
+    <>
+      <option value="1">Original</option>
+      <option value="2">Original y duplicado</option>
+      <option value="3">Original, duplicado y triplicado</option>
+    </>
+  );
+
+  if (!settings) {
+    return <div className="text-sm text-slate-500">{error ?? "Cargando configuración..."}</div>;
+  }
+
+  const campo = (label: string, input: ReactNode) => (
+    <div className="space-y-1">
+      <Label className="text-slate-700 dark:text-slate-300">{label}</Label>
+      {input}
+    </div>
+  );
+
+  return (
+    <div>
+      <h3 className="text-lg font-semibold text-slate-900 dark:text-white mb-1">Instalación</h3>
+      <p className="text-sm text-slate-600 dark:text-slate-300 mb-4">
+        Datos de la institución y de la zona de riego que se imprimen en las órdenes de compra e informes.
+      </p>
+
+      <div className="grid grid-cols-1 md:grid-cols-2 gap-3">
+        {campo("Institución", (
+          <Input value={settings.institucion.nombre} onChange={(e) => update("institucion", "nombre", e.target.value)} className={inputClass} />
+        ))}
+        {campo("CUIT", (
+          <Input value={settings.institucion.cuit} onChange={(e) => update("institucion", "cuit", e.target.value)} className={inputClass} />
+        ))}
+        {campo("Zona", (
+          <select value={settings.zona.id} onChange={(e) => update("zona", "id", e.target.value)} className={selectClass}>
+            {zonas.map((zona) => (
+              <option key={zona.id} value={zona.id}>
+                {zona.nombre}
+              </option>
+            ))}
+          </select>
+        ))}
+        {campo("Destino de las OC", (
+          <Input value={settings.zona.destino} onChange={(e) => update("zona", "destino", e.target.value)} className={inputClass} />
+        ))}
+        {campo("Domicilio para facturas", (
+          <Input value={settings.zona.domicilio} onChange={(e) => update("zona", "domicilio", e.target.value)} className={inputClass} />
+        ))}
+        {campo("Localidad (fecha de la OC)", (
+          <Input value={settings.zona.localidad} onChange={(e) => update("zona", "localidad", e.target.value)} className={inputClass} />
+        ))}
+        {campo("IVA responsable inscripto (%)", (
+          <Input type="number" step="0.01" value={settings.iva.inscripto} onChange={(e) => update("iva", "inscripto", Number(e.target.value))} className={inputClass} />
+        ))}
+        {campo("IVA no inscripto (%)", (
+          <Input type="number" step="0.01" value={settings.iva.no_inscripto} onChange={(e) => update("iva", "no_inscripto", Number(e.target.value))} className={inputClass} />
+        ))}
+        {campo("Servidor PostgreSQL", (
+          <Input
+            value={settings.base_datos.postgres_url ?? ""}
+            placeholder="Sin servidor (sólo base local)"
+            onChange={(e) => update("base_datos", "postgres_url", e.target.value || null)}
+            className={inputClass}
+          />
+        ))}
+        {campo("Área responsable (altas desde InfoGov)", (
+          <Input value={settings.infogov.area_responsable} onChange={(e) => update("infogov", "area_responsable", e.target.value)} className={inputClass} />
+        ))}
+        {campo("Fecha de inicio (altas desde InfoGov)", (
+          <select
+            value={settings.infogov.fecha_inicio}
+            onChange={(e) => update("infogov", "fecha_inicio", e.target.value as Settings["infogov"]["fecha_inicio"])}
+            className={selectClass}
+          >
+            <option value="FECHA_PASE">Fecha del pase</option>
+            <option value="HOY">Día de la captura</option>
+          </select>
+        ))}
+        {campo("Prioridad (altas desde InfoGov)", (
+          <select
+            value={settings.infogov.prioridad}
+            onChange={(e) => update("infogov", "prioridad", e.target.value as Prioridad)}
+            className={selectClass}
+          >
+            <option value="Baja">Baja</option>
+            <option value="Media">Media</option>
+            <option value="Alta">Alta</option>
+            <option value="Urgente">Urgente</option>
+          </select>
+        ))}
+        {campo("Conversor a PDF", (
+          <select
+            value={settings.impresion.motor_pdf}
+            onChange={(e) => update("impresion", "motor_pdf", e.target.value as Settings["impresion"]["motor_pdf"])}
+            className={selectClass}
+          >
+            <option value="AUTOMATICO">Automático (LibreOffice si está instalado)</option>
+            <option value="LIBRE_OFFICE">LibreOffice</option>
+            <option value="NATIVO">Nativo</option>
+          </select>
+        ))}
+        {campo("Ejecutable de LibreOffice", (
+          <Input
+            value={settings.impresion.ruta_soffice ?? ""}
+            placeholder="Buscar automáticamente"
+            onChange={(e) => update("impresion", "ruta_soffice", e.target.value || null)}
+            className={inputClass}
+          />
+        ))}
+        {campo("Copias de cada OC", (
+          <select
+            value={settings.impresion.copias}
+            onChange={(e) => update("impresion", "copias", Number(e.target.value))}
+            className={selectClass}
+          >
+            {opcionesCopias}
+          </select>
+        ))}
+        {campo("Marca de agua", (
+          <label className="flex items-center gap-2 h-9 text-sm text-slate-700 dark:text-slate-300">
+            <input
+              type="checkbox"
+              checked={settings.impresion.marca_agua}
+              onChange={(e) => update("impresion", "marca_agua", e.target.checked)}
+            />
+            Imprimir ORIGINAL / DUPLICADO / TRIPLICADO de fondo
+          </label>
+        ))}
+        {tiposContratacion.map((tipo) => (
+          <div key={tipo}>
+            {campo(`Copias: ${tipo}`, (
+              <select
+                value={settings.impresion.copias_por_tipo[tipo] ?? ""}
+                onChange={(e) => updateCopiasPorTipo(tipo, e.target.value)}
+                className={selectClass}
+              >
+                <option value="">Las de todas las OC</option>
+                {opcionesCopias}
+              </select>
+            ))}
+          </div>
+        ))}
+      </div>
+
+      {error && <p className="text-sm text-red-600 mt-3">{error}</p>}
+      {guardado && <p className="text-sm text-green-600 mt-3">Configuración guardada</p>}
+
+      <Button variant="outline" onClick={saveSettings} disabled={saving} className="mt-4">
+        {saving ? "Guardando..." : "Guardar configuración"}
+      </Button>
+    </div>
+  );
+}
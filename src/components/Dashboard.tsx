@@ -64,6 +64,9 @@ import Personal from "@/components/Personal";
 import FormularioOC from "@/components/FormularioOC";
 import PreviewOC from "@/components/PreviewOC";
 import ConfigTopes from "@/components/ConfigTopes";
+import ConfigAtajos from "@/components/ConfigAtajos";
+import ConfigInstalacion from "@/components/ConfigInstalacion";
+import { AtajosService } from "@/services/atajos.service";
 import Notificaciones from "@/components/Notificaciones";
 import NotificationBellIcon from "@/components/NotificationBellIcon";
 import { ToastContainer, useToast } from "@/components/Toast";
@@ -80,6 +83,10 @@ export default function Dashboard() {
   const [isEditDialogOpen, setIsEditDialogOpen] = useState(false);
   const [editingExpediente, setEditingExpediente] = useState<Expediente | null>(null);
   const [searchQuery, setSearchQuery] = useState("");
+  const searchInputRef = useRef<HTMLInputElement>(null);
+  // Pedidos de los atajos globales (se atienden en un efecto para usar el estado actual)
+  const [busquedaSolicitada, setBusquedaSolicitada] = useState(0);
+  const [ocSolicitada, setOcSolicitada] = useState(0);
   const [activeFilter, setActiveFilter] = useState<FilterType>("all");
   const [isOnline] = useState(true);
   const [hoveredRow, setHoveredRow] = useState<string | null>(null);
@@ -92,7 +99,7 @@ export default function Dashboard() {
   const [isGeneratingPDF, setIsGeneratingPDF] = useState(false);
   const [pdfGenerationStatus, setPdfGenerationStatus] = useState<'idle' | 'loading' | 'success' | 'error'>('idle');
   const lastPdfRequestRef = useRef<string | null>(null);
-  const lastPdfPathRef = useRef<string | null>(null);
+  const lastPdfPathRef = useRef<string[] | null>(null);
   const [newExpediente, setNewExpediente] = useState<Partial<CreateExpedienteInput> & { estado?: EstadoExpediente }>({
     prioridad: "Media",
     fecha_inicio: new Date().toISOString().split('T')[0],
@@ -227,7 +234,7 @@ export default function Dashboard() {
     };
   }, []);
 
-  // Escuchar eventos del atajo Alt+I (expediente procesado desde InfoGov)
+  // Escuchar eventos de los atajos globales (captura de InfoGov, búsqueda, OC)
   useEffect(() => {
     const unlisteners: (() => void)[] = [];
 
@@ -249,7 +256,7 @@ export default function Dashboard() {
           
           // Mostrar detalles en la UI
           if (nro_infogov) {
-            showInfo("El expediente está disponible en la lista", "Presiona Alt+I nuevamente o busca por el número");
+            showInfo("El expediente está disponible en la lista", "Usá el atajo de captura nuevamente o buscá por el número");
           }
         });
         unlisteners.push(unlisten1);
@@ -270,6 +277,42 @@ export default function Dashboard() {
         });
         unlisteners.push(unlisten2);
 
+        // Evento: Resumen de una captura de varias filas (Alt+I)
+        const unlisten3 = await listen<any>('captura_infogov_resumen', (event) => {
+          const { creados = [], actualizados = [], errores = [] } = event.payload;
+          console.log("📋 Captura InfoGov:", event.payload);
+
+          if (creados.length + actualizados.length > 0) {
+            const mensaje = `${creados.length} creados, ${actualizados.length} actualizados`;
+            showSuccess("Captura desde InfoGov", mensaje);
+            void notifySuccess("Expedientes procesados", mensaje);
+            loadExpedientes();
+          }
+
+          if (errores.length > 0) {
+            const detalle = errores
+              .map((e: { fila: number; error: string }) => `Fila ${e.fila}: ${e.error}`)
+              .join("\n");
+            showError(`${errores.length} filas no se pudieron importar`, detalle);
+          }
+        });
+        unlisteners.push(unlisten3);
+
+        // Eventos de los atajos globales configurables
+        const unlisten4 = await listen('abrir_busqueda', () => setBusquedaSolicitada((n) => n + 1));
+        unlisteners.push(unlisten4);
+        const unlisten5 = await listen('abrir_formulario_oc', () => setOcSolicitada((n) => n + 1));
+        unlisteners.push(unlisten5);
+
+        // Atajos que no se pudieron registrar al iniciar (otra aplicación usa la combinación)
+        const atajos = await AtajosService.obtenerAtajos();
+        const sinRegistrar = atajos
+          .filter((a) => a.error)
+          .map((a) => `${a.combinacion} (${a.descripcion}): ${a.error}`);
+        if (sinRegistrar.length > 0) {
+          showError("Hay atajos de teclado sin registrar", sinRegistrar.join("\n"));
+        }
+
         console.log("✅ Listeners de eventos configurados");
       } catch (err) {
         console.error("Error configurando listeners:", err);
@@ -487,6 +530,22 @@ export default function Dashboard() {
     }
   };
 
+  useEffect(() => {
+    if (busquedaSolicitada === 0) return;
+    setActiveView("dashboard");
+    // El buscador se monta con la vista de dashboard
+    setTimeout(() => searchInputRef.current?.focus(), 0);
+  }, [busquedaSolicitada]);
+
+  useEffect(() => {
+    if (ocSolicitada === 0) return;
+    if (selectedRecord) {
+      void handleGenerarOC(selectedRecord);
+    } else {
+      showInfo("Seleccioná un expediente", "El formulario de OC se abre para el expediente seleccionado");
+    }
+  }, [ocSolicitada]);
+
   const filterOptions: { label: string; value: FilterType; count: number }[] = [
     { label: "Todos", value: "all", count: expedientes.length },
     { label: "Iniciado", value: "Iniciado", count: expedientes.filter((r) => r.estado === "Iniciado").length },
@@ -677,6 +736,7 @@ export default function Dashboard() {
               <div className="relative">
                 <Search className="absolute left-4 top-1/2 -translate-y-1/2 h-5 w-5 text-slate-400" />
                 <Input
+                  ref={searchInputRef}
                   type="text"
                   placeholder="Buscar expedientes... (⌘K)"
                   value={searchQuery}
@@ -1049,11 +1109,14 @@ export default function Dashboard() {
                     es_iva_inscripto: ocDraft.es_iva_inscripto,
                   };
 
-                  const excelPath = await invoke<string>('generar_excel', {
+                  const excelPaths = await invoke<string[]>('generar_excel', {
                     data: excelRequest,
                     outputDir: selectedOutputDir,
+                    opciones: { salida: editedData.salidaCopias },
                   });
-                  await openPath(excelPath);
+                  for (const excelPath of excelPaths) {
+                    await openPath(excelPath);
+                  }
                   setPdfGenerationStatus('success');
 
                   setTimeout(() => {
@@ -1114,18 +1177,24 @@ export default function Dashboard() {
                     es_iva_inscripto: ocDraft.es_iva_inscripto,
                   };
                   
-                  const requestKey = JSON.stringify(pdfRequest);
+                  const opciones = { salida: editedData.salidaCopias };
+                  const requestKey = JSON.stringify({ pdfRequest, opciones });
                   if (lastPdfRequestRef.current === requestKey && lastPdfPathRef.current) {
-                    await openPath(lastPdfPathRef.current);
+                    for (const pdfPath of lastPdfPathRef.current) {
+                      await openPath(pdfPath);
+                    }
                     setPdfGenerationStatus('success');
                   } else {
-                    const pdfPath = await invoke<string>('generar_pdf', {
+                    const pdfPaths = await invoke<string[]>('generar_pdf', {
                       data: pdfRequest,
                       outputDir: selectedOutputDir,
+                      opciones,
                     });
                     lastPdfRequestRef.current = requestKey;
-                    lastPdfPathRef.current = pdfPath;
-                    await openPath(pdfPath);
+                    lastPdfPathRef.current = pdfPaths;
+                    for (const pdfPath of pdfPaths) {
+                      await openPath(pdfPath);
+                    }
                     setPdfGenerationStatus('success');
                   }
                   
@@ -1218,6 +1287,10 @@ export default function Dashboard() {
                       </div>
                     </div>
 
+                    <ConfigInstalacion />
+
+                    <ConfigAtajos />
+
                     <div>
                       <h3 className="text-lg font-semibold text-slate-900 dark:text-white mb-4">Archivos exportados</h3>
                       <div className="space-y-3">
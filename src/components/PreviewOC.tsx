@@ -2,7 +2,7 @@ import { useState } from "react";
 import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { Textarea } from "@/components/ui/textarea";
-import type { NuevaOCPreparada, CreateRenglon } from "@/types/orden_compra";
+import type { NuevaOCPreparada, CreateRenglon, SalidaCopias } from "@/types/orden_compra";
 import { ArrowLeft, Download, Loader2, CheckCircle, XCircle, Plus, Trash2, FileSpreadsheet, FileText } from "lucide-react";
 import {
   DropdownMenu,
@@ -36,6 +36,8 @@ export default function PreviewOC({ data, renglones: initialRenglones, onBack, o
   });
 
   const [renglones, setRenglones] = useState<CreateRenglon[]>(initialRenglones);
+  // Las copias (ORIGINAL, DUPLICADO, TRIPLICADO) salen de la configuración según el tipo de contratación
+  const [salidaCopias, setSalidaCopias] = useState<SalidaCopias>("UN_DOCUMENTO");
 
   const subtotal = renglones.reduce((acc, r) => acc + r.cantidad * r.valor_unitario, 0);
   const iva = subtotal * (data.es_iva_inscripto ? 0.21 : 0.105);
@@ -69,12 +71,12 @@ export default function PreviewOC({ data, renglones: initialRenglones, onBack, o
 
   const handleGeneratePDF = () => {
     if (isGenerating) return;
-    onGeneratePDF({ ...editedData, renglones, subtotal, iva, total });
+    onGeneratePDF({ ...editedData, renglones, subtotal, iva, total, salidaCopias });
   };
 
   const handleGenerateExcel = () => {
     if (isGenerating) return;
-    onGenerateExcel({ ...editedData, renglones, subtotal, iva, total });
+    onGenerateExcel({ ...editedData, renglones, subtotal, iva, total, salidaCopias });
   };
 
   return (
@@ -85,52 +87,63 @@ export default function PreviewOC({ data, renglones: initialRenglones, onBack, o
           <ArrowLeft className="h-4 w-4 mr-2" />
           Volver a Editar
         </Button>
-        <DropdownMenu>
-          <DropdownMenuTrigger asChild>
-            <Button
-              className="gap-2 min-w-[200px] transition-all duration-300"
-              disabled={isGenerating || generationStatus === 'success'}
-              variant={generationStatus === 'success' ? 'default' : generationStatus === 'error' ? 'destructive' : 'default'}
-            >
-              <span className="inline-flex items-center gap-2 transition-all duration-300">
-                {generationStatus === 'loading' && (
-                  <Loader2 className="h-4 w-4 animate-spin" />
-                )}
-                {generationStatus === 'success' && (
-                  <CheckCircle className="h-4 w-4 animate-in zoom-in duration-300" />
-                )}
-                {generationStatus === 'error' && (
-                  <XCircle className="h-4 w-4 animate-in zoom-in duration-300" />
-                )}
-                {generationStatus === 'idle' && (
-                  <Download className="h-4 w-4" />
-                )}
-                {generationStatus === 'loading' && 'Generando...'}
-                {generationStatus === 'success' && 'Generado correctamente'}
-                {generationStatus === 'error' && 'Error al generar'}
-                {generationStatus === 'idle' && 'Generar'}
-              </span>
-            </Button>
-          </DropdownMenuTrigger>
-          <DropdownMenuContent align="end" className="w-56 bg-white border border-slate-200 shadow-lg">
-            <DropdownMenuItem
-              disabled={isGenerating}
-              onClick={handleGenerateExcel}
-              className="cursor-pointer hover:bg-slate-100 focus:bg-slate-100"
-            >
-              <FileSpreadsheet className="h-4 w-4 mr-2" />
-              Generar Excel
-            </DropdownMenuItem>
-            <DropdownMenuItem
-              disabled={isGenerating}
-              onClick={handleGeneratePDF}
-              className="cursor-pointer hover:bg-slate-100 focus:bg-slate-100"
-            >
-              <FileText className="h-4 w-4 mr-2" />
-              Generar PDF
-            </DropdownMenuItem>
-          </DropdownMenuContent>
-        </DropdownMenu>
+        <div className="flex items-center gap-2">
+          <select
+            value={salidaCopias}
+            onChange={(e) => setSalidaCopias(e.target.value as SalidaCopias)}
+            disabled={isGenerating}
+            className="h-9 rounded-md border border-slate-200 bg-white px-3 text-sm"
+          >
+            <option value="UN_DOCUMENTO">Todas las copias en un archivo</option>
+            <option value="ARCHIVO_POR_COPIA">Un archivo por copia</option>
+          </select>
+          <DropdownMenu>
+            <DropdownMenuTrigger asChild>
+              <Button
+                className="gap-2 min-w-[200px] transition-all duration-300"
+                disabled={isGenerating || generationStatus === 'success'}
+                variant={generationStatus === 'success' ? 'default' : generationStatus === 'error' ? 'destructive' : 'default'}
+              >
+                <span className="inline-flex items-center gap-2 transition-all duration-300">
+                  {generationStatus === 'loading' && (
+                    <Loader2 className="h-4 w-4 animate-spin" />
+                  )}
+                  {generationStatus === 'success' && (
+                    <CheckCircle className="h-4 w-4 animate-in zoom-in duration-300" />
+                  )}
+                  {generationStatus === 'error' && (
+                    <XCircle className="h-4 w-4 animate-in zoom-in duration-300" />
+                  )}
+                  {generationStatus === 'idle' && (
+                    <Download className="h-4 w-4" />
+                  )}
+                  {generationStatus === 'loading' && 'Generando...'}
+                  {generationStatus === 'success' && 'Generado correctamente'}
+                  {generationStatus === 'error' && 'Error al generar'}
+                  {generationStatus === 'idle' && 'Generar'}
+                </span>
+              </Button>
+            </DropdownMenuTrigger>
+            <DropdownMenuContent align="end" className="w-56 bg-white border border-slate-200 shadow-lg">
+              <DropdownMenuItem
+                disabled={isGenerating}
+                onClick={handleGenerateExcel}
+                className="cursor-pointer hover:bg-slate-100 focus:bg-slate-100"
+              >
+                <FileSpreadsheet className="h-4 w-4 mr-2" />
+                Generar Excel
+              </DropdownMenuItem>
+              <DropdownMenuItem
+                disabled={isGenerating}
+                onClick={handleGeneratePDF}
+                className="cursor-pointer hover:bg-slate-100 focus:bg-slate-100"
+              >
+                <FileText className="h-4 w-4 mr-2" />
+                Generar PDF
+              </DropdownMenuItem>
+            </DropdownMenuContent>
+          </DropdownMenu>
+        </div>
       </div>
 
       {/* Simulación del Excel */}
@@ -0,0 +1,24 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { AtajoTeclado, EstadoAtajo, FilaCapturaRapida } from "@/types/atajos";
+
+export class AtajosService {
+  static async obtenerAtajos(): Promise<EstadoAtajo[]> {
+    return invoke<EstadoAtajo[]>("get_atajos");
+  }
+
+  static async actualizarAtajos(atajos: AtajoTeclado[]): Promise<EstadoAtajo[]> {
+    return invoke<EstadoAtajo[]>("update_atajos", { atajos });
+  }
+
+  static async obtenerCapturaRapida(): Promise<FilaCapturaRapida[]> {
+    return invoke<FilaCapturaRapida[]>("get_captura_rapida");
+  }
+
+  static async guardarCapturaRapida(filas: FilaCapturaRapida[]) {
+    return invoke("guardar_captura_rapida", { filas });
+  }
+
+  static async descartarCapturaRapida(): Promise<void> {
+    return invoke("descartar_captura_rapida");
+  }
+}
@@ -0,0 +1,16 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { Settings, Zona } from "@/types/configuracion";
+
+export class ConfiguracionService {
+  static async obtenerSettings(): Promise<Settings> {
+    return invoke<Settings>("get_settings");
+  }
+
+  static async actualizarSettings(settings: Settings): Promise<Settings> {
+    return invoke<Settings>("update_settings", { settings });
+  }
+
+  static async obtenerZonas(): Promise<Zona[]> {
+    return invoke<Zona[]>("get_zonas");
+  }
+}
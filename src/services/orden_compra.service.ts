@@ -1,5 +1,15 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { ConfigTope, CreateOrdenCompra, NuevaOCPreparada } from "@/types/orden_compra";
+import type {
+  ConfigTope,
+  CreateEntrega,
+  CreateOrdenCompra,
+  InformeNumeracion,
+  NuevaOCPreparada,
+  OrdenCompra,
+  OrdenCompraCompleta,
+  OrdenCompraEntrega,
+  UpdateOrdenCompra,
+} from "@/types/orden_compra";
 
 export class OrdenCompraService {
   static async prepararNuevaOC(expedienteId: string): Promise<NuevaOCPreparada> {
@@ -15,6 +25,34 @@ export class OrdenCompraService {
   }
 
   static async crearOrdenCompra(data: CreateOrdenCompra) {
-    return invoke("crear_orden_compra", { data });
+    return invoke<OrdenCompraCompleta>("crear_orden_compra", { data });
+  }
+
+  static async obtenerOrdenesCompra(): Promise<OrdenCompra[]> {
+    return invoke<OrdenCompra[]>("obtener_ordenes_compra");
+  }
+
+  static async editarOrdenCompra(id: string, data: UpdateOrdenCompra): Promise<OrdenCompraCompleta> {
+    return invoke<OrdenCompraCompleta>("editar_orden_compra", { id, data });
+  }
+
+  static async emitirOrdenCompra(id: string): Promise<OrdenCompra> {
+    return invoke<OrdenCompra>("emitir_orden_compra", { id });
+  }
+
+  static async anularOrdenCompra(id: string, motivo: string): Promise<OrdenCompra> {
+    return invoke<OrdenCompra>("anular_orden_compra", { id, motivo });
+  }
+
+  static async registrarEntrega(id: string, data: CreateEntrega): Promise<OrdenCompra> {
+    return invoke<OrdenCompra>("registrar_entrega_orden_compra", { id, data });
+  }
+
+  static async obtenerEntregas(id: string): Promise<OrdenCompraEntrega[]> {
+    return invoke<OrdenCompraEntrega[]>("obtener_entregas_orden_compra", { id });
+  }
+
+  static async conciliarNumeracion(): Promise<InformeNumeracion> {
+    return invoke<InformeNumeracion>("conciliar_numeracion_oc");
   }
 }
@@ -0,0 +1,32 @@
+// Tipos para atajos globales y captura rápida de InfoGov
+// Coinciden con los modelos de Rust
+
+export type AccionAtajo = "CAPTURAR_INFO_GOV" | "ABRIR_BUSQUEDA" | "ABRIR_ORDEN_COMPRA" | "ALTERNAR_VENTANA";
+
+export interface AtajoTeclado {
+  accion: AccionAtajo;
+  combinacion: string; // "Alt+I"; vacío = sin atajo
+}
+
+export interface EstadoAtajo extends AtajoTeclado {
+  descripcion: string;
+  registrado: boolean;
+  error: string | null;
+}
+
+export interface InfoGovExpediente {
+  nro_infogov: string;
+  tema: string;
+  nro_gde: string;
+  fecha_pase: string;
+  estado: string;
+  oficina: string | null;
+  resumen: string;
+}
+
+export interface FilaCapturaRapida {
+  fila: number;
+  texto: string;
+  expediente: InfoGovExpediente | null;
+  error: string | null;
+}
@@ -0,0 +1,62 @@
+// Tipos para la configuración de la instalación
+// Coinciden con los modelos de Rust (models/configuracion.rs)
+
+import type { Prioridad } from "@/types/expediente";
+
+export interface Institucion {
+  nombre: string;
+  cuit: string;
+}
+
+// Zona de riego (tabla zonas)
+export interface Zona {
+  id: string;
+  codigo: string;
+  nombre: string;
+  activa: boolean;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface ZonaRiego {
+  id: string; // zona activa: filtra expedientes, OC, vehículos y agentes
+  destino: string;
+  domicilio: string;
+  localidad: string;
+}
+
+export interface TasasIva {
+  inscripto: number; // porcentaje (21)
+  no_inscripto: number;
+}
+
+export interface BaseDatos {
+  postgres_url: string | null; // null = sólo SQLite local; llega con la contraseña oculta (********)
+}
+
+export type FechaInicioInfoGov = "FECHA_PASE" | "HOY";
+
+export interface DefectosInfoGov {
+  fecha_inicio: FechaInicioInfoGov;
+  area_responsable: string;
+  prioridad: Prioridad;
+}
+
+export type MotorPdf = "AUTOMATICO" | "LIBRE_OFFICE" | "NATIVO";
+
+export interface Impresion {
+  motor_pdf: MotorPdf;
+  ruta_soffice: string | null; // null = buscar LibreOffice en el PATH
+  copias: number; // 1 = ORIGINAL, 2 = + DUPLICADO, 3 = + TRIPLICADO
+  copias_por_tipo: Record<string, number>; // tipo de contratación → copias (los ausentes usan `copias`)
+  marca_agua: boolean;
+}
+
+export interface Settings {
+  institucion: Institucion;
+  zona: ZonaRiego;
+  iva: TasasIva;
+  base_datos: BaseDatos;
+  infogov: DefectosInfoGov;
+  impresion: Impresion;
+}
@@ -53,4 +53,100 @@ export interface CreateOrdenCompra {
   plazo_entrega?: string;
   es_iva_inscripto: boolean;
   renglones: CreateRenglon[];
+  borrador?: boolean; // true = queda en BORRADOR (se emite después)
+}
+
+// Ciclo de vida: BORRADOR -> EMITIDA -> RECIBIDA_PARCIAL / RECIBIDA, o ANULADA (conserva el número)
+export type EstadoOrdenCompra = "BORRADOR" | "EMITIDA" | "ANULADA" | "RECIBIDA_PARCIAL" | "RECIBIDA";
+
+export interface OrdenCompra {
+  id: string;
+  numero_oc: string;
+  pedido_nro: number;
+  destino: string;
+  fecha: string;
+  expediente_id: string;
+  resolucion_nro?: string | null;
+  forma_pago: string;
+  plazo_entrega: string;
+  es_iva_inscripto: boolean;
+  tipo_contratacion: string;
+  subtotal: number;
+  iva: number;
+  total: number;
+  estado: EstadoOrdenCompra;
+  motivo_anulacion?: string | null;
+  fecha_anulacion?: string | null;
+}
+
+export interface OrdenCompraRenglon {
+  id: string;
+  oc_id: string;
+  renglon_nro: number;
+  cantidad: number;
+  detalle: string;
+  marca?: string | null;
+  valor_unitario: number;
+}
+
+export interface OrdenCompraCompleta {
+  orden: OrdenCompra;
+  renglones: OrdenCompraRenglon[];
+  total_en_letras: string;
+}
+
+// Sólo para OC en BORRADOR: los renglones reemplazan a los anteriores
+export interface UpdateOrdenCompra {
+  destino?: string;
+  resolucion_nro?: string;
+  forma_pago: string;
+  plazo_entrega?: string;
+  es_iva_inscripto: boolean;
+  renglones: CreateRenglon[];
+}
+
+export interface CreateEntrega {
+  fecha?: string; // YYYY-MM-DD, hoy si no se indica
+  remito?: string;
+  observaciones?: string;
+  renglones: { renglon_id: string; cantidad: number }[];
+}
+
+export interface OrdenCompraEntrega {
+  id: string;
+  oc_id: string;
+  renglon_id: string;
+  cantidad: number;
+  fecha: string;
+  remito?: string | null;
+  observaciones?: string | null;
+}
+
+// Conciliación de la numeración de OC de la zona ([id, numero_oc] en las listas)
+export interface NumeroDuplicado {
+  numero: number;
+  ordenes: [string, string][];
+}
+
+export interface ConciliacionNumeracion {
+  año: number;
+  ultimo_numero: number;
+  faltantes: number[];
+  duplicados: NumeroDuplicado[];
+}
+
+export interface InformeNumeracion {
+  años: ConciliacionNumeracion[];
+  invalidos: [string, string][];
+}
+
+// Copias de la OC: ORIGINAL (proveedor), DUPLICADO (Contaduría), TRIPLICADO (archivo)
+export type Copia = "ORIGINAL" | "DUPLICADO" | "TRIPLICADO";
+
+export type SalidaCopias = "UN_DOCUMENTO" | "ARCHIVO_POR_COPIA";
+
+export interface OpcionesCopias {
+  copias?: Copia[] | null; // null = las configuradas para el tipo de contratación
+  salida?: SalidaCopias;
+  marca_agua?: boolean | null; // null = según la configuración
 }